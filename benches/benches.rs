@@ -1,10 +1,10 @@
 use criterion::*;
 
 #[derive(Default)]
-struct Small(u8);
+struct Small(#[allow(dead_code)] u8);
 
 #[derive(Default)]
-struct Big([usize; 32]);
+struct Big(#[allow(dead_code)] [usize; 32]);
 
 fn alloc<T: Default>(n: usize) {
     let arena = bumpalo::Bump::with_capacity(n * std::mem::size_of::<T>());
@@ -176,6 +176,46 @@ fn bench_extend_from_slice_copy(c: &mut Criterion) {
     }
 }
 
+fn bench_alloc_slice_copy(c: &mut Criterion) {
+    for len in [64usize, 1024, 4 * 1024, 64 * 1024, 1024 * 1024] {
+        let src = vec![0x5a_u8; len];
+        let mut group = c.benchmark_group(format!("alloc_slice_copy {len} bytes"));
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_function("alloc_slice_copy", |b| {
+            let mut bump = bumpalo::Bump::with_capacity(len + 64);
+            b.iter(|| {
+                bump.reset();
+                // Misalign the bump pointer, as a preceding allocation would.
+                bump.alloc(0_u8);
+                black_box(bump.alloc_slice_copy(black_box(&src[..])));
+            });
+        });
+        group.finish();
+    }
+}
+
+fn bench_alloc_slice_fill_copy(c: &mut Criterion) {
+    for len in [64usize, 4 * 1024, 1024 * 1024] {
+        let mut group = c.benchmark_group(format!("alloc_slice_fill_copy {len} bytes"));
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_function("u8", |b| {
+            let mut bump = bumpalo::Bump::with_capacity(len);
+            b.iter(|| {
+                bump.reset();
+                black_box(bump.alloc_slice_fill_copy(len, black_box(0_u8)));
+            });
+        });
+        group.bench_function("u64", |b| {
+            let mut bump = bumpalo::Bump::with_capacity(len);
+            b.iter(|| {
+                bump.reset();
+                black_box(bump.alloc_slice_fill_copy(len / 8, black_box(0_u64)));
+            });
+        });
+        group.finish();
+    }
+}
+
 fn bench_extend_from_slices_copy(c: &mut Criterion) {
     // The number of slices that will be copied into the Vec
     let slice_counts = &[1, 2, 4, 8, 16, 32];
@@ -196,9 +236,9 @@ fn bench_extend_from_slices_copy(c: &mut Criterion) {
     for is_preallocated in is_preallocated_settings {
         for num_slices in slice_counts.iter().copied() {
             // Create an appropriately named benchmark group
-            let mut group = c.benchmark_group(
-                format!("extend_from_slices num_slices={num_slices}, is_preallocated={is_preallocated}")
-            );
+            let mut group = c.benchmark_group(format!(
+                "extend_from_slices num_slices={num_slices}, is_preallocated={is_preallocated}"
+            ));
 
             // Cycle over `data` to construct a slice of slices to append
             let slices = data
@@ -223,7 +263,8 @@ fn bench_extend_from_slices_copy(c: &mut Criterion) {
             group.bench_function("loop over extend_from_slice_copy", |b| {
                 b.iter(|| {
                     bump.reset();
-                    let mut vec = bumpalo::collections::Vec::<u8>::with_capacity_in(size_to_allocate, &bump);
+                    let mut vec =
+                        bumpalo::collections::Vec::<u8>::with_capacity_in(size_to_allocate, &bump);
                     for slice in black_box(&slices) {
                         vec.extend_from_slice_copy(slice);
                     }
@@ -237,7 +278,8 @@ fn bench_extend_from_slices_copy(c: &mut Criterion) {
             group.bench_function("extend_from_slices_copy", |b| {
                 b.iter(|| {
                     bump.reset();
-                    let mut vec = bumpalo::collections::Vec::<u8>::with_capacity_in(size_to_allocate, &bump);
+                    let mut vec =
+                        bumpalo::collections::Vec::<u8>::with_capacity_in(size_to_allocate, &bump);
                     vec.extend_from_slices_copy(black_box(slices.as_slice()));
                     black_box(vec.as_slice());
                 });
@@ -359,6 +401,25 @@ fn bench_format_realloc(c: &mut Criterion) {
     }
 }
 
+fn bench_vec_push_growth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec-push-growth");
+
+    // Large enough that the vector outgrows several chunks.
+    for n in [1 << 12, 1 << 16, 1 << 20] {
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::new("vec-push-growth", n), &n, |b, n| {
+            b.iter(|| {
+                let bump = bumpalo::Bump::new();
+                let mut v = bumpalo::collections::Vec::new_in(&bump);
+                for i in 0..*n {
+                    v.push(black_box(i as u64));
+                }
+                black_box(v.len());
+            });
+        });
+    }
+}
+
 fn bench_string_from_str_in(c: &mut Criterion) {
     let len: usize = 16;
 
@@ -369,7 +430,7 @@ fn bench_string_from_str_in(c: &mut Criterion) {
         let str = "x".repeat(len);
         b.iter(|| {
             bump.reset();
-            string_from_str_in(&bump, &*str);
+            string_from_str_in(&bump, &str);
         });
     });
 }
@@ -384,15 +445,46 @@ fn bench_string_push_str(c: &mut Criterion) {
         let str = "x".repeat(len);
         b.iter(|| {
             bump.reset();
-            string_push_str(&bump, &*str);
+            string_push_str(&bump, &str);
         });
     });
 }
 
+fn bench_json_pipeline(c: &mut Criterion) {
+    #[cfg(feature = "examples-lib")]
+    {
+        let mut input = std::string::String::from("[");
+        for i in 0..1000 {
+            if i > 0 {
+                input.push(',');
+            }
+            input.push_str(&format!(
+                r#"{{"id":{},"name":"item \"{}\"","tags":["a","b"],"score":{}.5}}"#,
+                i, i, i
+            ));
+        }
+        input.push(']');
+
+        let mut group = c.benchmark_group("json");
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function("pipeline", |b| {
+            let mut bump = bumpalo::Bump::new();
+            b.iter(|| {
+                bump.reset();
+                black_box(bumpalo::examples::json::pipeline(&bump, input.as_bytes()).unwrap());
+            });
+        });
+    }
+    #[cfg(not(feature = "examples-lib"))]
+    let _ = c;
+}
+
 criterion_group!(
     benches,
     bench_extend_from_slice_copy,
     bench_extend_from_slices_copy,
+    bench_alloc_slice_copy,
+    bench_alloc_slice_fill_copy,
     bench_alloc,
     bench_alloc_with,
     bench_alloc_try_with,
@@ -402,7 +494,9 @@ criterion_group!(
     bench_try_alloc_try_with,
     bench_try_alloc_try_with_err,
     bench_format_realloc,
+    bench_vec_push_growth,
     bench_string_from_str_in,
-    bench_string_push_str
+    bench_string_push_str,
+    bench_json_pipeline
 );
 criterion_main!(benches);
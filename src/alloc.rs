@@ -11,6 +11,7 @@
 #![allow(unstable_name_collisions)]
 #![allow(dead_code)]
 #![allow(deprecated)]
+#![allow(clippy::missing_safety_doc)]
 
 //! Memory allocation APIs
 
@@ -18,7 +19,6 @@ use core::cmp;
 use core::fmt;
 use core::mem;
 use core::ptr::{self, NonNull};
-use core::usize;
 
 pub use core::alloc::{Layout, LayoutErr};
 
@@ -81,7 +81,7 @@ impl UnstableLayoutMethods for Layout {
     }
 
     fn array<T>(n: usize) -> Result<Layout, LayoutErr> {
-        Layout::new::<T>().repeat(n).map(|(k, offs)| {
+        UnstableLayoutMethods::repeat(&Layout::new::<T>(), n).map(|(k, offs)| {
             debug_assert!(offs == mem::size_of::<T>());
             k
         })
@@ -102,18 +102,92 @@ fn size_align<T>() -> (usize, usize) {
 /// that may be due to resource exhaustion or to
 /// something wrong when combining the given input arguments with this
 /// allocator.
+///
+/// Its [`kind`](AllocErr::kind) tells apart why the allocation failed, e.g.
+/// to handle hitting an arena's
+/// [allocation limit](crate::Bump::set_allocation_limit) differently from
+/// running out of memory.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::{AllocErrKind, Bump};
+///
+/// let bump = Bump::new();
+/// bump.set_allocation_limit(Some(0));
+/// let err = bump.try_alloc(1_u32).unwrap_err();
+/// assert_eq!(err.kind(), AllocErrKind::LimitExceeded);
+///
+/// let err = bump.try_alloc_uninit_slice::<u32>(usize::MAX).unwrap_err();
+/// assert_eq!(err.kind(), AllocErrKind::SizeOverflow);
+/// ```
 // #[unstable(feature = "allocator_api", issue = "32838")]
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct AllocErr;
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AllocErr {
+    kind: AllocErrKind,
+}
+
+/// Why an allocation failed. See [`AllocErr::kind`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum AllocErrKind {
+    /// The backing allocator failed to provide memory, or the allocation
+    /// could otherwise not be satisfied.
+    OutOfMemory,
+    /// Making room for the allocation would have exceeded the arena's
+    /// allocation limit.
+    LimitExceeded,
+    /// The requested size overflowed, or does not describe a valid layout.
+    SizeOverflow,
+}
+
+impl AllocErr {
+    pub(crate) const OUT_OF_MEMORY: AllocErr = AllocErr::new(AllocErrKind::OutOfMemory);
+    pub(crate) const LIMIT_EXCEEDED: AllocErr = AllocErr::new(AllocErrKind::LimitExceeded);
+    pub(crate) const SIZE_OVERFLOW: AllocErr = AllocErr::new(AllocErrKind::SizeOverflow);
+
+    /// Construct an error of the given kind, e.g. for a backing allocator
+    /// or a wrapper around an arena.
+    #[inline]
+    pub const fn new(kind: AllocErrKind) -> AllocErr {
+        AllocErr { kind }
+    }
+
+    /// Why the allocation failed.
+    #[inline]
+    pub fn kind(&self) -> AllocErrKind {
+        self.kind
+    }
+}
+
+/// `AllocErr` used to be a unit struct, so keep `AllocErr` usable as a value,
+/// which is an out-of-memory error.
+#[doc(hidden)]
+#[allow(non_upper_case_globals)]
+pub const AllocErr: AllocErr = AllocErr::OUT_OF_MEMORY;
+
+impl From<AllocErrKind> for AllocErr {
+    #[inline]
+    fn from(kind: AllocErrKind) -> AllocErr {
+        AllocErr::new(kind)
+    }
+}
 
 // (we need this for downstream impl of trait Error)
 // #[unstable(feature = "allocator_api", issue = "32838")]
 impl fmt::Display for AllocErr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("memory allocation failed")
+        f.write_str(match self.kind {
+            AllocErrKind::OutOfMemory => "memory allocation failed",
+            AllocErrKind::LimitExceeded => "memory allocation failed: allocation limit exceeded",
+            AllocErrKind::SizeOverflow => "memory allocation failed: size overflow",
+        })
     }
 }
 
+#[cfg(any(feature = "std", feature = "core_error"))]
+impl crate::StdError for AllocErr {}
+
 /// The `CannotReallocInPlace` error is used when `grow_in_place` or
 /// `shrink_in_place` were unable to reuse the given memory block for
 /// a requested layout.
@@ -136,6 +210,9 @@ impl fmt::Display for CannotReallocInPlace {
     }
 }
 
+#[cfg(any(feature = "std", feature = "core_error"))]
+impl crate::StdError for CannotReallocInPlace {}
+
 /// An implementation of `Alloc` can allocate, reallocate, and
 /// deallocate arbitrary blocks of data described via `Layout`.
 ///
@@ -633,7 +710,7 @@ pub unsafe trait Alloc {
         if k.size() > 0 {
             unsafe { self.alloc(k).map(|p| p.cast()) }
         } else {
-            Err(AllocErr)
+            Err(AllocErr::SIZE_OVERFLOW)
         }
     }
 
@@ -702,7 +779,7 @@ pub unsafe trait Alloc {
     {
         match Layout::array::<T>(n) {
             Ok(layout) if layout.size() > 0 => unsafe { self.alloc(layout).map(|p| p.cast()) },
-            _ => Err(AllocErr),
+            _ => Err(AllocErr::SIZE_OVERFLOW),
         }
     }
 
@@ -755,7 +832,7 @@ pub unsafe trait Alloc {
                 self.realloc(ptr.cast(), *k_old, k_new.size())
                     .map(NonNull::cast)
             }
-            _ => Err(AllocErr),
+            _ => Err(AllocErr::SIZE_OVERFLOW),
         }
     }
 
@@ -788,7 +865,7 @@ pub unsafe trait Alloc {
                 self.dealloc(ptr.cast(), k);
                 Ok(())
             }
-            _ => Err(AllocErr),
+            _ => Err(AllocErr::SIZE_OVERFLOW),
         }
     }
 }
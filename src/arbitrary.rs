@@ -0,0 +1,64 @@
+//! Generating arena-allocated structures from fuzz input.
+//!
+//! The [`Arbitrary`] trait has no way to thread a [`Bump`] through to the
+//! values being built, so the bump collections cannot implement it. This
+//! module provides [`ArbitraryIn`], which is implemented for
+//! [`collections::Vec`][crate::collections::Vec],
+//! [`collections::String`][crate::collections::String], and
+//! [`boxed::Box`][crate::boxed::Box] (when the respective features are
+//! enabled), as well as for every type that implements `Arbitrary`. This lets
+//! fuzz targets build nested structures such as a `Vec<'bump, Box<'bump, T>>`
+//! directly from their input.
+//!
+//! ## Example
+//!
+//! ```
+//! # #[cfg(feature = "collections")]
+//! # {
+//! use arbitrary::Unstructured;
+//! use bumpalo::{arbitrary::ArbitraryIn, collections::Vec, Bump};
+//!
+//! let bump = Bump::new();
+//! let mut u = Unstructured::new(&[1, 10, 1, 20, 0]);
+//! let v = Vec::<u8>::arbitrary_in(&mut u, &bump).unwrap();
+//! assert_eq!(v, [10, 20]);
+//! # }
+//! ```
+
+use crate::Bump;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// A data structure that can be generated from fuzz input into a [`Bump`]
+/// arena.
+///
+/// This is the arena-aware counterpart of [`Arbitrary`]. See the
+/// [module-level documentation](self) for more details.
+pub trait ArbitraryIn<'a, 'bump>: Sized {
+    /// Generate a value from the given unstructured data, allocating any
+    /// storage it needs inside `bump`.
+    fn arbitrary_in(u: &mut Unstructured<'a>, bump: &'bump Bump) -> Result<Self>;
+
+    /// Generate a value from all of the remaining unstructured data,
+    /// allocating any storage it needs inside `bump`.
+    ///
+    /// See [`Arbitrary::arbitrary_take_rest`]. By default, this is the same
+    /// as [`arbitrary_in`](ArbitraryIn::arbitrary_in).
+    fn arbitrary_take_rest_in(mut u: Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+        Self::arbitrary_in(&mut u, bump)
+    }
+}
+
+impl<'a, 'bump, T> ArbitraryIn<'a, 'bump> for T
+where
+    T: Arbitrary<'a>,
+{
+    #[inline]
+    fn arbitrary_in(u: &mut Unstructured<'a>, _bump: &'bump Bump) -> Result<Self> {
+        T::arbitrary(u)
+    }
+
+    #[inline]
+    fn arbitrary_take_rest_in(u: Unstructured<'a>, _bump: &'bump Bump) -> Result<Self> {
+        T::arbitrary_take_rest(u)
+    }
+}
@@ -0,0 +1,256 @@
+//! Backing allocators that a `Bump` requests its chunks of memory from.
+
+use crate::Bump;
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::{self, NonNull};
+use core_alloc::alloc::{alloc, alloc_zeroed, dealloc};
+
+/// An allocator that a [`Bump`] can request its chunks of memory from.
+///
+/// By default, a `Bump` allocates its chunks from the [`Global`] allocator. A
+/// `Bump<A>` instead gets them from the backing allocator `A`, which can be
+/// used to place arenas in huge pages, inside an `mmap`ed region, or nested
+/// inside another `Bump`. Use [`Bump::new_in`] and friends, or
+/// [`BumpBuilder::build_in`][crate::BumpBuilder::build_in], to construct one.
+///
+/// The `bumpalo::collections` types and `bumpalo::boxed::Box` borrow a
+/// `Bump` with the default, global backing allocator. With other backing
+/// allocators, use the arena's allocation methods directly.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::Bump;
+///
+/// // An arena whose chunks are carved out of another arena.
+/// let outer = Bump::new();
+/// let inner = Bump::new_in(&outer);
+/// let x = inner.alloc(42);
+/// assert_eq!(*x, 42);
+/// assert!(outer.allocated_bytes() > 0);
+/// ```
+///
+/// ## Safety
+///
+/// Implementations must return memory blocks that fit the given layout and
+/// that stay valid, and are not handed out again, until they are passed to
+/// [`deallocate`](BackingAllocator::deallocate). A `Bump` only ever passes a
+/// block back to the same allocator it came from, with the layout it was
+/// requested with.
+pub unsafe trait BackingAllocator {
+    /// Allocate a block of memory for the given layout, or return `None` if
+    /// that is not possible.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Allocate a zero-initialized block of memory for the given layout, or
+    /// return `None` if that is not possible.
+    ///
+    /// The default implementation zeroes the memory returned by
+    /// [`allocate`](BackingAllocator::allocate).
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let ptr = self.allocate(layout)?;
+        unsafe {
+            ptr::write_bytes(ptr.as_ptr(), 0, layout.size());
+        }
+        Some(ptr)
+    }
+
+    /// Deallocate a block of memory.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must have been returned by this allocator for the given
+    /// `layout`, and must not have been deallocated already.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Called when a [`Bump`] is [reset](Bump::reset), with the region of
+    /// the chunk it keeps that is now unused.
+    ///
+    /// Implementations may release the memory backing that region, as long
+    /// as it stays readable and writable. The default implementation does
+    /// nothing.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr..ptr + len` must lie within a block of memory returned by this
+    /// allocator that has not been deallocated yet, and must not be in use.
+    #[inline]
+    unsafe fn discard(&self, ptr: NonNull<u8>, len: usize) {
+        let _ = (ptr, len);
+    }
+}
+
+/// The global memory allocator, which a [`Bump`] allocates its chunks from
+/// by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+unsafe impl BackingAllocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { alloc(layout) })
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { alloc_zeroed(layout) })
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        dealloc(ptr.as_ptr(), layout)
+    }
+}
+
+/// Chunks come straight from the operating system's allocator, even when
+/// another allocator is registered as the `#[global_allocator]`.
+#[cfg(feature = "std")]
+unsafe impl BackingAllocator for std::alloc::System {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { std::alloc::GlobalAlloc::alloc(self, layout) })
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { std::alloc::GlobalAlloc::alloc_zeroed(self, layout) })
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        std::alloc::GlobalAlloc::dealloc(self, ptr.as_ptr(), layout)
+    }
+}
+
+/// A fixed, caller-provided buffer of memory, for arenas that never touch
+/// the global allocator.
+///
+/// A `FixedBuffer` hands out its buffer as a single chunk, and refuses any
+/// other requests while that chunk is in use. A `Bump` backed by one never
+/// grows: once the buffer is full, allocations fail. This is meant for
+/// embedded and other `no_std` environments without a global allocator, or
+/// for arenas in stack or `static` buffers. Use
+/// [`Bump::from_buffer`][crate::Bump::from_buffer] to construct one.
+pub struct FixedBuffer<'a> {
+    ptr: NonNull<u8>,
+    len: usize,
+    in_use: Cell<bool>,
+    marker: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+// A `FixedBuffer` has exclusive access to its buffer, just like the `&mut`
+// it is created from.
+unsafe impl Send for FixedBuffer<'_> {}
+
+impl<'a> FixedBuffer<'a> {
+    /// Create a backing allocator for the given buffer.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> FixedBuffer<'a> {
+        let len = buf.len();
+        unsafe { FixedBuffer::from_raw_parts(NonNull::from(buf).cast(), len) }
+    }
+
+    /// Create a backing allocator for the `len` bytes at `ptr`.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr..ptr + len` must be valid for reads and writes, and must not be
+    /// accessed through any other pointer, for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(ptr: NonNull<u8>, len: usize) -> FixedBuffer<'a> {
+        FixedBuffer {
+            ptr,
+            len,
+            in_use: Cell::new(false),
+            marker: PhantomData,
+        }
+    }
+
+    /// The size of the buffer in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer has a size of zero.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many bytes must be skipped at the start of the buffer to align
+    /// it to `align`.
+    pub(crate) fn align_offset(&self, align: usize) -> usize {
+        (self.ptr.as_ptr() as usize).wrapping_neg() & (align - 1)
+    }
+}
+
+impl fmt::Debug for FixedBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBuffer")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("in_use", &self.in_use.get())
+            .finish()
+    }
+}
+
+unsafe impl BackingAllocator for FixedBuffer<'_> {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if self.in_use.get() {
+            return None;
+        }
+        let offset = self.align_offset(layout.align());
+        if offset > self.len || self.len - offset < layout.size() {
+            return None;
+        }
+        self.in_use.set(true);
+        unsafe { Some(NonNull::new_unchecked(self.ptr.as_ptr().add(offset))) }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        self.in_use.set(false);
+    }
+}
+
+unsafe impl<A: BackingAllocator + ?Sized> BackingAllocator for &A {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        (**self).allocate(layout)
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        (**self).allocate_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn discard(&self, ptr: NonNull<u8>, len: usize) {
+        (**self).discard(ptr, len)
+    }
+}
+
+/// Chunks are carved out of the other arena, and are only freed when it is
+/// reset or dropped.
+unsafe impl<A: BackingAllocator> BackingAllocator for Bump<A> {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.try_alloc_layout(layout).ok()
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.try_alloc_layout_zeroed(layout).ok()
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.dealloc(ptr, layout)
+    }
+}
@@ -120,7 +120,7 @@
 //! [`Layout::for_value(&*value)`]: https://doc.rust-lang.org/std/alloc/struct.Layout.html#method.for_value
 
 use {
-    crate::Bump,
+    crate::{AllocErr, Bump, DropEntry},
     {
         core::{
             any::Any,
@@ -130,15 +130,83 @@ use {
             future::Future,
             hash::{Hash, Hasher},
             iter::FusedIterator,
+            marker::PhantomData,
             mem::ManuallyDrop,
             ops::{Deref, DerefMut},
             pin::Pin,
+            ptr::{self, NonNull},
             task::{Context, Poll},
         },
         core_alloc::fmt,
     },
 };
 
+/// Converts a [`Box<'a, T>`][Box] into a `Box<'a, U>`, where `U` is an
+/// unsized type that `T` coerces to, such as a trait object or a slice.
+///
+/// `std::boxed::Box` supports these conversions as implicit coercions, but
+/// that relies on unstable language features, so bumpalo's `Box` needs this
+/// macro instead. The target type is inferred from the context. Only
+/// unsizing conversions are possible, so this is safe to use.
+///
+/// The `in $bump; $value` form allocates `$value` in `$bump` first, like
+/// [`Box::new_in`], and then converts the result.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, boxed::Box};
+/// use std::fmt::Display;
+///
+/// let b = Bump::new();
+///
+/// let five = Box::new_in(5, &b);
+/// let five: Box<dyn Display> = bumpalo::unsize_box!(five);
+/// assert_eq!(five.to_string(), "5");
+///
+/// let hello: Box<dyn Display> = bumpalo::unsize_box!(in &b; "hello");
+/// assert_eq!(hello.to_string(), "hello");
+///
+/// let array = Box::new_in([1, 2, 3], &b);
+/// let slice: Box<[i32]> = bumpalo::unsize_box!(array);
+/// assert_eq!(&*slice, [1, 2, 3]);
+/// ```
+///
+/// The resulting `Box` still borrows the arena, so it cannot outlive it:
+///
+/// ```compile_fail
+/// use bumpalo::{Bump, boxed::Box};
+/// use std::fmt::Display;
+///
+/// let escaped: Box<dyn Display> = {
+///     let b = Bump::new();
+///     bumpalo::unsize_box!(in &b; 5)
+/// };
+/// ```
+///
+/// Plain references returned by [`Bump::alloc`] do not need this macro, as
+/// they coerce to trait objects on their own:
+///
+/// ```
+/// use std::fmt::Debug;
+///
+/// let b = bumpalo::Bump::new();
+/// let x: &mut dyn Debug = b.alloc(42);
+/// assert_eq!(format!("{:?}", x), "42");
+/// ```
+#[macro_export]
+macro_rules! unsize_box {
+    (in $bump:expr; $value:expr) => {
+        $crate::unsize_box!($crate::boxed::Box::new_in($value, $bump))
+    };
+    ($boxed:expr) => {{
+        let (raw, lifetime) = $crate::boxed::Box::__into_raw_parts($boxed);
+        // Raw pointers only support unsizing coercions, so the only thing
+        // `raw` can turn into here is a pointer to the same value.
+        unsafe { $crate::boxed::Box::__from_raw_parts(raw, lifetime) }
+    }};
+}
+
 /// An owned pointer to a bump-allocated `T` value, that runs `Drop`
 /// implementations.
 ///
@@ -165,8 +233,86 @@ impl<'a, T> Box<'a, T> {
         Box(a.alloc(x))
     }
 
+    /// Tries to allocate memory in the arena and place `x` into it, or else
+    /// returns an `Err`.
+    ///
+    /// This doesn't actually allocate if `T` is zero-sized.
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space for `T` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    /// b.set_allocation_limit(Some(0));
+    ///
+    /// assert!(Box::try_new_in([0_u8; 64], &b).is_err());
+    /// ```
+    #[inline(always)]
+    pub fn try_new_in(x: T, a: &'a Bump) -> Result<Box<'a, T>, AllocErr> {
+        a.try_alloc(x).map(Box)
+    }
+
+    /// Allocates memory in the arena and places the result of calling `f`
+    /// into it.
+    ///
+    /// Like [`Bump::alloc_with`], this allocates before calling `f`, so that
+    /// the compiler can construct the value in place, rather than on the
+    /// stack first, which matters for large values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let big = Box::new_with_in(|| [0_u8; 1 << 16], &b);
+    /// assert_eq!(big.len(), 1 << 16);
+    /// ```
+    #[inline(always)]
+    pub fn new_with_in<F>(f: F, a: &'a Bump) -> Box<'a, T>
+    where
+        F: FnOnce() -> T,
+    {
+        Box(a.alloc_with(f))
+    }
+
+    /// Allocates memory in the arena and places `T`'s default value into it.
+    ///
+    /// The value is constructed in place, as with
+    /// [`new_with_in`](Box::new_with_in).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let empty: Box<Vec<u32>> = Box::default_in(&b);
+    /// assert!(empty.is_empty());
+    /// ```
+    #[inline(always)]
+    pub fn default_in(a: &'a Bump) -> Box<'a, T>
+    where
+        T: Default,
+    {
+        Box::new_with_in(T::default, a)
+    }
+
     /// Constructs a new `Pin<Box<T>>`. If `T` does not implement `Unpin`, then
     /// `x` will be pinned in memory and unable to be moved.
+    ///
+    /// Note that the arena does not keep track of pinned boxes. If one is
+    /// leaked, e.g. with [`core::mem::forget`], its `Drop` implementation
+    /// never runs, even when the arena is reset or dropped. Use
+    /// [`BoxIn::pin_in`] for a pinned box that remembers its arena and has
+    /// the arena drop its value in that case.
     #[inline(always)]
     pub fn pin_in(x: T, a: &'a Bump) -> Pin<Box<'a, T>> {
         Box(a.alloc(x)).into()
@@ -284,6 +430,21 @@ impl<'a, T: ?Sized> Box<'a, T> {
         b.deref_mut().0 as *mut T
     }
 
+    // Implementation details of `unsize_box!`. Unlike `into_raw` and
+    // `from_raw`, these carry the `'a` lifetime through the conversion.
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn __into_raw_parts(b: Box<'a, T>) -> (*mut T, PhantomData<&'a ()>) {
+        (Box::into_raw(b), PhantomData)
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub unsafe fn __from_raw_parts(raw: *mut T, _lifetime: PhantomData<&'a ()>) -> Box<'a, T> {
+        Box(&mut *raw)
+    }
+
     /// Consumes and leaks the `Box`, returning a mutable reference,
     /// `&'a mut T`. Note that the type `T` must outlive the chosen lifetime
     /// `'a`. If the type has only static references, or none at all, then this
@@ -363,10 +524,6 @@ impl<'a, 'b, T: ?Sized + PartialEq> PartialEq<Box<'b, T>> for Box<'a, T> {
     fn eq(&self, other: &Box<'b, T>) -> bool {
         PartialEq::eq(&**self, &**other)
     }
-    #[inline]
-    fn ne(&self, other: &Box<'b, T>) -> bool {
-        PartialEq::ne(&**self, &**other)
-    }
 }
 
 impl<'a, 'b, T: ?Sized + PartialOrd> PartialOrd<Box<'b, T>> for Box<'a, T> {
@@ -464,6 +621,43 @@ impl<'a, T: ?Sized> From<Box<'a, T>> for Pin<Box<'a, T>> {
     }
 }
 
+/// Converts a boxed future into a pinned, type-erased future, ready to be
+/// polled or stored alongside futures of other types.
+///
+/// This impl replaces unsize coercion, which is not available on stable Rust.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, boxed::Box};
+/// use std::future::Future;
+/// use std::pin::Pin;
+///
+/// let b = Bump::new();
+///
+/// let futures: [Pin<Box<dyn Future<Output = u32>>>; 2] = [
+///     Box::new_in(async { 1 }, &b).into(),
+///     Box::new_in(async { 2 }, &b).into(),
+/// ];
+/// # let _ = futures;
+/// ```
+impl<'a, F: Future + 'a> From<Box<'a, F>> for Pin<Box<'a, dyn Future<Output = F::Output> + 'a>> {
+    fn from(boxed: Box<'a, F>) -> Self {
+        let raw: *mut (dyn Future<Output = F::Output> + 'a) = Box::<F>::into_raw(boxed);
+        unsafe { Pin::new_unchecked(Box::from_raw(raw)) }
+    }
+}
+
+/// Like the `dyn Future` conversion above, but keeps the future `Send`.
+impl<'a, F: Future + Send + 'a> From<Box<'a, F>>
+    for Pin<Box<'a, dyn Future<Output = F::Output> + Send + 'a>>
+{
+    fn from(boxed: Box<'a, F>) -> Self {
+        let raw: *mut (dyn Future<Output = F::Output> + Send + 'a) = Box::<F>::into_raw(boxed);
+        unsafe { Pin::new_unchecked(Box::from_raw(raw)) }
+    }
+}
+
 impl<'a> Box<'a, dyn Any> {
     #[inline]
     /// Attempt to downcast the box to a concrete type.
@@ -526,6 +720,34 @@ impl<'a> Box<'a, dyn Any + Send> {
     }
 }
 
+impl<'a> Box<'a, dyn Any + Send + Sync> {
+    #[inline]
+    /// Attempt to downcast the box to a concrete type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    /// use std::any::Any;
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let value: Box<dyn Any + Send + Sync> = bumpalo::unsize_box!(in &b; 42_u32);
+    /// let value = value.downcast::<String>().unwrap_err();
+    /// assert_eq!(*value.downcast::<u32>().unwrap(), 42);
+    /// ```
+    pub fn downcast<T: Any>(self) -> Result<Box<'a, T>, Box<'a, dyn Any + Send + Sync>> {
+        if self.is::<T>() {
+            unsafe {
+                let raw: *mut (dyn Any + Send + Sync) = Box::into_raw(self);
+                Ok(Box::from_raw(raw as *mut T))
+            }
+        } else {
+            Err(self)
+        }
+    }
+}
+
 impl<'a, T: fmt::Display + ?Sized> fmt::Display for Box<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&**self, f)
@@ -623,29 +845,125 @@ impl<'a, A> Box<'a, [A]> {
         vec.extend(iter);
         vec.into_boxed_slice()
     }
+
+    /// Converts the boxed slice into a [`Vec`] without copying or
+    /// allocating.
+    ///
+    /// `a` should be the arena that the slice was allocated in, which the
+    /// vector will grow in. The resulting vector's capacity is equal to its
+    /// length.
+    ///
+    /// [`Vec`]: ../collections/vec/struct.Vec.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let slice = Box::from_slice_copy_in(&[1, 2, 3], &b);
+    /// let mut vec = slice.into_vec(&b);
+    /// vec.push(4);
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// ```
+    pub fn into_vec(self, a: &'a Bump) -> crate::collections::Vec<'a, A> {
+        let len = self.len();
+        let ptr = Box::into_raw(self) as *mut A;
+        // The vector will give the memory back to `a` when it is done with
+        // it, which `Bump` ignores if the memory did not come from `a`.
+        unsafe { crate::collections::Vec::from_raw_parts_in(ptr, len, len, a) }
+    }
+}
+
+#[cfg(feature = "collections")]
+impl<'a> Box<'a, str> {
+    /// Converts the boxed string into a [`String`] without copying or
+    /// allocating.
+    ///
+    /// `a` should be the arena that the string was allocated in, which the
+    /// resulting `String` will grow in.
+    ///
+    /// [`String`]: ../collections/string/struct.String.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let s = Box::from_str_in("hello", &b);
+    /// let mut s = s.into_string(&b);
+    /// s.push_str(", world");
+    /// assert_eq!(s, "hello, world");
+    /// ```
+    pub fn into_string(self, a: &'a Bump) -> crate::collections::String<'a> {
+        let len = self.len();
+        let ptr = Box::into_raw(self) as *mut u8;
+        unsafe { crate::collections::String::from_raw_parts_in(ptr, len, len, a) }
+    }
+}
+
+impl<'a, T: Copy> Box<'a, [T]> {
+    /// Copies a slice into a new boxed slice in the given arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let slice = Box::from_slice_copy_in(&[1, 2, 3], &b);
+    /// assert_eq!(*slice, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn from_slice_copy_in(src: &[T], a: &'a Bump) -> Box<'a, [T]> {
+        Box(a.alloc_slice_copy(src))
+    }
+}
+
+impl<'a> Box<'a, str> {
+    /// Copies a string slice into a new boxed string in the given arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::Box};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let s = Box::from_str_in("hello", &b);
+    /// assert_eq!(&*s, "hello");
+    /// ```
+    #[inline]
+    pub fn from_str_in(src: &str, a: &'a Bump) -> Box<'a, str> {
+        Box(a.alloc_str(src))
+    }
 }
 
 impl<'a, T: ?Sized> borrow::Borrow<T> for Box<'a, T> {
     fn borrow(&self) -> &T {
-        &**self
+        self
     }
 }
 
 impl<'a, T: ?Sized> borrow::BorrowMut<T> for Box<'a, T> {
     fn borrow_mut(&mut self) -> &mut T {
-        &mut **self
+        self
     }
 }
 
 impl<'a, T: ?Sized> AsRef<T> for Box<'a, T> {
     fn as_ref(&self) -> &T {
-        &**self
+        self
     }
 }
 
 impl<'a, T: ?Sized> AsMut<T> for Box<'a, T> {
     fn as_mut(&mut self) -> &mut T {
-        &mut **self
+        self
     }
 }
 
@@ -682,11 +1000,363 @@ impl<'a, T, const N: usize> TryFrom<Box<'a, [T]>> for Box<'a, [T; N]> {
     }
 }
 
+/// Values that can be cloned into a new [`Box`] in a given arena.
+///
+/// This is implemented for all `T: Clone`, for slices of `T: Clone`, and for
+/// `str`. It is what [`Box::clone_in`] and the `Clone` implementation of
+/// [`BoxIn`] are built on.
+pub trait CloneIn {
+    /// Clone `self` into a new `Box` allocated in `bump`.
+    fn clone_in<'b>(&self, bump: &'b Bump) -> Box<'b, Self>;
+}
+
+impl<T: Clone> CloneIn for T {
+    #[inline]
+    fn clone_in<'b>(&self, bump: &'b Bump) -> Box<'b, T> {
+        Box::new_in(self.clone(), bump)
+    }
+}
+
+impl<T: Clone> CloneIn for [T] {
+    #[inline]
+    fn clone_in<'b>(&self, bump: &'b Bump) -> Box<'b, [T]> {
+        Box(bump.alloc_slice_clone(self))
+    }
+}
+
+impl CloneIn for str {
+    #[inline]
+    fn clone_in<'b>(&self, bump: &'b Bump) -> Box<'b, str> {
+        Box(bump.alloc_str(self))
+    }
+}
+
+impl<'a, T: ?Sized + CloneIn> Box<'a, T> {
+    /// Clones the boxed value into a new `Box` in the given arena.
+    ///
+    /// `Box` does not remember which arena it was allocated in, so it cannot
+    /// implement `Clone`. Use this method instead, or wrap the box in a
+    /// [`BoxIn`], which does implement `Clone`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::{Box, CloneIn}};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let x = Box::new_in(String::from("hello"), &b);
+    /// let y = x.clone_in(&b);
+    /// assert_eq!(x, y);
+    ///
+    /// let s: Box<str> = "world".clone_in(&b);
+    /// assert_eq!(s.clone_in(&b), s);
+    /// ```
+    #[inline]
+    pub fn clone_in<'b>(&self, bump: &'b Bump) -> Box<'b, T> {
+        (**self).clone_in(bump)
+    }
+}
+
+/// A [`Box`] that remembers the arena it was allocated in.
+///
+/// Unlike a plain `Box`, a `BoxIn` implements `Clone`, by cloning the boxed
+/// value into the same arena, so it can be used in generic code that
+/// requires `Clone`. It is two pointers larger than a `Box`.
+///
+/// A `BoxIn` pinned with [`BoxIn::pin_in`] is registered with its arena,
+/// which drops the value when it is reset or dropped if the box was leaked.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, boxed::BoxIn};
+///
+/// let b = Bump::new();
+///
+/// let x = BoxIn::new_in(vec![1, 2, 3], &b);
+/// let mut y = x.clone();
+/// y.push(4);
+/// assert_eq!(*x, [1, 2, 3]);
+/// assert_eq!(*y, [1, 2, 3, 4]);
+/// ```
+pub struct BoxIn<'a, T: ?Sized> {
+    boxed: Box<'a, T>,
+    bump: &'a Bump,
+    // The entry in the arena's drop list, if the value is pinned.
+    drop_entry: Option<NonNull<DropEntry>>,
+}
+
+impl<'a, T> BoxIn<'a, T> {
+    /// Allocates `x` in the given arena, and remembers the arena.
+    #[inline]
+    pub fn new_in(x: T, bump: &'a Bump) -> BoxIn<'a, T> {
+        BoxIn {
+            boxed: Box::new_in(x, bump),
+            bump,
+            drop_entry: None,
+        }
+    }
+}
+
+impl<'a, T> BoxIn<'a, T> {
+    /// Allocates `x` in the given arena and pins it, remembering the arena.
+    ///
+    /// Unlike with [`Box::pin_in`], the value is dropped even if the pinned
+    /// box is leaked, e.g. with [`core::mem::forget`]: the arena drops it
+    /// when it is reset or dropped, before the value's memory is reused. That
+    /// is why the value must be `Send` and `'static`, since the arena may
+    /// outlive anything else the value borrows and may be sent to another
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, boxed::BoxIn};
+    /// use std::future::Future;
+    /// use std::pin::Pin;
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let fut = BoxIn::pin_in(async { 42 }, &b);
+    ///
+    /// // Futures can also be pinned and type-erased in one step.
+    /// let erased: Pin<BoxIn<dyn Future<Output = u32>>> = BoxIn::new_in(async { 1 }, &b).into();
+    /// # let _ = (fut, erased);
+    /// ```
+    #[inline]
+    pub fn pin_in(x: T, bump: &'a Bump) -> Pin<BoxIn<'a, T>>
+    where
+        T: Send + 'static,
+    {
+        BoxIn::new_in(x, bump).into()
+    }
+}
+
+impl<'a, T: ?Sized> BoxIn<'a, T> {
+    /// Wraps a `Box` together with the arena it was allocated in.
+    ///
+    /// `bump` should be the arena that `boxed` was allocated in. Otherwise
+    /// clones of the `BoxIn` are allocated in a different arena than the
+    /// original, which is safe, but probably not what was intended.
+    #[inline]
+    pub fn from_box(boxed: Box<'a, T>, bump: &'a Bump) -> BoxIn<'a, T> {
+        BoxIn {
+            boxed,
+            bump,
+            drop_entry: None,
+        }
+    }
+
+    /// Unwraps the underlying `Box`, forgetting the arena.
+    #[inline]
+    pub fn into_box(b: BoxIn<'a, T>) -> Box<'a, T> {
+        let (boxed, _) = BoxIn::into_parts(b);
+        boxed
+    }
+
+    /// Splits `b` into its box and arena, taking the value out of the arena's
+    /// drop list.
+    fn into_parts(b: BoxIn<'a, T>) -> (Box<'a, T>, &'a Bump) {
+        let b = ManuallyDrop::new(b);
+        if let Some(entry) = b.drop_entry {
+            unsafe { DropEntry::disarm(entry) };
+        }
+        // Safety: `b` is never used or dropped again.
+        (unsafe { ptr::read(&b.boxed) }, b.bump)
+    }
+
+    /// Returns the arena that the value is allocated in.
+    #[inline]
+    pub fn bump(b: &BoxIn<'a, T>) -> &'a Bump {
+        b.bump
+    }
+}
+
+impl<'a, T: ?Sized + CloneIn> Clone for BoxIn<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let boxed = self.boxed.clone_in(self.bump);
+        if self.drop_entry.is_some() {
+            // A clone of a pinned box is pinned, too. The original could be
+            // registered, so the clone, which has the same type, can be, too.
+            unsafe { BoxIn::register(boxed, self.bump) }
+        } else {
+            BoxIn::from_box(boxed, self.bump)
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Drop for BoxIn<'a, T> {
+    fn drop(&mut self) {
+        // The box drops the value right after this, so the arena must not.
+        if let Some(entry) = self.drop_entry {
+            unsafe { DropEntry::disarm(entry) };
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for BoxIn<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.boxed
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for BoxIn<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.boxed
+    }
+}
+
+impl<'a, T: ?Sized> borrow::Borrow<T> for BoxIn<'a, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T: ?Sized> borrow::BorrowMut<T> for BoxIn<'a, T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'a, T: ?Sized> AsRef<T> for BoxIn<'a, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T: ?Sized> AsMut<T> for BoxIn<'a, T> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'a, T: ?Sized> From<BoxIn<'a, T>> for Box<'a, T> {
+    #[inline]
+    fn from(b: BoxIn<'a, T>) -> Box<'a, T> {
+        BoxIn::into_box(b)
+    }
+}
+
+impl<'a, 'b, T: ?Sized + PartialEq> PartialEq<BoxIn<'b, T>> for BoxIn<'a, T> {
+    #[inline]
+    fn eq(&self, other: &BoxIn<'b, T>) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<'a, 'b, T: ?Sized + PartialOrd> PartialOrd<BoxIn<'b, T>> for BoxIn<'a, T> {
+    #[inline]
+    fn partial_cmp(&self, other: &BoxIn<'b, T>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: ?Sized + Ord> Ord for BoxIn<'a, T> {
+    #[inline]
+    fn cmp(&self, other: &BoxIn<'a, T>) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: ?Sized + Eq> Eq for BoxIn<'a, T> {}
+
+impl<'a, T: ?Sized + Hash> Hash for BoxIn<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<'a, T: ?Sized + Send + 'static> From<BoxIn<'a, T>> for Pin<BoxIn<'a, T>> {
+    /// Converts a `BoxIn<T>` into a `Pin<BoxIn<T>>`, in place, registering
+    /// the value with the arena as [`BoxIn::pin_in`] does.
+    fn from(b: BoxIn<'a, T>) -> Self {
+        let (boxed, bump) = BoxIn::into_parts(b);
+        // As with `Box`, the value can't be moved out of a `Pin<BoxIn<T>>`
+        // when `T: !Unpin`, and the arena drops it if the box is leaked.
+        unsafe { Pin::new_unchecked(BoxIn::register(boxed, bump)) }
+    }
+}
+
+impl<'a, T: ?Sized> BoxIn<'a, T> {
+    /// Registers `boxed`'s value with `bump`'s drop list.
+    ///
+    /// ## Safety
+    ///
+    /// `boxed` must be allocated in `bump`, and its value must be `Send` and
+    /// `'static`, although its type may be erased to a trait object that
+    /// isn't.
+    unsafe fn register(boxed: Box<'a, T>, bump: &'a Bump) -> BoxIn<'a, T> {
+        // The box must be derived from the pointer that the arena drops the
+        // value through, so that using the box doesn't invalidate it.
+        let raw = Box::into_raw(boxed);
+        let drop_entry = Some(bump.register_drop(raw));
+        BoxIn {
+            boxed: Box::from_raw(raw),
+            bump,
+            drop_entry,
+        }
+    }
+}
+
+/// Converts a future boxed together with its arena into a pinned, type-erased
+/// future.
+///
+/// The future must be `Send` and `'static`, like for [`BoxIn::pin_in`].
+impl<'a, F: Future + Send + 'static> From<BoxIn<'a, F>>
+    for Pin<BoxIn<'a, dyn Future<Output = F::Output> + 'a>>
+{
+    fn from(b: BoxIn<'a, F>) -> Self {
+        let (boxed, bump) = BoxIn::into_parts(b);
+        let raw: *mut (dyn Future<Output = F::Output> + 'a) = Box::<F>::into_raw(boxed);
+        unsafe { Pin::new_unchecked(BoxIn::register(Box::from_raw(raw), bump)) }
+    }
+}
+
+/// Like the `dyn Future` conversion above, but keeps the future `Send`.
+impl<'a, F: Future + Send + 'static> From<BoxIn<'a, F>>
+    for Pin<BoxIn<'a, dyn Future<Output = F::Output> + Send + 'a>>
+{
+    fn from(b: BoxIn<'a, F>) -> Self {
+        let (boxed, bump) = BoxIn::into_parts(b);
+        let raw: *mut (dyn Future<Output = F::Output> + Send + 'a) = Box::<F>::into_raw(boxed);
+        unsafe { Pin::new_unchecked(BoxIn::register(Box::from_raw(raw), bump)) }
+    }
+}
+
+impl<'a, T: ?Sized> Unpin for BoxIn<'a, T> {}
+
+impl<'a, F: ?Sized + Future + Unpin> Future for BoxIn<'a, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        F::poll(Pin::new(&mut *self), cx)
+    }
+}
+
+impl<'a, T: fmt::Display + ?Sized> fmt::Display for BoxIn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'a, T: fmt::Debug + ?Sized> fmt::Debug for BoxIn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serialize {
     use super::*;
 
-    use serde::{Serialize, Serializer};
+    use crate::serde::DeserializeIn;
+    use serde::{Deserializer, Serialize, Serializer};
 
     impl<'a, T> Serialize for Box<'a, T>
     where
@@ -696,4 +1366,37 @@ mod serialize {
             T::serialize(self, serializer)
         }
     }
+
+    impl<'de, 'a, T> DeserializeIn<'de, 'a> for Box<'a, T>
+    where
+        T: DeserializeIn<'de, 'a>,
+    {
+        fn deserialize_in<D>(deserializer: D, bump: &'a Bump) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            T::deserialize_in(deserializer, bump).map(|x| Box::new_in(x, bump))
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_in {
+    use super::*;
+
+    use crate::arbitrary::ArbitraryIn;
+    use arbitrary::{Result, Unstructured};
+
+    impl<'a, 'bump, T> ArbitraryIn<'a, 'bump> for Box<'bump, T>
+    where
+        T: ArbitraryIn<'a, 'bump>,
+    {
+        fn arbitrary_in(u: &mut Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+            T::arbitrary_in(u, bump).map(|x| Box::new_in(x, bump))
+        }
+
+        fn arbitrary_take_rest_in(u: Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+            T::arbitrary_take_rest_in(u, bump).map(|x| Box::new_in(x, bump))
+        }
+    }
 }
@@ -0,0 +1,357 @@
+//! A priority queue implemented with a binary heap, that allocates inside a
+//! bump arena.
+//!
+//! See [`BinaryHeap`] for details.
+
+use crate::collections::vec::Vec;
+use crate::Bump;
+use core::fmt;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+/// A priority queue implemented with a binary max-heap, stored in a
+/// [`Vec`] allocated in a bump arena.
+///
+/// This is like `std::collections::BinaryHeap`, except that it allocates in
+/// the arena, so algorithms driven by a priority queue, such as Dijkstra's or
+/// A* search, or event scheduling, can run without touching the global heap.
+/// [`pop`](BinaryHeap::pop) returns the greatest item first; wrap items in
+/// [`core::cmp::Reverse`] for a min-heap.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::BinaryHeap};
+/// use core::cmp::Reverse;
+///
+/// let b = Bump::new();
+///
+/// // Schedule events by time, earliest first.
+/// let mut events = BinaryHeap::new_in(&b);
+/// events.push(Reverse((30, "render")));
+/// events.push(Reverse((10, "input")));
+/// events.push(Reverse((20, "update")));
+///
+/// assert_eq!(events.peek(), Some(&Reverse((10, "input"))));
+/// assert_eq!(events.len(), 3);
+///
+/// let order: std::vec::Vec<_> = std::iter::from_fn(|| events.pop())
+///     .map(|Reverse((_, name))| name)
+///     .collect();
+/// assert_eq!(order, ["input", "update", "render"]);
+/// ```
+#[derive(Clone)]
+pub struct BinaryHeap<'bump, T> {
+    // Every item is at least as great as its children, the items at
+    // `2 * i + 1` and `2 * i + 2`.
+    data: Vec<'bump, T>,
+}
+
+impl<'bump, T: Ord> BinaryHeap<'bump, T> {
+    /// Constructs a new, empty `BinaryHeap`.
+    ///
+    /// This does not allocate.
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> BinaryHeap<'bump, T> {
+        BinaryHeap {
+            data: Vec::new_in(bump),
+        }
+    }
+
+    /// Constructs a new, empty `BinaryHeap` with room for at least `capacity`
+    /// items.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> BinaryHeap<'bump, T> {
+        BinaryHeap {
+            data: Vec::with_capacity_in(capacity, bump),
+        }
+    }
+
+    /// Returns a mutable reference to the greatest item in the heap, or
+    /// `None` if it is empty.
+    ///
+    /// If the item is modified, the heap is restored when the returned
+    /// [`PeekMut`] is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::BinaryHeap};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut heap = BinaryHeap::new_in(&b);
+    /// heap.extend([1, 5, 2]);
+    ///
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 0;
+    /// }
+    /// assert_eq!(heap.peek(), Some(&2));
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, 'bump, T>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut {
+                heap: self,
+                sift: false,
+            })
+        }
+    }
+
+    /// Removes the greatest item from the heap and returns it, or `None` if
+    /// it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                mem::swap(&mut item, &mut self.data[0]);
+                self.sift_down(0, self.data.len());
+            }
+            item
+        })
+    }
+
+    /// Pushes an item onto the heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    /// Consumes the heap and returns its items in ascending order, in the
+    /// same arena.
+    ///
+    /// This sorts the items in place, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::BinaryHeap};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut heap = BinaryHeap::new_in(&b);
+    /// heap.extend([4, 1, 3, 2]);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 4]);
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<'bump, T> {
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    /// Moves the item at `pos` up towards the root until its parent is at
+    /// least as great.
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.data[pos] <= self.data[parent] {
+                break;
+            }
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    /// Moves the item at `pos` down towards the leaves until it is at least
+    /// as great as its children, only considering the items before `end`.
+    fn sift_down(&mut self, mut pos: usize, end: usize) {
+        loop {
+            let mut child = 2 * pos + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && self.data[child + 1] > self.data[child] {
+                child += 1;
+            }
+            if self.data[pos] >= self.data[child] {
+                break;
+            }
+            self.data.swap(pos, child);
+            pos = child;
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let len = self.data.len();
+        for pos in (0..len / 2).rev() {
+            self.sift_down(pos, len);
+        }
+    }
+}
+
+impl<'bump, T> BinaryHeap<'bump, T> {
+    /// Returns the arena this heap allocates in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.data.bump()
+    }
+
+    /// Returns the greatest item in the heap, or `None` if it is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the number of items in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the heap contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the number of items the heap can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves room for at least `additional` more items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Removes all items from the heap.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the items in the heap, in their internal order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Returns an iterator over the items in the heap, in their internal
+    /// order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Consumes the heap and returns its items in their internal order.
+    #[inline]
+    pub fn into_vec(self) -> Vec<'bump, T> {
+        self.data
+    }
+}
+
+impl<'bump, T: Ord> From<Vec<'bump, T>> for BinaryHeap<'bump, T> {
+    /// Turns a vector into a heap, in place and in linear time.
+    fn from(data: Vec<'bump, T>) -> BinaryHeap<'bump, T> {
+        let mut heap = BinaryHeap { data };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<'bump, T> From<BinaryHeap<'bump, T>> for Vec<'bump, T> {
+    #[inline]
+    fn from(heap: BinaryHeap<'bump, T>) -> Vec<'bump, T> {
+        heap.data
+    }
+}
+
+impl<'bump, T: fmt::Debug> fmt::Debug for BinaryHeap<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'bump, T: Ord> Extend<T> for BinaryHeap<'bump, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, 'bump, T: 'a + Ord + Copy> Extend<&'a T> for BinaryHeap<'bump, T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a, 'bump, T> IntoIterator for &'a BinaryHeap<'bump, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// A mutable reference to the greatest item in a [`BinaryHeap`].
+///
+/// This is returned by [`BinaryHeap::peek_mut`]. If the item is modified
+/// through it, the heap is restored when it is dropped.
+pub struct PeekMut<'a, 'bump, T: Ord> {
+    heap: &'a mut BinaryHeap<'bump, T>,
+    // Whether the item was borrowed mutably, and may have changed.
+    sift: bool,
+}
+
+impl<'a, 'bump, T: Ord> PeekMut<'a, 'bump, T> {
+    /// Removes the peeked item from the heap and returns it.
+    pub fn pop(mut this: PeekMut<'a, 'bump, T>) -> T {
+        // The heap is restored by popping, so dropping `this` doesn't need
+        // to.
+        this.sift = false;
+        this.heap.pop().unwrap()
+    }
+}
+
+impl<'a, 'bump, T: Ord> Deref for PeekMut<'a, 'bump, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<'a, 'bump, T: Ord> DerefMut for PeekMut<'a, 'bump, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.sift = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<'a, 'bump, T: Ord> Drop for PeekMut<'a, 'bump, T> {
+    fn drop(&mut self) {
+        if self.sift {
+            let len = self.heap.len();
+            self.heap.sift_down(0, len);
+        }
+    }
+}
+
+impl<'a, 'bump, T: Ord + fmt::Debug> fmt::Debug for PeekMut<'a, 'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&**self).finish()
+    }
+}
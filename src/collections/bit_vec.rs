@@ -0,0 +1,471 @@
+//! A vector of bits, packed into words, that allocates inside a bump arena.
+//!
+//! See [`BitVec`] for details.
+
+use crate::collections::vec::Vec;
+use crate::Bump;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::ops::{Index, Range};
+use core::slice;
+
+const BITS: usize = usize::BITS as usize;
+
+/// The number of words needed to store `len` bits.
+#[inline]
+fn words_for(len: usize) -> usize {
+    len / BITS + (len % BITS != 0) as usize
+}
+
+/// A growable vector of bits, packed into `usize` words allocated in a bump
+/// arena.
+///
+/// Besides working as a vector of `bool`s, a `BitVec` can be used as a fixed
+/// size bit set: [`union`](BitVec::union), [`intersect`](BitVec::intersect),
+/// and [`difference`](BitVec::difference) combine two bit vectors of the same
+/// length in place, a word at a time, and [`ones`](BitVec::ones) iterates
+/// over the indices of the set bits.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::BitVec};
+///
+/// let b = Bump::new();
+///
+/// let mut live = BitVec::from_elem_in(100, false, &b);
+/// live.set(3, true);
+/// live.set(70, true);
+///
+/// let mut gen = BitVec::from_elem_in(100, false, &b);
+/// gen.set(42, true);
+///
+/// // `union` reports whether anything changed, which is handy for finding
+/// // fixed points.
+/// assert!(live.union(&gen));
+/// assert!(!live.union(&gen));
+/// assert!(live.ones().eq([3, 42, 70]));
+/// ```
+#[derive(Clone)]
+pub struct BitVec<'bump> {
+    // Bits past `len` in the last word are always zero.
+    words: Vec<'bump, usize>,
+    len: usize,
+}
+
+impl<'bump> BitVec<'bump> {
+    /// Constructs a new, empty `BitVec`.
+    ///
+    /// This does not allocate.
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> BitVec<'bump> {
+        BitVec {
+            words: Vec::new_in(bump),
+            len: 0,
+        }
+    }
+
+    /// Constructs a new, empty `BitVec` with room for at least `bits` bits.
+    #[inline]
+    pub fn with_capacity_in(bits: usize, bump: &'bump Bump) -> BitVec<'bump> {
+        BitVec {
+            words: Vec::with_capacity_in(words_for(bits), bump),
+            len: 0,
+        }
+    }
+
+    /// Constructs a `BitVec` of `len` bits, all set to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::BitVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let bits = BitVec::from_elem_in(10, true, &b);
+    /// assert_eq!(bits.len(), 10);
+    /// assert!(bits.all());
+    /// ```
+    pub fn from_elem_in(len: usize, value: bool, bump: &'bump Bump) -> BitVec<'bump> {
+        let mut bits = BitVec::with_capacity_in(len, bump);
+        bits.grow(len, value);
+        bits
+    }
+
+    /// Returns the number of bits in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bits the vector can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.words.capacity().saturating_mul(BITS)
+    }
+
+    /// Returns the bit at index `i`, or `None` if `i` is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if i < self.len {
+            Some(self.words[i / BITS] & (1 << (i % BITS)) != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the bit at index `i` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(
+            i < self.len,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len,
+            i
+        );
+        let word = &mut self.words[i / BITS];
+        let mask = 1 << (i % BITS);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /// Sets every bit to `false`, keeping the length unchanged.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.fill_words(0);
+    }
+
+    /// Sets every bit to `true`.
+    #[inline]
+    pub fn set_all(&mut self) {
+        self.fill_words(!0);
+    }
+
+    /// Flips every bit.
+    pub fn negate(&mut self) {
+        for word in self.words.iter_mut() {
+            *word = !*word;
+        }
+        self.mask_last_word();
+    }
+
+    /// Appends a bit to the back of the vector.
+    #[inline]
+    pub fn push(&mut self, value: bool) {
+        if self.len % BITS == 0 {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    /// Removes the last bit from the vector and returns it, or `None` if the
+    /// vector is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<bool> {
+        let value = self.get(self.len.checked_sub(1)?)?;
+        self.truncate(self.len - 1);
+        Some(value)
+    }
+
+    /// Shortens the vector to `len` bits.
+    ///
+    /// If `len` is greater than the vector's current length, this has no
+    /// effect.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.len = len;
+            self.words.truncate(words_for(len));
+            self.mask_last_word();
+        }
+    }
+
+    /// Appends `n` bits, all set to `value`, to the back of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new length overflows `usize`.
+    pub fn grow(&mut self, n: usize, value: bool) {
+        let new_len = self.len.checked_add(n).expect("capacity overflow");
+        if value && self.len % BITS != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last |= !0 << (self.len % BITS);
+            }
+        }
+        self.words
+            .resize(words_for(new_len), if value { !0 } else { 0 });
+        self.len = new_len;
+        self.mask_last_word();
+    }
+
+    /// Sets every bit that is set in `other`, and returns whether this
+    /// changed any bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vectors have different lengths.
+    #[inline]
+    pub fn union(&mut self, other: &BitVec<'_>) -> bool {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Clears every bit that is not set in `other`, and returns whether this
+    /// changed any bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vectors have different lengths.
+    #[inline]
+    pub fn intersect(&mut self, other: &BitVec<'_>) -> bool {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Clears every bit that is set in `other`, and returns whether this
+    /// changed any bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vectors have different lengths.
+    #[inline]
+    pub fn difference(&mut self, other: &BitVec<'_>) -> bool {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Returns the number of bits that are set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns `true` if any bit is set.
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&w| w != 0)
+    }
+
+    /// Returns `true` if no bit is set.
+    #[inline]
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    /// Returns `true` if every bit is set.
+    ///
+    /// This is `true` for an empty vector.
+    pub fn all(&self) -> bool {
+        self.count_ones() == self.len
+    }
+
+    /// Returns an iterator over the bits of the vector, as `bool`s.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            bits: self,
+            range: 0..self.len,
+        }
+    }
+
+    /// Returns an iterator over the indices of the set bits, in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::BitVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut bits = BitVec::from_elem_in(200, false, &b);
+    /// bits.set(1, true);
+    /// bits.set(150, true);
+    /// assert!(bits.ones().eq([1, 150]));
+    /// ```
+    #[inline]
+    pub fn ones(&self) -> Ones<'_> {
+        let mut words = self.words.iter();
+        Ones {
+            current: words.next().copied().unwrap_or(0),
+            base: 0,
+            words,
+        }
+    }
+
+    /// Returns the words that the bits are packed into.
+    ///
+    /// Bit `i` is stored in bit `i % usize::BITS` of word `i / usize::BITS`,
+    /// and the bits past the end of the vector in the last word are always
+    /// zero.
+    #[inline]
+    pub fn words(&self) -> &[usize] {
+        &self.words
+    }
+
+    fn fill_words(&mut self, value: usize) {
+        for word in self.words.iter_mut() {
+            *word = value;
+        }
+        self.mask_last_word();
+    }
+
+    /// Clears the bits past the end of the vector in the last word.
+    #[inline]
+    fn mask_last_word(&mut self) {
+        let rem = self.len % BITS;
+        if rem != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << rem) - 1;
+            }
+        }
+    }
+
+    fn combine(&mut self, other: &BitVec<'_>, f: impl Fn(usize, usize) -> usize) -> bool {
+        assert_eq!(self.len, other.len, "bit vectors must have the same length");
+        let mut changed = false;
+        for (a, &b) in self.words.iter_mut().zip(other.words.iter()) {
+            let new = f(*a, b);
+            changed |= new != *a;
+            *a = new;
+        }
+        changed
+    }
+}
+
+impl<'bump> fmt::Debug for BitVec<'bump> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BitVec(")?;
+        for bit in self {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'b> PartialEq<BitVec<'b>> for BitVec<'a> {
+    #[inline]
+    fn eq(&self, other: &BitVec<'b>) -> bool {
+        self.len == other.len && self.words[..] == other.words[..]
+    }
+}
+
+impl<'bump> Eq for BitVec<'bump> {}
+
+impl<'bump> Hash for BitVec<'bump> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.words[..].hash(state);
+    }
+}
+
+impl<'bump> Index<usize> for BitVec<'bump> {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, i: usize) -> &bool {
+        match self.get(i) {
+            Some(true) => &true,
+            Some(false) => &false,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, i
+            ),
+        }
+    }
+}
+
+impl<'bump> Extend<bool> for BitVec<'bump> {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.words
+            .reserve(words_for(self.len.saturating_add(lower)) - self.words.len());
+        for bit in iter {
+            self.push(bit);
+        }
+    }
+}
+
+impl<'a, 'bump> IntoIterator for &'a BitVec<'bump> {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the bits of a [`BitVec`].
+///
+/// This struct is created by [`BitVec::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    bits: &'a BitVec<'a>,
+    range: Range<usize>,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().map(|i| self.bits[i])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<bool> {
+        self.range.next_back().map(|i| self.bits[i])
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// An iterator over the indices of the set bits of a [`BitVec`].
+///
+/// This struct is created by [`BitVec::ones`].
+#[derive(Clone, Debug)]
+pub struct Ones<'a> {
+    // The not yet yielded bits of the word starting at bit `base`.
+    current: usize,
+    base: usize,
+    words: slice::Iter<'a, usize>,
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.current = *self.words.next()?;
+            self.base += BITS;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        // Clear the lowest set bit.
+        self.current &= self.current - 1;
+        Some(self.base + bit)
+    }
+}
+
+impl FusedIterator for Ones<'_> {}
@@ -1,6 +1,6 @@
 #[cfg(feature = "boxed")]
 use crate::boxed::Box;
-use crate::collections::{String, Vec};
+use crate::collections::{BinaryHeap, BitVec, InlineVec, LinkedList, String, Vec};
 use crate::Bump;
 
 /// A trait for types that support being constructed from an iterator, parameterized by an allocator.
@@ -51,6 +51,54 @@ impl<'bump, T> FromIteratorIn<T> for Vec<'bump, T> {
     }
 }
 
+impl<'bump, T, const N: usize> FromIteratorIn<T> for InlineVec<'bump, T, N> {
+    type Alloc = &'bump Bump;
+
+    fn from_iter_in<I>(iter: I, alloc: Self::Alloc) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut v = InlineVec::new_in(alloc);
+        v.extend(iter);
+        v
+    }
+}
+
+impl<'bump> FromIteratorIn<bool> for BitVec<'bump> {
+    type Alloc = &'bump Bump;
+
+    fn from_iter_in<I>(iter: I, alloc: Self::Alloc) -> Self
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        let mut bits = BitVec::new_in(alloc);
+        bits.extend(iter);
+        bits
+    }
+}
+
+impl<'bump, T: Ord> FromIteratorIn<T> for BinaryHeap<'bump, T> {
+    type Alloc = &'bump Bump;
+
+    fn from_iter_in<I>(iter: I, alloc: Self::Alloc) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        BinaryHeap::from(Vec::from_iter_in(iter, alloc))
+    }
+}
+
+impl<'bump, T> FromIteratorIn<T> for LinkedList<'bump, T> {
+    type Alloc = &'bump Bump;
+
+    fn from_iter_in<I>(iter: I, alloc: Self::Alloc) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        LinkedList::from_iter_in(iter, alloc)
+    }
+}
+
 impl<T, V: FromIteratorIn<T>> FromIteratorIn<Option<T>> for Option<V> {
     type Alloc = V::Alloc;
     fn from_iter_in<I>(iter: I, alloc: Self::Alloc) -> Self
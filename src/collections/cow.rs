@@ -0,0 +1,329 @@
+//! A clone-on-write smart pointer whose owned form lives in a bump arena.
+//!
+//! See [`Cow`] for details.
+
+use crate::collections::{String, Vec};
+use crate::Bump;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// Borrowed data that can be cloned into an owned form inside a bump arena.
+///
+/// This is the arena-aware counterpart of [`std::borrow::ToOwned`], and is
+/// used by [`Cow`]. It is implemented for slices, whose owned form is a
+/// [`Vec`], and for `str`, whose owned form is a [`String`].
+///
+/// [`std::borrow::ToOwned`]: https://doc.rust-lang.org/std/borrow/trait.ToOwned.html
+pub trait ToOwnedIn<'bump> {
+    /// The resulting type after obtaining ownership.
+    type Owned: Borrow<Self>;
+
+    /// Creates owned data from borrowed data, by cloning it into `bump`.
+    fn to_owned_in(&self, bump: &'bump Bump) -> Self::Owned;
+}
+
+impl<'bump, T: 'bump + Clone> ToOwnedIn<'bump> for [T] {
+    type Owned = Vec<'bump, T>;
+
+    #[inline]
+    fn to_owned_in(&self, bump: &'bump Bump) -> Vec<'bump, T> {
+        let mut v = Vec::with_capacity_in(self.len(), bump);
+        v.extend_from_slice(self);
+        v
+    }
+}
+
+impl<'bump> ToOwnedIn<'bump> for str {
+    type Owned = String<'bump>;
+
+    #[inline]
+    fn to_owned_in(&self, bump: &'bump Bump) -> String<'bump> {
+        String::from_str_in(self, bump)
+    }
+}
+
+/// A clone-on-write smart pointer whose owned form lives in a bump arena.
+///
+/// This is the arena counterpart of [`std::borrow::Cow`]: it holds either
+/// borrowed data, such as a `&'bump [T]` or a `&'bump str`, or the
+/// corresponding owned bump collection, a [`Vec`] or a [`String`]. The data is
+/// only cloned into the arena when it is first mutated via
+/// [`to_mut`](Cow::to_mut).
+///
+/// Because borrowed data does not know which arena to clone itself into, the
+/// methods that may need to clone take the [`Bump`] as an argument.
+///
+/// [`std::borrow::Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::{Cow, String}};
+///
+/// fn expand_tabs<'bump>(s: &'bump str, bump: &'bump Bump) -> Cow<'bump, str> {
+///     if s.contains('\t') {
+///         Cow::Owned(String::from_str_in(&s.replace('\t', "    "), bump))
+///     } else {
+///         Cow::Borrowed(s)
+///     }
+/// }
+///
+/// let b = Bump::new();
+/// let plain = expand_tabs("no tabs", &b);
+/// assert!(plain.is_borrowed());
+///
+/// let tabbed = expand_tabs("\tone tab", &b);
+/// assert!(tabbed.is_owned());
+/// assert_eq!(tabbed, "    one tab");
+/// ```
+pub enum Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + ?Sized,
+{
+    /// Borrowed data.
+    Borrowed(&'bump B),
+
+    /// Owned data, allocated in a bump arena.
+    Owned(<B as ToOwnedIn<'bump>>::Owned),
+}
+
+impl<'bump, B> Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + ?Sized,
+{
+    /// Returns `true` if the data is borrowed.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Cow::Borrowed(_))
+    }
+
+    /// Returns `true` if the data is owned.
+    #[inline]
+    pub fn is_owned(&self) -> bool {
+        !self.is_borrowed()
+    }
+
+    /// Acquires a mutable reference to the owned form of the data, cloning
+    /// the data into `bump` if it is not already owned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Cow};
+    ///
+    /// let b = Bump::new();
+    /// let data = [1, 2, 3];
+    /// let mut cow = Cow::Borrowed(&data[..]);
+    ///
+    /// cow.to_mut(&b).push(4);
+    /// assert!(cow.is_owned());
+    /// assert_eq!(*cow, [1, 2, 3, 4]);
+    ///
+    /// // Already owned, so this does not clone again.
+    /// cow.to_mut(&b)[0] = 0;
+    /// assert_eq!(*cow, [0, 2, 3, 4]);
+    /// ```
+    pub fn to_mut(&mut self, bump: &'bump Bump) -> &mut <B as ToOwnedIn<'bump>>::Owned {
+        if let Cow::Borrowed(borrowed) = *self {
+            *self = Cow::Owned(borrowed.to_owned_in(bump));
+        }
+        match self {
+            Cow::Borrowed(_) => unreachable!(),
+            Cow::Owned(owned) => owned,
+        }
+    }
+
+    /// Extracts the owned data, cloning it into `bump` if it is not already
+    /// owned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Cow};
+    ///
+    /// let b = Bump::new();
+    /// let cow: Cow<str> = Cow::Borrowed("hello");
+    /// let s = cow.into_owned(&b);
+    /// assert_eq!(s, "hello");
+    /// ```
+    pub fn into_owned(self, bump: &'bump Bump) -> <B as ToOwnedIn<'bump>>::Owned {
+        match self {
+            Cow::Borrowed(borrowed) => borrowed.to_owned_in(bump),
+            Cow::Owned(owned) => owned,
+        }
+    }
+}
+
+impl<'bump, B> Deref for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + ?Sized,
+{
+    type Target = B;
+
+    #[inline]
+    fn deref(&self) -> &B {
+        match self {
+            Cow::Borrowed(borrowed) => borrowed,
+            Cow::Owned(owned) => owned.borrow(),
+        }
+    }
+}
+
+impl<'bump, B> AsRef<B> for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + ?Sized,
+{
+    #[inline]
+    fn as_ref(&self) -> &B {
+        self
+    }
+}
+
+impl<'bump, B> Borrow<B> for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + ?Sized,
+{
+    #[inline]
+    fn borrow(&self) -> &B {
+        self
+    }
+}
+
+impl<'bump, B> Clone for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + ?Sized,
+    <B as ToOwnedIn<'bump>>::Owned: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Cow::Borrowed(borrowed) => Cow::Borrowed(borrowed),
+            Cow::Owned(owned) => Cow::Owned(owned.clone()),
+        }
+    }
+}
+
+impl<'bump, B> fmt::Debug for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + fmt::Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'bump, B> fmt::Display for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + fmt::Display + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'bump, B> Hash for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + Hash + ?Sized,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<'a, 'b, B, C> PartialEq<Cow<'b, C>> for Cow<'a, B>
+where
+    B: 'a + ToOwnedIn<'a> + PartialEq<C> + ?Sized,
+    C: 'b + ToOwnedIn<'b> + ?Sized,
+{
+    #[inline]
+    fn eq(&self, other: &Cow<'b, C>) -> bool {
+        **self == **other
+    }
+}
+
+impl<'bump, B> Eq for Cow<'bump, B> where B: 'bump + ToOwnedIn<'bump> + Eq + ?Sized {}
+
+impl<'bump, B> PartialOrd for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + PartialOrd + ?Sized,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'bump, B> Ord for Cow<'bump, B>
+where
+    B: 'bump + ToOwnedIn<'bump> + Ord + ?Sized,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<'bump> PartialEq<str> for Cow<'bump, str> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl<'a, 'bump> PartialEq<&'a str> for Cow<'bump, str> {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        &**self == *other
+    }
+}
+
+impl<'bump, T, U, const N: usize> PartialEq<[U; N]> for Cow<'bump, [T]>
+where
+    T: 'bump + Clone + PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &[U; N]) -> bool {
+        **self == other[..]
+    }
+}
+
+impl<'a, 'bump, T, U> PartialEq<&'a [U]> for Cow<'bump, [T]>
+where
+    T: 'bump + Clone + PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &&'a [U]) -> bool {
+        **self == **other
+    }
+}
+
+impl<'bump> From<&'bump str> for Cow<'bump, str> {
+    #[inline]
+    fn from(s: &'bump str) -> Self {
+        Cow::Borrowed(s)
+    }
+}
+
+impl<'bump> From<String<'bump>> for Cow<'bump, str> {
+    #[inline]
+    fn from(s: String<'bump>) -> Self {
+        Cow::Owned(s)
+    }
+}
+
+impl<'bump, T: 'bump + Clone> From<&'bump [T]> for Cow<'bump, [T]> {
+    #[inline]
+    fn from(s: &'bump [T]) -> Self {
+        Cow::Borrowed(s)
+    }
+}
+
+impl<'bump, T: 'bump + Clone> From<Vec<'bump, T>> for Cow<'bump, [T]> {
+    #[inline]
+    fn from(v: Vec<'bump, T>) -> Self {
+        Cow::Owned(v)
+    }
+}
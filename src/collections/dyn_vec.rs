@@ -0,0 +1,323 @@
+//! A vector of arena-allocated trait objects.
+//!
+//! See [`DynVec`] for details.
+
+use crate::collections::vec::Vec;
+use crate::Bump;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+use core::ptr::{self, NonNull};
+use core::slice;
+
+/// A vector of values of different types, which are allocated in a bump
+/// arena and accessed through a common unsized type, usually a trait object.
+///
+/// Each element is allocated in the arena on its own, and the vector stores
+/// the fat pointers to them, just like a `Vec<Box<dyn Trait>>`. Unlike
+/// values allocated with [`Bump::alloc`], the elements of a `DynVec` are
+/// owned by it: they are dropped when they are removed from the vector, or
+/// when the vector is dropped.
+///
+/// Stable Rust cannot express "any type that coerces to `T`" as a trait
+/// bound, so [`push`](DynVec::push) takes a function that performs the
+/// coercion. For trait objects and slices, `|x| x` does the job.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::DynVec};
+///
+/// trait Op {
+///     fn apply(&self, x: i64) -> i64;
+/// }
+///
+/// struct Add(i64);
+/// impl Op for Add {
+///     fn apply(&self, x: i64) -> i64 {
+///         x + self.0
+///     }
+/// }
+///
+/// struct Mul(i64);
+/// impl Op for Mul {
+///     fn apply(&self, x: i64) -> i64 {
+///         x * self.0
+///     }
+/// }
+///
+/// let b = Bump::new();
+/// let mut program: DynVec<dyn Op> = DynVec::new_in(&b);
+/// program.push(Add(2), |op| op);
+/// program.push(Mul(10), |op| op);
+/// program.push(Add(-1), |op| op);
+///
+/// let result = program.iter().fold(1, |acc, op| op.apply(acc));
+/// assert_eq!(result, 29);
+/// ```
+pub struct DynVec<'bump, T: ?Sized + 'bump> {
+    items: Vec<'bump, NonNull<T>>,
+    // The vector owns its elements, and drops them.
+    marker: PhantomData<T>,
+}
+
+impl<'bump, T: ?Sized + 'bump> DynVec<'bump, T> {
+    /// Constructs a new, empty `DynVec` that allocates in `bump`.
+    ///
+    /// The vector will not allocate until elements are pushed onto it.
+    pub fn new_in(bump: &'bump Bump) -> DynVec<'bump, T> {
+        DynVec {
+            items: Vec::new_in(bump),
+            marker: PhantomData,
+        }
+    }
+
+    /// Constructs a new, empty `DynVec` with room for `capacity` elements
+    /// before its list of pointers has to grow.
+    ///
+    /// The elements themselves are allocated separately, as they are pushed.
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> DynVec<'bump, T> {
+        DynVec {
+            items: Vec::with_capacity_in(capacity, bump),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the arena that this vector allocates in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.items.bump()
+    }
+
+    /// Allocates `value` in the arena and appends it to the back of the
+    /// vector, as a `T`.
+    ///
+    /// `coerce` converts the reference to the new value into a reference to
+    /// `T`. Normally this is `|x| x`, which lets the compiler insert the
+    /// unsizing coercion, for example from `&mut Add` to `&mut dyn Op`. If
+    /// `coerce` returns a reference to anything but the whole value, e.g. to
+    /// one of its fields, only that part is dropped when it is removed from
+    /// the vector, and the rest of the value is leaked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocating the value or growing the vector fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::DynVec};
+    /// use std::fmt::Debug;
+    ///
+    /// let b = Bump::new();
+    /// let mut things: DynVec<dyn Debug> = DynVec::new_in(&b);
+    /// things.push(1, |x| x);
+    /// things.push("two", |x| x);
+    /// things.push([3.0], |x| x);
+    /// assert_eq!(format!("{:?}", things), r#"[1, "two", [3.0]]"#);
+    /// ```
+    pub fn push<U>(&mut self, value: U, coerce: fn(&mut U) -> &mut T) {
+        // Reserve first, so that the value isn't leaked if that fails.
+        self.items.reserve(1);
+        let value = self.bump().alloc(value);
+        self.items.push(NonNull::from(coerce(value)));
+    }
+
+    /// Removes the last element from the vector and drops it.
+    ///
+    /// Returns `false` if the vector was empty.
+    pub fn pop_and_drop(&mut self) -> bool {
+        match self.items.pop() {
+            Some(p) => {
+                unsafe { ptr::drop_in_place(p.as_ptr()) };
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    ///
+    /// Has no effect if `len` is not less than the vector's current length.
+    pub fn truncate(&mut self, len: usize) {
+        while self.items.len() > len {
+            self.pop_and_drop();
+        }
+    }
+
+    /// Removes and drops all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it is out
+    /// of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index).map(|p| unsafe { p.as_ref() })
+    }
+
+    /// Returns an exclusive reference to the element at `index`, or `None` if
+    /// it is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.items.get_mut(index).map(|p| unsafe { p.as_mut() })
+    }
+
+    /// Returns an iterator over shared references to the elements.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.items.iter(),
+        }
+    }
+
+    /// Returns an iterator over exclusive references to the elements.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            inner: self.items.iter_mut(),
+        }
+    }
+}
+
+impl<'bump, T: ?Sized + 'bump> Drop for DynVec<'bump, T> {
+    fn drop(&mut self) {
+        for p in self.items.iter() {
+            unsafe { ptr::drop_in_place(p.as_ptr()) };
+        }
+    }
+}
+
+impl<'bump, T: ?Sized + 'bump> Index<usize> for DynVec<'bump, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        unsafe { self.items[index].as_ref() }
+    }
+}
+
+impl<'bump, T: ?Sized + 'bump> IndexMut<usize> for DynVec<'bump, T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        unsafe { self.items[index].as_mut() }
+    }
+}
+
+impl<'bump, T: ?Sized + fmt::Debug + 'bump> fmt::Debug for DynVec<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, 'bump, T: ?Sized + 'bump> IntoIterator for &'a DynVec<'bump, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, 'bump, T: ?Sized + 'bump> IntoIterator for &'a mut DynVec<'bump, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over shared references to the elements of a [`DynVec`].
+///
+/// This struct is created by [`DynVec::iter`].
+pub struct Iter<'a, T: ?Sized> {
+    inner: slice::Iter<'a, NonNull<T>>,
+}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|p| unsafe { p.as_ref() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back().map(|p| unsafe { p.as_ref() })
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
+
+/// An iterator over exclusive references to the elements of a [`DynVec`].
+///
+/// This struct is created by [`DynVec::iter_mut`].
+pub struct IterMut<'a, T: ?Sized> {
+    inner: slice::IterMut<'a, NonNull<T>>,
+}
+
+impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.next().map(|p| unsafe { p.as_mut() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.inner.next_back().map(|p| unsafe { p.as_mut() })
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for IterMut<'_, T> {}
+
+impl<T: ?Sized> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut")
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
@@ -0,0 +1,340 @@
+//! A UTF-8 string that stores a small number of bytes inline before spilling
+//! into a bump arena.
+//!
+//! See [`InlineString`] for details.
+
+use crate::collections::inline_vec::InlineVec;
+use crate::collections::string::String;
+use crate::Bump;
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt;
+use core::hash;
+use core::ops;
+use core::str;
+
+/// A UTF-8 string with inline storage for up to `N` bytes.
+///
+/// An `InlineString` keeps strings of up to `N` bytes inside the
+/// `InlineString` value itself, e.g. on the stack or inside the structure that
+/// embeds it. Only once it grows past `N` bytes does it move its contents
+/// into the [`Bump`] arena. This is the string counterpart of
+/// [`InlineVec`](crate::collections::InlineVec).
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::InlineString};
+///
+/// let b = Bump::new();
+///
+/// let mut s: InlineString<24> = InlineString::from_str_in("hello", &b);
+/// s.push_str(", world");
+/// assert!(!s.spilled());
+/// assert_eq!(b.allocated_bytes(), 0);
+///
+/// s.push_str(", and everyone in it");
+/// assert!(s.spilled());
+/// assert_eq!(s, "hello, world, and everyone in it");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct InlineString<'bump, const N: usize> {
+    vec: InlineVec<'bump, u8, N>,
+}
+
+impl<'bump, const N: usize> InlineString<'bump, N> {
+    /// Creates a new, empty `InlineString` that will spill into the given
+    /// arena once it holds more than `N` bytes.
+    ///
+    /// This does not allocate.
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> InlineString<'bump, N> {
+        InlineString {
+            vec: InlineVec::new_in(bump),
+        }
+    }
+
+    /// Creates a new, empty `InlineString` with room for at least `capacity`
+    /// bytes.
+    ///
+    /// If `capacity` is greater than `N`, the string is allocated in the arena
+    /// right away.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> InlineString<'bump, N> {
+        InlineString {
+            vec: InlineVec::with_capacity_in(capacity, bump),
+        }
+    }
+
+    /// Creates a new `InlineString` containing a copy of `s`.
+    ///
+    /// This only allocates in the arena if `s` is longer than `N` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineString};
+    ///
+    /// let b = Bump::new();
+    /// let s: InlineString<8> = InlineString::from_str_in("hi", &b);
+    /// assert_eq!(s, "hi");
+    /// ```
+    pub fn from_str_in(s: &str, bump: &'bump Bump) -> InlineString<'bump, N> {
+        let mut t = InlineString::with_capacity_in(s.len(), bump);
+        t.push_str(s);
+        t
+    }
+
+    /// Returns a shared reference to the arena this string spills into.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.vec.bump()
+    }
+
+    /// Returns `true` if the contents have been moved into the arena.
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        self.vec.spilled()
+    }
+
+    /// Returns the length of this string, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns `true` if this string has a length of zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns this string's capacity, in bytes.
+    ///
+    /// This is `N` until the string spills into the arena.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(self.vec.as_slice()) }
+    }
+
+    /// Extracts a mutable string slice containing the entire string.
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(self.vec.as_mut_slice()) }
+    }
+
+    /// Returns a byte slice of this string's contents.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.vec.as_slice()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    ///
+    /// If the inline storage cannot hold them, the string spills into the
+    /// arena.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional)
+    }
+
+    /// Appends the given `char` to the end of this string.
+    #[inline]
+    pub fn push(&mut self, ch: char) {
+        match ch.len_utf8() {
+            1 => self.vec.push(ch as u8),
+            _ => self.push_str(ch.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    /// Appends a given string slice onto the end of this string.
+    #[inline]
+    pub fn push_str(&mut self, string: &str) {
+        self.vec.extend(string.as_bytes())
+    }
+
+    /// Removes the last character from the string and returns it, or `None`
+    /// if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        let new_len = self.len() - ch.len_utf8();
+        self.vec.truncate(new_len);
+        Some(ch)
+    }
+
+    /// Shortens this string to the specified length.
+    ///
+    /// If `new_len` is greater than the string's current length, this has no
+    /// effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a [`char`] boundary.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(self.is_char_boundary(new_len));
+            self.vec.truncate(new_len)
+        }
+    }
+
+    /// Truncates this string, removing all contents.
+    ///
+    /// A spilled string keeps its arena allocation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+
+    /// Converts this `InlineString` into a [`String`] in the same arena.
+    ///
+    /// If the contents are still stored inline, they are copied into a new
+    /// arena allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineString};
+    ///
+    /// let b = Bump::new();
+    /// let s: InlineString<8> = InlineString::from_str_in("hi", &b);
+    /// let s = s.into_string();
+    /// assert_eq!(s, "hi");
+    /// ```
+    #[inline]
+    pub fn into_string(self) -> String<'bump> {
+        unsafe { String::from_utf8_unchecked(self.vec.into_vec()) }
+    }
+}
+
+impl<'bump, const N: usize> ops::Deref for InlineString<'bump, N> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'bump, const N: usize> ops::DerefMut for InlineString<'bump, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<'bump, const N: usize> AsRef<str> for InlineString<'bump, N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<'bump, const N: usize> AsRef<[u8]> for InlineString<'bump, N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'bump, const N: usize> Borrow<str> for InlineString<'bump, N> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<'bump, const N: usize> BorrowMut<str> for InlineString<'bump, N> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+impl<'bump, const N: usize> fmt::Write for InlineString<'bump, N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+impl<'bump, const N: usize> fmt::Display for InlineString<'bump, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'bump, const N: usize> fmt::Debug for InlineString<'bump, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'bump, const N: usize> hash::Hash for InlineString<'bump, N> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        (**self).hash(hasher)
+    }
+}
+
+impl<'bump, const N: usize> Extend<char> for InlineString<'bump, N> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        let iterator = iter.into_iter();
+        let (lower_bound, _) = iterator.size_hint();
+        self.reserve(lower_bound);
+        for ch in iterator {
+            self.push(ch)
+        }
+    }
+}
+
+impl<'a, 'bump, const N: usize> Extend<&'a str> for InlineString<'bump, N> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_str(s)
+        }
+    }
+}
+
+impl<'bump, const N: usize> PartialEq<str> for InlineString<'bump, N> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a, 'bump, const N: usize> PartialEq<&'a str> for InlineString<'bump, N> {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<'bump, const N: usize> PartialEq<InlineString<'bump, N>> for str {
+    #[inline]
+    fn eq(&self, other: &InlineString<'bump, N>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<'bump, const N: usize> PartialEq<InlineString<'bump, N>> for &str {
+    #[inline]
+    fn eq(&self, other: &InlineString<'bump, N>) -> bool {
+        *self == other.as_str()
+    }
+}
@@ -0,0 +1,838 @@
+//! A vector that stores a small number of elements inline before spilling
+//! into a bump arena.
+//!
+//! See [`InlineVec`] for details.
+
+use crate::collections::vec::Vec;
+use crate::Bump;
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp;
+use core::fmt;
+use core::hash::{self, Hash};
+use core::iter::FusedIterator;
+use core::mem::{self, MaybeUninit};
+use core::ops;
+use core::ptr;
+use core::slice;
+
+/// A vector with inline storage for up to `N` elements.
+///
+/// An `InlineVec` keeps its first `N` elements inside the `InlineVec` value
+/// itself, e.g. on the stack or inside the structure that embeds it. Only
+/// once more than `N` elements are pushed does it move its contents into a
+/// [`Vec`] allocated in its [`Bump`] arena. After that, it behaves exactly
+/// like that `Vec`, and never moves back to inline storage.
+///
+/// This avoids touching the arena at all for the common case of short
+/// vectors, at the cost of a larger `InlineVec` value.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::InlineVec};
+///
+/// let b = Bump::new();
+///
+/// let mut v: InlineVec<u32, 4> = InlineVec::new_in(&b);
+/// v.extend([1, 2, 3, 4]);
+/// assert!(!v.spilled());
+/// assert_eq!(b.allocated_bytes(), 0);
+///
+/// v.push(5);
+/// assert!(v.spilled());
+/// assert_eq!(v, [1, 2, 3, 4, 5]);
+/// ```
+pub struct InlineVec<'bump, T, const N: usize> {
+    bump: &'bump Bump,
+    data: Data<'bump, T, N>,
+}
+
+enum Data<'bump, T, const N: usize> {
+    Inline {
+        len: usize,
+        buf: [MaybeUninit<T>; N],
+    },
+    Spilled(Vec<'bump, T>),
+}
+
+impl<'bump, T: 'bump, const N: usize> InlineVec<'bump, T, N> {
+    /// Constructs a new, empty `InlineVec<T, N>` that will spill into the
+    /// given arena once it holds more than `N` elements.
+    ///
+    /// This does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    /// let v: InlineVec<i32, 8> = InlineVec::new_in(&b);
+    /// assert!(v.is_empty());
+    /// assert_eq!(v.capacity(), 8);
+    /// ```
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> InlineVec<'bump, T, N> {
+        InlineVec {
+            bump,
+            data: Data::Inline {
+                len: 0,
+                // Safety: an array of `MaybeUninit`s does not need
+                // initialization.
+                buf: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            },
+        }
+    }
+
+    /// Constructs a new, empty `InlineVec<T, N>` with room for at least
+    /// `capacity` elements.
+    ///
+    /// If `capacity` is greater than `N`, the vector is allocated in the arena
+    /// right away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let v: InlineVec<i32, 8> = InlineVec::with_capacity_in(4, &b);
+    /// assert!(!v.spilled());
+    ///
+    /// let v: InlineVec<i32, 8> = InlineVec::with_capacity_in(16, &b);
+    /// assert!(v.spilled());
+    /// assert!(v.capacity() >= 16);
+    /// ```
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> InlineVec<'bump, T, N> {
+        if capacity <= N {
+            InlineVec::new_in(bump)
+        } else {
+            InlineVec {
+                bump,
+                data: Data::Spilled(Vec::with_capacity_in(capacity, bump)),
+            }
+        }
+    }
+
+    /// Returns a shared reference to the arena this vector spills into.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.bump
+    }
+
+    /// Returns `true` if the elements have been moved into the arena.
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        matches!(self.data, Data::Spilled(_))
+    }
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.data {
+            Data::Inline { len, .. } => len,
+            Data::Spilled(ref v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements the vector can hold without
+    /// reallocating.
+    ///
+    /// This is `N` until the vector spills into the arena.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match self.data {
+            Data::Inline { .. } => N,
+            Data::Spilled(ref v) => v.capacity(),
+        }
+    }
+
+    /// Extracts a slice containing the entire vector.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        match self.data {
+            Data::Inline { len, ref buf } => unsafe {
+                slice::from_raw_parts(buf.as_ptr() as *const T, len)
+            },
+            Data::Spilled(ref v) => v,
+        }
+    }
+
+    /// Extracts a mutable slice of the entire vector.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match self.data {
+            Data::Inline { len, ref mut buf } => unsafe {
+                slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, len)
+            },
+            Data::Spilled(ref mut v) => v,
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// If the inline storage cannot hold them, the vector spills into the
+    /// arena.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve(&mut self, additional: usize) {
+        match self.data {
+            Data::Inline { len, .. } => {
+                let needed = len.checked_add(additional).expect("capacity overflow");
+                if needed > N {
+                    self.spill(needed);
+                }
+            }
+            Data::Spilled(ref mut v) => v.reserve(additional),
+        }
+    }
+
+    /// Appends an element to the back of the vector, spilling into the arena
+    /// if the inline storage is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<i32, 2> = InlineVec::new_in(&b);
+    /// v.push(1);
+    /// v.push(2);
+    /// assert!(!v.spilled());
+    /// v.push(3);
+    /// assert!(v.spilled());
+    /// assert_eq!(v, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        match self.data {
+            Data::Inline {
+                ref mut len,
+                ref mut buf,
+            } if *len < N => {
+                buf[*len] = MaybeUninit::new(value);
+                *len += 1;
+            }
+            Data::Inline { len, .. } => {
+                self.spill(cmp::max(len.saturating_mul(2), 4));
+                self.push(value);
+            }
+            Data::Spilled(ref mut v) => v.push(value),
+        }
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if
+    /// it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        match self.data {
+            Data::Inline {
+                ref mut len,
+                ref buf,
+            } => {
+                if *len == 0 {
+                    None
+                } else {
+                    *len -= 1;
+                    Some(unsafe { ptr::read(buf[*len].as_ptr()) })
+                }
+            }
+            Data::Spilled(ref mut v) => v.pop(),
+        }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        let len = self.len();
+        assert!(index <= len, "insertion index is out of bounds");
+
+        self.reserve(1);
+        match self.data {
+            Data::Inline {
+                ref mut len,
+                ref mut buf,
+            } => unsafe {
+                let p = (buf.as_mut_ptr() as *mut T).add(index);
+                ptr::copy(p, p.add(1), *len - index);
+                ptr::write(p, element);
+                *len += 1;
+            },
+            Data::Spilled(ref mut v) => v.insert(index, element),
+        }
+    }
+
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        match self.data {
+            Data::Inline {
+                ref mut len,
+                ref mut buf,
+            } => {
+                assert!(index < *len, "removal index is out of bounds");
+                unsafe {
+                    let p = (buf.as_mut_ptr() as *mut T).add(index);
+                    let ret = ptr::read(p);
+                    ptr::copy(p.add(1), p, *len - index - 1);
+                    *len -= 1;
+                    ret
+                }
+            }
+            Data::Spilled(ref mut v) => v.remove(index),
+        }
+    }
+
+    /// Removes an element from the vector and returns it, replacing it with
+    /// the last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<&str, 4> = InlineVec::new_in(&b);
+    /// v.extend(["foo", "bar", "baz", "qux"]);
+    /// assert_eq!(v.swap_remove(1), "bar");
+    /// assert_eq!(v, ["foo", "qux", "baz"]);
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(index < len, "swap_remove index is out of bounds");
+        self.as_mut_slice().swap(index, len - 1);
+        self.pop().unwrap()
+    }
+
+    /// Retains only the elements specified by the predicate, in their
+    /// original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    /// v.extend([1, 2, 3, 4]);
+    /// v.retain(|&x| x % 2 == 0);
+    /// assert_eq!(v, [2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let mut deleted = 0;
+        {
+            // Only swaps elements around, so a panic in `f` leaves every
+            // element in the vector.
+            let v = self.as_mut_slice();
+            for i in 0..len {
+                if !f(&v[i]) {
+                    deleted += 1;
+                } else if deleted > 0 {
+                    v.swap(i - deleted, i);
+                }
+            }
+        }
+        if deleted > 0 {
+            self.truncate(len - deleted);
+        }
+    }
+
+    /// Clones and appends all elements in a slice to the vector, spilling
+    /// into the arena at most once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<i32, 2> = InlineVec::new_in(&b);
+    /// v.extend_from_slice(&[1, 2, 3]);
+    /// assert!(v.spilled());
+    /// assert_eq!(v, [1, 2, 3]);
+    /// ```
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(other.len());
+        for x in other {
+            self.push(x.clone());
+        }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than the vector's current length, this has no
+    /// effect.
+    pub fn truncate(&mut self, new_len: usize) {
+        match self.data {
+            Data::Inline {
+                ref mut len,
+                ref mut buf,
+            } => {
+                if new_len < *len {
+                    let old_len = *len;
+                    // Update the length first, so that a panicking destructor
+                    // can't cause a double drop.
+                    *len = new_len;
+                    unsafe {
+                        ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                            (buf.as_mut_ptr() as *mut T).add(new_len),
+                            old_len - new_len,
+                        ));
+                    }
+                }
+            }
+            Data::Spilled(ref mut v) => v.truncate(new_len),
+        }
+    }
+
+    /// Clears the vector, removing all values.
+    ///
+    /// A spilled vector keeps its arena allocation.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Converts this `InlineVec` into a [`Vec`] in the same arena.
+    ///
+    /// If the elements are still stored inline, they are moved into a new
+    /// arena allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    /// v.extend([1, 2, 3]);
+    /// let v = v.into_vec();
+    /// assert_eq!(v, [1, 2, 3]);
+    /// ```
+    pub fn into_vec(mut self) -> Vec<'bump, T> {
+        if !self.spilled() {
+            let len = self.len();
+            self.spill(len);
+        }
+        match self.take_data() {
+            Data::Spilled(v) => v,
+            Data::Inline { .. } => unreachable!(),
+        }
+    }
+
+    /// Converts this `InlineVec` into a slice that lives as long as the arena.
+    ///
+    /// If the elements are still stored inline, they are moved into a new
+    /// arena allocation. As with [`Vec::into_bump_slice`], the elements'
+    /// destructors will never run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    /// v.extend([1, 2]);
+    /// let s: &[i32] = v.into_bump_slice();
+    /// assert_eq!(s, [1, 2]);
+    /// ```
+    pub fn into_bump_slice(self) -> &'bump [T] {
+        self.into_vec().into_bump_slice()
+    }
+
+    /// Like [`into_bump_slice`](InlineVec::into_bump_slice), but returns a
+    /// mutable slice.
+    pub fn into_bump_slice_mut(self) -> &'bump mut [T] {
+        self.into_vec().into_bump_slice_mut()
+    }
+
+    /// Takes the storage out of the vector, leaving it empty and inline.
+    fn take_data(&mut self) -> Data<'bump, T, N> {
+        mem::replace(
+            &mut self.data,
+            Data::Inline {
+                len: 0,
+                buf: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            },
+        )
+    }
+
+    /// Moves the inline elements into a new arena allocation with room for at
+    /// least `capacity` elements.
+    #[cold]
+    fn spill(&mut self, capacity: usize) {
+        let mut vec = Vec::with_capacity_in(capacity, self.bump);
+        if let Data::Inline {
+            ref mut len,
+            ref buf,
+        } = self.data
+        {
+            unsafe {
+                ptr::copy_nonoverlapping(buf.as_ptr() as *const T, vec.as_mut_ptr(), *len);
+                vec.set_len(*len);
+            }
+            // The elements are now owned by `vec`.
+            *len = 0;
+        }
+        self.data = Data::Spilled(vec);
+    }
+}
+
+impl<'bump, T, const N: usize> Drop for InlineVec<'bump, T, N> {
+    fn drop(&mut self) {
+        if let Data::Inline { len, ref mut buf } = self.data {
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    buf.as_mut_ptr() as *mut T,
+                    len,
+                ));
+            }
+        }
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> ops::Deref for InlineVec<'bump, T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> ops::DerefMut for InlineVec<'bump, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> AsRef<[T]> for InlineVec<'bump, T, N> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> AsMut<[T]> for InlineVec<'bump, T, N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> Borrow<[T]> for InlineVec<'bump, T, N> {
+    #[inline]
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> BorrowMut<[T]> for InlineVec<'bump, T, N> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<'bump, T: 'bump + Clone, const N: usize> Clone for InlineVec<'bump, T, N> {
+    fn clone(&self) -> Self {
+        let mut v = InlineVec::with_capacity_in(self.len(), self.bump);
+        v.extend(self.iter().cloned());
+        v
+    }
+}
+
+impl<'bump, T: 'bump + fmt::Debug, const N: usize> fmt::Debug for InlineVec<'bump, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'bump, T: 'bump + Hash, const N: usize> Hash for InlineVec<'bump, T, N> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> Extend<T> for InlineVec<'bump, T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, 'bump, T: 'bump + Copy + 'a, const N: usize> Extend<&'a T> for InlineVec<'bump, T, N> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<'a, 'bump, T: 'bump, const N: usize> IntoIterator for &'a InlineVec<'bump, T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, 'bump, T: 'bump, const N: usize> IntoIterator for &'a mut InlineVec<'bump, T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<'bump, T: 'bump, const N: usize> IntoIterator for InlineVec<'bump, T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<'bump, T, N>;
+
+    /// Creates a consuming iterator, that moves each value out of the vector.
+    ///
+    /// Inline elements are moved out of the inline storage, without
+    /// allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::InlineVec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v: InlineVec<String, 2> = InlineVec::new_in(&b);
+    /// v.push("a".to_string());
+    /// v.push("b".to_string());
+    /// let joined: String = v.into_iter().collect();
+    /// assert_eq!(joined, "ab");
+    /// ```
+    fn into_iter(mut self) -> IntoIter<'bump, T, N> {
+        let inner = match self.take_data() {
+            Data::Inline { len, buf } => IntoIterInner::Inline {
+                buf,
+                start: 0,
+                end: len,
+            },
+            Data::Spilled(v) => IntoIterInner::Spilled(v.into_iter()),
+        };
+        IntoIter { inner }
+    }
+}
+
+/// An iterator that moves out of an [`InlineVec`].
+///
+/// This `struct` is created by the `into_iter` method on [`InlineVec`] (provided
+/// by the [`IntoIterator`] trait).
+pub struct IntoIter<'bump, T, const N: usize> {
+    inner: IntoIterInner<'bump, T, N>,
+}
+
+enum IntoIterInner<'bump, T, const N: usize> {
+    // The elements in `start..end` have not been yielded yet.
+    Inline {
+        buf: [MaybeUninit<T>; N],
+        start: usize,
+        end: usize,
+    },
+    Spilled(crate::collections::vec::IntoIter<'bump, T>),
+}
+
+impl<'bump, T, const N: usize> IntoIter<'bump, T, N> {
+    /// Returns the remaining items of this iterator as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        match self.inner {
+            IntoIterInner::Inline {
+                ref buf,
+                start,
+                end,
+            } => unsafe {
+                slice::from_raw_parts((buf.as_ptr() as *const T).add(start), end - start)
+            },
+            IntoIterInner::Spilled(ref iter) => iter.as_slice(),
+        }
+    }
+}
+
+impl<'bump, T, const N: usize> Iterator for IntoIter<'bump, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        match self.inner {
+            IntoIterInner::Inline {
+                ref buf,
+                ref mut start,
+                end,
+            } => {
+                if *start == end {
+                    None
+                } else {
+                    *start += 1;
+                    Some(unsafe { ptr::read(buf[*start - 1].as_ptr()) })
+                }
+            }
+            IntoIterInner::Spilled(ref mut iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.as_slice().len();
+        (len, Some(len))
+    }
+}
+
+impl<'bump, T, const N: usize> DoubleEndedIterator for IntoIter<'bump, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        match self.inner {
+            IntoIterInner::Inline {
+                ref buf,
+                start,
+                ref mut end,
+            } => {
+                if start == *end {
+                    None
+                } else {
+                    *end -= 1;
+                    Some(unsafe { ptr::read(buf[*end].as_ptr()) })
+                }
+            }
+            IntoIterInner::Spilled(ref mut iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<'bump, T, const N: usize> ExactSizeIterator for IntoIter<'bump, T, N> {}
+
+impl<'bump, T, const N: usize> FusedIterator for IntoIter<'bump, T, N> {}
+
+impl<'bump, T, const N: usize> Drop for IntoIter<'bump, T, N> {
+    fn drop(&mut self) {
+        if let IntoIterInner::Inline {
+            ref mut buf,
+            start,
+            end,
+        } = self.inner
+        {
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    (buf.as_mut_ptr() as *mut T).add(start),
+                    end - start,
+                ));
+            }
+        }
+    }
+}
+
+impl<'bump, T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<'bump, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<'a, 'b, A, B, const N: usize, const M: usize> PartialEq<InlineVec<'b, B, M>>
+    for InlineVec<'a, A, N>
+where
+    A: 'a + PartialEq<B>,
+    B: 'b,
+{
+    #[inline]
+    fn eq(&self, other: &InlineVec<'b, B, M>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'bump, A, B, const N: usize> PartialEq<[B]> for InlineVec<'bump, A, N>
+where
+    A: 'bump + PartialEq<B>,
+{
+    #[inline]
+    fn eq(&self, other: &[B]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'a, 'bump, A, B, const N: usize> PartialEq<&'a [B]> for InlineVec<'bump, A, N>
+where
+    A: 'bump + PartialEq<B>,
+{
+    #[inline]
+    fn eq(&self, other: &&'a [B]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'bump, A, B, const N: usize, const M: usize> PartialEq<[B; M]> for InlineVec<'bump, A, N>
+where
+    A: 'bump + PartialEq<B>,
+{
+    #[inline]
+    fn eq(&self, other: &[B; M]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<'bump, T: 'bump + Eq, const N: usize> Eq for InlineVec<'bump, T, N> {}
+
+impl<'bump, T: 'bump + PartialOrd, const N: usize> PartialOrd for InlineVec<'bump, T, N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'bump, T: 'bump + Ord, const N: usize> Ord for InlineVec<'bump, T, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
@@ -0,0 +1,1159 @@
+//! A doubly-linked list whose nodes are allocated in a bump arena.
+//!
+//! See [`LinkedList`] for details.
+
+use crate::Bump;
+use core::alloc::Layout;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::{self, NonNull};
+
+/// A doubly-linked list whose nodes are allocated in a bump arena.
+///
+/// This is the arena counterpart of [`std::collections::LinkedList`]. Each
+/// node is a separate bump allocation, so pushing an element is only a
+/// pointer bump rather than a call into the global allocator, and nodes that
+/// are removed from the list are simply left in the arena until it is reset,
+/// unless they happen to be its most recent allocation.
+///
+/// Besides pushing and popping at both ends, elements can be inserted and
+/// removed anywhere in the list in O(1) via a [`CursorMut`], and whole lists
+/// can be spliced into each other in O(1) with [`append`](LinkedList::append),
+/// [`CursorMut::splice_before`], and [`CursorMut::splice_after`].
+///
+/// [`std::collections::LinkedList`]: https://doc.rust-lang.org/std/collections/struct.LinkedList.html
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::LinkedList};
+///
+/// let b = Bump::new();
+///
+/// let mut list = LinkedList::new_in(&b);
+/// list.push_back(2);
+/// list.push_back(3);
+/// list.push_front(1);
+/// assert_eq!(list, [1, 2, 3]);
+///
+/// // Insert an element in the middle of the list.
+/// let mut cursor = list.cursor_front_mut();
+/// cursor.move_next();
+/// cursor.insert_after(10);
+/// assert_eq!(list, [1, 2, 10, 3]);
+/// ```
+pub struct LinkedList<'bump, T: 'bump> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    bump: &'bump Bump,
+    marker: PhantomData<T>,
+}
+
+struct Node<T> {
+    next: Option<NonNull<Node<T>>>,
+    prev: Option<NonNull<Node<T>>>,
+    element: T,
+}
+
+/// The head, tail, and length of a chain of linked nodes.
+type Chain<T> = (NonNull<Node<T>>, NonNull<Node<T>>, usize);
+
+impl<'bump, T: 'bump> LinkedList<'bump, T> {
+    /// Creates an empty `LinkedList` whose nodes will be allocated in the
+    /// given arena.
+    ///
+    /// This does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::LinkedList};
+    ///
+    /// let b = Bump::new();
+    /// let list: LinkedList<u32> = LinkedList::new_in(&b);
+    /// assert!(list.is_empty());
+    /// ```
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> LinkedList<'bump, T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            bump,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a `LinkedList` in the given arena from the elements of an
+    /// iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::LinkedList};
+    ///
+    /// let b = Bump::new();
+    /// let list = LinkedList::from_iter_in(1..=3, &b);
+    /// assert_eq!(list, [1, 2, 3]);
+    /// ```
+    pub fn from_iter_in<I: IntoIterator<Item = T>>(iter: I, bump: &'bump Bump) -> Self {
+        let mut list = LinkedList::new_in(bump);
+        list.extend(iter);
+        list
+    }
+
+    /// Returns a shared reference to the arena this list allocates its nodes
+    /// in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.bump
+    }
+
+    /// Returns the number of elements in the list.
+    ///
+    /// This is O(1).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Removes all elements from the list.
+    pub fn clear(&mut self) {
+        *self = LinkedList::new_in(self.bump);
+    }
+
+    /// Returns `true` if the list contains an element equal to the given
+    /// value.
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|e| e == x)
+    }
+
+    /// Returns a reference to the front element, or `None` if the list is
+    /// empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Returns a mutable reference to the front element, or `None` if the
+    /// list is empty.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.head
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Returns a reference to the back element, or `None` if the list is
+    /// empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    /// Returns a mutable reference to the back element, or `None` if the list
+    /// is empty.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.tail
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Adds an element to the front of the list.
+    ///
+    /// This is O(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::LinkedList};
+    ///
+    /// let b = Bump::new();
+    /// let mut list = LinkedList::new_in(&b);
+    /// list.push_front(2);
+    /// list.push_front(1);
+    /// assert_eq!(list, [1, 2]);
+    /// ```
+    pub fn push_front(&mut self, elt: T) {
+        let node = self.alloc_node(elt);
+        unsafe { self.splice_nodes(None, self.head, node, node, 1) }
+    }
+
+    /// Removes the front element and returns it, or `None` if the list is
+    /// empty.
+    ///
+    /// This is O(1).
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|node| unsafe {
+            self.unlink_node(node);
+            self.take_node(node)
+        })
+    }
+
+    /// Appends an element to the back of the list.
+    ///
+    /// This is O(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::LinkedList};
+    ///
+    /// let b = Bump::new();
+    /// let mut list = LinkedList::new_in(&b);
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// assert_eq!(list, [1, 2]);
+    /// ```
+    pub fn push_back(&mut self, elt: T) {
+        let node = self.alloc_node(elt);
+        unsafe { self.splice_nodes(self.tail, None, node, node, 1) }
+    }
+
+    /// Removes the back element and returns it, or `None` if the list is
+    /// empty.
+    ///
+    /// This is O(1).
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|node| unsafe {
+            self.unlink_node(node);
+            self.take_node(node)
+        })
+    }
+
+    /// Moves all elements from `other` to the end of this list, leaving
+    /// `other` empty.
+    ///
+    /// This is O(1), and does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::LinkedList};
+    ///
+    /// let b = Bump::new();
+    /// let mut a = LinkedList::from_iter_in([1, 2], &b);
+    /// let mut c = LinkedList::from_iter_in([3, 4], &b);
+    ///
+    /// a.append(&mut c);
+    /// assert_eq!(a, [1, 2, 3, 4]);
+    /// assert!(c.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut LinkedList<'bump, T>) {
+        let bump = other.bump;
+        let other = mem::replace(other, LinkedList::new_in(bump));
+        if let Some((head, tail, len)) = other.into_raw_parts() {
+            unsafe { self.splice_nodes(self.tail, None, head, tail, len) }
+        }
+    }
+
+    /// Splits the list into two at the given index, returning everything from
+    /// the index onwards.
+    ///
+    /// This is O(min(at, len - at)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::LinkedList};
+    ///
+    /// let b = Bump::new();
+    /// let mut list = LinkedList::from_iter_in(1..=5, &b);
+    /// let tail = list.split_off(2);
+    /// assert_eq!(list, [1, 2]);
+    /// assert_eq!(tail, [3, 4, 5]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> LinkedList<'bump, T> {
+        let len = self.len();
+        assert!(at <= len, "cannot split off at a nonexistent index");
+        if at == 0 {
+            return mem::replace(self, LinkedList::new_in(self.bump));
+        }
+
+        // Find the node that will become this list's new tail, walking from
+        // whichever end is closer.
+        let split_node = if at - 1 <= len - at {
+            let mut iter = self.iter_mut();
+            for _ in 0..at - 1 {
+                iter.next();
+            }
+            iter.head
+        } else {
+            let mut iter = self.iter_mut();
+            for _ in 0..len - at {
+                iter.next_back();
+            }
+            iter.tail
+        };
+        unsafe { self.split_off_after_node(split_node, at) }
+    }
+
+    /// Returns a front-to-back iterator over references to the elements.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a front-to-back iterator over mutable references to the
+    /// elements.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a cursor pointing at the front element.
+    ///
+    /// If the list is empty, the cursor points at the "ghost" non-element.
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, 'bump, T> {
+        Cursor {
+            index: 0,
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor pointing at the back element.
+    ///
+    /// If the list is empty, the cursor points at the "ghost" non-element.
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, 'bump, T> {
+        Cursor {
+            index: self.len.saturating_sub(1),
+            current: self.tail,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor that can edit the list, pointing at the front element.
+    ///
+    /// If the list is empty, the cursor points at the "ghost" non-element.
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, 'bump, T> {
+        CursorMut {
+            index: 0,
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor that can edit the list, pointing at the back element.
+    ///
+    /// If the list is empty, the cursor points at the "ghost" non-element.
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, 'bump, T> {
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            current: self.tail,
+            list: self,
+        }
+    }
+
+    fn alloc_node(&self, element: T) -> NonNull<Node<T>> {
+        NonNull::from(self.bump.alloc(Node {
+            next: None,
+            prev: None,
+            element,
+        }))
+    }
+
+    /// Moves the element out of an unlinked node, and gives the node's memory
+    /// back to the arena if possible.
+    unsafe fn take_node(&self, node: NonNull<Node<T>>) -> T {
+        let element = ptr::read(&(*node.as_ptr()).element);
+        self.bump.dealloc(node.cast(), Layout::new::<Node<T>>());
+        element
+    }
+
+    /// Unlinks `node` from the list, without touching its own links.
+    ///
+    /// `node` must be in this list.
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) {
+        let node = &mut *node.as_ptr();
+        match node.prev {
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+    }
+
+    /// Links the chain of `splice_len` nodes from `splice_start` to
+    /// `splice_end` in between `existing_prev` and `existing_next`.
+    ///
+    /// `existing_prev` and `existing_next` must be adjacent nodes of this
+    /// list, or `None` for its ends, and the chain must not be in any list.
+    unsafe fn splice_nodes(
+        &mut self,
+        existing_prev: Option<NonNull<Node<T>>>,
+        existing_next: Option<NonNull<Node<T>>>,
+        mut splice_start: NonNull<Node<T>>,
+        mut splice_end: NonNull<Node<T>>,
+        splice_len: usize,
+    ) {
+        match existing_prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(splice_start),
+            None => self.head = Some(splice_start),
+        }
+        match existing_next {
+            Some(next) => (*next.as_ptr()).prev = Some(splice_end),
+            None => self.tail = Some(splice_end),
+        }
+        splice_start.as_mut().prev = existing_prev;
+        splice_end.as_mut().next = existing_next;
+        self.len += splice_len;
+    }
+
+    /// Splits off everything after `split_node`, which is the `at`th node of
+    /// the list, or everything if `split_node` is `None`.
+    unsafe fn split_off_after_node(
+        &mut self,
+        split_node: Option<NonNull<Node<T>>>,
+        at: usize,
+    ) -> LinkedList<'bump, T> {
+        let split_node = match split_node {
+            Some(node) => node,
+            None => return mem::replace(self, LinkedList::new_in(self.bump)),
+        };
+        let second_head = (*split_node.as_ptr()).next.take();
+        let second = LinkedList {
+            head: second_head,
+            tail: if second_head.is_some() {
+                self.tail
+            } else {
+                None
+            },
+            len: self.len - at,
+            bump: self.bump,
+            marker: PhantomData,
+        };
+        if let Some(head) = second_head {
+            (*head.as_ptr()).prev = None;
+        }
+        self.tail = Some(split_node);
+        self.len = at;
+        second
+    }
+
+    /// Splits off everything before `split_node`, which has `at` nodes
+    /// before it, or everything if `split_node` is `None`.
+    unsafe fn split_off_before_node(
+        &mut self,
+        split_node: Option<NonNull<Node<T>>>,
+        at: usize,
+    ) -> LinkedList<'bump, T> {
+        let split_node = match split_node {
+            Some(node) => node,
+            None => return mem::replace(self, LinkedList::new_in(self.bump)),
+        };
+        let first_tail = (*split_node.as_ptr()).prev.take();
+        let first = LinkedList {
+            head: if first_tail.is_some() {
+                self.head
+            } else {
+                None
+            },
+            tail: first_tail,
+            len: at,
+            bump: self.bump,
+            marker: PhantomData,
+        };
+        if let Some(tail) = first_tail {
+            (*tail.as_ptr()).next = None;
+        }
+        self.head = Some(split_node);
+        self.len -= at;
+        first
+    }
+
+    /// Takes the node chain out of this list without dropping it.
+    fn into_raw_parts(self) -> Option<Chain<T>> {
+        let this = mem::ManuallyDrop::new(self);
+        Some((this.head?, this.tail?, this.len))
+    }
+}
+
+impl<'bump, T> Drop for LinkedList<'bump, T> {
+    fn drop(&mut self) {
+        // Keep dropping the remaining elements if one of them panics.
+        struct DropGuard<'a, 'bump, T>(&'a mut LinkedList<'bump, T>);
+
+        impl<T> Drop for DropGuard<'_, '_, T> {
+            fn drop(&mut self) {
+                while self.0.pop_front().is_some() {}
+            }
+        }
+
+        while let Some(elt) = self.pop_front() {
+            let guard = DropGuard(self);
+            drop(elt);
+            mem::forget(guard);
+        }
+    }
+}
+
+impl<'bump, T: 'bump + Clone> Clone for LinkedList<'bump, T> {
+    fn clone(&self) -> Self {
+        LinkedList::from_iter_in(self.iter().cloned(), self.bump)
+    }
+}
+
+impl<'bump, T: 'bump + fmt::Debug> fmt::Debug for LinkedList<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<'bump, T: 'bump + Hash> Hash for LinkedList<'bump, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for elt in self {
+            elt.hash(state);
+        }
+    }
+}
+
+impl<'bump, T: 'bump> Extend<T> for LinkedList<'bump, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elt in iter {
+            self.push_back(elt);
+        }
+    }
+}
+
+impl<'a, 'bump, T: 'bump + Copy + 'a> Extend<&'a T> for LinkedList<'bump, T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<'bump, T: 'bump> IntoIterator for LinkedList<'bump, T> {
+    type Item = T;
+    type IntoIter = IntoIter<'bump, T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<'bump, T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, 'bump, T: 'bump> IntoIterator for &'a LinkedList<'bump, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, 'bump, T: 'bump> IntoIterator for &'a mut LinkedList<'bump, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<'a, 'b, A, B> PartialEq<LinkedList<'b, B>> for LinkedList<'a, A>
+where
+    A: 'a + PartialEq<B>,
+    B: 'b,
+{
+    fn eq(&self, other: &LinkedList<'b, B>) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<'bump, A, B> PartialEq<[B]> for LinkedList<'bump, A>
+where
+    A: 'bump + PartialEq<B>,
+{
+    fn eq(&self, other: &[B]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<'a, 'bump, A, B> PartialEq<&'a [B]> for LinkedList<'bump, A>
+where
+    A: 'bump + PartialEq<B>,
+{
+    #[inline]
+    fn eq(&self, other: &&'a [B]) -> bool {
+        *self == **other
+    }
+}
+
+impl<'bump, A, B, const N: usize> PartialEq<[B; N]> for LinkedList<'bump, A>
+where
+    A: 'bump + PartialEq<B>,
+{
+    #[inline]
+    fn eq(&self, other: &[B; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<'bump, T: 'bump + Eq> Eq for LinkedList<'bump, T> {}
+
+impl<'bump, T: 'bump + PartialOrd> PartialOrd for LinkedList<'bump, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<'bump, T: 'bump + Ord> Ord for LinkedList<'bump, T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+/// An iterator over the elements of a [`LinkedList`].
+///
+/// This struct is created by [`LinkedList::iter`].
+pub struct Iter<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.len).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.len -= 1;
+            self.head = node.next;
+            &node.element
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.len -= 1;
+            self.tail = node.prev;
+            &node.element
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// A mutable iterator over the elements of a [`LinkedList`].
+///
+/// This struct is created by [`LinkedList::iter_mut`].
+pub struct IterMut<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IterMut").field(&self.len).finish()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            self.len -= 1;
+            self.head = node.next;
+            &mut node.element
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            self.len -= 1;
+            self.tail = node.prev;
+            &mut node.element
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a [`LinkedList`].
+///
+/// This struct is created by the `into_iter` method on [`LinkedList`]
+/// (provided by the [`IntoIterator`] trait).
+pub struct IntoIter<'bump, T: 'bump> {
+    list: LinkedList<'bump, T>,
+}
+
+impl<'bump, T: 'bump + fmt::Debug> fmt::Debug for IntoIter<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.list).finish()
+    }
+}
+
+impl<'bump, T: 'bump> Iterator for IntoIter<'bump, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<'bump, T: 'bump> DoubleEndedIterator for IntoIter<'bump, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<'bump, T: 'bump> ExactSizeIterator for IntoIter<'bump, T> {}
+
+impl<'bump, T: 'bump> FusedIterator for IntoIter<'bump, T> {}
+
+/// A cursor over a [`LinkedList`].
+///
+/// A cursor is like an iterator, except that it can freely seek
+/// back-and-forth. It always rests between two elements of the list, and
+/// logically points at one of them. To accommodate the ends of the list,
+/// there is a "ghost" non-element after the back element and before the
+/// front element, which yields `None` when it is the cursor's current
+/// element.
+///
+/// This struct is created by [`LinkedList::cursor_front`] and
+/// [`LinkedList::cursor_back`].
+pub struct Cursor<'a, 'bump, T> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a LinkedList<'bump, T>,
+}
+
+impl<T> Clone for Cursor<'_, '_, T> {
+    fn clone(&self) -> Self {
+        Cursor { ..*self }
+    }
+}
+
+impl<'bump, T: 'bump + fmt::Debug> fmt::Debug for Cursor<'_, 'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<'a, 'bump, T: 'bump> Cursor<'a, 'bump, T> {
+    /// Returns the index of the cursor's current element, or `None` if it is
+    /// pointing at the "ghost" non-element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        let _ = self.current?;
+        Some(self.index)
+    }
+
+    /// Moves the cursor to the next element of the list.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, this moves it to
+    /// the front element. If it is pointing at the back element, this moves
+    /// it to the "ghost" non-element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(current) => unsafe {
+                self.current = (*current.as_ptr()).next;
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Moves the cursor to the previous element of the list.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, this moves it to
+    /// the back element. If it is pointing at the front element, this moves
+    /// it to the "ghost" non-element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            Some(current) => unsafe {
+                self.current = (*current.as_ptr()).prev;
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len);
+            },
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns a reference to the cursor's current element, or `None` if it
+    /// is pointing at the "ghost" non-element.
+    #[inline]
+    pub fn current(&self) -> Option<&'a T> {
+        self.current
+            .map(|current| unsafe { &(*current.as_ptr()).element })
+    }
+
+    /// Returns a reference to the next element, without moving the cursor.
+    #[inline]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.current {
+            Some(current) => unsafe { (*current.as_ptr()).next },
+            None => self.list.head,
+        };
+        next.map(|next| unsafe { &(*next.as_ptr()).element })
+    }
+
+    /// Returns a reference to the previous element, without moving the
+    /// cursor.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match self.current {
+            Some(current) => unsafe { (*current.as_ptr()).prev },
+            None => self.list.tail,
+        };
+        prev.map(|prev| unsafe { &(*prev.as_ptr()).element })
+    }
+}
+
+/// A cursor over a [`LinkedList`] with editing operations.
+///
+/// Like a [`Cursor`], except that it can also insert and remove elements at
+/// its position, and splice whole lists in or split them off, all in O(1).
+///
+/// This struct is created by [`LinkedList::cursor_front_mut`] and
+/// [`LinkedList::cursor_back_mut`].
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::LinkedList};
+///
+/// let b = Bump::new();
+/// let mut list = LinkedList::from_iter_in([1, 2, 3, 4], &b);
+///
+/// // Remove every even element, and put a 0 before every odd one.
+/// let mut cursor = list.cursor_front_mut();
+/// while let Some(&mut x) = cursor.current() {
+///     if x % 2 == 0 {
+///         cursor.remove_current();
+///     } else {
+///         cursor.insert_before(0);
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(list, [0, 1, 0, 3]);
+/// ```
+pub struct CursorMut<'a, 'bump, T> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut LinkedList<'bump, T>,
+}
+
+impl<'bump, T: 'bump + fmt::Debug> fmt::Debug for CursorMut<'_, 'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+            .field(&self.list)
+            .field(&self.index())
+            .finish()
+    }
+}
+
+impl<'a, 'bump, T: 'bump> CursorMut<'a, 'bump, T> {
+    /// Returns the index of the cursor's current element, or `None` if it is
+    /// pointing at the "ghost" non-element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        let _ = self.current?;
+        Some(self.index)
+    }
+
+    /// Moves the cursor to the next element of the list.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, this moves it to
+    /// the front element. If it is pointing at the back element, this moves
+    /// it to the "ghost" non-element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(current) => unsafe {
+                self.current = (*current.as_ptr()).next;
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Moves the cursor to the previous element of the list.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, this moves it to
+    /// the back element. If it is pointing at the front element, this moves
+    /// it to the "ghost" non-element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            Some(current) => unsafe {
+                self.current = (*current.as_ptr()).prev;
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len);
+            },
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the cursor's current element, or `None`
+    /// if it is pointing at the "ghost" non-element.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current
+            .map(|current| unsafe { &mut (*current.as_ptr()).element })
+    }
+
+    /// Returns a mutable reference to the next element, without moving the
+    /// cursor.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(current) => unsafe { (*current.as_ptr()).next },
+            None => self.list.head,
+        };
+        next.map(|next| unsafe { &mut (*next.as_ptr()).element })
+    }
+
+    /// Returns a mutable reference to the previous element, without moving
+    /// the cursor.
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(current) => unsafe { (*current.as_ptr()).prev },
+            None => self.list.tail,
+        };
+        prev.map(|prev| unsafe { &mut (*prev.as_ptr()).element })
+    }
+
+    /// Returns a read-only cursor pointing at the same element.
+    ///
+    /// The `CursorMut` is borrowed for as long as the returned cursor lives.
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, 'bump, T> {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            list: self.list,
+        }
+    }
+
+    /// Inserts a new element right after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, the new element
+    /// becomes the front of the list.
+    pub fn insert_after(&mut self, item: T) {
+        let node = self.list.alloc_node(item);
+        unsafe {
+            let next = match self.current {
+                Some(current) => (*current.as_ptr()).next,
+                None => self.list.head,
+            };
+            self.list.splice_nodes(self.current, next, node, node, 1);
+        }
+        if self.current.is_none() {
+            // The "ghost" non-element's index has changed.
+            self.index = self.list.len;
+        }
+    }
+
+    /// Inserts a new element right before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, the new element
+    /// becomes the back of the list.
+    pub fn insert_before(&mut self, item: T) {
+        let node = self.list.alloc_node(item);
+        unsafe {
+            let prev = match self.current {
+                Some(current) => (*current.as_ptr()).prev,
+                None => self.list.tail,
+            };
+            self.list.splice_nodes(prev, self.current, node, node, 1);
+        }
+        self.index += 1;
+    }
+
+    /// Removes the current element from the list and returns it, moving the
+    /// cursor to the next element.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, nothing is
+    /// removed and `None` is returned.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        unsafe {
+            self.current = (*node.as_ptr()).next;
+            self.list.unlink_node(node);
+            Some(self.list.take_node(node))
+        }
+    }
+
+    /// Moves all elements of `list` into this one, right after the current
+    /// element.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, the elements are
+    /// inserted at the front of the list. This is O(1).
+    pub fn splice_after(&mut self, list: LinkedList<'bump, T>) {
+        let (head, tail, len) = match list.into_raw_parts() {
+            Some(parts) => parts,
+            None => return,
+        };
+        unsafe {
+            let next = match self.current {
+                Some(current) => (*current.as_ptr()).next,
+                None => self.list.head,
+            };
+            self.list.splice_nodes(self.current, next, head, tail, len);
+        }
+        if self.current.is_none() {
+            self.index = self.list.len;
+        }
+    }
+
+    /// Moves all elements of `list` into this one, right before the current
+    /// element.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, the elements are
+    /// inserted at the back of the list. This is O(1).
+    pub fn splice_before(&mut self, list: LinkedList<'bump, T>) {
+        let (head, tail, len) = match list.into_raw_parts() {
+            Some(parts) => parts,
+            None => return,
+        };
+        unsafe {
+            let prev = match self.current {
+                Some(current) => (*current.as_ptr()).prev,
+                None => self.list.tail,
+            };
+            self.list.splice_nodes(prev, self.current, head, tail, len);
+        }
+        self.index += len;
+    }
+
+    /// Splits the list in two after the current element, and returns
+    /// everything after it as a new list.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, the whole list
+    /// is returned, leaving this one empty. This is O(1).
+    pub fn split_after(&mut self) -> LinkedList<'bump, T> {
+        let at = if self.current.is_some() {
+            self.index + 1
+        } else {
+            self.index = 0;
+            0
+        };
+        unsafe { self.list.split_off_after_node(self.current, at) }
+    }
+
+    /// Splits the list in two before the current element, and returns
+    /// everything before it as a new list.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element, the whole list
+    /// is returned, leaving this one empty. This is O(1).
+    pub fn split_before(&mut self) -> LinkedList<'bump, T> {
+        let at = mem::replace(&mut self.index, 0);
+        unsafe { self.list.split_off_before_node(self.current, at) }
+    }
+}
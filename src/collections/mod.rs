@@ -23,12 +23,39 @@ mod str;
 pub mod string;
 pub use self::string::String;
 
+pub mod bit_vec;
+pub use self::bit_vec::BitVec;
+
+pub mod inline_vec;
+pub use self::inline_vec::InlineVec;
+
+pub mod inline_string;
+pub use self::inline_string::InlineString;
+
+pub mod cow;
+pub use self::cow::{Cow, ToOwnedIn};
+
+pub mod linked_list;
+pub use self::linked_list::LinkedList;
+
+pub mod slot_map;
+pub use self::slot_map::SlotMap;
+
+pub mod dyn_vec;
+pub use self::dyn_vec::DynVec;
+
+pub mod binary_heap;
+pub use self::binary_heap::BinaryHeap;
+
 mod collect_in;
 pub use collect_in::{CollectIn, FromIteratorIn};
 
-// pub mod binary_heap;
+#[cfg(feature = "rayon")]
+mod par_collect_in;
+#[cfg(feature = "rayon")]
+pub use par_collect_in::ParallelCollectIn;
+
 // mod btree;
-// pub mod linked_list;
 // pub mod vec_deque;
 
 // pub mod btree_map {
@@ -41,22 +68,17 @@ pub use collect_in::{CollectIn, FromIteratorIn};
 //     pub use super::btree::set::*;
 // }
 
-// #[doc(no_inline)]
-// pub use self::binary_heap::BinaryHeap;
-
 // #[doc(no_inline)]
 // pub use self::btree_map::BTreeMap;
 
 // #[doc(no_inline)]
 // pub use self::btree_set::BTreeSet;
 
-// #[doc(no_inline)]
-// pub use self::linked_list::LinkedList;
-
 // #[doc(no_inline)]
 // pub use self::vec_deque::VecDeque;
 
 use crate::alloc::{AllocErr, LayoutErr};
+use core::fmt;
 
 /// Augments `AllocErr` with a `CapacityOverflow` variant.
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -66,14 +88,33 @@ pub enum CollectionAllocErr {
     /// (usually `isize::MAX` bytes).
     CapacityOverflow,
     /// Error due to the allocator (see the documentation for the [`AllocErr`] type).
-    AllocErr,
+    AllocErr(AllocErr),
 }
 
 // #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
 impl From<AllocErr> for CollectionAllocErr {
     #[inline]
-    fn from(AllocErr: AllocErr) -> Self {
-        CollectionAllocErr::AllocErr
+    fn from(e: AllocErr) -> Self {
+        CollectionAllocErr::AllocErr(e)
+    }
+}
+
+impl fmt::Display for CollectionAllocErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CollectionAllocErr::CapacityOverflow => f.write_str("capacity overflow"),
+            CollectionAllocErr::AllocErr(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core_error"))]
+impl crate::StdError for CollectionAllocErr {
+    fn source(&self) -> Option<&(dyn crate::StdError + 'static)> {
+        match self {
+            CollectionAllocErr::CapacityOverflow => None,
+            CollectionAllocErr::AllocErr(err) => Some(err),
+        }
     }
 }
 
@@ -0,0 +1,91 @@
+use crate::collections::FromIteratorIn;
+use core_alloc::collections::{linked_list, LinkedList};
+use core_alloc::vec::{self, Vec};
+use rayon::iter::ParallelIterator;
+
+/// Extension trait for rayon's parallel iterators, to collect them into
+/// allocator-parameterized collections.
+///
+/// The items are gathered on rayon's worker threads, in temporary buffers on
+/// the heap, and are then moved into the collection on the calling thread,
+/// in their original order. The arena is only ever accessed from the calling
+/// thread, so this works even though [`Bump`](crate::Bump) is not `Sync`.
+///
+/// This trait is only available with the `rayon` Cargo feature.
+pub trait ParallelCollectIn: ParallelIterator {
+    /// Collect all items from a parallel iterator, into a collection
+    /// parameterized by an allocator. Similar to rayon's
+    /// `ParallelIterator::collect`.
+    ///
+    /// The collection is built with [`FromIteratorIn`], from an iterator
+    /// whose `size_hint` is exact, so collections like
+    /// [`Vec`](crate::collections::Vec) are allocated at their final size,
+    /// once.
+    ///
+    /// ```
+    /// use bumpalo::collections::{ParallelCollectIn, Vec};
+    /// use bumpalo::Bump;
+    /// use rayon::prelude::*;
+    ///
+    /// let bump = Bump::new();
+    ///
+    /// let squares: Vec<u64> = (0..1000_u64).into_par_iter().map(|x| x * x).collect_in(&bump);
+    /// assert_eq!(squares.len(), 1000);
+    /// assert_eq!(squares[999], 999 * 999);
+    /// ```
+    fn collect_in<C: FromIteratorIn<Self::Item>>(self, alloc: C::Alloc) -> C {
+        let chunks = self
+            .fold(Vec::new, |mut chunk, item| {
+                chunk.push(item);
+                chunk
+            })
+            .map(|chunk| {
+                let mut list = LinkedList::new();
+                list.push_back(chunk);
+                list
+            })
+            .reduce(LinkedList::new, |mut a, mut b| {
+                a.append(&mut b);
+                a
+            });
+
+        let len = chunks.iter().map(Vec::len).sum();
+        C::from_iter_in(
+            Chunks {
+                chunks: chunks.into_iter(),
+                current: Vec::new().into_iter(),
+                len,
+            },
+            alloc,
+        )
+    }
+}
+
+impl<I: ParallelIterator> ParallelCollectIn for I {}
+
+/// The items of a list of chunks, with an exact `size_hint`.
+struct Chunks<T> {
+    chunks: linked_list::IntoIter<Vec<T>>,
+    current: vec::IntoIter<T>,
+    len: usize,
+}
+
+impl<T> Iterator for Chunks<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = self.current.next() {
+                self.len -= 1;
+                return Some(item);
+            }
+            self.current = self.chunks.next()?.into_iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<T> {}
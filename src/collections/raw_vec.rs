@@ -17,7 +17,7 @@ use core::cmp;
 use core::mem;
 use core::ptr::{self, NonNull};
 
-use crate::alloc::{handle_alloc_error, Alloc, Layout, UnstableLayoutMethods};
+use crate::alloc::{handle_alloc_error, Alloc, Layout};
 use crate::collections::CollectionAllocErr;
 use crate::collections::CollectionAllocErr::*;
 // use boxed::Box;
@@ -82,14 +82,31 @@ impl<'a, T> RawVec<'a, T> {
         RawVec::allocate_in(cap, true, a)
     }
 
-    fn allocate_in(cap: usize, zeroed: bool, mut a: &'a Bump) -> Self {
+    /// Like `with_capacity_in` but returns an error instead of panicking
+    /// if the allocation fails.
+    #[inline]
+    pub fn try_with_capacity_in(cap: usize, a: &'a Bump) -> Result<Self, CollectionAllocErr> {
+        RawVec::try_allocate_in(cap, false, a)
+    }
+
+    fn allocate_in(cap: usize, zeroed: bool, a: &'a Bump) -> Self {
+        match RawVec::try_allocate_in(cap, zeroed, a) {
+            Ok(raw) => raw,
+            Err(CapacityOverflow) => capacity_overflow(),
+            Err(AllocErr(_)) => handle_alloc_error(Layout::array::<T>(cap).unwrap()),
+        }
+    }
+
+    fn try_allocate_in(
+        cap: usize,
+        zeroed: bool,
+        mut a: &'a Bump,
+    ) -> Result<Self, CollectionAllocErr> {
         unsafe {
             let elem_size = mem::size_of::<T>();
 
-            let alloc_size = cap
-                .checked_mul(elem_size)
-                .unwrap_or_else(|| capacity_overflow());
-            alloc_guard(alloc_size).unwrap_or_else(|_| capacity_overflow());
+            let alloc_size = cap.checked_mul(elem_size).ok_or(CapacityOverflow)?;
+            alloc_guard(alloc_size)?;
 
             // handles ZSTs and `cap = 0` alike
             let ptr = if alloc_size == 0 {
@@ -102,13 +119,10 @@ impl<'a, T> RawVec<'a, T> {
                 } else {
                     Alloc::alloc(&mut a, layout)
                 };
-                match result {
-                    Ok(ptr) => ptr.cast(),
-                    Err(_) => handle_alloc_error(layout),
-                }
+                result?.cast()
             };
 
-            RawVec { ptr, cap, a }
+            Ok(RawVec { ptr, cap, a })
         }
     }
 }
@@ -473,7 +487,7 @@ impl<'a, T> RawVec<'a, T> {
             // (regardless of whether `self.cap - used_cap` wrapped).
             // Therefore we can safely call grow_in_place.
 
-            let new_layout = Layout::new::<T>().repeat(new_cap).unwrap().0;
+            let new_layout = Layout::array::<T>(new_cap).unwrap();
             // FIXME: may crash and burn on over-reserve
             alloc_guard(new_layout.size()).unwrap_or_else(|_| capacity_overflow());
             match self
@@ -631,7 +645,7 @@ impl<'a, T> RawVec<'a, T> {
         // call site.
         match self.reserve_internal(used_cap, needed_extra_cap, Infallible, strategy) {
             Err(CapacityOverflow) => capacity_overflow(),
-            Err(AllocErr) => unreachable!(),
+            Err(AllocErr(_)) => unreachable!(),
             Ok(()) => { /* yay */ }
         }
     }
@@ -639,10 +653,11 @@ impl<'a, T> RawVec<'a, T> {
     #[inline(never)]
     fn reserve_internal_or_error(
         &mut self,
-         used_cap: usize,
-         needed_extra_cap: usize,
-         fallibility: Fallibility,
-         strategy: ReserveStrategy,)-> Result<(), CollectionAllocErr> {
+        used_cap: usize,
+        needed_extra_cap: usize,
+        fallibility: Fallibility,
+        strategy: ReserveStrategy,
+    ) -> Result<(), CollectionAllocErr> {
         // Delegates the call to `reserve_internal`, which can be inlined.
         self.reserve_internal(used_cap, needed_extra_cap, fallibility, strategy)
     }
@@ -657,8 +672,6 @@ impl<'a, T> RawVec<'a, T> {
         strategy: ReserveStrategy,
     ) -> Result<(), CollectionAllocErr> {
         unsafe {
-            use crate::AllocErr;
-
             // NOTE: we don't early branch on ZSTs here because we want this
             // to actually catch "asking for more than usize::MAX" in that case.
             // If we make it past the first branch then we are guaranteed to
@@ -683,7 +696,7 @@ impl<'a, T> RawVec<'a, T> {
                 None => Alloc::alloc(&mut self.a, new_layout),
             };
 
-            if let (Err(AllocErr), Infallible) = (&res, fallibility) {
+            if let (Err(_), Infallible) = (&res, fallibility) {
                 handle_alloc_error(new_layout);
             }
 
@@ -727,7 +740,7 @@ impl<'a, T> Drop for RawVec<'a, T> {
 
 #[inline]
 fn alloc_guard(alloc_size: usize) -> Result<(), CollectionAllocErr> {
-    if mem::size_of::<usize>() < 8 && alloc_size > ::core::isize::MAX as usize {
+    if mem::size_of::<usize>() < 8 && alloc_size > isize::MAX as usize {
         Err(CapacityOverflow)
     } else {
         Ok(())
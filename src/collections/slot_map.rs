@@ -0,0 +1,651 @@
+//! A map with generational keys that allocates inside a bump arena.
+//!
+//! See [`SlotMap`] for details.
+
+use crate::collections::vec::Vec;
+use crate::Bump;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Index, IndexMut};
+
+// Marks the end of the free list.
+const NONE: u32 = u32::MAX;
+
+/// A key that refers to a value in a [`SlotMap`].
+///
+/// A key is a slot index and the generation of that slot at the time the value
+/// was inserted. Removing a value bumps the generation of its slot, so keys to
+/// removed values stay invalid even once their slot is reused.
+///
+/// Keys are only meaningful for the map that handed them out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key {
+    index: u32,
+    generation: u32,
+}
+
+impl Key {
+    /// Returns the index of the slot this key refers to.
+    ///
+    /// Keys to values that are live at the same time have distinct indices,
+    /// which are dense enough to index side tables with.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+
+    /// Returns the generation of the slot this key refers to.
+    #[inline]
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+enum Slot<T> {
+    Occupied { generation: u32, value: T },
+    Vacant { generation: u32, next_free: u32 },
+}
+
+impl<T> Slot<T> {
+    #[inline]
+    fn get(&self, generation: u32) -> Option<&T> {
+        match self {
+            Slot::Occupied {
+                generation: g,
+                value,
+            } if *g == generation => Some(value),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, generation: u32) -> Option<&mut T> {
+        match self {
+            Slot::Occupied {
+                generation: g,
+                value,
+            } if *g == generation => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// A map from small, generational [`Key`]s to values, whose storage is
+/// allocated in a bump arena.
+///
+/// Inserting a value returns a fresh key for it. Removed values leave a
+/// vacant slot behind, which is reused by later insertions, so the storage
+/// only grows with the largest number of values that were live at once. Since
+/// keys carry a generation, a stale key to a removed value is never mistaken
+/// for a key to whatever value was inserted in its slot afterwards.
+///
+/// This makes a `SlotMap` a good fit for entities in an ECS, or the nodes of a
+/// graph that refer to each other by key.
+///
+/// Generations are 32 bits and wrap around, so a stale key could in principle
+/// be confused with a new one after a single slot has been reused four billion
+/// times.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::SlotMap};
+///
+/// let b = Bump::new();
+/// let mut entities = SlotMap::new_in(&b);
+///
+/// let player = entities.insert("player");
+/// let enemy = entities.insert("enemy");
+/// assert_eq!(entities[player], "player");
+///
+/// assert_eq!(entities.remove(enemy), Some("enemy"));
+/// assert_eq!(entities.get(enemy), None);
+///
+/// // The enemy's slot is reused, but its old key stays invalid.
+/// let boss = entities.insert("boss");
+/// assert_eq!(boss.index(), enemy.index());
+/// assert_eq!(entities.get(enemy), None);
+/// assert_eq!(entities[boss], "boss");
+/// ```
+pub struct SlotMap<'bump, T> {
+    slots: Vec<'bump, Slot<T>>,
+    // Index of the first vacant slot, or `NONE`.
+    free_head: u32,
+    len: usize,
+}
+
+impl<'bump, T> SlotMap<'bump, T> {
+    /// Constructs a new, empty `SlotMap`.
+    ///
+    /// This does not allocate.
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> SlotMap<'bump, T> {
+        SlotMap {
+            slots: Vec::new_in(bump),
+            free_head: NONE,
+            len: 0,
+        }
+    }
+
+    /// Constructs a new, empty `SlotMap` with room for at least `capacity`
+    /// values.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> SlotMap<'bump, T> {
+        SlotMap {
+            slots: Vec::with_capacity_in(capacity, bump),
+            free_head: NONE,
+            len: 0,
+        }
+    }
+
+    /// Returns the arena this map allocates in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.slots.bump()
+    }
+
+    /// Returns the number of values in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of values the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        // Vacant slots are reused before the storage grows.
+        self.slots.capacity() - self.len
+    }
+
+    /// Reserves room for at least `additional` more values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve(&mut self, additional: usize) {
+        let vacant = self.slots.len() - self.len;
+        if additional > vacant {
+            self.slots.reserve(additional - vacant);
+        }
+    }
+
+    /// Inserts `value` and returns its key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map would hold more than `u32::MAX - 1` slots.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::SlotMap};
+    ///
+    /// let b = Bump::new();
+    /// let mut map = SlotMap::new_in(&b);
+    /// let k = map.insert(42);
+    /// assert_eq!(map[k], 42);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: T) -> Key {
+        self.insert_with_key(|_| value)
+    }
+
+    /// Inserts the value returned by `f`, which is passed the value's key, and
+    /// returns that key.
+    ///
+    /// This allows values to hold on to their own keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map would hold more than `u32::MAX - 1` slots.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::SlotMap};
+    ///
+    /// let b = Bump::new();
+    /// let mut map = SlotMap::new_in(&b);
+    /// let k = map.insert_with_key(|k| (k, "me"));
+    /// assert_eq!(map[k], (k, "me"));
+    /// ```
+    pub fn insert_with_key<F>(&mut self, f: F) -> Key
+    where
+        F: FnOnce(Key) -> T,
+    {
+        if self.free_head != NONE {
+            let index = self.free_head;
+            let slot = &mut self.slots[index as usize];
+            let (generation, next_free) = match *slot {
+                Slot::Vacant {
+                    generation,
+                    next_free,
+                } => (generation, next_free),
+                Slot::Occupied { .. } => unreachable!("occupied slot in the free list"),
+            };
+            let key = Key { index, generation };
+            *slot = Slot::Occupied {
+                generation,
+                value: f(key),
+            };
+            self.free_head = next_free;
+            self.len += 1;
+            return key;
+        }
+
+        let index = self.slots.len();
+        assert!(index < NONE as usize, "too many slots in SlotMap");
+        let key = Key {
+            index: index as u32,
+            generation: 0,
+        };
+        self.slots.push(Slot::Occupied {
+            generation: 0,
+            value: f(key),
+        });
+        self.len += 1;
+        key
+    }
+
+    /// Returns `true` if `key` refers to a value in the map.
+    #[inline]
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` refers to, or `None` if it
+    /// has been removed.
+    #[inline]
+    pub fn get(&self, key: Key) -> Option<&T> {
+        self.slots
+            .get(key.index as usize)
+            .and_then(|slot| slot.get(key.generation))
+    }
+
+    /// Returns a mutable reference to the value that `key` refers to, or
+    /// `None` if it has been removed.
+    #[inline]
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        self.slots
+            .get_mut(key.index as usize)
+            .and_then(|slot| slot.get_mut(key.generation))
+    }
+
+    /// Removes the value that `key` refers to and returns it, or returns
+    /// `None` if it has already been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::SlotMap};
+    ///
+    /// let b = Bump::new();
+    /// let mut map = SlotMap::new_in(&b);
+    /// let k = map.insert("a");
+    /// assert_eq!(map.remove(k), Some("a"));
+    /// assert_eq!(map.remove(k), None);
+    /// ```
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        let free_head = self.free_head;
+        let slot = self.slots.get_mut(key.index as usize)?;
+        slot.get(key.generation)?;
+        let vacant = Slot::Vacant {
+            generation: key.generation.wrapping_add(1),
+            next_free: free_head,
+        };
+        match mem::replace(slot, vacant) {
+            Slot::Occupied { value, .. } => {
+                self.free_head = key.index;
+                self.len -= 1;
+                Some(value)
+            }
+            Slot::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    /// Retains only the values for which `f` returns `true`, and removes the
+    /// others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::SlotMap};
+    ///
+    /// let b = Bump::new();
+    /// let mut map = SlotMap::new_in(&b);
+    /// for i in 0..6 {
+    ///     map.insert(i);
+    /// }
+    /// map.retain(|_, v| *v % 2 == 0);
+    /// assert!(map.values().copied().eq([0, 2, 4]));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Key, &mut T) -> bool,
+    {
+        for index in 0..self.slots.len() {
+            let key = match &mut self.slots[index] {
+                Slot::Occupied { generation, value } => {
+                    let key = Key {
+                        index: index as u32,
+                        generation: *generation,
+                    };
+                    if f(key, value) {
+                        continue;
+                    }
+                    key
+                }
+                Slot::Vacant { .. } => continue,
+            };
+            self.remove(key);
+        }
+    }
+
+    /// Removes all values from the map.
+    ///
+    /// The map keeps its storage, and all keys handed out so far become
+    /// invalid.
+    pub fn clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+
+    /// Returns an iterator over the keys and values of the map, in slot order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: self.slots.iter().enumerate(),
+            len: self.len,
+        }
+    }
+
+    /// Returns an iterator over the keys and mutable references to the values
+    /// of the map, in slot order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.iter_mut().enumerate(),
+            len: self.len,
+        }
+    }
+
+    /// Returns an iterator over the keys of the map, in slot order.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, T> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Returns an iterator over the values of the map, in slot order.
+    #[inline]
+    pub fn values(&self) -> Values<'_, T> {
+        Values { inner: self.iter() }
+    }
+
+    /// Returns an iterator over mutable references to the values of the map,
+    /// in slot order.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, T> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+}
+
+impl<'bump, T: fmt::Debug> fmt::Debug for SlotMap<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'bump, T> Index<Key> for SlotMap<'bump, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, key: Key) -> &T {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("invalid SlotMap key: {:?}", key),
+        }
+    }
+}
+
+impl<'bump, T> IndexMut<Key> for SlotMap<'bump, T> {
+    #[inline]
+    fn index_mut(&mut self, key: Key) -> &mut T {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("invalid SlotMap key: {:?}", key),
+        }
+    }
+}
+
+impl<'a, 'bump, T> IntoIterator for &'a SlotMap<'bump, T> {
+    type Item = (Key, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, 'bump, T> IntoIterator for &'a mut SlotMap<'bump, T> {
+    type Item = (Key, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the keys and values of a [`SlotMap`].
+///
+/// This struct is created by [`SlotMap::iter`].
+pub struct Iter<'a, T> {
+    slots: core::iter::Enumerate<core::slice::Iter<'a, Slot<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Key, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Key, &'a T)> {
+        for (index, slot) in &mut self.slots {
+            if let Slot::Occupied { generation, value } = slot {
+                self.len -= 1;
+                let key = Key {
+                    index: index as u32,
+                    generation: *generation,
+                };
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            slots: self.slots.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the keys and mutable references to the values of a
+/// [`SlotMap`].
+///
+/// This struct is created by [`SlotMap::iter_mut`].
+pub struct IterMut<'a, T> {
+    slots: core::iter::Enumerate<core::slice::IterMut<'a, Slot<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Key, &'a mut T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Key, &'a mut T)> {
+        for (index, slot) in &mut self.slots {
+            if let Slot::Occupied { generation, value } = slot {
+                self.len -= 1;
+                let key = Key {
+                    index: index as u32,
+                    generation: *generation,
+                };
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> fmt::Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut").field("len", &self.len).finish()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An iterator over the keys of a [`SlotMap`].
+///
+/// This struct is created by [`SlotMap::keys`].
+pub struct Keys<'a, T> {
+    inner: Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Keys<'a, T> {
+    type Item = Key;
+
+    #[inline]
+    fn next(&mut self) -> Option<Key> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> Clone for Keys<'a, T> {
+    fn clone(&self) -> Self {
+        Keys {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Keys<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> ExactSizeIterator for Keys<'_, T> {}
+
+impl<T> FusedIterator for Keys<'_, T> {}
+
+/// An iterator over the values of a [`SlotMap`].
+///
+/// This struct is created by [`SlotMap::values`].
+pub struct Values<'a, T> {
+    inner: Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Values<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> Clone for Values<'a, T> {
+    fn clone(&self) -> Self {
+        Values {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Values<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> ExactSizeIterator for Values<'_, T> {}
+
+impl<T> FusedIterator for Values<'_, T> {}
+
+/// An iterator over mutable references to the values of a [`SlotMap`].
+///
+/// This struct is created by [`SlotMap::values_mut`].
+pub struct ValuesMut<'a, T> {
+    inner: IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for ValuesMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> fmt::Debug for ValuesMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValuesMut")
+            .field("len", &self.inner.len)
+            .finish()
+    }
+}
+
+impl<T> ExactSizeIterator for ValuesMut<'_, T> {}
+
+impl<T> FusedIterator for ValuesMut<'_, T> {}
@@ -25,9 +25,7 @@ impl<'a> Utf8Lossy<'a> {
     }
 
     pub fn chunks(&self) -> Utf8LossyChunksIter<'a> {
-        Utf8LossyChunksIter {
-            source: &self.bytes,
-        }
+        Utf8LossyChunksIter { source: self.bytes }
     }
 }
 
@@ -54,6 +54,7 @@
 
 use crate::collections::str::lossy;
 use crate::collections::vec::Vec;
+use crate::collections::CollectionAllocErr;
 use crate::Bump;
 use core::borrow::{Borrow, BorrowMut};
 use core::char::decode_utf16;
@@ -470,6 +471,39 @@ impl<'bump> String<'bump> {
         }
     }
 
+    /// Attempts to create a new empty `String` with a particular capacity.
+    ///
+    /// This is the fallible counterpart of
+    /// [`with_capacity_in`](String::with_capacity_in).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows, or if allocating the
+    /// buffer fails, e.g. because the arena's allocation limit is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    /// b.set_allocation_limit(Some(1024));
+    ///
+    /// let s = String::try_with_capacity_in(100, &b).unwrap();
+    /// assert!(s.capacity() >= 100);
+    ///
+    /// assert!(String::try_with_capacity_in(1 << 20, &b).is_err());
+    /// ```
+    #[inline]
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        bump: &'bump Bump,
+    ) -> Result<String<'bump>, CollectionAllocErr> {
+        Ok(String {
+            vec: Vec::try_with_capacity_in(capacity, bump)?,
+        })
+    }
+
     /// Converts a vector of bytes to a `String`.
     ///
     /// A string (`String`) is made of bytes ([`u8`]), and a vector of bytes
@@ -606,9 +640,7 @@ impl<'bump> String<'bump> {
             let lossy::Utf8LossyChunk { valid, broken } = chunk;
             if valid.len() == v.len() {
                 debug_assert!(broken.is_empty());
-                unsafe {
-                    return String::from_utf8_unchecked(Vec::from_iter_in(v.iter().cloned(), bump));
-                }
+                return String::from_str_in(valid, bump);
             }
             (valid, broken)
         } else {
@@ -667,6 +699,33 @@ impl<'bump> String<'bump> {
         Ok(ret)
     }
 
+    /// Decode a UTF-16 encoded slice `v` into a `String`, replacing
+    /// invalid data with [the replacement character (`U+FFFD`)][U+FFFD].
+    ///
+    /// [U+FFFD]: core::char::REPLACEMENT_CHARACTER
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// // 𝄞mus<invalid>ic<invalid>
+    /// let v = &[0xD834, 0xDD1E, 0x006d, 0x0075, 0x0073, 0xDD1E, 0x0069, 0x0063, 0xD834];
+    ///
+    /// assert_eq!(String::from_utf16_lossy_in(v, &b), "𝄞mus\u{FFFD}ic\u{FFFD}");
+    /// ```
+    pub fn from_utf16_lossy_in(v: &[u16], bump: &'bump Bump) -> String<'bump> {
+        let mut ret = String::with_capacity_in(v.len(), bump);
+        ret.extend(
+            decode_utf16(v.iter().cloned()).map(|c| c.unwrap_or(core::char::REPLACEMENT_CHARACTER)),
+        );
+        ret
+    }
+
     /// Construct a new `String<'bump>` from a string slice.
     ///
     /// # Examples
@@ -809,6 +868,45 @@ impl<'bump> String<'bump> {
         String { vec: bytes }
     }
 
+    /// Wraps bytes that live in the arena as a `String`, without copying
+    /// them and without checking that they are valid UTF-8.
+    ///
+    /// The `String` takes over the slice: its length and capacity are both
+    /// `bytes.len()`. This is meant for parsers that have already validated
+    /// input that they copied into the arena, e.g. with
+    /// [`Bump::alloc_slice_copy`], and want to keep editing it as a string.
+    ///
+    /// The bytes don't have to be `bump`'s most recent allocation, but when
+    /// they are, growing the `String` extends that allocation instead of
+    /// leaving the old bytes behind.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8. See
+    /// [`from_utf8_unchecked`](String::from_utf8_unchecked) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let input = b.alloc_slice_copy(b"key=value");
+    /// assert!(std::str::from_utf8(input).is_ok());
+    ///
+    /// let mut s = unsafe { String::from_utf8_unchecked_in(input, &b) };
+    /// s.push_str("!");
+    /// assert_eq!(s, "key=value!");
+    /// ```
+    #[inline]
+    pub unsafe fn from_utf8_unchecked_in(
+        bytes: &'bump mut [u8],
+        bump: &'bump Bump,
+    ) -> String<'bump> {
+        String::from_raw_parts_in(bytes.as_mut_ptr(), bytes.len(), bytes.len(), bump)
+    }
+
     /// Returns a shared reference to the allocator backing this `String`.
     ///
     /// # Examples
@@ -849,6 +947,31 @@ impl<'bump> String<'bump> {
         self.vec
     }
 
+    /// Decomposes a `String` into its raw components: the pointer to its
+    /// bytes, its length, and its capacity, all in bytes.
+    ///
+    /// The memory stays allocated in the arena, and the `String` can be
+    /// rebuilt with [`from_raw_parts_in`](String::from_raw_parts_in).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let s = String::from_str_in("hello", &b);
+    /// let (ptr, len, capacity) = s.into_raw_parts();
+    ///
+    /// let rebuilt = unsafe { String::from_raw_parts_in(ptr, len, capacity, &b) };
+    /// assert_eq!(rebuilt, "hello");
+    /// ```
+    #[inline]
+    pub fn into_raw_parts(self) -> (*mut u8, usize, usize) {
+        let mut me = mem::ManuallyDrop::new(self);
+        (me.vec.as_mut_ptr(), me.len(), me.capacity())
+    }
+
     /// Convert this `String<'bump>` into a `&'bump str`. This is analogous to
     /// [`std::string::String::into_boxed_str`][into_boxed_str].
     ///
@@ -868,12 +991,63 @@ impl<'bump> String<'bump> {
     pub fn into_bump_str(self) -> &'bump str {
         let s = unsafe {
             let s = self.as_str();
-            mem::transmute(s)
+            mem::transmute::<&str, &'bump str>(s)
         };
         mem::forget(self);
         s
     }
 
+    /// Consumes the `String` and leaks its contents, returning an exclusive
+    /// reference to them that lives as long as the arena.
+    ///
+    /// Like [`into_bump_str`](String::into_bump_str), but the reference is
+    /// exclusive, and the string's excess capacity is given back to the arena
+    /// first if its buffer is the most recent allocation in it. See
+    /// [`Vec::leak`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut s = String::with_capacity_in(100, &b);
+    /// s.push_str("hello");
+    ///
+    /// let s: &mut str = s.leak();
+    /// s.make_ascii_uppercase();
+    /// assert_eq!(s, "HELLO");
+    /// ```
+    pub fn leak(self) -> &'bump mut str {
+        let bytes = self.vec.leak();
+        // The bytes came from a `String`, so they are valid UTF-8.
+        unsafe { str::from_utf8_unchecked_mut(bytes) }
+    }
+
+    /// Converts this `String` into a [`Box<str>`][owned str].
+    ///
+    /// Note that this will drop any excess capacity.
+    ///
+    /// [owned str]: ../../boxed/struct.Box.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let s = String::from_str_in("hello", &b);
+    /// let boxed = s.into_boxed_str();
+    /// assert_eq!(&*boxed, "hello");
+    /// ```
+    #[cfg(feature = "boxed")]
+    pub fn into_boxed_str(self) -> crate::boxed::Box<'bump, str> {
+        let slice = self.vec.into_boxed_slice();
+        unsafe { crate::boxed::Box::from_raw(crate::boxed::Box::into_raw(slice) as *mut str) }
+    }
+
     /// Extracts a string slice containing the entire `String`.
     ///
     /// # Examples
@@ -939,6 +1113,33 @@ impl<'bump> String<'bump> {
         self.vec.extend_from_slice_copy(string.as_bytes())
     }
 
+    /// Attempts to append a given string slice onto the end of this `String`.
+    ///
+    /// This is the fallible counterpart of [`push_str`](String::push_str).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the string unchanged, if it needs to grow
+    /// and allocating its new buffer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut s = String::from_str_in("foo", &b);
+    /// s.try_push_str("bar").unwrap();
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), CollectionAllocErr> {
+        self.vec.try_reserve(string.len())?;
+        self.push_str(string);
+        Ok(())
+    }
+
     /// Returns this `String`'s capacity, in bytes.
     ///
     /// # Examples
@@ -1126,6 +1327,35 @@ impl<'bump> String<'bump> {
         }
     }
 
+    /// Attempts to append the given [`char`] to the end of this `String`.
+    ///
+    /// This is the fallible counterpart of [`push`](String::push).
+    ///
+    /// [`char`]: https://doc.rust-lang.org/std/primitive.char.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the string unchanged, if it needs to grow
+    /// and allocating its new buffer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut s = String::from_str_in("abc", &b);
+    /// s.try_push('1').unwrap();
+    /// assert_eq!("abc1", s);
+    /// ```
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), CollectionAllocErr> {
+        self.vec.try_reserve(ch.len_utf8())?;
+        self.push(ch);
+        Ok(())
+    }
+
     /// Returns a byte slice of this `String`'s contents.
     ///
     /// The inverse of this method is [`from_utf8`].
@@ -1212,7 +1442,7 @@ impl<'bump> String<'bump> {
     /// ```
     #[inline]
     pub fn pop(&mut self) -> Option<char> {
-        let ch = self.chars().rev().next()?;
+        let ch = self.chars().next_back()?;
         let newlen = self.len() - ch.len_utf8();
         unsafe {
             self.vec.set_len(newlen);
@@ -1362,6 +1592,41 @@ impl<'bump> String<'bump> {
         }
     }
 
+    /// Attempts to insert a character into this `String` at a byte position.
+    ///
+    /// This is the fallible counterpart of [`insert`](String::insert).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the string unchanged, if it needs to grow
+    /// and allocating its new buffer fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// [`char`]: https://doc.rust-lang.org/std/primitive.char.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut s = String::from_str_in("oo", &b);
+    /// s.try_insert(0, 'f').unwrap();
+    /// assert_eq!("foo", s);
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, idx: usize, ch: char) -> Result<(), CollectionAllocErr> {
+        assert!(self.is_char_boundary(idx));
+        self.vec.try_reserve(ch.len_utf8())?;
+        self.insert(idx, ch);
+        Ok(())
+    }
+
     unsafe fn insert_bytes(&mut self, idx: usize, bytes: &[u8]) {
         let len = self.len();
         let amt = bytes.len();
@@ -1412,6 +1677,98 @@ impl<'bump> String<'bump> {
         }
     }
 
+    /// Attempts to insert a string slice into this `String` at a byte
+    /// position.
+    ///
+    /// This is the fallible counterpart of [`insert_str`](String::insert_str).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string needs to grow and allocating its new
+    /// buffer fails. The string is left unchanged in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut s = String::from_str_in("bar", &b);
+    /// s.try_insert_str(0, "foo").unwrap();
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    pub fn try_insert_str(&mut self, idx: usize, string: &str) -> Result<(), CollectionAllocErr> {
+        assert!(self.is_char_boundary(idx));
+        self.vec.try_reserve(string.len())?;
+        unsafe {
+            self.insert_bytes(idx, string.as_bytes());
+        }
+        Ok(())
+    }
+
+    /// Copies the bytes in the range `src` of this string to its end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range has `start > end` or `end > len`, or if either end
+    /// of the range does not lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::String};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut s = String::from_str_in("abcde", &b);
+    ///
+    /// s.extend_from_within(2..);
+    /// assert_eq!(s, "abcdecde");
+    ///
+    /// s.extend_from_within(..2);
+    /// assert_eq!(s, "abcdecdeab");
+    ///
+    /// s.extend_from_within(4..8);
+    /// assert_eq!(s, "abcdecdeabecde");
+    /// ```
+    pub fn extend_from_within<R>(&mut self, src: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let start = match src.start_bound() {
+            Included(&n) => n,
+            Excluded(&n) => n.checked_add(1).expect("range start overflowed"),
+            Unbounded => 0,
+        };
+        let end = match src.end_bound() {
+            Included(&n) => n.checked_add(1).expect("range end overflowed"),
+            Excluded(&n) => n,
+            Unbounded => len,
+        };
+        assert!(start <= end, "range start is greater than range end");
+        assert!(end <= len, "range end is out of bounds");
+        assert!(self.is_char_boundary(start));
+        assert!(self.is_char_boundary(end));
+
+        let amt = end - start;
+        self.vec.reserve(amt);
+        unsafe {
+            // The source is within the initialized part of the buffer and the
+            // destination is its spare capacity, so they cannot overlap.
+            let ptr = self.vec.as_mut_ptr();
+            ptr::copy_nonoverlapping(ptr.add(start), ptr.add(len), amt);
+            self.vec.set_len(len + amt);
+        }
+    }
+
     /// Returns a mutable reference to the contents of this `String`.
     ///
     /// # Safety
@@ -1765,6 +2122,43 @@ impl fmt::Display for FromUtf16Error {
     }
 }
 
+#[cfg(any(feature = "std", feature = "core_error"))]
+impl<'bump> crate::StdError for FromUtf8Error<'bump> {
+    fn source(&self) -> Option<&(dyn crate::StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core_error"))]
+impl crate::StdError for FromUtf16Error {}
+
+#[cfg(feature = "boxed")]
+impl<'bump> From<String<'bump>> for crate::boxed::Box<'bump, str> {
+    fn from(s: String<'bump>) -> crate::boxed::Box<'bump, str> {
+        s.into_boxed_str()
+    }
+}
+
+impl<'bump> From<String<'bump>> for Vec<'bump, u8> {
+    /// Converts the given `String` to a vector of its bytes, without copying
+    /// them.
+    #[inline]
+    fn from(s: String<'bump>) -> Vec<'bump, u8> {
+        s.into_bytes()
+    }
+}
+
+impl<'bump> TryFrom<Vec<'bump, u8>> for String<'bump> {
+    type Error = FromUtf8Error<'bump>;
+
+    /// Converts the given vector of bytes to a `String` if it is valid UTF-8,
+    /// without copying them. See [`String::from_utf8`].
+    #[inline]
+    fn try_from(bytes: Vec<'bump, u8>) -> Result<Self, Self::Error> {
+        String::from_utf8(bytes)
+    }
+}
+
 impl<'bump> Clone for String<'bump> {
     fn clone(&self) -> Self {
         String {
@@ -1926,7 +2320,7 @@ impl<'bump> hash::Hash for String<'bump> {
 /// let b = " world";
 /// let c = String::from_str_in(a, &bump) + b;
 /// ```
-impl<'a, 'bump> Add<&'a str> for String<'bump> {
+impl<'bump> Add<&str> for String<'bump> {
     type Output = String<'bump>;
 
     #[inline]
@@ -2016,7 +2410,7 @@ impl<'bump> ops::IndexMut<ops::RangeFrom<usize>> for String<'bump> {
 impl<'bump> ops::IndexMut<ops::RangeFull> for String<'bump> {
     #[inline]
     fn index_mut(&mut self, _index: ops::RangeFull) -> &mut str {
-        unsafe { str::from_utf8_unchecked_mut(&mut *self.vec) }
+        unsafe { str::from_utf8_unchecked_mut(&mut self.vec) }
     }
 }
 impl<'bump> ops::IndexMut<ops::RangeInclusive<usize>> for String<'bump> {
@@ -2044,7 +2438,7 @@ impl<'bump> ops::Deref for String<'bump> {
 impl<'bump> ops::DerefMut for String<'bump> {
     #[inline]
     fn deref_mut(&mut self) -> &mut str {
-        unsafe { str::from_utf8_unchecked_mut(&mut *self.vec) }
+        unsafe { str::from_utf8_unchecked_mut(&mut self.vec) }
     }
 }
 
@@ -2150,3 +2544,79 @@ impl<'a, 'bump> DoubleEndedIterator for Drain<'a, 'bump> {
 }
 
 impl<'a, 'bump> FusedIterator for Drain<'a, 'bump> {}
+
+#[cfg(feature = "serde")]
+mod serialize {
+    use super::*;
+
+    use crate::serde::DeserializeIn;
+    use serde::{
+        de::{Error, Unexpected, Visitor},
+        Deserializer, Serialize, Serializer,
+    };
+
+    impl<'bump> Serialize for String<'bump> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_str(self)
+        }
+    }
+
+    impl<'de, 'bump> DeserializeIn<'de, 'bump> for String<'bump> {
+        fn deserialize_in<D>(deserializer: D, bump: &'bump Bump) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct StringVisitor<'bump> {
+                bump: &'bump Bump,
+            }
+
+            impl<'de, 'bump> Visitor<'de> for StringVisitor<'bump> {
+                type Value = String<'bump>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a string")
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    Ok(String::from_str_in(v, self.bump))
+                }
+
+                fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    match str::from_utf8(v) {
+                        Ok(s) => Ok(String::from_str_in(s, self.bump)),
+                        Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_str(StringVisitor { bump })
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_in {
+    use super::*;
+
+    use crate::arbitrary::ArbitraryIn;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    impl<'a, 'bump> ArbitraryIn<'a, 'bump> for String<'bump> {
+        fn arbitrary_in(u: &mut Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+            <&str>::arbitrary(u).map(|s| String::from_str_in(s, bump))
+        }
+
+        fn arbitrary_take_rest_in(u: Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+            <&str>::arbitrary_take_rest(u).map(|s| String::from_str_in(s, bump))
+        }
+    }
+}
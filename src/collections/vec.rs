@@ -189,7 +189,7 @@ where
             if !same_bucket(&mut *ptr_read, &mut *prev_ptr_write) {
                 if next_read != next_write {
                     let ptr_write = prev_ptr_write.offset(1);
-                    mem::swap(&mut *ptr_read, &mut *ptr_write);
+                    ptr::swap(ptr_read, ptr_write);
                 }
                 next_write += 1;
             }
@@ -205,7 +205,7 @@ where
     T: Sized,
 {
     let pointee_size = mem::size_of::<T>();
-    assert!(0 < pointee_size && pointee_size <= isize::max_value() as usize);
+    assert!(0 < pointee_size && pointee_size <= isize::MAX as usize);
 
     // This is the same sequence that Clang emits for pointer subtraction.
     // It can be neither `nsw` nor `nuw` because the input is treated as
@@ -253,18 +253,9 @@ where
 /// [`Clone`]: https://doc.rust-lang.org/std/clone/trait.Clone.html
 #[macro_export]
 macro_rules! vec {
-    (in $bump:expr; $elem:expr; $n:expr) => {{
-        let n = $n;
-        let mut v = $crate::collections::Vec::with_capacity_in(n, $bump);
-        if n > 0 {
-            let elem = $elem;
-            for _ in 0..n - 1 {
-                v.push(elem.clone());
-            }
-            v.push(elem);
-        }
-        v
-    }};
+    (in $bump:expr; $elem:expr; $n:expr) => {
+        $crate::collections::Vec::from_elem_in($elem, $n, $bump)
+    };
     (in $bump:expr) => { $crate::collections::Vec::new_in($bump) };
     (in $bump:expr; $($x:expr),*) => {{
         let mut v = $crate::collections::Vec::new_in($bump);
@@ -590,6 +581,41 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Attempts to construct a new, empty `Vec<'bump, T>` with the specified
+    /// capacity.
+    ///
+    /// This is the fallible counterpart of
+    /// [`with_capacity_in`](Vec::with_capacity_in).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows, or if allocating the
+    /// buffer fails, e.g. because the arena's allocation limit is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// b.set_allocation_limit(Some(1024));
+    ///
+    /// let vec = Vec::<u8>::try_with_capacity_in(100, &b).unwrap();
+    /// assert!(vec.capacity() >= 100);
+    ///
+    /// assert!(Vec::<u8>::try_with_capacity_in(1 << 20, &b).is_err());
+    /// ```
+    #[inline]
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        bump: &'bump Bump,
+    ) -> Result<Vec<'bump, T>, CollectionAllocErr> {
+        Ok(Vec {
+            buf: RawVec::try_with_capacity_in(capacity, bump)?,
+            len: 0,
+        })
+    }
+
     /// Construct a new `Vec` from the given iterator's items.
     ///
     /// # Examples
@@ -608,6 +634,100 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         v
     }
 
+    /// Construct a new `Vec` holding a copy of the given slice.
+    ///
+    /// The buffer is allocated at exactly the slice's length, and filled
+    /// with a single `memcpy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let v = Vec::from_slice_copy_in(&[1, 2, 3], &b);
+    /// assert_eq!(v, [1, 2, 3]);
+    /// assert_eq!(v.capacity(), 3);
+    /// ```
+    pub fn from_slice_copy_in(src: &[T], bump: &'bump Bump) -> Vec<'bump, T>
+    where
+        T: Copy,
+    {
+        let mut v = Vec::with_capacity_in(src.len(), bump);
+        v.extend_from_slice_copy(src);
+        v
+    }
+
+    /// Construct a new `Vec` holding clones of the given slice's elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let names = [String::from("a"), String::from("b")];
+    /// let v = Vec::from_slice_clone_in(&names, &b);
+    /// assert_eq!(v, names);
+    /// ```
+    pub fn from_slice_clone_in(src: &[T], bump: &'bump Bump) -> Vec<'bump, T>
+    where
+        T: Clone,
+    {
+        let mut v = Vec::with_capacity_in(src.len(), bump);
+        v.extend_from_slice(src);
+        v
+    }
+
+    /// Construct a new `Vec` holding the elements of the given array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let v = Vec::from_array_in([String::from("a"), String::from("b")], &b);
+    /// assert_eq!(v, ["a", "b"]);
+    /// assert_eq!(v.capacity(), 2);
+    /// ```
+    pub fn from_array_in<const N: usize>(array: [T; N], bump: &'bump Bump) -> Vec<'bump, T> {
+        let mut v = Vec::with_capacity_in(N, bump);
+        unsafe {
+            let array = mem::ManuallyDrop::new(array);
+            ptr::copy_nonoverlapping(array.as_ptr(), v.as_mut_ptr(), N);
+            v.set_len(N);
+        }
+        v
+    }
+
+    /// Construct a new `Vec` holding `n` clones of `elem`.
+    ///
+    /// The buffer is allocated once, at its final size, and then filled in a
+    /// single pass; for `Copy` element types like `u8`, the fill typically
+    /// compiles down to a `memset`. This is what [`vec![in &b; elem;
+    /// n]`](crate::vec!) expands to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let v = Vec::from_elem_in(0_u8, 4096, &b);
+    /// assert_eq!(v.len(), 4096);
+    /// assert_eq!(v.capacity(), 4096);
+    /// assert!(v.iter().all(|&x| x == 0));
+    /// ```
+    pub fn from_elem_in(elem: T, n: usize, bump: &'bump Bump) -> Vec<'bump, T>
+    where
+        T: Clone,
+    {
+        let mut v = Vec::with_capacity_in(n, bump);
+        v.extend_with(n, ExtendElement(elem));
+        v
+    }
+
     /// Creates a `Vec<'bump, T>` directly from the raw components of another vector.
     ///
     /// # Safety
@@ -821,6 +941,12 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// It will drop down as close as possible to the length but the allocator
     /// may still inform the vector that there is space for a few more elements.
     ///
+    /// If the vector's buffer is the most recent allocation in its arena, the
+    /// released capacity is given back to the arena, and subsequent
+    /// allocations reuse it. Since the arena allocates downwards, this moves
+    /// the vector's elements up to the end of the buffer. Otherwise, the
+    /// released capacity can't be reused until the arena is reset.
+    ///
     /// # Examples
     ///
     /// ```
@@ -834,12 +960,59 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// vec.shrink_to_fit();
     /// assert!(vec.capacity() >= 3);
     /// ```
+    ///
+    /// The released capacity is reused by the next allocation:
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = Vec::with_capacity_in(100, &b);
+    /// vec.extend_from_slice(&[1_u32, 2, 3]);
+    /// let used = b.allocated_bytes_in_current_chunk();
+    ///
+    /// vec.shrink_to_fit();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// assert_eq!(b.allocated_bytes_in_current_chunk(), used - 97 * 4);
+    /// ```
     pub fn shrink_to_fit(&mut self) {
         if self.capacity() != self.len {
             self.buf.shrink_to_fit(self.len);
         }
     }
 
+    /// Shrinks the capacity of the vector with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length and the
+    /// supplied value. If the current capacity is less than the lower limit,
+    /// this is a no-op.
+    ///
+    /// Like [`shrink_to_fit`](Vec::shrink_to_fit), this gives the released
+    /// capacity back to the arena when the vector's buffer is the most recent
+    /// allocation in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = Vec::with_capacity_in(10, &b);
+    /// vec.extend([1, 2, 3].iter().cloned());
+    /// assert_eq!(vec.capacity(), 10);
+    /// vec.shrink_to(4);
+    /// assert!(vec.capacity() >= 4);
+    /// vec.shrink_to(0);
+    /// assert!(vec.capacity() >= 3);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        if self.capacity() > min_capacity {
+            self.buf.shrink_to_fit(self.len.max(min_capacity));
+        }
+    }
+
     /// Converts the vector into `&'bump [T]`.
     ///
     /// # Examples
@@ -887,6 +1060,35 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         unsafe { slice::from_raw_parts_mut(ptr, len) }
     }
 
+    /// Consumes the vector and leaks its elements, returning an exclusive
+    /// reference to them that lives as long as the arena.
+    ///
+    /// This is the same as [`into_bump_slice_mut`](Vec::into_bump_slice_mut),
+    /// except that the vector's excess capacity is given back to the arena
+    /// first if its buffer is the most recent allocation in it, as with
+    /// [`shrink_to_fit`](Vec::shrink_to_fit). The elements are never dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v = Vec::with_capacity_in(100, &b);
+    /// v.extend_from_slice(&[1_u32, 2, 3]);
+    /// let used = b.allocated_bytes_in_current_chunk();
+    ///
+    /// let slice: &mut [u32] = v.leak();
+    /// slice[0] = 10;
+    /// assert_eq!(slice, [10, 2, 3]);
+    /// assert_eq!(b.allocated_bytes_in_current_chunk(), used - 97 * 4);
+    /// ```
+    pub fn leak(mut self) -> &'bump mut [T] {
+        self.shrink_to_fit();
+        self.into_bump_slice_mut()
+    }
+
     /// Shortens the vector, keeping the first `len` elements and dropping
     /// the rest.
     ///
@@ -1198,6 +1400,76 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Removes the elements at the given `indices` from the vector, replacing
+    /// each of them with one of the last elements.
+    ///
+    /// This does not preserve ordering, but is *O*(`indices.len()`). It is the
+    /// batch version of [`swap_remove`]: elements are removed from the highest
+    /// index down, so that no element that is still to be removed gets moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indices` is not sorted in strictly ascending order, or if
+    /// one of them is out of bounds. The vector is left unchanged in that
+    /// case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v = bumpalo::vec![in &b; "a", "b", "c", "d", "e", "f"];
+    /// v.swap_remove_many(&[0, 2]);
+    /// assert_eq!(v, ["e", "b", "f", "d"]);
+    /// ```
+    ///
+    /// [`swap_remove`]: #method.swap_remove
+    pub fn swap_remove_many(&mut self, indices: &[usize]) {
+        check_removal_indices(indices, self.len());
+        for &index in indices.iter().rev() {
+            self.swap_remove(index);
+        }
+    }
+
+    /// Removes the elements at the given `indices` from the vector, shifting
+    /// the remaining elements to the left.
+    ///
+    /// This preserves the order of the remaining elements, and compacts the
+    /// vector in a single pass over it, instead of the *O*(*n*) shift that
+    /// each [`remove`] call costs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indices` is not sorted in strictly ascending order, or if
+    /// one of them is out of bounds. The vector is left unchanged in that
+    /// case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut v = bumpalo::vec![in &b; "a", "b", "c", "d", "e", "f"];
+    /// v.remove_many(&[0, 2, 3]);
+    /// assert_eq!(v, ["b", "e", "f"]);
+    /// ```
+    ///
+    /// [`remove`]: #method.remove
+    pub fn remove_many(&mut self, indices: &[usize]) {
+        check_removal_indices(indices, self.len());
+        let mut indices = indices.iter().peekable();
+        let mut i = 0;
+        self.drain_filter(|_| {
+            let remove = indices.next_if_eq(&&i).is_some();
+            i += 1;
+            remove
+        });
+    }
+
     /// Inserts an element at position `index` within the vector, shifting all
     /// elements after it to the right.
     ///
@@ -1243,6 +1515,40 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Attempts to insert an element at position `index` within the vector,
+    /// shifting all elements after it to the right.
+    ///
+    /// This is the fallible counterpart of [`insert`](Vec::insert).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and drops `element`, if the vector needs to grow and
+    /// allocating its new buffer fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; 1, 2, 3];
+    /// vec.try_insert(1, 4).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// ```
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), CollectionAllocErr> {
+        assert!(index <= self.len());
+        if self.len == self.buf.cap() {
+            self.try_reserve(1)?;
+        }
+        self.insert(index, element);
+        Ok(())
+    }
+
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     ///
@@ -1306,6 +1612,67 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         self.drain_filter(|x| !f(x));
     }
 
+    /// Retains only the elements specified by the predicate, passing a mutable
+    /// reference to it.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns
+    /// `false`. This method operates in place and preserves the order of the
+    /// retained elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; 1, 2, 3, 4];
+    /// vec.retain_mut(|x| if *x <= 3 {
+    ///     *x += 1;
+    ///     true
+    /// } else {
+    ///     false
+    /// });
+    /// assert_eq!(vec, [2, 3, 4]);
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.drain_filter(|x| !f(x));
+    }
+
+    /// Retains only the elements specified by the predicate, moving the
+    /// removed elements into a new vector allocated in `bump`.
+    ///
+    /// This partitions the vector in a single pass: afterwards `self` holds
+    /// the elements for which `f` returned `true`, and the returned vector
+    /// holds the rest. Both preserve the original relative order of their
+    /// elements.
+    ///
+    /// `bump` does not have to be the arena that `self` is allocated in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; 1, 2, 3, 4, 5];
+    /// let odds = vec.retain_into(|&x| x % 2 == 0, &b);
+    /// assert_eq!(vec, [2, 4]);
+    /// assert_eq!(odds, [1, 3, 5]);
+    /// ```
+    pub fn retain_into<'b, F>(&mut self, mut f: F, bump: &'b Bump) -> Vec<'b, T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut removed = Vec::new_in(bump);
+        removed.extend(self.drain_filter(|x| !f(x)));
+        removed
+    }
+
     /// Creates an iterator that removes the elements in the vector
     /// for which the predicate returns `true` and yields the removed items.
     ///
@@ -1344,6 +1711,86 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Sorts the vector with a key extraction function, calling it only once
+    /// per element.
+    ///
+    /// This behaves like the slice method of the same name, but the keys are
+    /// cached in a scratch vector in this vector's arena, rather than on the
+    /// global heap. The sort is stable. Use
+    /// [`sort_by_cached_key_in`](Vec::sort_by_cached_key_in) to put the
+    /// scratch vector in another arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; -5i32, 4, 32, -3, 2];
+    ///
+    /// vec.sort_by_cached_key(|k| k.to_string());
+    ///
+    /// assert_eq!(vec, [-3, -5, 2, 32, 4]);
+    /// ```
+    pub fn sort_by_cached_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sort_by_cached_key_in(f, self.bump())
+    }
+
+    /// Sorts the vector with a key extraction function, calling it only once
+    /// per element, and caching the keys in `scratch`.
+    ///
+    /// This behaves like [`sort_by_cached_key`](Vec::sort_by_cached_key),
+    /// except that the scratch vector of keys is allocated in `scratch`,
+    /// which does not have to be the arena that `self` is allocated in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// let scratch = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; "apple", "fig", "banana", "kiwi"];
+    ///
+    /// vec.sort_by_cached_key_in(|s| s.len(), &scratch);
+    ///
+    /// assert_eq!(vec, ["fig", "kiwi", "apple", "banana"]);
+    /// ```
+    pub fn sort_by_cached_key_in<K, F>(&mut self, f: F, scratch: &Bump)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+
+        // Pair each key with its element's index, which also makes the
+        // unstable sort below stable, since no two pairs compare equal.
+        let mut indices =
+            Vec::from_iter_in(self.iter().map(f).enumerate().map(|(i, k)| (k, i)), scratch);
+        indices.sort_unstable();
+
+        // Apply the permutation in place. Element `i` belongs at the index in
+        // `indices[i]`, unless that element was already swapped away, in
+        // which case following the swaps made so far finds where it went.
+        for i in 0..len {
+            let mut index = indices[i].1;
+            while index < i {
+                index = indices[index].1;
+            }
+            indices[i].1 = index;
+            self.swap(i, index);
+        }
+    }
+
     /// Removes all but the first of consecutive elements in the vector that resolve to the same
     /// key.
     ///
@@ -1404,6 +1851,51 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         self.truncate(len);
     }
 
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying a given equality relation, moving the removed elements into
+    /// a new vector allocated in `bump`.
+    ///
+    /// This behaves like [`dedup_by`](Vec::dedup_by), except that the
+    /// duplicates are returned instead of dropped. The order of the elements
+    /// in the returned vector is unspecified.
+    ///
+    /// `bump` does not have to be the arena that `self` is allocated in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; "foo", "bar", "Bar", "baz", "bar"];
+    ///
+    /// let removed = vec.dedup_by_into(|a, b| a.eq_ignore_ascii_case(b), &b);
+    ///
+    /// assert_eq!(vec, ["foo", "bar", "baz", "bar"]);
+    /// assert_eq!(removed, ["Bar"]);
+    /// ```
+    pub fn dedup_by_into<'b, F>(&mut self, same_bucket: F, bump: &'b Bump) -> Vec<'b, T>
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = {
+            let (dedup, _) = partition_dedup_by(self.as_mut_slice(), same_bucket);
+            dedup.len()
+        };
+        let removed_len = self.len() - len;
+
+        // Reserve before touching `self`'s length so that an allocation
+        // failure leaves `self` intact.
+        let mut removed = Vec::with_capacity_in(removed_len, bump);
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr().add(len), removed.as_mut_ptr(), removed_len);
+            self.set_len(len);
+            removed.set_len(removed_len);
+        }
+        removed
+    }
+
     /// Appends an element to the back of a vector.
     ///
     /// # Panics
@@ -1435,6 +1927,36 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
         }
     }
 
+    /// Attempts to append an element to the back of a vector.
+    ///
+    /// This is the fallible counterpart of [`push`](Vec::push).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and drops `value`, if the vector needs to grow and
+    /// allocating its new buffer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    /// b.set_allocation_limit(Some(0));
+    ///
+    /// let mut vec = Vec::new_in(&b);
+    /// assert!(vec.try_push(1).is_err());
+    /// assert!(vec.is_empty());
+    /// ```
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), CollectionAllocErr> {
+        if self.len == self.buf.cap() {
+            self.try_reserve(1)?;
+        }
+        self.push(value);
+        Ok(())
+    }
+
     /// Removes the last element from a vector and returns it, or [`None`] if it
     /// is empty.
     ///
@@ -1493,7 +2015,7 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// Appends elements to `Self` from other buffer.
     #[inline]
     unsafe fn append_elements(&mut self, other: *const [T]) {
-        let count = (*other).len();
+        let count = (&*other).len();
         self.reserve(count);
         let len = self.len();
         ptr::copy_nonoverlapping(other as *const T, self.as_mut_ptr().add(len), count);
@@ -1533,7 +2055,7 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// v.drain(..);
     /// assert_eq!(v, &[]);
     /// ```
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, '_, T>
     where
         R: RangeBounds<usize>,
     {
@@ -1775,6 +2297,83 @@ impl<'bump, T: 'bump + Clone> Vec<'bump, T> {
     pub fn extend_from_slice(&mut self, other: &[T]) {
         self.extend(other.iter().cloned())
     }
+
+    /// Attempts to clone and append all elements in a slice to the `Vec`.
+    ///
+    /// This is the fallible counterpart of
+    /// [`extend_from_slice`](Vec::extend_from_slice). Either all of the
+    /// elements are appended, or none of them are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vector needs to grow and allocating its new
+    /// buffer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; 1];
+    /// vec.try_extend_from_slice(&[2, 3, 4]).unwrap();
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// ```
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CollectionAllocErr> {
+        self.try_reserve(other.len())?;
+        self.extend_from_slice(other);
+        Ok(())
+    }
+
+    /// Clones the elements from `src` range to the end of the vector.
+    ///
+    /// Space for all of the new elements is reserved up front, with a single
+    /// reallocation at most.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the
+    /// end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut characters = bumpalo::vec![in &b; 'a', 'b', 'c', 'd', 'e'];
+    /// characters.extend_from_within(2..);
+    /// assert_eq!(characters, ['a', 'b', 'c', 'd', 'e', 'c', 'd', 'e']);
+    ///
+    /// let mut numbers = bumpalo::vec![in &b; 0, 1, 2, 3, 4];
+    /// numbers.extend_from_within(..2);
+    /// assert_eq!(numbers, [0, 1, 2, 3, 4, 0, 1]);
+    ///
+    /// let mut strings = bumpalo::vec![in &b; String::from("hello"), String::from("world")];
+    /// strings.extend_from_within(1..=1);
+    /// assert_eq!(strings, ["hello", "world", "world"]);
+    /// ```
+    pub fn extend_from_within<R>(&mut self, src: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = slice_range(src, self.len());
+        self.reserve(end - start);
+
+        unsafe {
+            let base = self.as_mut_ptr();
+            let mut dst = base.add(self.len());
+            let mut local_len = SetLenOnDrop::new(&mut self.len);
+            for i in start..end {
+                ptr::write(dst, (*base.add(i)).clone());
+                dst = dst.add(1);
+                // Increment the length in every step in case clone() panics
+                local_len.increment_len(1);
+            }
+        }
+    }
 }
 
 impl<'bump, T: 'bump + Copy> Vec<'bump, T> {
@@ -1805,7 +2404,6 @@ impl<'bump, T: 'bump + Copy> Vec<'bump, T> {
         }
     }
 
-
     /// Copies all elements in the slice `other` and appends them to the `Vec`.
     ///
     /// Note that this function is same as [`extend_from_slice`] except that it is optimized for
@@ -1901,6 +2499,54 @@ impl<'bump, T: 'bump + Copy> Vec<'bump, T> {
             });
         }
     }
+
+    /// Copies the elements from `src` range to the end of the vector.
+    ///
+    /// This is the same as [`extend_from_within`], but optimized for types
+    /// that implement the `Copy` trait: after reserving space for the new
+    /// elements, they are appended with a single `memcpy`. This is handy for
+    /// back-references in LZ-style decompressors that decompress directly
+    /// into arena memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the
+    /// end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut out = bumpalo::vec![in &b; b'a', b'b', b'c'];
+    /// // Copy 3 bytes from 3 bytes back.
+    /// out.extend_from_within_copy(out.len() - 3..);
+    /// assert_eq!(out, b"abcabc");
+    /// ```
+    ///
+    /// [`extend_from_within`]: #method.extend_from_within
+    pub fn extend_from_within_copy<R>(&mut self, src: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = slice_range(src, self.len());
+        self.reserve(end - start);
+
+        // SAFETY:
+        // * `start..end` lies within the initialized part of the vector, as
+        //   `slice_range` checked.
+        // * The destination starts at `self.len()`, after the end of the
+        //   source, and has room for `end - start` items as `self.reserve`
+        //   above guarantees that, so the two ranges cannot overlap.
+        unsafe {
+            let len = self.len();
+            let base = self.as_mut_ptr();
+            ptr::copy_nonoverlapping(base.add(start), base.add(len), end - start);
+            self.set_len(len + end - start);
+        }
+    }
 }
 
 // This code generalises `extend_with_{element,default}`.
@@ -1950,6 +2596,41 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     }
 }
 
+// Check that `indices` are strictly ascending indices into a vector of length
+// `len`, for the batch removal methods.
+fn check_removal_indices(indices: &[usize], len: usize) {
+    assert!(
+        indices.windows(2).all(|w| w[0] < w[1]),
+        "removal indices are not strictly ascending"
+    );
+    if let Some(&last) = indices.last() {
+        assert!(
+            last < len,
+            "removal index (is {}) should be < len (is {})",
+            last,
+            len
+        );
+    }
+}
+
+// Convert `range` into the indices `(start, end)` of a subslice of a slice of
+// length `len`, panicking if it is out of bounds.
+fn slice_range<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Included(&n) => n,
+        Excluded(&n) => n.checked_add(1).expect("range start overflows usize"),
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(&n) => n.checked_add(1).expect("range end overflows usize"),
+        Excluded(&n) => n,
+        Unbounded => len,
+    };
+    assert!(start <= end, "range start is greater than range end");
+    assert!(end <= len, "range end is out of bounds");
+    (start, end)
+}
+
 // Set the length of the vec when the `SetLenOnDrop` value goes out of scope.
 //
 // The idea is: The length field in SetLenOnDrop is a local variable
@@ -2010,6 +2691,32 @@ impl<'bump, T: 'bump + PartialEq> Vec<'bump, T> {
     pub fn dedup(&mut self) {
         self.dedup_by(|a, b| a == b)
     }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation, moving the removed elements into a
+    /// new vector allocated in `bump`.
+    ///
+    /// The order of the elements in the returned vector is unspecified. See
+    /// [`dedup_by_into`](Vec::dedup_by_into) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::{Bump, collections::Vec};
+    ///
+    /// let b = Bump::new();
+    ///
+    /// let mut vec = bumpalo::vec![in &b; 1, 2, 2, 3, 2];
+    ///
+    /// let removed = vec.dedup_into(&b);
+    ///
+    /// assert_eq!(vec, [1, 2, 3, 2]);
+    /// assert_eq!(removed, [2]);
+    /// ```
+    #[inline]
+    pub fn dedup_into<'b>(&mut self, bump: &'b Bump) -> Vec<'b, T> {
+        self.dedup_by_into(|a, b| a == b, bump)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -2199,7 +2906,7 @@ impl<'bump, T: 'bump> Vec<'bump, T> {
     /// assert_eq!(u, &[1, 2]);
     /// ```
     #[inline]
-    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<I::IntoIter>
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, '_, I::IntoIter>
     where
         R: RangeBounds<usize>,
         I: IntoIterator<Item = T>,
@@ -2318,6 +3025,42 @@ impl<'bump, T: 'bump> From<Vec<'bump, T>> for crate::boxed::Box<'bump, [T]> {
     }
 }
 
+/// Gets the entire contents of the `Vec` as an array, if its length matches
+/// the array's length exactly.
+///
+/// Otherwise, the vector is handed back as the error.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::{Bump, collections::Vec, vec};
+///
+/// let b = Bump::new();
+///
+/// let array: [u32; 3] = vec![in &b; 1, 2, 3].try_into().unwrap();
+/// assert_eq!(array, [1, 2, 3]);
+///
+/// let v = vec![in &b; 1, 2];
+/// let err = <[u32; 3]>::try_from(v).unwrap_err();
+/// assert_eq!(err, [1, 2]);
+/// ```
+impl<'bump, T: 'bump, const N: usize> TryFrom<Vec<'bump, T>> for [T; N] {
+    type Error = Vec<'bump, T>;
+
+    fn try_from(mut vec: Vec<'bump, T>) -> Result<[T; N], Vec<'bump, T>> {
+        if vec.len() != N {
+            return Err(vec);
+        }
+
+        unsafe {
+            // The elements are moved out here, and the buffer is freed when
+            // `vec` is dropped.
+            vec.set_len(0);
+            Ok(ptr::read(vec.as_ptr() as *const [T; N]))
+        }
+    }
+}
+
 impl<'bump, T: 'bump> Borrow<[T]> for Vec<'bump, T> {
     #[inline]
     fn borrow(&self) -> &[T] {
@@ -2399,7 +3142,7 @@ impl<'bump, T: 'bump> IntoIter<'bump, T> {
     /// assert_eq!(into_iter.as_slice(), &['b', 'c']);
     /// ```
     pub fn as_slice(&self) -> &[T] {
-        unsafe { slice::from_raw_parts(self.ptr, self.len()) }
+        unsafe { slice::from_raw_parts(self.slice_ptr(), self.len()) }
     }
 
     /// Returns the remaining items of this iterator as a mutable slice.
@@ -2420,7 +3163,26 @@ impl<'bump, T: 'bump> IntoIter<'bump, T> {
     /// assert_eq!(into_iter.next().unwrap(), 'z');
     /// ```
     pub fn as_mut_slice(&mut self) -> &mut [T] {
-        unsafe { slice::from_raw_parts_mut(self.ptr as *mut T, self.len()) }
+        unsafe { slice::from_raw_parts_mut(self.slice_ptr() as *mut T, self.len()) }
+    }
+
+    /// The start of the remaining items, suitably aligned to build a slice
+    /// from.
+    #[inline]
+    fn slice_ptr(&self) -> *const T {
+        if mem::size_of::<T>() == 0 {
+            // For zero-sized elements, `ptr` is used as a byte counter, and is
+            // not necessarily aligned.
+            NonNull::dangling().as_ptr()
+        } else {
+            self.ptr
+        }
+    }
+}
+
+impl<'bump, T: 'bump> AsRef<[T]> for IntoIter<'bump, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
     }
 }
 
@@ -2433,7 +3195,7 @@ impl<'bump, T: 'bump> Iterator for IntoIter<'bump, T> {
     #[inline]
     fn next(&mut self) -> Option<T> {
         unsafe {
-            if self.ptr as *const _ == self.end {
+            if ptr::eq(self.ptr, self.end) {
                 None
             } else if mem::size_of::<T>() == 0 {
                 // purposefully don't use 'ptr.offset' because for
@@ -2495,8 +3257,10 @@ impl<'bump, T: 'bump> FusedIterator for IntoIter<'bump, T> {}
 
 impl<'bump, T> Drop for IntoIter<'bump, T> {
     fn drop(&mut self) {
-        // drop all remaining elements
-        self.for_each(drop);
+        // Drop all remaining elements. Dropping them in place as a slice keeps
+        // going even if one of their destructors panics, so the rest of them
+        // are not leaked.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
     }
 }
 
@@ -2765,7 +3529,14 @@ impl<'bump> io::Write for Vec<'bump, u8> {
 mod serialize {
     use super::*;
 
-    use serde::{ser::SerializeSeq, Serialize, Serializer};
+    use core::cmp;
+
+    use crate::serde::{DeserializeIn, DeserializeInSeed};
+    use serde::{
+        de::{SeqAccess, Visitor},
+        ser::SerializeSeq,
+        Deserializer, Serialize, Serializer,
+    };
 
     impl<'a, T> Serialize for Vec<'a, T>
     where
@@ -2783,4 +3554,82 @@ mod serialize {
         }
     }
 
+    impl<'de, 'bump, T> DeserializeIn<'de, 'bump> for Vec<'bump, T>
+    where
+        T: 'bump + DeserializeIn<'de, 'bump>,
+    {
+        fn deserialize_in<D>(deserializer: D, bump: &'bump Bump) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct VecVisitor<'bump, T> {
+                bump: &'bump Bump,
+                marker: PhantomData<fn() -> T>,
+            }
+
+            impl<'de, 'bump, T> Visitor<'de> for VecVisitor<'bump, T>
+            where
+                T: 'bump + DeserializeIn<'de, 'bump>,
+            {
+                type Value = Vec<'bump, T>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a sequence")
+                }
+
+                fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                where
+                    A: SeqAccess<'de>,
+                {
+                    // Don't trust the size hint blindly: an untrusted input
+                    // could otherwise make us reserve a huge chunk up front.
+                    const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+                    let max = MAX_PREALLOC_BYTES / cmp::max(mem::size_of::<T>(), 1);
+                    let capacity = cmp::min(seq.size_hint().unwrap_or(0), max);
+
+                    let mut vec = Vec::with_capacity_in(capacity, self.bump);
+                    while let Some(e) = seq.next_element_seed(DeserializeInSeed::new(self.bump))? {
+                        vec.push(e);
+                    }
+                    Ok(vec)
+                }
+            }
+
+            deserializer.deserialize_seq(VecVisitor {
+                bump,
+                marker: PhantomData,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_in {
+    use super::*;
+
+    use crate::arbitrary::ArbitraryIn;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    impl<'a, 'bump, T> ArbitraryIn<'a, 'bump> for Vec<'bump, T>
+    where
+        T: 'bump + ArbitraryIn<'a, 'bump>,
+    {
+        fn arbitrary_in(u: &mut Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+            // Like `arbitrary`'s own collections, decide whether to keep going
+            // before each element. An exhausted input says to stop.
+            let mut vec = Vec::new_in(bump);
+            while bool::arbitrary(u)? {
+                vec.push(T::arbitrary_in(u, bump)?);
+            }
+            Ok(vec)
+        }
+
+        fn arbitrary_take_rest_in(mut u: Unstructured<'a>, bump: &'bump Bump) -> Result<Self> {
+            let mut vec = Vec::new_in(bump);
+            while !u.is_empty() {
+                vec.push(T::arbitrary_in(&mut u, bump)?);
+            }
+            Ok(vec)
+        }
+    }
 }
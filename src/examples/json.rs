@@ -0,0 +1,481 @@
+//! Parse JSON into an arena-allocated AST, transform it, and serialize it back.
+//!
+//! Every node, string, and array of the AST lives inside a single [`Bump`],
+//! so the whole document is freed at once when the arena is dropped or reset.
+//! Strings without escape sequences are copied into the arena with
+//! [`Bump::alloc_str`], strings with escape sequences are decoded into a
+//! [`String`], and arrays and objects are built up in [`Vec`]s that are then
+//! frozen with [`Vec::into_bump_slice`].
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::{examples::json, Bump};
+//!
+//! let bump = Bump::new();
+//! let output = json::pipeline(&bump, br#"{"b": [1, 2.5, null], "a": "x\ty"}"#).unwrap();
+//! assert_eq!(output, r#"{"a":"x\ty","b":[1,2.5,null]}"#);
+//! ```
+
+use crate::collections::{CollectIn, String, Vec};
+use crate::Bump;
+use core::fmt::{self, Write};
+use core::str;
+
+/// How deeply arrays and objects may be nested before [`parse`] gives up,
+/// rather than overflowing the stack.
+pub const MAX_DEPTH: usize = 128;
+
+/// A JSON value whose contents are allocated in a [`Bump`] arena.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value<'bump> {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A string, with escape sequences already decoded.
+    String(&'bump str),
+    /// An array of values.
+    Array(&'bump [Value<'bump>]),
+    /// An object, as a list of key/value pairs in source order.
+    Object(&'bump [(&'bump str, Value<'bump>)]),
+}
+
+/// An error returned from [`parse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    offset: usize,
+    message: &'static str,
+}
+
+impl ParseError {
+    /// The byte offset in the input at which the error was detected.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parse a JSON document into an AST allocated in `bump`.
+///
+/// ## Errors
+///
+/// Errors if `input` is not valid UTF-8, is not a single valid JSON value, or
+/// nests arrays and objects more than [`MAX_DEPTH`] levels deep.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::{examples::json::{self, Value}, Bump};
+///
+/// let bump = Bump::new();
+/// let value = json::parse(&bump, b"[true, \"hi\"]").unwrap();
+/// assert_eq!(value, Value::Array(&[Value::Bool(true), Value::String("hi")]));
+///
+/// assert!(json::parse(&bump, b"[1, 2").is_err());
+/// ```
+pub fn parse<'bump>(bump: &'bump Bump, input: &[u8]) -> Result<Value<'bump>, ParseError> {
+    let input = str::from_utf8(input).map_err(|e| ParseError {
+        offset: e.valid_up_to(),
+        message: "invalid UTF-8",
+    })?;
+    let mut parser = Parser {
+        bump,
+        input,
+        pos: 0,
+    };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+/// Return a copy of `value` in which the keys of every object are sorted.
+///
+/// When an object contains duplicate keys, only the last value for each key
+/// is kept, matching how most JSON implementations interpret such objects.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::{examples::json::{self, Value}, Bump};
+///
+/// let bump = Bump::new();
+/// let value = json::parse(&bump, br#"{"b": 1, "a": 2, "b": 3}"#).unwrap();
+/// let sorted = json::sort_keys(&bump, &value);
+/// assert_eq!(
+///     sorted,
+///     Value::Object(&[("a", Value::Number(2.0)), ("b", Value::Number(3.0))]),
+/// );
+/// ```
+pub fn sort_keys<'bump>(bump: &'bump Bump, value: &Value<'bump>) -> Value<'bump> {
+    match *value {
+        Value::Array(elements) => {
+            let elements: Vec<_> = elements.iter().map(|e| sort_keys(bump, e)).collect_in(bump);
+            Value::Array(elements.into_bump_slice())
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members
+                .iter()
+                .map(|(k, v)| (*k, sort_keys(bump, v)))
+                .collect_in(bump);
+            // A stable sort keeps duplicates in source order, so keeping the
+            // last of each run keeps the last occurrence of each key.
+            members.sort_by(|a, b| a.0.cmp(b.0));
+            members.reverse();
+            members.dedup_by(|a, b| a.0 == b.0);
+            members.reverse();
+            Value::Object(members.into_bump_slice())
+        }
+        other => other,
+    }
+}
+
+/// Serialize `value` as compact JSON into a new [`String`] allocated in
+/// `bump`.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::{examples::json::{self, Value}, Bump};
+///
+/// let bump = Bump::new();
+/// let value = Value::Array(&[Value::Null, Value::String("a\"b")]);
+/// assert_eq!(json::to_string_in(&bump, &value), r#"[null,"a\"b"]"#);
+/// ```
+pub fn to_string_in<'bump>(bump: &'bump Bump, value: &Value<'_>) -> String<'bump> {
+    let mut out = String::new_in(bump);
+    write_value(&mut out, value).expect("writing to a `String` cannot fail");
+    out
+}
+
+/// Parse `input`, sort the keys of all of its objects, and serialize the
+/// result back to compact JSON, all inside `bump`.
+///
+/// ## Errors
+///
+/// Errors if `input` cannot be parsed; see [`parse`].
+pub fn pipeline<'bump>(bump: &'bump Bump, input: &[u8]) -> Result<String<'bump>, ParseError> {
+    let value = parse(bump, input)?;
+    let value = sort_keys(bump, &value);
+    Ok(to_string_in(bump, &value))
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(f, self)
+    }
+}
+
+fn write_value<W: Write>(out: &mut W, value: &Value<'_>) -> fmt::Result {
+    match *value {
+        Value::Null => out.write_str("null"),
+        Value::Bool(b) => write!(out, "{}", b),
+        Value::Number(n) => write!(out, "{}", n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(elements) => {
+            out.write_char('[')?;
+            for (i, e) in elements.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_value(out, e)?;
+            }
+            out.write_char(']')
+        }
+        Value::Object(members) => {
+            out.write_char('{')?;
+            for (i, (k, v)) in members.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_string(out, k)?;
+                out.write_char(':')?;
+                write_value(out, v)?;
+            }
+            out.write_char('}')
+        }
+    }
+}
+
+fn write_string<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+struct Parser<'bump, 'a> {
+    bump: &'bump Bump,
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'bump, 'a> Parser<'bump, 'a> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            offset: self.pos,
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), ParseError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Value<'bump>) -> Result<Value<'bump>, ParseError> {
+        if self.input[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value<'bump>, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => self.array(depth + 1),
+            Some(b'{') => self.object(depth + 1),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("expected a value")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value<'bump>, ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.pos += 1;
+
+        let mut elements = Vec::new_in(self.bump);
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(elements.into_bump_slice()));
+        }
+        loop {
+            elements.push(self.value(depth)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(elements.into_bump_slice()));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value<'bump>, ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.pos += 1;
+
+        let mut members = Vec::new_in(self.bump);
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members.into_bump_slice()));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a string key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':', "expected `:`")?;
+            let value = self.value(depth)?;
+            members.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members.into_bump_slice()));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value<'bump>, ParseError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.error("expected a digit")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("expected a digit"));
+            }
+            self.digits();
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("expected a digit"));
+            }
+            self.digits();
+        }
+        self.input[start..self.pos]
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| ParseError {
+                offset: start,
+                message: "invalid number",
+            })
+    }
+
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn string(&mut self) -> Result<&'bump str, ParseError> {
+        self.pos += 1;
+        let start = self.pos;
+
+        // Fast path: no escape sequences, so the string can be copied into the
+        // arena verbatim.
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    let s = self.bump.alloc_str(&self.input[start..self.pos]);
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                Some(b'\\') => break,
+                Some(0..=0x1f) => return Err(self.error("control character in string")),
+                Some(_) => self.pos += 1,
+            }
+        }
+
+        // Slow path: decode escape sequences into an arena `String`.
+        let mut s = String::from_str_in(&self.input[start..self.pos], self.bump);
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(s.into_bump_str());
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let c = self.unicode_escape()?;
+                            s.push(c);
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    self.pos += 1;
+                    s.push(c);
+                }
+                Some(0..=0x1f) => return Err(self.error("control character in string")),
+                Some(_) => {
+                    let c = self.input[self.pos..].chars().next().unwrap();
+                    self.pos += c.len_utf8();
+                    s.push(c);
+                }
+            }
+        }
+    }
+
+    /// Decode the `XXXX` of a `\uXXXX` escape, and the second half of a
+    /// surrogate pair if there is one.
+    fn unicode_escape(&mut self) -> Result<char, ParseError> {
+        let hi = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&hi) {
+            if !self.input[self.pos..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let lo = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&lo) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00)
+        } else {
+            hi
+        };
+        char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(self.error("invalid unicode escape"));
+        }
+        let code = u32::from_str_radix(digits, 16).unwrap();
+        self.pos += 4;
+        Ok(code)
+    }
+}
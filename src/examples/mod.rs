@@ -0,0 +1,9 @@
+//! Small, complete programs built on top of `bumpalo`.
+//!
+//! These are enabled by the `examples-lib` cargo feature. They exist to show
+//! how the different parts of `bumpalo`'s API are intended to be combined, and
+//! double as an end-to-end test and benchmark of the whole API surface. They
+//! are not meant to be fully featured or particularly fast implementations of
+//! their respective tasks.
+
+pub mod json;
@@ -0,0 +1,150 @@
+//! Read-only arenas loaded from snapshots.
+//!
+//! A [`RelativeBump`](crate::relative::RelativeBump) hands out
+//! [`BumpOffset`]s that stay valid when the arena's
+//! [`used_bytes`](crate::relative::RelativeBump::used_bytes) are copied
+//! elsewhere. A [`FrozenBump`] wraps such a copy, for example a file that was
+//! written at build time and memory-mapped or `include_bytes!`ed back in, and
+//! resolves the offsets that were recorded when it was built. No parsing or
+//! copying happens on load: the bytes are used exactly where they are.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::frozen::FrozenBump;
+//! use bumpalo::relative::RelativeBump;
+//!
+//! #[derive(Clone, Copy)]
+//! #[repr(C)]
+//! struct Entry {
+//!     key: u32,
+//!     value: u32,
+//! }
+//!
+//! // At build time: fill a relative arena and record the offsets of interest.
+//! let arena = RelativeBump::with_capacity(64);
+//! let first = arena.alloc_rel(Entry { key: 1, value: 10 });
+//! let second = arena.alloc_rel(Entry { key: 2, value: 20 });
+//! let snapshot = unsafe { arena.used_bytes() }.to_vec();
+//! drop(arena);
+//!
+//! // At run time: load the snapshot into a buffer whose end is aligned for
+//! // `Entry`, and look the entries up again.
+//! let mut storage = vec![0_u32; 16];
+//! let buf: &mut [u8] = unsafe {
+//!     std::slice::from_raw_parts_mut(storage.as_mut_ptr().cast(), 64)
+//! };
+//! let start = buf.len() - snapshot.len();
+//! buf[start..].copy_from_slice(&snapshot);
+//!
+//! let frozen = FrozenBump::new(&buf[start..]);
+//! // Safety: the offsets were returned by `alloc_rel` on the arena that
+//! // `snapshot` was taken from, and `Entry` contains no pointers.
+//! unsafe {
+//!     assert_eq!(frozen.get(first).value, 10);
+//!     assert_eq!(frozen.get(second).key, 2);
+//! }
+//! ```
+
+use crate::relative::BumpOffset;
+use core::fmt;
+use core::mem;
+
+/// A read-only view of a [`RelativeBump`](crate::relative::RelativeBump)
+/// snapshot, resolving [`BumpOffset`] handles into references that live as
+/// long as the underlying bytes.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Clone, Copy)]
+pub struct FrozenBump<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> FrozenBump<'a> {
+    /// Wrap a snapshot of a relative arena's used bytes.
+    ///
+    /// `bytes` must end exactly where the original arena's chunk ended; a copy
+    /// of [`RelativeBump::used_bytes`](crate::relative::RelativeBump::used_bytes)
+    /// fulfils that. Its end must also be at least as aligned as every type
+    /// that is resolved in it.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> FrozenBump<'a> {
+        FrozenBump { bytes }
+    }
+
+    /// Get the underlying snapshot bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Get the length of the snapshot in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Is the snapshot empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Resolve a handle into a shared reference into the snapshot.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `T` would not lie within the snapshot, or if it would not
+    /// be properly aligned.
+    ///
+    /// ## Safety
+    ///
+    /// `offset` must have been returned by
+    /// [`alloc_rel`](crate::relative::RelativeBump::alloc_rel) on the arena
+    /// that the snapshot was taken from, after the value was fully written,
+    /// and `T` must not contain any absolute pointers or references.
+    #[inline]
+    pub unsafe fn get<T>(&self, offset: BumpOffset<T>) -> &'a T {
+        offset.get_in(self.bytes)
+    }
+
+    /// Attempt to resolve a handle into a shared reference into the snapshot.
+    ///
+    /// Returns `None` instead of panicking if the `T` would not lie within the
+    /// snapshot, or if it would not be properly aligned. This is useful for
+    /// snapshots that come from outside the program, such as files on disk.
+    ///
+    /// ## Safety
+    ///
+    /// Same as [`get`](Self::get). Note that the bounds and alignment checks
+    /// say nothing about whether the bytes at `offset` are a valid `T`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::frozen::FrozenBump;
+    /// use bumpalo::relative::BumpOffset;
+    ///
+    /// let frozen = FrozenBump::new(&[0; 4]);
+    /// assert!(unsafe { frozen.try_get(BumpOffset::<u8>::from_raw(8)) }.is_none());
+    /// ```
+    pub unsafe fn try_get<T>(&self, offset: BumpOffset<T>) -> Option<&'a T> {
+        let offset = offset.to_raw() as usize;
+        if mem::size_of::<T>() > offset || offset > self.bytes.len() {
+            return None;
+        }
+        let p = self.bytes.as_ptr().add(self.bytes.len() - offset);
+        if p as usize % mem::align_of::<T>() != 0 {
+            return None;
+        }
+        Some(&*(p as *const T))
+    }
+}
+
+impl fmt::Debug for FrozenBump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrozenBump")
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
@@ -0,0 +1,154 @@
+//! Using an arena as the global allocator.
+//!
+//! [`GlobalBump`] implements [`GlobalAlloc`] on top of a [`Bump`] behind a
+//! lock, so that it can be registered with `#[global_allocator]`. Memory is
+//! essentially never freed: deallocating the most recent allocation hands its
+//! bytes back to the arena, but everything else stays allocated for the rest
+//! of the program. That makes allocation very cheap, and is a good fit for
+//! tests, fuzzing harnesses, and short-lived command-line tools whose memory
+//! use is bounded anyway.
+//!
+//! The arena's chunks are requested from [`System`], the operating system's
+//! allocator, and are never returned to it.
+//!
+//! This module is only available with the `std` Cargo feature.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::global::GlobalBump;
+//!
+//! #[global_allocator]
+//! static ALLOC: GlobalBump = GlobalBump::new();
+//!
+//! let before = ALLOC.allocated_bytes();
+//! let v: Vec<u64> = (0..1000).collect();
+//! assert_eq!(v.len(), 1000);
+//! assert!(ALLOC.allocated_bytes() > before);
+//! ```
+
+use crate::Bump;
+use core::alloc::{GlobalAlloc, Layout};
+use core::fmt;
+use core::ptr::{self, NonNull};
+use std::alloc::System;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A [`GlobalAlloc`] implementation that bump allocates out of a single,
+/// process-wide arena.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// Every allocation and deallocation takes a lock, so this is not meant for
+/// heavily multi-threaded programs. Diagnostic events that the arena emits
+/// through the `log` or `defmt` features are emitted while that lock is held;
+/// a logger that allocates will deadlock.
+pub struct GlobalBump {
+    bump: Mutex<Bump<System>>,
+}
+
+impl GlobalBump {
+    /// Construct a new global arena.
+    ///
+    /// This does not allocate, so it can initialize the `static` registered
+    /// as the `#[global_allocator]`.
+    pub const fn new() -> GlobalBump {
+        GlobalBump {
+            bump: Mutex::new(Bump::empty_in(System)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Bump<System>> {
+        // Nothing panics while the lock is held, but a poisoned lock would not
+        // leave the arena in an inconsistent state anyway.
+        self.bump.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the total number of bytes that the arena has requested from the
+    /// operating system for its chunks.
+    ///
+    /// See [`Bump::allocated_bytes`] for details.
+    pub fn allocated_bytes(&self) -> usize {
+        self.lock().allocated_bytes()
+    }
+
+    /// Set the arena's allocation limit, in bytes.
+    ///
+    /// Once the limit is reached, allocations fail, which for most of the
+    /// standard library means aborting the process. See
+    /// [`Bump::set_allocation_limit`] for details.
+    pub fn set_allocation_limit(&self, limit: Option<usize>) {
+        self.lock().set_allocation_limit(limit);
+    }
+
+    /// Reset the arena, making all of its memory available for allocation
+    /// again.
+    ///
+    /// See [`Bump::reset`] for details.
+    ///
+    /// ## Safety
+    ///
+    /// Every allocation made through this allocator so far is invalidated.
+    /// None of them may be used, or deallocated, afterwards. When this
+    /// allocator is the `#[global_allocator]`, that includes memory owned by
+    /// the standard library, such as thread-local storage, buffered standard
+    /// streams, and the names of spawned threads, so this is only sound at
+    /// carefully chosen points, for example between iterations of a fuzzing
+    /// loop that has not allocated anything that outlives the iteration.
+    pub unsafe fn reset_unchecked(&self) {
+        self.lock().reset();
+    }
+}
+
+impl Default for GlobalBump {
+    fn default() -> GlobalBump {
+        GlobalBump::new()
+    }
+}
+
+impl fmt::Debug for GlobalBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalBump")
+            .field("allocated_bytes", &self.allocated_bytes())
+            .finish()
+    }
+}
+
+unsafe impl GlobalAlloc for GlobalBump {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match self.lock().try_alloc_layout(layout) {
+            Ok(p) => p.as_ptr(),
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        match self.lock().try_alloc_layout_zeroed(layout) {
+            Ok(p) => p.as_ptr(),
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.lock().dealloc(NonNull::new_unchecked(ptr), layout);
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let ptr = NonNull::new_unchecked(ptr);
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let bump = self.lock();
+        let result = if new_size <= layout.size() {
+            bump.shrink(ptr, layout, new_layout)
+        } else {
+            bump.grow(ptr, layout, new_layout)
+        };
+        match result {
+            Ok(p) => p.as_ptr(),
+            Err(_) => ptr::null_mut(),
+        }
+    }
+}
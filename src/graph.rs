@@ -0,0 +1,572 @@
+//! Directed graphs whose nodes and adjacency lists are allocated in an arena.
+//!
+//! A [`Graph`] keeps its nodes in an arena-allocated vector, and each
+//! [`Node`] keeps its outgoing edges in an arena-allocated vector of its own,
+//! in the same arena. Nodes are referred to by [`NodeId`]s, which are small,
+//! copyable indices, so there are no lifetimes or reference cycles to fight
+//! with when nodes refer to each other, as in control flow graphs, call
+//! graphs, or dependency graphs.
+//!
+//! [`Graph::dfs`] and [`Graph::bfs`] walk the nodes reachable from a starting
+//! node. Their worklists and visited sets are allocated in a separate scratch
+//! arena, which can be reset after the walk, without touching the graph.
+//!
+//! This module is only available with the `collections` Cargo feature.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::{graph::Graph, Bump};
+//!
+//! let bump = Bump::new();
+//! let mut cfg = Graph::new_in(&bump);
+//!
+//! let entry = cfg.add_node("entry");
+//! let then = cfg.add_node("then");
+//! let els = cfg.add_node("else");
+//! let exit = cfg.add_node("exit");
+//! cfg.add_edge(entry, then, ());
+//! cfg.add_edge(entry, els, ());
+//! cfg.add_edge(then, exit, ());
+//! cfg.add_edge(els, exit, ());
+//!
+//! let mut scratch = Bump::new();
+//! let order: Vec<_> = cfg.dfs(entry, &scratch).map(|id| cfg[id]).collect();
+//! assert_eq!(order, ["entry", "then", "exit", "else"]);
+//!
+//! scratch.reset();
+//! let order: Vec<_> = cfg.bfs(entry, &scratch).map(|id| cfg[id]).collect();
+//! assert_eq!(order, ["entry", "then", "else", "exit"]);
+//! ```
+
+use crate::collections::{BitVec, Vec};
+use crate::Bump;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// The identifier of a node in a [`Graph`].
+///
+/// Node identifiers are handed out in order, starting at zero, so they can
+/// index side tables. They are only meaningful for the graph that handed them
+/// out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Returns the index of this node in its graph.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// An outgoing edge of a [`Node`], with a weight of type `E`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Edge<E = ()> {
+    target: NodeId,
+    weight: E,
+}
+
+impl<E> Edge<E> {
+    /// Returns the node this edge points to.
+    #[inline]
+    pub fn target(&self) -> NodeId {
+        self.target
+    }
+
+    /// Returns a reference to this edge's weight.
+    #[inline]
+    pub fn weight(&self) -> &E {
+        &self.weight
+    }
+
+    /// Returns a mutable reference to this edge's weight.
+    #[inline]
+    pub fn weight_mut(&mut self) -> &mut E {
+        &mut self.weight
+    }
+}
+
+/// A node in a [`Graph`], with data of type `N` and outgoing edges with
+/// weights of type `E`.
+///
+/// The node's edges are kept in an arena-allocated vector, in the order they
+/// were added.
+pub struct Node<'bump, N, E = ()> {
+    data: N,
+    edges: Vec<'bump, Edge<E>>,
+}
+
+impl<'bump, N, E> Node<'bump, N, E> {
+    /// Returns a reference to this node's data.
+    #[inline]
+    pub fn data(&self) -> &N {
+        &self.data
+    }
+
+    /// Returns a mutable reference to this node's data.
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut N {
+        &mut self.data
+    }
+
+    /// Returns this node's outgoing edges.
+    #[inline]
+    pub fn edges(&self) -> &[Edge<E>] {
+        &self.edges
+    }
+
+    /// Returns this node's outgoing edges, with mutable weights.
+    #[inline]
+    pub fn edges_mut(&mut self) -> &mut [Edge<E>] {
+        &mut self.edges
+    }
+
+    /// Returns an iterator over the nodes this node has edges to.
+    #[inline]
+    pub fn successors(&self) -> Successors<'_, E> {
+        Successors {
+            edges: self.edges.iter(),
+        }
+    }
+}
+
+impl<'bump, N: fmt::Debug, E: fmt::Debug> fmt::Debug for Node<'bump, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("data", &self.data)
+            .field("edges", &self.edges)
+            .finish()
+    }
+}
+
+/// A directed graph with node data of type `N` and edge weights of type `E`,
+/// whose nodes and adjacency lists are allocated in a bump arena.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct Graph<'bump, N, E = ()> {
+    nodes: Vec<'bump, Node<'bump, N, E>>,
+    edge_count: usize,
+}
+
+impl<'bump, N, E> Graph<'bump, N, E> {
+    /// Constructs a new, empty `Graph`.
+    ///
+    /// This does not allocate.
+    #[inline]
+    pub fn new_in(bump: &'bump Bump) -> Graph<'bump, N, E> {
+        Graph {
+            nodes: Vec::new_in(bump),
+            edge_count: 0,
+        }
+    }
+
+    /// Constructs a new, empty `Graph` with room for at least `nodes` nodes.
+    #[inline]
+    pub fn with_capacity_in(nodes: usize, bump: &'bump Bump) -> Graph<'bump, N, E> {
+        Graph {
+            nodes: Vec::with_capacity_in(nodes, bump),
+            edge_count: 0,
+        }
+    }
+
+    /// Returns the arena this graph allocates in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.nodes.bump()
+    }
+
+    /// Returns the number of nodes in the graph.
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of edges in the graph.
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns `true` if the graph has no nodes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds a node with the given data, and returns its identifier.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the graph would have more than `u32::MAX` nodes.
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        let id = u32::try_from(self.nodes.len()).expect("too many nodes in Graph");
+        let bump = self.bump();
+        self.nodes.push(Node {
+            data,
+            edges: Vec::new_in(bump),
+        });
+        NodeId(id)
+    }
+
+    /// Adds an edge from `from` to `to` with the given weight.
+    ///
+    /// Edges are directed, and a pair of nodes may be connected by any number
+    /// of edges.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either node is not in the graph.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) {
+        assert!(
+            to.index() < self.nodes.len(),
+            "invalid Graph node: {:?}",
+            to
+        );
+        let node = match self.nodes.get_mut(from.index()) {
+            Some(node) => node,
+            None => panic!("invalid Graph node: {:?}", from),
+        };
+        node.edges.push(Edge { target: to, weight });
+        self.edge_count += 1;
+    }
+
+    /// Returns a reference to the node with the given identifier, if it is in
+    /// the graph.
+    #[inline]
+    pub fn node(&self, id: NodeId) -> Option<&Node<'bump, N, E>> {
+        self.nodes.get(id.index())
+    }
+
+    /// Returns a mutable reference to the node with the given identifier, if
+    /// it is in the graph.
+    #[inline]
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<'bump, N, E>> {
+        self.nodes.get_mut(id.index())
+    }
+
+    /// Returns an iterator over the nodes that `id` has edges to.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the node is not in the graph.
+    #[inline]
+    pub fn successors(&self, id: NodeId) -> Successors<'_, E> {
+        self.node_ref(id).successors()
+    }
+
+    /// Returns an iterator over the identifiers of all nodes in the graph, in
+    /// the order they were added.
+    #[inline]
+    pub fn node_ids(&self) -> NodeIds {
+        NodeIds {
+            range: 0..self.nodes.len() as u32,
+        }
+    }
+
+    /// Returns an iterator over the identifiers and nodes of the graph, in
+    /// the order they were added.
+    #[inline]
+    pub fn nodes(&self) -> Nodes<'_, 'bump, N, E> {
+        Nodes {
+            nodes: self.nodes.iter().enumerate(),
+        }
+    }
+
+    /// Returns a depth-first, preorder iterator over the nodes reachable from
+    /// `start`, allocating its worklist in `scratch`.
+    ///
+    /// Successors are visited in the order their edges were added.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `start` is not in the graph.
+    pub fn dfs<'g, 's>(&'g self, start: NodeId, scratch: &'s Bump) -> Dfs<'g, 's, 'bump, N, E> {
+        assert!(
+            self.node(start).is_some(),
+            "invalid Graph node: {:?}",
+            start
+        );
+        let mut stack = Vec::new_in(scratch);
+        stack.push(start);
+        Dfs {
+            graph: self,
+            stack,
+            visited: BitVec::from_elem_in(self.nodes.len(), false, scratch),
+        }
+    }
+
+    /// Returns a breadth-first iterator over the nodes reachable from
+    /// `start`, allocating its worklist in `scratch`.
+    ///
+    /// Successors are visited in the order their edges were added.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `start` is not in the graph.
+    pub fn bfs<'g, 's>(&'g self, start: NodeId, scratch: &'s Bump) -> Bfs<'g, 's, 'bump, N, E> {
+        assert!(
+            self.node(start).is_some(),
+            "invalid Graph node: {:?}",
+            start
+        );
+        let mut visited = BitVec::from_elem_in(self.nodes.len(), false, scratch);
+        visited.set(start.index(), true);
+        let mut queue = Vec::new_in(scratch);
+        queue.push(start);
+        Bfs {
+            graph: self,
+            queue,
+            head: 0,
+            visited,
+        }
+    }
+
+    #[inline]
+    fn node_ref(&self, id: NodeId) -> &Node<'bump, N, E> {
+        match self.node(id) {
+            Some(node) => node,
+            None => panic!("invalid Graph node: {:?}", id),
+        }
+    }
+}
+
+impl<'bump, N: fmt::Debug, E: fmt::Debug> fmt::Debug for Graph<'bump, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.nodes()).finish()
+    }
+}
+
+impl<'bump, N, E> Index<NodeId> for Graph<'bump, N, E> {
+    type Output = N;
+
+    #[inline]
+    fn index(&self, id: NodeId) -> &N {
+        &self.node_ref(id).data
+    }
+}
+
+impl<'bump, N, E> IndexMut<NodeId> for Graph<'bump, N, E> {
+    #[inline]
+    fn index_mut(&mut self, id: NodeId) -> &mut N {
+        match self.node_mut(id) {
+            Some(node) => &mut node.data,
+            None => panic!("invalid Graph node: {:?}", id),
+        }
+    }
+}
+
+/// An iterator over the successors of a node in a [`Graph`].
+///
+/// This struct is created by [`Graph::successors`] and [`Node::successors`].
+pub struct Successors<'a, E> {
+    edges: slice::Iter<'a, Edge<E>>,
+}
+
+impl<E> Iterator for Successors<'_, E> {
+    type Item = NodeId;
+
+    #[inline]
+    fn next(&mut self) -> Option<NodeId> {
+        self.edges.next().map(Edge::target)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl<E> DoubleEndedIterator for Successors<'_, E> {
+    #[inline]
+    fn next_back(&mut self) -> Option<NodeId> {
+        self.edges.next_back().map(Edge::target)
+    }
+}
+
+impl<E> ExactSizeIterator for Successors<'_, E> {}
+
+impl<E> FusedIterator for Successors<'_, E> {}
+
+impl<E> Clone for Successors<'_, E> {
+    fn clone(&self) -> Self {
+        Successors {
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for Successors<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// An iterator over the identifiers of the nodes in a [`Graph`].
+///
+/// This struct is created by [`Graph::node_ids`].
+#[derive(Clone, Debug)]
+pub struct NodeIds {
+    range: core::ops::Range<u32>,
+}
+
+impl Iterator for NodeIds {
+    type Item = NodeId;
+
+    #[inline]
+    fn next(&mut self) -> Option<NodeId> {
+        self.range.next().map(NodeId)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for NodeIds {
+    #[inline]
+    fn next_back(&mut self) -> Option<NodeId> {
+        self.range.next_back().map(NodeId)
+    }
+}
+
+impl ExactSizeIterator for NodeIds {}
+
+impl FusedIterator for NodeIds {}
+
+/// An iterator over the identifiers and nodes of a [`Graph`].
+///
+/// This struct is created by [`Graph::nodes`].
+pub struct Nodes<'a, 'bump, N, E> {
+    nodes: core::iter::Enumerate<slice::Iter<'a, Node<'bump, N, E>>>,
+}
+
+impl<'a, 'bump, N, E> Iterator for Nodes<'a, 'bump, N, E> {
+    type Item = (NodeId, &'a Node<'bump, N, E>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes
+            .next()
+            .map(|(index, node)| (NodeId(index as u32), node))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<N, E> ExactSizeIterator for Nodes<'_, '_, N, E> {}
+
+impl<N, E> FusedIterator for Nodes<'_, '_, N, E> {}
+
+impl<N, E> fmt::Debug for Nodes<'_, '_, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Nodes")
+            .field("remaining", &self.nodes.len())
+            .finish()
+    }
+}
+
+/// A depth-first, preorder iterator over the nodes of a [`Graph`] that are
+/// reachable from a starting node.
+///
+/// This struct is created by [`Graph::dfs`].
+pub struct Dfs<'g, 's, 'bump, N, E> {
+    graph: &'g Graph<'bump, N, E>,
+    stack: Vec<'s, NodeId>,
+    visited: BitVec<'s>,
+}
+
+impl<'g, 's, 'bump, N, E> Dfs<'g, 's, 'bump, N, E> {
+    /// Returns `true` if the walk has already yielded the given node.
+    #[inline]
+    pub fn visited(&self, id: NodeId) -> bool {
+        self.visited.get(id.index()).unwrap_or(false)
+    }
+}
+
+impl<N, E> Iterator for Dfs<'_, '_, '_, N, E> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        while let Some(id) = self.stack.pop() {
+            if self.visited(id) {
+                continue;
+            }
+            self.visited.set(id.index(), true);
+            // Push successors in reverse, so that they are popped in order.
+            for succ in self.graph.successors(id).rev() {
+                if !self.visited(succ) {
+                    self.stack.push(succ);
+                }
+            }
+            return Some(id);
+        }
+        None
+    }
+}
+
+impl<N, E> FusedIterator for Dfs<'_, '_, '_, N, E> {}
+
+impl<N, E> fmt::Debug for Dfs<'_, '_, '_, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dfs")
+            .field("stack", &self.stack)
+            .field("visited", &self.visited)
+            .finish()
+    }
+}
+
+/// A breadth-first iterator over the nodes of a [`Graph`] that are reachable
+/// from a starting node.
+///
+/// This struct is created by [`Graph::bfs`].
+pub struct Bfs<'g, 's, 'bump, N, E> {
+    graph: &'g Graph<'bump, N, E>,
+    // Nodes before `head` have been yielded, and the rest are queued. Every
+    // queued node is already marked as visited, so it is queued only once.
+    queue: Vec<'s, NodeId>,
+    head: usize,
+    visited: BitVec<'s>,
+}
+
+impl<'g, 's, 'bump, N, E> Bfs<'g, 's, 'bump, N, E> {
+    /// Returns `true` if the walk has already yielded or queued the given
+    /// node.
+    #[inline]
+    pub fn visited(&self, id: NodeId) -> bool {
+        self.visited.get(id.index()).unwrap_or(false)
+    }
+}
+
+impl<N, E> Iterator for Bfs<'_, '_, '_, N, E> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = *self.queue.get(self.head)?;
+        self.head += 1;
+        for succ in self.graph.successors(id) {
+            if !self.visited(succ) {
+                self.visited.set(succ.index(), true);
+                self.queue.push(succ);
+            }
+        }
+        Some(id)
+    }
+}
+
+impl<N, E> FusedIterator for Bfs<'_, '_, '_, N, E> {}
+
+impl<N, E> fmt::Debug for Bfs<'_, '_, '_, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bfs")
+            .field("queue", &&self.queue[self.head..])
+            .field("visited", &self.visited)
+            .finish()
+    }
+}
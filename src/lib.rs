@@ -1,28 +1,72 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
+#![allow(clippy::mut_from_ref)]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 #[doc(hidden)]
 pub extern crate alloc as core_alloc;
 
+// Emits a diagnostic event about an arena through the `log` and/or `defmt`
+// facades, depending on which of those Cargo features are enabled. Expands to
+// nothing when neither is enabled.
+//
+// Only format arguments that both facades support, i.e. plain `{}`
+// placeholders for integers, should be used.
+macro_rules! event {
+    ($level:ident, $($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::$level!($($arg)*);
+        #[cfg(feature = "defmt")]
+        defmt::$level!($($arg)*);
+    };
+}
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
 #[cfg(feature = "boxed")]
 pub mod boxed;
 #[cfg(feature = "collections")]
 pub mod collections;
+#[cfg(feature = "examples-lib")]
+pub mod examples;
+pub mod frozen;
+#[cfg(feature = "std")]
+pub mod global;
+#[cfg(feature = "collections")]
+pub mod graph;
+#[cfg(all(feature = "mmap", unix))]
+pub mod mmap;
+pub mod pool;
+pub mod relative;
+pub mod scratch;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod set;
+#[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+pub mod shared;
+pub mod split;
+#[cfg(target_has_atomic = "ptr")]
+pub mod sync;
+#[cfg(feature = "std")]
+pub mod thread_local;
+pub mod typed;
+pub mod uninit;
+pub mod writer;
 
 mod alloc;
+mod backing;
 
 use core::cell::Cell;
-use core::fmt::Display;
+use core::fmt::{self, Display};
 use core::iter;
 use core::marker::PhantomData;
 use core::mem;
 use core::ptr::{self, NonNull};
 use core::slice;
 use core::str;
-use core_alloc::alloc::{alloc, dealloc, Layout};
+use core_alloc::alloc::Layout;
 
 #[cfg(feature = "allocator_api")]
 use core_alloc::alloc::{AllocError, Allocator};
@@ -30,7 +74,16 @@ use core_alloc::alloc::{AllocError, Allocator};
 #[cfg(all(feature = "allocator-api2", not(feature = "allocator_api")))]
 use allocator_api2::alloc::{AllocError, Allocator};
 
-pub use alloc::AllocErr;
+pub use alloc::{AllocErr, AllocErrKind};
+pub use backing::{BackingAllocator, FixedBuffer, Global};
+
+// The `Error` trait that bumpalo's error types implement: `core`'s with the
+// `core_error` feature, since it needs a newer Rust than our MSRV, and `std`'s
+// otherwise.
+#[cfg(feature = "core_error")]
+use core::error::Error as StdError;
+#[cfg(all(feature = "std", not(feature = "core_error")))]
+use std::error::Error as StdError;
 
 /// An error returned from [`Bump::try_alloc_try_with`].
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -57,6 +110,15 @@ impl<E: Display> Display for AllocOrInitError<E> {
         }
     }
 }
+#[cfg(any(feature = "std", feature = "core_error"))]
+impl<E: StdError + 'static> StdError for AllocOrInitError<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            AllocOrInitError::Alloc(err) => Some(err),
+            AllocOrInitError::Init(err) => Some(err),
+        }
+    }
+}
 
 /// An arena to bump allocate into.
 ///
@@ -217,6 +279,10 @@ impl<E: Display> Display for AllocOrInitError<E> {
 /// [`Result`] and will attempt to undo the initial allocation if this closure
 /// returns [`Err`].
 ///
+/// [`alloc_init`](Bump::alloc_init) and [`try_alloc_init`](Bump::try_alloc_init)
+/// work the same way, but their closure initializes the allocation in place
+/// through a pointer, and only returns whether that succeeded.
+///
 /// #### Warning
 ///
 /// If the inner closure returns [`Ok`], space for the entire [`Result`] remains
@@ -282,17 +348,398 @@ impl<E: Display> Display for AllocOrInitError<E> {
 /// assert_eq!(bump.allocation_limit(), None);
 /// ```
 ///
+/// A [limit hook][Bump::set_limit_hook] can be registered to be notified when
+/// an allocation would exceed the limit, and to decide whether to fail it,
+/// allow it anyways, or retry it after raising the limit.
+///
 /// #### Warning
 ///
 /// Because of backwards compatibility, allocations that fail
 /// due to allocation limits will not present differently than
 /// errors due to resource exhaustion.
+///
+/// ### Chunk Growth Policy
+///
+/// By default, each new chunk is about twice as large as the previous one.
+/// Use a [`BumpBuilder`] to choose the initial chunk size, the growth
+/// factor, a maximum chunk size, and a minimum chunk alignment instead, or
+/// a [`ChunkGrowthPolicy`] to size new chunks arbitrarily.
+///
+/// ### Zero-Sized Types
+///
+/// Allocating a value of a zero-sized type (ZST), or a slice of them, with
+/// any of the typed allocation methods (such as [`alloc`][Bump::alloc],
+/// [`try_alloc_with`][Bump::try_alloc_with], or
+/// [`alloc_slice_copy`][Bump::alloc_slice_copy]) never touches the arena: no
+/// space is reserved, the bump pointer is not moved, no new chunks are
+/// allocated, and [`allocated_bytes`][Bump::allocated_bytes] is unaffected.
+/// The returned reference is dangling, but well-aligned for the type, which
+/// is all that is required of a reference to a ZST. The check for whether a
+/// type is zero-sized happens at compile time, so allocating ZSTs has no
+/// per-call cost beyond running any initializer.
+///
+/// Allocating a zero-sized [`Layout`] with [`alloc_layout`][Bump::alloc_layout]
+/// is still handled by the regular allocation path, and may align the bump
+/// pointer.
+///
+/// [`Layout`]: https://doc.rust-lang.org/std/alloc/struct.Layout.html
+///
+/// ### Front Allocations
+///
+/// Regular allocations are bumped downwards from the end of each chunk. A
+/// second, independent kind of allocation is bumped upwards from the start
+/// of the current chunk with [`alloc_front`][Bump::alloc_front], sharing the
+/// chunk's free space. Front allocations can be released on their own with
+/// [`reset_front`][Bump::reset_front], which makes them a good fit for
+/// short-lived scratch data that lives alongside longer-lived results.
 
 #[derive(Debug)]
-pub struct Bump {
+pub struct Bump<A: BackingAllocator = Global> {
     // The current chunk we are bump allocating within.
-    current_chunk_footer: Cell<NonNull<ChunkFooter>>,
+    current_chunk_footer: CurrentChunk,
     allocation_limit: Cell<Option<usize>>,
+    limit_hook: HookSlot<LimitHook<A>>,
+    #[cfg(feature = "trace")]
+    trace_hook: HookSlot<TraceHook<A>>,
+    reset_policy: Cell<ResetPolicy>,
+    chunk_policy: ChunkPolicy,
+    growth_policy: Cell<GrowthPolicySlot>,
+    // Chunks that were kept around by `reset`, to be reused or freed later.
+    spare_chunks: SpareChunks,
+    // Pinned values to drop before their memory is reused or freed.
+    #[cfg(feature = "boxed")]
+    drop_list: DropList,
+    // Where this arena's chunks come from.
+    backing: A,
+}
+
+/// What a [limit hook](Bump::set_limit_hook) decides to do about an
+/// allocation that would exceed its arena's allocation limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LimitDecision {
+    /// Fail the allocation, just as if no hook were set.
+    Deny,
+    /// Try the allocation again, e.g. because the hook raised the limit. If
+    /// it would still exceed the limit, the hook is called again.
+    Retry,
+    /// Go ahead with the allocation, ignoring the limit this once.
+    Allow,
+}
+
+/// What an arena does with the bytes that were allocated in it when it is
+/// reset or dropped. See [`Bump::set_reset_policy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ResetPolicy {
+    /// Leave the bytes as they are. This is the default.
+    #[default]
+    Leave,
+    /// Overwrite the bytes with zeros, in a way that the compiler cannot
+    /// optimize away, before they are reused or handed back to the backing
+    /// allocator.
+    Zero,
+}
+
+/// A callback that is invoked when an allocation would exceed an arena's
+/// allocation limit. See [`Bump::set_limit_hook`].
+pub type LimitHook<A = Global> = fn(&Bump<A>, Layout) -> LimitDecision;
+
+/// A callback that is invoked for every allocation in an arena. See
+/// [`Bump::set_trace_hook`].
+#[cfg(feature = "trace")]
+pub type TraceHook<A = Global> = fn(&Bump<A>, AllocationEvent);
+
+/// An allocation that was made in an arena, as reported to its
+/// [trace hook](Bump::set_trace_hook).
+#[cfg(feature = "trace")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AllocationEvent {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    chunk_id: usize,
+    is_front: bool,
+}
+
+#[cfg(feature = "trace")]
+impl AllocationEvent {
+    /// The pointer that the allocation returned.
+    #[inline]
+    pub fn ptr(&self) -> NonNull<u8> {
+        self.ptr
+    }
+
+    /// The layout that was allocated.
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Identifies the chunk that the allocation was made in.
+    ///
+    /// This is the chunk's offset into the arena, as for
+    /// [`alloc_layout_with_offset_tracking`](Bump::alloc_layout_with_offset_tracking),
+    /// so it is different for each of the arena's chunks, and grows with each
+    /// new chunk. Chunks are numbered from zero again after the arena is
+    /// reset.
+    #[inline]
+    pub fn chunk_id(&self) -> usize {
+        self.chunk_id
+    }
+
+    /// Whether this is a [front allocation](Bump::alloc_front).
+    #[inline]
+    pub fn is_front(&self) -> bool {
+        self.is_front
+    }
+}
+
+/// Values that an arena drops before it reuses or frees their memory, unless
+/// their owners dropped them already.
+///
+/// Pinned [`BoxIn`](boxed::BoxIn)s register their values here, because the
+/// `Pin` contract requires that a pinned value is dropped before its memory
+/// is invalidated, even if its box is leaked. The entries themselves are
+/// allocated in the arena.
+#[cfg(feature = "boxed")]
+#[derive(Debug)]
+struct DropList {
+    head: Cell<Option<NonNull<DropEntry>>>,
+}
+
+/// An entry in a [`DropList`], and the start of a `TypedDropEntry`.
+#[cfg(feature = "boxed")]
+#[derive(Debug)]
+pub(crate) struct DropEntry {
+    next: Option<NonNull<DropEntry>>,
+    // Drops the value, or `None` once it was dropped.
+    drop: Cell<Option<DropFn>>,
+}
+
+#[cfg(feature = "boxed")]
+type DropFn = unsafe fn(NonNull<DropEntry>);
+
+#[cfg(feature = "boxed")]
+#[repr(C)]
+struct TypedDropEntry<T: ?Sized> {
+    entry: DropEntry,
+    value: *mut T,
+}
+
+#[cfg(feature = "boxed")]
+impl DropList {
+    const fn new() -> DropList {
+        DropList {
+            head: Cell::new(None),
+        }
+    }
+}
+
+#[cfg(feature = "boxed")]
+impl DropEntry {
+    /// Forget about the entry's value, because it was dropped or is no longer
+    /// pinned.
+    ///
+    /// ## Safety
+    ///
+    /// `entry` must have been returned by [`Bump::register_drop`] for an arena
+    /// that hasn't been reset or dropped since.
+    pub(crate) unsafe fn disarm(entry: NonNull<DropEntry>) {
+        entry.as_ref().drop.set(None);
+    }
+}
+
+#[cfg(feature = "boxed")]
+unsafe fn drop_entry_value<T: ?Sized>(entry: NonNull<DropEntry>) {
+    ptr::drop_in_place(entry.cast::<TypedDropEntry<T>>().as_ref().value);
+}
+
+/// Where a `Bump` keeps a hook.
+struct HookSlot<H> {
+    hook: Cell<Option<H>>,
+    // Whether the hook is currently running. Allocations that the hook makes
+    // itself don't invoke it again.
+    running: Cell<bool>,
+}
+
+impl<H: Copy> HookSlot<H> {
+    const fn new() -> Self {
+        HookSlot {
+            hook: Cell::new(None),
+            running: Cell::new(false),
+        }
+    }
+
+    /// Call `f` with the hook, unless there is none or it is already
+    /// running.
+    fn call<R>(&self, f: impl FnOnce(H) -> R) -> Option<R> {
+        struct Running<'a>(&'a Cell<bool>);
+
+        impl Drop for Running<'_> {
+            fn drop(&mut self) {
+                self.0.set(false);
+            }
+        }
+
+        match self.hook.get() {
+            Some(hook) if !self.running.get() => {
+                self.running.set(true);
+                let _running = Running(&self.running);
+                Some(f(hook))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<H: Copy> fmt::Debug for HookSlot<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.hook.get() {
+            Some(_) => f.write_str("Some(..)"),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+/// Decides how large the chunks that an arena allocates to grow are, and
+/// observes the chunks it allocates.
+///
+/// Set with [`BumpBuilder::growth_policy`] or [`Bump::set_growth_policy`]. By
+/// default, each new chunk is the previous chunk's size times the arena's
+/// growth factor, capped at its maximum chunk size. A policy can replace that
+/// computation, e.g. to grow arithmetically or to cap growth depending on
+/// what the process is doing, and can keep track of the chunks that get
+/// allocated.
+///
+/// Any `Fn(usize, Layout) -> usize` closure or function is a policy that
+/// returns the size of the next chunk from the previous chunk's size and the
+/// layout of the allocation that needs the new chunk.
+///
+/// Policies are `'static` references, so they can be shared between arenas
+/// and builders freely. A policy that carries configuration can live in a
+/// `static`, or be leaked with `Box::leak`.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::{Bump, ChunkGrowthPolicy};
+/// use std::alloc::Layout;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// // Grow by 4 KiB at a time, and count the chunks that get allocated.
+/// struct Arithmetic {
+///     chunks: AtomicUsize,
+/// }
+///
+/// impl ChunkGrowthPolicy for Arithmetic {
+///     fn next_chunk_size(&self, previous_size: usize, _layout: Layout) -> Option<usize> {
+///         Some(previous_size + 4096)
+///     }
+///
+///     fn chunk_allocated(&self, _size: usize) {
+///         self.chunks.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// static POLICY: Arithmetic = Arithmetic { chunks: AtomicUsize::new(0) };
+///
+/// let bump = Bump::builder().growth_policy(&POLICY).build();
+/// for _ in 0..100 {
+///     bump.alloc([0_u8; 1000]);
+/// }
+/// assert!(POLICY.chunks.load(Ordering::Relaxed) > 1);
+/// ```
+pub trait ChunkGrowthPolicy: Sync {
+    /// Return the number of usable bytes that the arena's next chunk should
+    /// have, or `None` to size it with the arena's growth factor and maximum
+    /// chunk size, as usual.
+    ///
+    /// `previous_size` is the number of usable bytes in the arena's current
+    /// chunk, or zero if it has none, and `layout` is the allocation that
+    /// needs a new chunk. Sizes smaller than `layout` or than the default
+    /// chunk size are rounded up, and all sizes are rounded to play nicely
+    /// with the backing allocator. If the backing allocator can't provide a
+    /// chunk of this size, the arena retries with smaller ones, as usual.
+    ///
+    /// The default implementation returns `None`.
+    fn next_chunk_size(&self, previous_size: usize, layout: Layout) -> Option<usize> {
+        let _ = (previous_size, layout);
+        None
+    }
+
+    /// Called with the number of usable bytes of every chunk that the arena
+    /// allocates, once it has been allocated.
+    ///
+    /// The default implementation does nothing.
+    fn chunk_allocated(&self, size: usize) {
+        let _ = size;
+    }
+}
+
+impl<F> ChunkGrowthPolicy for F
+where
+    F: Fn(usize, Layout) -> usize + Sync,
+{
+    fn next_chunk_size(&self, previous_size: usize, layout: Layout) -> Option<usize> {
+        Some(self(previous_size, layout))
+    }
+}
+
+/// Where a `Bump` or `BumpBuilder` keeps its growth policy.
+#[derive(Clone, Copy)]
+struct GrowthPolicySlot(Option<&'static dyn ChunkGrowthPolicy>);
+
+impl fmt::Debug for GrowthPolicySlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("Some(..)"),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+/// How a `Bump` sizes and aligns the new chunks it allocates as it grows.
+#[derive(Debug, Clone, Copy)]
+struct ChunkPolicy {
+    // Each new chunk is this many times larger than the previous one.
+    growth_factor: usize,
+    // Chunks allocated to grow the arena are at most this large, unless a
+    // single allocation requires more.
+    max_chunk_size: Option<usize>,
+    // Chunks are aligned to at least this many bytes.
+    chunk_align: usize,
+    // Regular allocations are aligned to, and their sizes padded to a
+    // multiple of, at least this many bytes. At most `CHUNK_ALIGN`, so that
+    // the end of every chunk, where allocation starts, is aligned to it.
+    alloc_align: usize,
+    // Whether `reset` keeps the chunks it would free as spares.
+    defer_frees: bool,
+    // The alignment, and size granularity, of cache-aligned allocations.
+    cache_align: usize,
+    // Allocations through the `Allocator` impl of up to this many bytes are
+    // rounded up to the next power of two. Zero disables this.
+    #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+    size_class_limit: usize,
+}
+
+impl ChunkPolicy {
+    const DEFAULT: ChunkPolicy = ChunkPolicy {
+        growth_factor: 2,
+        max_chunk_size: None,
+        chunk_align: CHUNK_ALIGN,
+        alloc_align: 1,
+        defer_frees: false,
+        cache_align: DEFAULT_CACHE_ALIGN,
+        #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+        size_class_limit: 0,
+    };
+
+    // Panics if `align` is not a valid `alloc_align`.
+    fn check_alloc_align(align: usize) {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        assert!(
+            align <= CHUNK_ALIGN,
+            "minimum allocation alignment must be at most 16"
+        );
+    }
 }
 
 #[repr(C)]
@@ -311,9 +758,14 @@ struct ChunkFooter {
     // chunk, whose `prev` link points to itself.
     prev: Cell<NonNull<ChunkFooter>>,
 
-    // Bump allocation finger that is always in the range `self.data..=self`.
+    // Bump allocation finger that is always in the range `self.front..=self`.
+    // Allocations are bumped downwards from the end of the chunk.
     ptr: Cell<NonNull<u8>>,
 
+    // Bump allocation finger for front allocations, which are bumped upwards
+    // from the start of the chunk. Always in the range `self.data..=self.ptr`.
+    front: Cell<NonNull<u8>>,
+
     // The bytes allocated in all chunks so far, the canonical empty chunk has
     // a size of 0 and for all other chunks, `allocated_bytes` will be
     // the allocated_bytes of the current chunk plus the allocated bytes
@@ -326,7 +778,11 @@ struct ChunkFooter {
 /// For the canonical empty chunk to be `static`, its type must be `Sync`, which
 /// is the purpose of this wrapper type. This is safe because the empty chunk is
 /// immutable and never actually modified.
-#[repr(transparent)]
+///
+/// It is aligned to `CHUNK_ALIGN` like the end of every other chunk, so that
+/// the bump pointer is always aligned to the arena's minimum allocation
+/// alignment.
+#[repr(C, align(16))]
 struct EmptyChunkFooter(ChunkFooter);
 
 unsafe impl Sync for EmptyChunkFooter {}
@@ -343,6 +799,11 @@ static EMPTY_CHUNK: EmptyChunkFooter = EmptyChunkFooter(ChunkFooter {
         NonNull::new_unchecked(&EMPTY_CHUNK as *const EmptyChunkFooter as *mut u8)
     }),
 
+    // As is the end of its (empty) front allocation region.
+    front: Cell::new(unsafe {
+        NonNull::new_unchecked(&EMPTY_CHUNK as *const EmptyChunkFooter as *mut u8)
+    }),
+
     // Invariant: the last chunk footer in all `ChunkFooter::prev` linked lists
     // is the empty chunk footer, whose `prev` points to itself.
     prev: Cell::new(unsafe {
@@ -359,9 +820,103 @@ impl EmptyChunkFooter {
     }
 }
 
+/// The footer of an arena's current chunk.
+///
+/// A null pointer stands for the canonical empty chunk. Constant functions
+/// cannot refer to `EMPTY_CHUNK` on our MSRV, so this is what lets
+/// `Bump::empty` be a `const fn`. Everything else only ever sees non-null
+/// footers.
+struct CurrentChunk(Cell<*mut ChunkFooter>);
+
+impl CurrentChunk {
+    #[inline]
+    const fn empty() -> CurrentChunk {
+        CurrentChunk(Cell::new(ptr::null_mut()))
+    }
+
+    #[inline(always)]
+    fn get(&self) -> NonNull<ChunkFooter> {
+        NonNull::new(self.0.get()).unwrap_or_else(|| EMPTY_CHUNK.get())
+    }
+
+    #[inline(always)]
+    fn set(&self, footer: NonNull<ChunkFooter>) {
+        self.0.set(footer.as_ptr());
+    }
+
+    #[inline(always)]
+    fn replace(&self, footer: NonNull<ChunkFooter>) -> NonNull<ChunkFooter> {
+        let old = self.get();
+        self.set(footer);
+        old
+    }
+}
+
+impl fmt::Debug for CurrentChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// The chunks that `reset` set aside instead of freeing them, with the
+/// [`BumpBuilder::defer_chunk_frees`] option.
+///
+/// `reset` detaches whole chains of chunks, still linked through their `prev`
+/// pointers, and pushing a chain must not walk it. So the spares are a stack
+/// of chains: the head of each chain links to the head of the next one
+/// through its bump finger, which a spare chunk has no use for.
+#[derive(Debug)]
+struct SpareChunks {
+    head: CurrentChunk,
+    // The total capacity of all spare chunks.
+    capacity: Cell<usize>,
+}
+
+impl SpareChunks {
+    const fn new() -> SpareChunks {
+        SpareChunks {
+            head: CurrentChunk::empty(),
+            capacity: Cell::new(0),
+        }
+    }
+
+    /// Push a chain of chunks, which must not be linked to anything else.
+    unsafe fn push_chain(&self, chain: NonNull<ChunkFooter>) {
+        let c = chain.as_ref();
+        if c.is_empty() {
+            return;
+        }
+        self.capacity.set(self.capacity.get() + c.allocated_bytes);
+        c.ptr.set(self.head.get().cast());
+        self.head.set(chain);
+    }
+
+    /// Pop a single chunk, whose `prev` link is left pointing at the empty
+    /// chunk.
+    unsafe fn pop(&self) -> Option<NonNull<ChunkFooter>> {
+        let head = self.head.get();
+        let h = head.as_ref();
+        if h.is_empty() {
+            return None;
+        }
+        let next_chain = h.ptr.get().cast::<ChunkFooter>();
+        let rest = h.prev.replace(EMPTY_CHUNK.get());
+        if rest.as_ref().is_empty() {
+            self.head.set(next_chain);
+        } else {
+            rest.as_ref().ptr.set(next_chain.cast());
+            self.head.set(rest);
+        }
+        self.capacity
+            .set(self.capacity.get() - (h.layout.size() - FOOTER_SIZE));
+        Some(head)
+    }
+}
+
 impl ChunkFooter {
     // Returns the start and length of the currently allocated region of this
-    // chunk.
+    // chunk, not counting front allocations, which are in
+    // `front_raw_parts`.
     fn as_raw_parts(&self) -> (*const u8, usize) {
         let data = self.data.as_ptr() as *const u8;
         let ptr = self.ptr.get().as_ptr() as *const u8;
@@ -371,6 +926,36 @@ impl ChunkFooter {
         (ptr, len)
     }
 
+    // Returns the start and length of the front allocations in this chunk.
+    fn front_raw_parts(&self) -> (*const u8, usize) {
+        let data = self.data.as_ptr() as *const u8;
+        let front = self.front.get().as_ptr() as *const u8;
+        debug_assert!(data <= front);
+        (data, front as usize - data as usize)
+    }
+
+    /// Get the sizes of this chunk and of its used part.
+    fn usage(&self, is_current: bool) -> ChunkUsage {
+        let data = self.data.as_ptr() as usize;
+        let end = self as *const ChunkFooter as usize;
+        let free = self.ptr.get().as_ptr() as usize - self.front.get().as_ptr() as usize;
+        ChunkUsage {
+            size: end - data,
+            used: end - data - free,
+            is_current,
+        }
+    }
+
+    /// Zero this chunk's allocated bytes, except for the last `keep` bytes
+    /// before the footer.
+    unsafe fn zero_used_bytes(&self, keep: usize) {
+        let front = self.data.as_ptr();
+        zero_volatile(front, self.front.get().as_ptr() as usize - front as usize);
+        let back = self.ptr.get().as_ptr();
+        let end = self as *const ChunkFooter as usize - keep;
+        zero_volatile(back, end - back as usize);
+    }
+
     /// Is this chunk the last empty chunk?
     fn is_empty(&self) -> bool {
         ptr::eq(self, EMPTY_CHUNK.get().as_ptr())
@@ -383,72 +968,465 @@ impl Default for Bump {
     }
 }
 
-impl Drop for Bump {
-    fn drop(&mut self) {
-        unsafe {
-            dealloc_chunk_list(self.current_chunk_footer.get());
-        }
-    }
+/// A builder for a [`Bump`] arena with a custom chunk growth policy.
+///
+/// `Bump` allocates its memory from the global allocator in chunks. By
+/// default, the first chunk is allocated lazily (or eagerly with the size
+/// given to [`Bump::with_capacity`]) and every following chunk is about twice
+/// as large as the one before it. A `BumpBuilder` lets embedders configure:
+///
+/// * the size of the initial chunk, which is allocated up front,
+/// * the factor by which each new chunk is larger than the previous one,
+/// * a maximum size for chunks allocated to grow the arena,
+/// * a [`ChunkGrowthPolicy`] that sizes new chunks instead,
+/// * a minimum alignment for each chunk's memory, and
+/// * the arena's [allocation limit][Bump::set_allocation_limit].
+///
+/// Chunk sizes are still rounded up to play nicely with the global
+/// allocator, so chunks may be slightly larger than requested.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::BumpBuilder;
+///
+/// // Start with a 4 KiB chunk and grow linearly, in chunks of at most 64 KiB.
+/// let bump = BumpBuilder::new()
+///     .initial_chunk_size(4096)
+///     .growth_factor(1)
+///     .max_chunk_size(Some(64 * 1024))
+///     .build();
+/// assert!(bump.chunk_capacity() >= 4096);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BumpBuilder {
+    initial_chunk_size: usize,
+    chunk_policy: ChunkPolicy,
+    growth_policy: GrowthPolicySlot,
+    allocation_limit: Option<usize>,
+    reset_policy: ResetPolicy,
 }
 
-#[inline]
-unsafe fn dealloc_chunk_list(mut footer: NonNull<ChunkFooter>) {
-    while !footer.as_ref().is_empty() {
-        let f = footer;
-        footer = f.as_ref().prev.get();
-        dealloc(f.as_ref().data.as_ptr(), f.as_ref().layout);
+impl Default for BumpBuilder {
+    fn default() -> BumpBuilder {
+        BumpBuilder::new()
     }
 }
 
-// `Bump`s are safe to send between threads because nothing aliases its owned
-// chunks until you start allocating from it. But by the time you allocate from
-// it, the returned references to allocations borrow the `Bump` and therefore
-// prevent sending the `Bump` across threads until the borrows end.
-unsafe impl Send for Bump {}
+impl BumpBuilder {
+    /// Construct a new builder with the default chunk growth policy.
+    ///
+    /// Building it as-is is equivalent to [`Bump::new`].
+    pub fn new() -> BumpBuilder {
+        BumpBuilder {
+            initial_chunk_size: 0,
+            chunk_policy: ChunkPolicy::DEFAULT,
+            growth_policy: GrowthPolicySlot(None),
+            allocation_limit: None,
+            reset_policy: ResetPolicy::Leave,
+        }
+    }
 
-#[inline]
-fn is_pointer_aligned_to<T>(pointer: *mut T, align: usize) -> bool {
-    debug_assert!(align.is_power_of_two());
+    /// Set the number of usable bytes in the arena's initial chunk.
+    ///
+    /// The initial chunk is allocated when the arena is built, and is sized
+    /// for exactly this many bytes, rounded up to play nicely with the
+    /// global allocator, but irrespective of the default chunk size. If this
+    /// is zero, which is the default, no chunk is allocated until the first
+    /// allocation.
+    pub fn initial_chunk_size(mut self, bytes: usize) -> BumpBuilder {
+        self.initial_chunk_size = bytes;
+        self
+    }
 
-    let pointer = pointer as usize;
-    let pointer_aligned = round_down_to(pointer, align);
-    pointer == pointer_aligned
-}
+    /// Set the factor by which each new chunk is larger than the previous
+    /// one.
+    ///
+    /// The default is `2`. A factor of `1` grows the arena linearly, in
+    /// chunks of the same size.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `factor` is zero.
+    pub fn growth_factor(mut self, factor: usize) -> BumpBuilder {
+        assert!(factor > 0, "growth factor must be at least 1");
+        self.chunk_policy.growth_factor = factor;
+        self
+    }
 
-#[inline]
-pub(crate) fn round_up_to(n: usize, divisor: usize) -> Option<usize> {
-    debug_assert!(divisor > 0);
-    debug_assert!(divisor.is_power_of_two());
-    Some(n.checked_add(divisor - 1)? & !(divisor - 1))
-}
+    /// Set the maximum number of usable bytes in chunks allocated to grow
+    /// the arena.
+    ///
+    /// Once chunks reach this size, the arena stops growing them and keeps
+    /// allocating chunks of this size instead. A single allocation that is
+    /// larger than this still gets a chunk that is large enough for it. The
+    /// initial chunk is not subject to this limit. The default is `None`,
+    /// meaning chunks grow without bound.
+    pub fn max_chunk_size(mut self, bytes: Option<usize>) -> BumpBuilder {
+        self.chunk_policy.max_chunk_size = bytes;
+        self
+    }
 
-#[inline]
-pub(crate) fn round_down_to(n: usize, divisor: usize) -> usize {
-    debug_assert!(divisor > 0);
-    debug_assert!(divisor.is_power_of_two());
-    n & !(divisor - 1)
-}
+    /// Set the policy that sizes the chunks allocated to grow the arena.
+    ///
+    /// See [`ChunkGrowthPolicy`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// // Grow by 64 KiB at a time.
+    /// let bump = bumpalo::Bump::builder()
+    ///     .growth_policy(&|prev: usize, _: Layout| prev + 64 * 1024)
+    ///     .build();
+    /// # drop(bump);
+    /// ```
+    pub fn growth_policy(mut self, policy: &'static dyn ChunkGrowthPolicy) -> BumpBuilder {
+        self.growth_policy = GrowthPolicySlot(Some(policy));
+        self
+    }
 
-/// Same as `round_down_to` but preserves pointer provenance.
-#[inline]
-pub(crate) fn round_mut_ptr_down_to(ptr: *mut u8, divisor: usize) -> *mut u8 {
-    debug_assert!(divisor > 0);
-    debug_assert!(divisor.is_power_of_two());
-    ptr.wrapping_sub(ptr as usize & (divisor - 1))
-}
+    /// Set the minimum alignment of each chunk's memory.
+    ///
+    /// Chunks are always aligned to at least 16 bytes, which is the default.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn min_align(mut self, align: usize) -> BumpBuilder {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        self.chunk_policy.chunk_align = align.max(CHUNK_ALIGN);
+        self
+    }
 
-// After this point, we try to hit page boundaries instead of powers of 2
-const PAGE_STRATEGY_CUTOFF: usize = 0x1000;
+    /// Set the minimum alignment of every allocation in the built arena.
+    ///
+    /// Unlike [`min_align`](BumpBuilder::min_align), which only affects the
+    /// alignment of whole chunks, this aligns each regular allocation to at
+    /// least `align` bytes, and pads its size to a multiple of `align`. See
+    /// [`Bump::with_min_align`] for details. The default is 1.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `align` is not a power of two, or if it is greater than 16.
+    pub fn min_alloc_align(mut self, align: usize) -> BumpBuilder {
+        ChunkPolicy::check_alloc_align(align);
+        self.chunk_policy.alloc_align = align;
+        self
+    }
 
-// We only support alignments of up to 16 bytes for iter_allocated_chunks.
-const SUPPORTED_ITER_ALIGNMENT: usize = 16;
-const CHUNK_ALIGN: usize = SUPPORTED_ITER_ALIGNMENT;
+    /// Set whether [`Bump::reset`] defers freeing the chunks it does not
+    /// keep.
+    ///
+    /// By default, `reset` returns every chunk but the current one to the
+    /// backing allocator, which takes time proportional to the number of
+    /// chunks. With deferred frees, `reset` sets them aside as spares in
+    /// constant time instead. When the arena needs a new chunk later, it
+    /// reuses the most recently set aside spare if the allocation fits in
+    /// it, and frees that spare otherwise. The remaining spares are freed by
+    /// [`Bump::release_spare_chunks`], or when the arena is dropped.
+    ///
+    /// Spare chunks do not count towards
+    /// [`allocated_bytes`](Bump::allocated_bytes); see
+    /// [`Bump::spare_capacity`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::builder().defer_chunk_frees(true).build();
+    /// for i in 0..10_000_u64 {
+    ///     bump.alloc(i);
+    /// }
+    /// assert!(bump.chunk_count() > 1);
+    ///
+    /// bump.reset();
+    /// assert_eq!(bump.chunk_count(), 1);
+    /// assert!(bump.spare_capacity() > 0);
+    ///
+    /// // Free the spares at a convenient time.
+    /// bump.release_spare_chunks();
+    /// assert_eq!(bump.spare_capacity(), 0);
+    /// ```
+    pub fn defer_chunk_frees(mut self, defer: bool) -> BumpBuilder {
+        self.chunk_policy.defer_frees = defer;
+        self
+    }
+
+    /// Set the alignment of the built arena's cache-aligned allocations, made
+    /// with [`Bump::alloc_cache_aligned`] and
+    /// [`Bump::alloc_slice_cache_aligned`].
+    ///
+    /// The default is 128 bytes on `x86_64`, `aarch64`, and `powerpc64`, whose
+    /// prefetchers pull in pairs of 64-byte cache lines, and 64 bytes
+    /// elsewhere.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn cache_line_size(mut self, align: usize) -> BumpBuilder {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        self.chunk_policy.cache_align = align;
+        self
+    }
+
+    /// Round small allocations made through the built arena's `Allocator`
+    /// implementation up to size classes, so that growing them is often free.
+    ///
+    /// Every allocation of at most `limit` bytes made through `Allocator` for
+    /// `&Bump` is rounded up to the next power of two, and the returned block
+    /// reports the rounded size. Growing such an allocation within its size
+    /// class then returns it as is, without copying, even when it is not the
+    /// arena's last allocation. This trades up to half of each small
+    /// allocation for far fewer copies when many small buffers grow a little
+    /// at a time in an interleaved fashion.
+    ///
+    /// The default is zero, which disables size classes.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `limit` is neither zero nor a power of two.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "allocator-api2")]
+    /// # {
+    /// use allocator_api2::alloc::{Allocator, Layout};
+    ///
+    /// let bump = bumpalo::Bump::builder().allocator_size_classes(256).build();
+    /// let small = Layout::array::<u8>(10).unwrap();
+    /// let a = (&bump).allocate(small).unwrap();
+    /// let b = (&bump).allocate(small).unwrap();
+    /// assert_eq!(a.len(), 16);
+    ///
+    /// // `a` is no longer the last allocation, but its size class has room
+    /// // for 16 bytes.
+    /// let grown = unsafe {
+    ///     (&bump).grow(a.cast(), small, Layout::array::<u8>(16).unwrap())
+    /// };
+    /// assert_eq!(grown.unwrap().cast::<u8>(), a.cast::<u8>());
+    /// # let _ = b;
+    /// # }
+    /// ```
+    #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+    pub fn allocator_size_classes(mut self, limit: usize) -> BumpBuilder {
+        assert!(
+            limit == 0 || limit.is_power_of_two(),
+            "size class limit must be zero or a power of two"
+        );
+        self.chunk_policy.size_class_limit = limit;
+        self
+    }
+
+    /// Set the built arena's allocation limit, in bytes.
+    ///
+    /// See [`Bump::set_allocation_limit`] for details.
+    pub fn allocation_limit(mut self, limit: Option<usize>) -> BumpBuilder {
+        self.allocation_limit = limit;
+        self
+    }
+
+    /// Set what the built arena does with its allocated bytes when it is
+    /// reset or dropped.
+    ///
+    /// See [`Bump::set_reset_policy`] for details.
+    pub fn reset_policy(mut self, policy: ResetPolicy) -> BumpBuilder {
+        self.reset_policy = policy;
+        self
+    }
+
+    /// Build the arena, allocating its initial chunk if one was requested.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the initial chunk fails, or if it would exceed
+    /// the allocation limit.
+    pub fn build(self) -> Bump {
+        self.build_in(Global)
+    }
+
+    /// Build the arena so that it allocates its chunks from the given
+    /// backing allocator, allocating its initial chunk if one was requested.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the initial chunk fails, or if it would exceed
+    /// the allocation limit.
+    pub fn build_in<A: BackingAllocator>(self, backing: A) -> Bump<A> {
+        self.try_build_in(backing).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempt to build the arena, allocating its initial chunk if one was
+    /// requested.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating the initial chunk fails, or if it would exceed
+    /// the allocation limit.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::BumpBuilder;
+    ///
+    /// let result = BumpBuilder::new()
+    ///     .initial_chunk_size(1024)
+    ///     .allocation_limit(Some(100))
+    ///     .try_build();
+    /// assert!(result.is_err());
+    /// ```
+    pub fn try_build(self) -> Result<Bump, AllocErr> {
+        self.try_build_in(Global)
+    }
+
+    /// Attempt to build the arena so that it allocates its chunks from the
+    /// given backing allocator, allocating its initial chunk if one was
+    /// requested.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating the initial chunk fails, or if it would exceed
+    /// the allocation limit.
+    pub fn try_build_in<A: BackingAllocator>(self, backing: A) -> Result<Bump<A>, AllocErr> {
+        let bump = Bump {
+            current_chunk_footer: CurrentChunk::empty(),
+            allocation_limit: Cell::new(self.allocation_limit),
+            limit_hook: HookSlot::new(),
+            #[cfg(feature = "trace")]
+            trace_hook: HookSlot::new(),
+            reset_policy: Cell::new(self.reset_policy),
+            chunk_policy: self.chunk_policy,
+            growth_policy: Cell::new(self.growth_policy),
+            spare_chunks: SpareChunks::new(),
+            #[cfg(feature = "boxed")]
+            drop_list: DropList::new(),
+            backing,
+        };
+        if self.initial_chunk_size == 0 {
+            return Ok(bump);
+        }
+
+        let layout = layout_from_size_align(self.initial_chunk_size, 1)?;
+        let chunk_memory_details = Bump::<A>::new_chunk_memory_details(
+            Some(self.initial_chunk_size),
+            layout,
+            self.chunk_policy.chunk_align,
+        )
+        .ok_or(AllocErr::SIZE_OVERFLOW)?;
+        if !Bump::<A>::chunk_fits_under_limit(self.allocation_limit, chunk_memory_details) {
+            return Err(AllocErr::LIMIT_EXCEEDED);
+        }
+
+        let chunk_footer = unsafe {
+            bump.new_chunk(chunk_memory_details, layout, EMPTY_CHUNK.get(), false)
+                .ok_or(AllocErr::OUT_OF_MEMORY)?
+        };
+        debug_assert!(
+            unsafe { chunk_footer.as_ref().layout.size() } - FOOTER_SIZE >= self.initial_chunk_size
+        );
+        bump.current_chunk_footer.set(chunk_footer);
+        Ok(bump)
+    }
+}
+
+impl<A: BackingAllocator> Drop for Bump<A> {
+    fn drop(&mut self) {
+        self.run_drop_list();
+        self.release_spare_chunks();
+        unsafe {
+            if self.reset_policy() == ResetPolicy::Zero {
+                let mut footer = self.current_chunk_footer.get();
+                while !footer.as_ref().is_empty() {
+                    footer.as_ref().zero_used_bytes(0);
+                    footer = footer.as_ref().prev.get();
+                }
+            }
+            dealloc_chunk_list(self.current_chunk_footer.get(), &self.backing);
+        }
+    }
+}
+
+#[inline]
+unsafe fn dealloc_chunk_list<A: BackingAllocator>(mut footer: NonNull<ChunkFooter>, backing: &A) {
+    while !footer.as_ref().is_empty() {
+        let f = footer;
+        footer = f.as_ref().prev.get();
+        // Hand the chunk back fully addressable, since its memory may be
+        // reused for anything.
+        let data = f.as_ref().data;
+        asan_unpoison(data.as_ptr(), f.as_ptr() as usize - data.as_ptr() as usize);
+        backing.deallocate(data, f.as_ref().layout);
+    }
+}
+
+// `Bump`s are safe to send between threads because nothing aliases its owned
+// chunks until you start allocating from it. But by the time you allocate from
+// it, the returned references to allocations borrow the `Bump` and therefore
+// prevent sending the `Bump` across threads until the borrows end.
+unsafe impl<A: BackingAllocator + Send> Send for Bump<A> {}
+
+#[inline]
+fn is_pointer_aligned_to<T>(pointer: *mut T, align: usize) -> bool {
+    debug_assert!(align.is_power_of_two());
+
+    let pointer = pointer as usize;
+    let pointer_aligned = round_down_to(pointer, align);
+    pointer == pointer_aligned
+}
+
+#[inline]
+pub(crate) fn round_up_to(n: usize, divisor: usize) -> Option<usize> {
+    debug_assert!(divisor > 0);
+    debug_assert!(divisor.is_power_of_two());
+    Some(n.checked_add(divisor - 1)? & !(divisor - 1))
+}
+
+#[inline]
+pub(crate) fn round_down_to(n: usize, divisor: usize) -> usize {
+    debug_assert!(divisor > 0);
+    debug_assert!(divisor.is_power_of_two());
+    n & !(divisor - 1)
+}
+
+/// Same as `round_down_to` but preserves pointer provenance.
+#[inline]
+pub(crate) fn round_mut_ptr_down_to(ptr: *mut u8, divisor: usize) -> *mut u8 {
+    debug_assert!(divisor > 0);
+    debug_assert!(divisor.is_power_of_two());
+    ptr.wrapping_sub(ptr as usize & (divisor - 1))
+}
+
+// After this point, we try to hit page boundaries instead of powers of 2
+const PAGE_STRATEGY_CUTOFF: usize = 0x1000;
+
+// We only support alignments of up to 16 bytes for iter_allocated_chunks.
+const SUPPORTED_ITER_ALIGNMENT: usize = 16;
+const CHUNK_ALIGN: usize = SUPPORTED_ITER_ALIGNMENT;
 const FOOTER_SIZE: usize = mem::size_of::<ChunkFooter>();
 
 // Assert that ChunkFooter is at most the supported alignment. This will give a compile time error if it is not the case
 const _FOOTER_ALIGN_ASSERTION: bool = mem::align_of::<ChunkFooter>() <= CHUNK_ALIGN;
 const _: [(); _FOOTER_ALIGN_ASSERTION as usize] = [()];
 
+// Slice copies of at least this many bytes have their destination aligned to
+// a cache line, when that is possible within the current chunk.
+const BULK_COPY_THRESHOLD: usize = 4096;
+const CACHE_LINE_SIZE: usize = 64;
+
+// The default alignment of cache-aligned allocations. Like `crossbeam`'s
+// `CachePadded`, this is two cache lines where the hardware prefetches pairs
+// of lines, so that neighbouring allocations don't share a pair either.
+const DEFAULT_CACHE_ALIGN: usize = if cfg!(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "powerpc64",
+)) {
+    128
+} else {
+    64
+};
+
 // Maximum typical overhead per allocation imposed by allocators.
 const MALLOC_OVERHEAD: usize = 16;
 
@@ -482,7 +1460,87 @@ struct NewChunkMemoryDetails {
 /// Wrapper around `Layout::from_size_align` that adds debug assertions.
 #[inline]
 fn layout_from_size_align(size: usize, align: usize) -> Result<Layout, AllocErr> {
-    Layout::from_size_align(size, align).map_err(|_| AllocErr)
+    Layout::from_size_align(size, align).map_err(|_| AllocErr::SIZE_OVERFLOW)
+}
+
+/// The byte that memory is filled with when it is deallocated or its arena is
+/// reset, with the `debug_poison` feature.
+#[cfg(feature = "debug_poison")]
+pub const POISON_FREED: u8 = 0xA5;
+
+/// The byte that freshly allocated, uninitialized memory is filled with, with
+/// the `debug_poison` feature in builds with debug assertions.
+#[cfg(feature = "debug_poison")]
+pub const POISON_FRESH: u8 = 0xCD;
+
+/// Fill memory that is no longer allocated with `POISON_FREED`, so that
+/// reading through a dangling reference to it returns garbage rather than
+/// the old, plausible-looking value.
+#[inline(always)]
+unsafe fn poison_freed(ptr: *mut u8, len: usize) {
+    #[cfg(feature = "debug_poison")]
+    ptr::write_bytes(ptr, POISON_FREED, len);
+    #[cfg(not(feature = "debug_poison"))]
+    let _ = (ptr, len);
+}
+
+/// Zero memory with writes that the compiler may not remove, even if the
+/// memory is never read again before it is freed.
+#[inline(never)]
+unsafe fn zero_volatile(ptr: *mut u8, len: usize) {
+    let end = ptr.add(len);
+    let mut p = ptr;
+    while p < end && p as usize % mem::align_of::<usize>() != 0 {
+        ptr::write_volatile(p, 0);
+        p = p.add(1);
+    }
+    while (end as usize - p as usize) >= mem::size_of::<usize>() {
+        ptr::write_volatile(p as *mut usize, 0);
+        p = p.add(mem::size_of::<usize>());
+    }
+    while p < end {
+        ptr::write_volatile(p, 0);
+        p = p.add(1);
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Fill freshly allocated, uninitialized memory with `POISON_FRESH`, so that
+/// reading it before initializing it is noticeable.
+#[inline(always)]
+unsafe fn poison_fresh(ptr: *mut u8, len: usize) {
+    #[cfg(all(feature = "debug_poison", debug_assertions))]
+    ptr::write_bytes(ptr, POISON_FRESH, len);
+    #[cfg(not(all(feature = "debug_poison", debug_assertions)))]
+    let _ = (ptr, len);
+}
+
+#[cfg(feature = "asan")]
+extern "C" {
+    fn __asan_poison_memory_region(addr: *const u8, size: usize);
+    fn __asan_unpoison_memory_region(addr: *const u8, size: usize);
+}
+
+/// Mark memory as unaddressable for AddressSanitizer, with the `asan`
+/// feature.
+///
+/// The free space of every chunk is kept poisoned, so that accesses through
+/// references that outlive a `reset` or a `dealloc` are reported.
+#[inline(always)]
+unsafe fn asan_poison(ptr: *const u8, len: usize) {
+    #[cfg(feature = "asan")]
+    __asan_poison_memory_region(ptr, len);
+    #[cfg(not(feature = "asan"))]
+    let _ = (ptr, len);
+}
+
+/// Mark memory as addressable for AddressSanitizer, with the `asan` feature.
+#[inline(always)]
+unsafe fn asan_unpoison(ptr: *const u8, len: usize) {
+    #[cfg(feature = "asan")]
+    __asan_unpoison_memory_region(ptr, len);
+    #[cfg(not(feature = "asan"))]
+    let _ = (ptr, len);
 }
 
 #[inline(never)]
@@ -503,6 +1561,69 @@ impl Bump {
         Self::with_capacity(0)
     }
 
+    /// Construct a new, empty arena, in a `const` context.
+    ///
+    /// This does not allocate: the arena's first chunk is allocated lazily,
+    /// on its first allocation, just like with [`Bump::new`]. Being a
+    /// `const fn`, this can initialize `static`s and `thread_local!`s
+    /// directly, without wrapping the arena in an `Option` or a `OnceCell`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    ///
+    /// thread_local! {
+    ///     static SCRATCH: Bump = const { Bump::empty() };
+    /// }
+    ///
+    /// SCRATCH.with(|bump| {
+    ///     let x = bump.alloc(42);
+    ///     assert_eq!(*x, 42);
+    /// });
+    /// ```
+    pub const fn empty() -> Bump {
+        Bump::empty_in(Global)
+    }
+
+    /// Construct a new arena whose allocations are all aligned to at least
+    /// `align` bytes.
+    ///
+    /// The size of every allocation is also padded to a multiple of `align`.
+    /// As long as allocations don't require any greater alignment, this keeps
+    /// the bump pointer aligned to `align` between allocations, so that no
+    /// padding has to be inserted between them. This is the runtime
+    /// counterpart to [`with_align`](Bump::with_align), for code that cannot
+    /// thread a const generic through to where it allocates, and it applies
+    /// for the arena's whole lifetime.
+    ///
+    /// Front allocations, and allocations made through
+    /// [`with_align`](Bump::with_align), are not affected.
+    ///
+    /// Like [`Bump::new`], this does not allocate. Use
+    /// [`BumpBuilder::min_alloc_align`] to combine a minimum alignment with
+    /// other options.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `align` is not a power of two, or if it is greater than 16.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_min_align(8);
+    /// assert_eq!(bump.min_align(), 8);
+    ///
+    /// let a = bump.alloc(1_u8) as *mut u8 as usize;
+    /// let b = bump.alloc(2_u16) as *mut u16 as usize;
+    /// assert_eq!(a % 8, 0);
+    /// assert_eq!(b % 8, 0);
+    /// assert_eq!(a - b, 8);
+    /// ```
+    pub fn with_min_align(align: usize) -> Bump {
+        Bump::builder().min_alloc_align(align).build()
+    }
+
     /// Attempt to construct a new arena to bump allocate into.
     ///
     /// ## Example
@@ -517,11 +1638,23 @@ impl Bump {
 
     /// Construct a new arena with the specified byte capacity to bump allocate into.
     ///
+    /// The capacity is the number of usable bytes: the size of the chunk's
+    /// footer and any rounding done to play nicely with the global allocator
+    /// are accounted for on top of `capacity`. That is, the new arena's
+    /// [`chunk_capacity`](Bump::chunk_capacity) is at least `capacity`, and
+    /// allocating up to `capacity` bytes of data with an alignment of one will
+    /// not require allocating another chunk. Allocations with greater
+    /// alignment may also consume some padding bytes.
+    ///
+    /// The initial chunk is sized for `capacity` alone, even if that is
+    /// smaller than the default chunk size. Use a [`BumpBuilder`] to also
+    /// control how later chunks are sized.
+    ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::with_capacity(100);
-    /// # let _ = bump;
+    /// assert!(bump.chunk_capacity() >= 100);
     /// ```
     pub fn with_capacity(capacity: usize) -> Bump {
         Bump::try_with_capacity(capacity).unwrap_or_else(|_| oom())
@@ -529,6 +1662,9 @@ impl Bump {
 
     /// Attempt to construct a new arena with the specified byte capacity to bump allocate into.
     ///
+    /// See [`with_capacity`](Bump::with_capacity) for details on how the
+    /// capacity is interpreted.
+    ///
     /// ## Example
     ///
     /// ```
@@ -536,81 +1672,687 @@ impl Bump {
     /// # let _ = bump.unwrap();
     /// ```
     pub fn try_with_capacity(capacity: usize) -> Result<Self, AllocErr> {
-        if capacity == 0 {
-            return Ok(Bump {
-                current_chunk_footer: Cell::new(EMPTY_CHUNK.get()),
-                allocation_limit: Cell::new(None),
-            });
-        }
-
-        let layout = layout_from_size_align(capacity, 1)?;
+        BumpBuilder::new().initial_chunk_size(capacity).try_build()
+    }
 
-        let chunk_footer = unsafe {
-            Self::new_chunk(
-                Bump::new_chunk_memory_details(None, layout).ok_or(AllocErr)?,
-                layout,
-                EMPTY_CHUNK.get(),
-            )
-            .ok_or(AllocErr)?
-        };
+    /// Construct a new arena in which at least `capacity` bytes can be bump
+    /// allocated before it needs another chunk.
+    ///
+    /// This is the same as [`with_capacity`](Bump::with_capacity), which
+    /// already treats its capacity as usable bytes, under a name that says
+    /// so. The arena's [`usable_capacity`](Bump::usable_capacity) reports how
+    /// many bytes it actually got, which may be more than `capacity` because
+    /// of rounding.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::with_usable_capacity(1000);
+    /// assert!(bump.usable_capacity() >= 1000);
+    ///
+    /// let allocated = bump.allocated_bytes();
+    /// bump.alloc_layout(Layout::from_size_align(1000, 1).unwrap());
+    /// assert_eq!(bump.allocated_bytes(), allocated);
+    /// ```
+    pub fn with_usable_capacity(capacity: usize) -> Bump {
+        Bump::with_capacity(capacity)
+    }
 
-        Ok(Bump {
-            current_chunk_footer: Cell::new(chunk_footer),
-            allocation_limit: Cell::new(None),
-        })
+    /// Attempt to construct a new arena in which at least `capacity` bytes can
+    /// be bump allocated before it needs another chunk.
+    ///
+    /// See [`with_usable_capacity`](Bump::with_usable_capacity) for details.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating the initial chunk fails.
+    pub fn try_with_usable_capacity(capacity: usize) -> Result<Self, AllocErr> {
+        Bump::try_with_capacity(capacity)
     }
 
-    /// The allocation limit for this arena in bytes.
+    /// Construct a new arena holding a compacted copy of the allocated bytes
+    /// of every chunk in `source`, and return it together with a map for
+    /// relocating pointers into `source` to their copies.
+    ///
+    /// The new arena consists of a single chunk, sized for the copied bytes.
+    /// This is useful for building a data structure in a sprawling arena with
+    /// many chunks, and then compacting it for long-term retention: the copy
+    /// is made, and `source` can be reset or dropped.
+    ///
+    /// The chunks are copied in allocation order, least recently allocated
+    /// chunk first, and each chunk's bytes keep their original address modulo
+    /// 16, so allocations with an alignment of up to 16 stay aligned. Front
+    /// allocations (see [`alloc_front`](Bump::alloc_front)) are copied too,
+    /// each chunk's just before its regular allocations.
+    ///
+    /// The bytes are copied verbatim: pointers stored in the copied values
+    /// still point into `source`. Use [`RelocationMap::relocate`] to find the
+    /// copies of the values they point to.
+    ///
+    /// ## Safety
+    ///
+    /// Every allocated byte in `source` must be initialized; in particular,
+    /// there must not be any padding bytes in or between allocations. See
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks) for the
+    /// conditions under which this is the case.
+    ///
+    /// The copies are bitwise, so they must only be used as values of types
+    /// for which that is sound, such as `Copy` types. In particular, a copy
+    /// of a value that owns a resource must not be used to release it, if
+    /// the original might be used to release it as well.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the new arena's chunk fails.
     ///
     /// ## Example
     ///
     /// ```
-    /// let bump = bumpalo::Bump::with_capacity(0);
+    /// use bumpalo::Bump;
     ///
-    /// assert_eq!(bump.allocation_limit(), None);
+    /// let mut source = Bump::with_capacity(16);
+    /// let mut ptrs = Vec::new();
+    /// for i in 0..100_u64 {
+    ///     ptrs.push(source.alloc(i) as *const u64);
+    /// }
+    /// assert!(source.iter_allocated_chunks().count() > 1);
     ///
-    /// bump.set_allocation_limit(Some(6));
+    /// // Safe because we've only allocated `u64`s in this arena, so there is
+    /// // no uninitialized padding.
+    /// let (mut compact, map) = unsafe { Bump::clone_from_bump(&mut source) };
+    /// source.reset();
     ///
-    /// assert_eq!(bump.allocation_limit(), Some(6));
+    /// assert_eq!(compact.iter_allocated_chunks().count(), 1);
+    /// for (i, p) in ptrs.into_iter().enumerate() {
+    ///     let p = map.relocate(p).unwrap();
+    ///     assert_eq!(unsafe { *p }, i as u64);
+    /// }
+    /// ```
+    pub unsafe fn clone_from_bump<B: BackingAllocator>(
+        source: &mut Bump<B>,
+    ) -> (Bump, RelocationMap) {
+        Bump::clone_from_bump_in(source, Global)
+    }
+
+    /// Construct a [`BumpBuilder`] for an arena with a custom chunk growth
+    /// policy.
     ///
-    /// bump.set_allocation_limit(None);
+    /// ## Example
     ///
-    /// assert_eq!(bump.allocation_limit(), None);
     /// ```
-    pub fn allocation_limit(&self) -> Option<usize> {
-        self.allocation_limit.get()
+    /// let bump = bumpalo::Bump::builder().growth_factor(4).build();
+    /// # let _ = bump;
+    /// ```
+    pub fn builder() -> BumpBuilder {
+        BumpBuilder::new()
     }
+}
 
-    /// Set the allocation limit in bytes for this arena.
+impl<'a> Bump<FixedBuffer<'a>> {
+    /// Construct an arena that allocates out of the given buffer, and never
+    /// calls into the global allocator.
     ///
-    /// The allocation limit is only enforced when allocating new backing chunks for
-    /// a `Bump`. Updating the allocation limit will not affect existing allocations
-    /// or any future allocations within the `Bump`'s current chunk.
+    /// The arena consists of a single chunk carved out of `buf`, and never
+    /// grows. Once that chunk is full, allocations fail: the fallible `try_*`
+    /// methods return errors, and the others panic. A small part of the
+    /// buffer is used for the chunk's bookkeeping and for alignment, so the
+    /// arena's [`chunk_capacity`](Bump::chunk_capacity) is somewhat smaller
+    /// than `buf`.
     ///
     /// ## Example
     ///
     /// ```
-    /// let bump = bumpalo::Bump::with_capacity(0);
+    /// use bumpalo::Bump;
+    /// use core::mem::MaybeUninit;
     ///
-    /// bump.set_allocation_limit(Some(0));
+    /// let mut buf = [MaybeUninit::uninit(); 1024];
+    /// let bump = Bump::from_buffer(&mut buf);
     ///
-    /// assert!(bump.try_alloc(5).is_err());
+    /// let x = bump.alloc(42_u64);
+    /// assert_eq!(*x, 42);
+    ///
+    /// // The buffer is all there is.
+    /// assert!(bump.try_alloc([0_u8; 2048]).is_err());
     /// ```
-    pub fn set_allocation_limit(&self, limit: Option<usize>) {
-        self.allocation_limit.set(limit);
+    pub fn from_buffer(buf: &'a mut [mem::MaybeUninit<u8>]) -> Bump<FixedBuffer<'a>> {
+        Bump::from_fixed_buffer(FixedBuffer::new(buf))
     }
 
-    /// How much headroom an arena has before it hits its allocation
-    /// limit.
-    fn allocation_limit_remaining(&self) -> Option<usize> {
-        self.allocation_limit.get().and_then(|allocation_limit| {
-            let allocated_bytes = self.allocated_bytes();
-            if allocated_bytes > allocation_limit {
-                None
-            } else {
-                Some(usize::abs_diff(allocation_limit, allocated_bytes))
-            }
-        })
+    /// Construct an arena that allocates out of the `len` bytes at `ptr`,
+    /// and never calls into the global allocator.
+    ///
+    /// See [`from_buffer`](Bump::from_buffer) for details.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr..ptr + len` must be valid for reads and writes, and must not be
+    /// accessed through any other pointer, for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(ptr: NonNull<u8>, len: usize) -> Bump<FixedBuffer<'a>> {
+        Bump::from_fixed_buffer(FixedBuffer::from_raw_parts(ptr, len))
+    }
+
+    fn from_fixed_buffer(buffer: FixedBuffer<'a>) -> Bump<FixedBuffer<'a>> {
+        // Size the chunk to use as much of the buffer as possible, rather
+        // than rounding its size like other chunks.
+        let offset = buffer.align_offset(CHUNK_ALIGN);
+        let capacity = buffer
+            .len()
+            .checked_sub(offset + FOOTER_SIZE)
+            .map(|n| round_down_to(n, CHUNK_ALIGN));
+
+        let bump = BumpBuilder::new().build_in(buffer);
+        if let Some(capacity) = capacity {
+            let details = NewChunkMemoryDetails {
+                new_size_without_footer: capacity,
+                align: CHUNK_ALIGN,
+                size: capacity + FOOTER_SIZE,
+            };
+            unsafe {
+                let layout = Layout::from_size_align_unchecked(0, 1);
+                if let Some(footer) = bump.new_chunk(details, layout, EMPTY_CHUNK.get(), false) {
+                    bump.current_chunk_footer.set(footer);
+                }
+            }
+        }
+        bump
+    }
+}
+
+impl<A: BackingAllocator> Bump<A> {
+    /// Construct a new arena that allocates its chunks from the given
+    /// backing allocator.
+    ///
+    /// See [`BackingAllocator`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, Global};
+    ///
+    /// let bump = Bump::new_in(Global);
+    /// # let _ = bump;
+    /// ```
+    pub fn new_in(backing: A) -> Bump<A> {
+        Bump::with_capacity_in(0, backing)
+    }
+
+    /// Construct a new, empty arena that allocates its chunks from the given
+    /// backing allocator, in a `const` context.
+    pub(crate) const fn empty_in(backing: A) -> Bump<A> {
+        Bump {
+            current_chunk_footer: CurrentChunk::empty(),
+            allocation_limit: Cell::new(None),
+            limit_hook: HookSlot::new(),
+            #[cfg(feature = "trace")]
+            trace_hook: HookSlot::new(),
+            reset_policy: Cell::new(ResetPolicy::Leave),
+            chunk_policy: ChunkPolicy::DEFAULT,
+            growth_policy: Cell::new(GrowthPolicySlot(None)),
+            spare_chunks: SpareChunks::new(),
+            #[cfg(feature = "boxed")]
+            drop_list: DropList::new(),
+            backing,
+        }
+    }
+
+    /// Attempt to construct a new arena that allocates its chunks from the
+    /// given backing allocator.
+    pub fn try_new_in(backing: A) -> Result<Bump<A>, AllocErr> {
+        Bump::try_with_capacity_in(0, backing)
+    }
+
+    /// Construct a new arena with the specified byte capacity, that allocates
+    /// its chunks from the given backing allocator.
+    ///
+    /// See [`with_capacity`](Bump::with_capacity) for details on how the
+    /// capacity is interpreted.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the initial chunk fails.
+    pub fn with_capacity_in(capacity: usize, backing: A) -> Bump<A> {
+        Bump::try_with_capacity_in(capacity, backing).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempt to construct a new arena with the specified byte capacity,
+    /// that allocates its chunks from the given backing allocator.
+    ///
+    /// See [`with_capacity`](Bump::with_capacity) for details on how the
+    /// capacity is interpreted.
+    pub fn try_with_capacity_in(capacity: usize, backing: A) -> Result<Bump<A>, AllocErr> {
+        BumpBuilder::new()
+            .initial_chunk_size(capacity)
+            .try_build_in(backing)
+    }
+
+    /// Construct a new arena that allocates its chunks from the given backing
+    /// allocator, holding a compacted copy of the allocated bytes of every
+    /// chunk in `source`.
+    ///
+    /// See [`clone_from_bump`](Bump::clone_from_bump) for details.
+    ///
+    /// ## Safety
+    ///
+    /// The same as for [`clone_from_bump`](Bump::clone_from_bump).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the new arena's chunk fails.
+    pub unsafe fn clone_from_bump_in<B: BackingAllocator>(
+        source: &mut Bump<B>,
+        backing: A,
+    ) -> (Bump<A>, RelocationMap) {
+        Bump::try_clone_from_bump_in(source, backing).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempt to construct a new arena that allocates its chunks from the
+    /// given backing allocator, holding a compacted copy of the allocated
+    /// bytes of every chunk in `source`.
+    ///
+    /// See [`clone_from_bump`](Bump::clone_from_bump) for details.
+    ///
+    /// ## Safety
+    ///
+    /// The same as for [`clone_from_bump`](Bump::clone_from_bump).
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating the new arena's chunk fails.
+    pub unsafe fn try_clone_from_bump_in<B: BackingAllocator>(
+        source: &mut Bump<B>,
+        backing: A,
+    ) -> Result<(Bump<A>, RelocationMap), AllocErr> {
+        let mut regions = source.allocated_regions_oldest_first();
+        regions.retain(|r| r.len != 0);
+
+        // Keep each region congruent to its original address modulo
+        // `CHUNK_ALIGN`, so that the allocations in it stay aligned.
+        let mut size = 0_usize;
+        for region in &mut regions {
+            let padding = region.address.wrapping_sub(size) & (CHUNK_ALIGN - 1);
+            region.offset = size.checked_add(padding).ok_or(AllocErr::SIZE_OVERFLOW)?;
+            size = region
+                .offset
+                .checked_add(region.len)
+                .ok_or(AllocErr::SIZE_OVERFLOW)?;
+        }
+
+        let bump = BumpBuilder::new()
+            .initial_chunk_size(size)
+            .try_build_in(backing)?;
+        let base = if regions.is_empty() {
+            NonNull::dangling()
+        } else {
+            let layout =
+                Layout::from_size_align(size, CHUNK_ALIGN).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+            bump.try_alloc_layout(layout)?
+        };
+        for region in &regions {
+            ptr::copy_nonoverlapping(
+                region.address as *const u8,
+                base.as_ptr().add(region.offset),
+                region.len,
+            );
+        }
+
+        let map = RelocationMap {
+            base,
+            bytes: AllocatedBytesMap { regions },
+        };
+        Ok((bump, map))
+    }
+
+    /// Get a shared reference to the allocator this arena allocates its
+    /// chunks from.
+    pub fn backing_allocator(&self) -> &A {
+        &self.backing
+    }
+
+    /// The minimum alignment of every allocation in this arena, as set by
+    /// [`Bump::with_min_align`] or [`BumpBuilder::min_alloc_align`].
+    ///
+    /// This is 1 unless configured otherwise.
+    #[inline]
+    pub fn min_align(&self) -> usize {
+        self.chunk_policy.alloc_align
+    }
+
+    /// The alignment of this arena's cache-aligned allocations, as set by
+    /// [`BumpBuilder::cache_line_size`].
+    #[inline]
+    pub fn cache_line_size(&self) -> usize {
+        self.chunk_policy.cache_align
+    }
+
+    /// The largest allocation that this arena's `Allocator` implementation
+    /// rounds up to a size class, as set by
+    /// [`BumpBuilder::allocator_size_classes`], or zero if it doesn't.
+    #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+    #[inline]
+    pub fn allocator_size_classes(&self) -> usize {
+        self.chunk_policy.size_class_limit
+    }
+
+    /// Round `layout` up to its size class, for allocations through the
+    /// `Allocator` implementation.
+    ///
+    /// Because the limit is a power of two, every size between a requested
+    /// size and its rounded size maps to the same rounded size, so callers
+    /// may describe a block with any of them, as `Allocator` allows.
+    #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+    #[inline]
+    fn size_class_layout(&self, layout: Layout) -> Layout {
+        if layout.size() == 0 || layout.size() > self.chunk_policy.size_class_limit {
+            return layout;
+        }
+        Layout::from_size_align(layout.size().next_power_of_two(), layout.align()).unwrap_or(layout)
+    }
+
+    /// Pad `layout` to this arena's minimum allocation alignment.
+    ///
+    /// Returns `None` if the padded size would overflow.
+    #[inline]
+    fn padded_layout(&self, layout: Layout) -> Option<Layout> {
+        let align = self.chunk_policy.alloc_align;
+        let size = layout.size().checked_add(align - 1)? & !(align - 1);
+        Layout::from_size_align(size, layout.align().max(align)).ok()
+    }
+
+    /// The allocation limit for this arena in bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(0);
+    ///
+    /// assert_eq!(bump.allocation_limit(), None);
+    ///
+    /// bump.set_allocation_limit(Some(6));
+    ///
+    /// assert_eq!(bump.allocation_limit(), Some(6));
+    ///
+    /// bump.set_allocation_limit(None);
+    ///
+    /// assert_eq!(bump.allocation_limit(), None);
+    /// ```
+    pub fn allocation_limit(&self) -> Option<usize> {
+        self.allocation_limit.get()
+    }
+
+    /// Set the allocation limit in bytes for this arena.
+    ///
+    /// The allocation limit is only enforced when allocating new backing chunks for
+    /// a `Bump`. Updating the allocation limit will not affect existing allocations
+    /// or any future allocations within the `Bump`'s current chunk.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(0);
+    ///
+    /// bump.set_allocation_limit(Some(0));
+    ///
+    /// assert!(bump.try_alloc(5).is_err());
+    /// ```
+    pub fn set_allocation_limit(&self, limit: Option<usize>) {
+        self.allocation_limit.set(limit);
+    }
+
+    /// What this arena does with its allocated bytes when it is reset or
+    /// dropped.
+    pub fn reset_policy(&self) -> ResetPolicy {
+        self.reset_policy.get()
+    }
+
+    /// Set what this arena does with its allocated bytes when it is reset or
+    /// dropped.
+    ///
+    /// With [`ResetPolicy::Zero`], [`reset`](Bump::reset) zeroes every byte
+    /// that was allocated since the previous reset before making it available
+    /// again, and dropping the arena zeroes them before handing its chunks
+    /// back to the backing allocator. This keeps secrets, such as keys or
+    /// tokens that a parser handled, from lingering in recycled memory. The
+    /// cost is proportional to the number of bytes that were allocated.
+    ///
+    /// Only the arena's own chunks are zeroed: copies that values made
+    /// elsewhere, and stale copies left behind by resizing allocations in the
+    /// arena, which are zeroed by the next reset, are not covered until then.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, ResetPolicy};
+    ///
+    /// let mut bump = Bump::new();
+    /// bump.set_reset_policy(ResetPolicy::Zero);
+    ///
+    /// let token = bump.alloc_str("hunter2");
+    /// assert_eq!(token, "hunter2");
+    ///
+    /// // The token's bytes are zeroed here.
+    /// bump.reset();
+    /// ```
+    pub fn set_reset_policy(&self, policy: ResetPolicy) {
+        self.reset_policy.set(policy);
+    }
+
+    /// The hook that is called when an allocation would exceed this arena's
+    /// allocation limit, if any.
+    pub fn limit_hook(&self) -> Option<LimitHook<A>> {
+        self.limit_hook.hook.get()
+    }
+
+    /// Set a hook that is called when an allocation would exceed this arena's
+    /// [allocation limit](Bump::set_allocation_limit), or remove it with
+    /// `None`.
+    ///
+    /// Instead of the allocation simply failing, the hook is passed the arena
+    /// and the layout of the allocation that needs a new chunk, and decides
+    /// what to do with a [`LimitDecision`]. It may log the event, shed load,
+    /// or raise the limit with
+    /// [`set_allocation_limit`](Bump::set_allocation_limit) and ask for a
+    /// retry.
+    ///
+    /// Allocations that the hook makes in the arena itself do not invoke it
+    /// again; they are subject to the limit as usual.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, LimitDecision};
+    /// use std::alloc::Layout;
+    ///
+    /// fn double_limit(bump: &Bump, _layout: Layout) -> LimitDecision {
+    ///     match bump.allocation_limit() {
+    ///         Some(limit) if limit < 1 << 20 => {
+    ///             bump.set_allocation_limit(Some(limit.max(1) * 2));
+    ///             LimitDecision::Retry
+    ///         }
+    ///         _ => LimitDecision::Deny,
+    ///     }
+    /// }
+    ///
+    /// let bump = Bump::new();
+    /// bump.set_allocation_limit(Some(0));
+    /// bump.set_limit_hook(Some(double_limit));
+    ///
+    /// assert!(bump.try_alloc([0_u8; 1000]).is_ok());
+    /// assert!(bump.allocation_limit().unwrap() >= 1000);
+    ///
+    /// assert!(bump.try_alloc_layout(Layout::array::<u8>(1 << 21).unwrap()).is_err());
+    /// ```
+    pub fn set_limit_hook(&self, hook: Option<LimitHook<A>>) {
+        self.limit_hook.hook.set(hook);
+    }
+
+    /// The hook that is called for every allocation in this arena, if any.
+    #[cfg(feature = "trace")]
+    pub fn trace_hook(&self) -> Option<TraceHook<A>> {
+        self.trace_hook.hook.get()
+    }
+
+    /// Set a hook that is called for every allocation in this arena, or
+    /// remove it with `None`. Requires the `trace` Cargo feature.
+    ///
+    /// The hook is passed the arena and an [`AllocationEvent`] with the
+    /// allocation's pointer, layout, and chunk, e.g. to feed a heap profiler
+    /// or to attribute the arena's usage to phases of a program. Allocations
+    /// of zero-sized types, reallocations that grow or shrink an allocation
+    /// in place, and allocations in the [`SubBump`](split::SubBump)s that
+    /// [`split_remaining`](Bump::split_remaining) hands out, which may happen
+    /// on other threads, are not reported.
+    ///
+    /// Allocations that the hook makes in the arena itself do not invoke it
+    /// again.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{AllocationEvent, Bump};
+    /// use std::cell::Cell;
+    ///
+    /// thread_local! {
+    ///     static TRACED_BYTES: Cell<usize> = Cell::new(0);
+    /// }
+    ///
+    /// fn count_bytes(_bump: &Bump, event: AllocationEvent) {
+    ///     TRACED_BYTES.with(|b| b.set(b.get() + event.layout().size()));
+    /// }
+    ///
+    /// let bump = Bump::new();
+    /// bump.set_trace_hook(Some(count_bytes));
+    ///
+    /// bump.alloc(1_u64);
+    /// bump.alloc_slice_copy(&[1_u8, 2, 3]);
+    /// assert_eq!(TRACED_BYTES.with(Cell::get), 11);
+    /// ```
+    #[cfg(feature = "trace")]
+    pub fn set_trace_hook(&self, hook: Option<TraceHook<A>>) {
+        self.trace_hook.hook.set(hook);
+    }
+
+    /// The policy that sizes the chunks allocated to grow this arena, if any.
+    pub fn growth_policy(&self) -> Option<&'static dyn ChunkGrowthPolicy> {
+        self.growth_policy.get().0
+    }
+
+    /// Set the policy that sizes the chunks allocated to grow this arena, or
+    /// go back to sizing them with the arena's growth factor and maximum chunk
+    /// size with `None`.
+    ///
+    /// The policy applies to chunks allocated from now on. See
+    /// [`ChunkGrowthPolicy`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// fn capped(prev: usize, _layout: Layout) -> usize {
+    ///     (prev * 2).min(16 * 1024)
+    /// }
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_growth_policy(Some(&capped));
+    /// for _ in 0..100 {
+    ///     bump.alloc([0_u8; 1000]);
+    /// }
+    /// assert!(bump.chunk_capacity() < 16 * 1024);
+    /// ```
+    pub fn set_growth_policy(&self, policy: Option<&'static dyn ChunkGrowthPolicy>) {
+        self.growth_policy.set(GrowthPolicySlot(policy));
+    }
+
+    /// Ask the limit hook, if any, what to do about an allocation of `layout`
+    /// that would exceed the allocation limit.
+    fn run_limit_hook(&self, layout: Layout) -> LimitDecision {
+        self.limit_hook
+            .call(|hook| hook(self, layout))
+            .unwrap_or(LimitDecision::Deny)
+    }
+
+    /// Make sure that `*value` is dropped before its memory is reused or freed,
+    /// unless the returned entry is [disarmed](DropEntry::disarm) first.
+    ///
+    /// ## Safety
+    ///
+    /// `value` must be allocated in this arena and stay valid until the entry
+    /// is disarmed. Since the value may be dropped whenever the arena is reset
+    /// or dropped, possibly on another thread, `T` must be `Send` and must not
+    /// borrow anything that doesn't outlive the arena, e.g. by being
+    /// `'static`.
+    #[cfg(feature = "boxed")]
+    pub(crate) unsafe fn register_drop<T: ?Sized>(&self, value: *mut T) -> NonNull<DropEntry> {
+        let entry = self.alloc(TypedDropEntry {
+            entry: DropEntry {
+                next: self.drop_list.head.get(),
+                drop: Cell::new(Some(drop_entry_value::<T>)),
+            },
+            value,
+        });
+        let entry = NonNull::from(entry).cast::<DropEntry>();
+        self.drop_list.head.set(Some(entry));
+        entry
+    }
+
+    /// Drop the values in the drop list that haven't been dropped yet, before
+    /// their memory is reused or freed.
+    #[inline]
+    fn run_drop_list(&mut self) {
+        #[cfg(feature = "boxed")]
+        while let Some(entry) = self.drop_list.head.get() {
+            // Unlink the entry first, so that if dropping its value panics,
+            // the list still holds exactly the values that are left to drop.
+            unsafe {
+                self.drop_list.head.set(entry.as_ref().next);
+                if let Some(drop) = entry.as_ref().drop.take() {
+                    drop(entry);
+                }
+            }
+        }
+    }
+
+    /// Report an allocation in the current chunk to the trace hook, if any,
+    /// with the `trace` feature.
+    #[inline(always)]
+    fn trace_alloc(&self, ptr: NonNull<u8>, layout: Layout, is_front: bool) {
+        #[cfg(feature = "trace")]
+        if self.trace_hook.hook.get().is_some() {
+            self.run_trace_hook(ptr, layout, is_front);
+        }
+        #[cfg(not(feature = "trace"))]
+        let _ = (ptr, layout, is_front);
+    }
+
+    #[cfg(feature = "trace")]
+    #[inline(never)]
+    fn run_trace_hook(&self, ptr: NonNull<u8>, layout: Layout, is_front: bool) {
+        let chunk_id = unsafe {
+            let footer = self.current_chunk_footer.get();
+            footer.as_ref().prev.get().as_ref().allocated_bytes
+        };
+        let event = AllocationEvent {
+            ptr,
+            layout,
+            chunk_id,
+            is_front,
+        };
+        self.trace_hook.call(|hook| hook(self, event));
+    }
+
+    /// How much headroom an arena has before it hits its allocation
+    /// limit.
+    fn allocation_limit_remaining(&self) -> Option<usize> {
+        self.allocation_limit.get().and_then(|allocation_limit| {
+            let allocated_bytes = self.allocated_bytes();
+            if allocated_bytes > allocation_limit {
+                None
+            } else {
+                Some(usize::abs_diff(allocation_limit, allocated_bytes))
+            }
+        })
     }
 
     /// Whether a request to allocate a new chunk with a given size for a given
@@ -629,21 +2371,24 @@ impl Bump {
     /// Determine the memory details including final size, alignment and
     /// final size without footer for a new chunk that would be allocated
     /// to fulfill an allocation request.
+    ///
+    /// Returns `None` if the chunk's size would overflow.
     fn new_chunk_memory_details(
         new_size_without_footer: Option<usize>,
         requested_layout: Layout,
+        min_align: usize,
     ) -> Option<NewChunkMemoryDetails> {
         let mut new_size_without_footer =
             new_size_without_footer.unwrap_or(DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER);
 
         // We want to have CHUNK_ALIGN or better alignment
-        let mut align = CHUNK_ALIGN;
+        debug_assert_eq!(min_align % CHUNK_ALIGN, 0);
+        let mut align = min_align;
 
         // If we already know we need to fulfill some request,
         // make sure we allocate at least enough to satisfy it
         align = align.max(requested_layout.align());
-        let requested_size =
-            round_up_to(requested_layout.size(), align).unwrap_or_else(allocation_size_overflow);
+        let requested_size = round_up_to(requested_layout.size(), align)?;
         new_size_without_footer = new_size_without_footer.max(requested_size);
 
         // We want our allocations to play nice with the memory allocator,
@@ -658,14 +2403,12 @@ impl Bump {
                 (new_size_without_footer + OVERHEAD).next_power_of_two() - OVERHEAD;
         } else {
             new_size_without_footer =
-                round_up_to(new_size_without_footer + OVERHEAD, 0x1000)? - OVERHEAD;
+                round_up_to(new_size_without_footer.checked_add(OVERHEAD)?, 0x1000)? - OVERHEAD;
         }
 
         debug_assert_eq!(align % CHUNK_ALIGN, 0);
         debug_assert_eq!(new_size_without_footer % CHUNK_ALIGN, 0);
-        let size = new_size_without_footer
-            .checked_add(FOOTER_SIZE)
-            .unwrap_or_else(allocation_size_overflow);
+        let size = new_size_without_footer.checked_add(FOOTER_SIZE)?;
 
         Some(NewChunkMemoryDetails {
             new_size_without_footer,
@@ -679,10 +2422,15 @@ impl Bump {
     /// If given, `layouts` is a tuple of the current chunk size and the
     /// layout of the allocation request that triggered us to fall back to
     /// allocating a new chunk of memory.
+    ///
+    /// If `zeroed` is true, the chunk's memory is requested from the backing
+    /// allocator already zeroed.
     unsafe fn new_chunk(
+        &self,
         new_chunk_memory_details: NewChunkMemoryDetails,
         requested_layout: Layout,
         prev: NonNull<ChunkFooter>,
+        zeroed: bool,
     ) -> Option<NonNull<ChunkFooter>> {
         let NewChunkMemoryDetails {
             new_size_without_footer,
@@ -694,8 +2442,29 @@ impl Bump {
 
         debug_assert!(size >= requested_layout.size());
 
-        let data = alloc(layout);
-        let data = NonNull::new(data)?;
+        let data = if zeroed {
+            self.backing.allocate_zeroed(layout)
+        } else {
+            self.backing.allocate(layout)
+        };
+        let data = match data {
+            Some(data) => data,
+            None => {
+                event!(
+                    debug,
+                    "bumpalo: backing allocator failed to allocate a chunk of {} bytes",
+                    size
+                );
+                return None;
+            }
+        };
+
+        event!(
+            debug,
+            "bumpalo: allocated a new chunk of {} bytes (align {})",
+            size,
+            align
+        );
 
         // The `ChunkFooter` is at the end of the chunk.
         let footer_ptr = data.as_ptr().add(new_size_without_footer);
@@ -707,6 +2476,12 @@ impl Bump {
         // bump out of.
         let ptr = Cell::new(NonNull::new_unchecked(footer_ptr as *mut u8));
 
+        // And the front bump pointer is initialized to the start of the range.
+        let front = Cell::new(data);
+
+        // Nothing has been allocated in the chunk yet.
+        asan_poison(data.as_ptr(), new_size_without_footer);
+
         // The `allocated_bytes` of a new chunk counts the total size
         // of the chunks, not how much of the chunks are used.
         let allocated_bytes = prev.as_ref().allocated_bytes + new_size_without_footer;
@@ -718,10 +2493,15 @@ impl Bump {
                 layout,
                 prev: Cell::new(prev),
                 ptr,
+                front,
                 allocated_bytes,
             },
         );
 
+        if let Some(policy) = self.growth_policy() {
+            policy.chunk_allocated(new_size_without_footer);
+        }
+
         Some(NonNull::new_unchecked(footer_ptr))
     }
 
@@ -733,7 +2513,9 @@ impl Bump {
     /// objects; see [the top-level documentation](struct.Bump.html) for details.
     ///
     /// If this arena has allocated multiple chunks to bump allocate into, then
-    /// the excess chunks are returned to the global allocator.
+    /// the excess chunks are returned to the global allocator, unless the
+    /// arena [defers chunk frees](BumpBuilder::defer_chunk_frees), in which
+    /// case they are set aside in constant time.
     ///
     /// ## Example
     ///
@@ -759,47 +2541,268 @@ impl Bump {
     pub fn reset(&mut self) {
         // Takes `&mut self` so `self` must be unique and there can't be any
         // borrows active that would get invalidated by resetting.
-        unsafe {
-            if self.current_chunk_footer.get().as_ref().is_empty() {
-                return;
-            }
+        unsafe { self.reset_keeping_top(0) }
+    }
 
-            let mut cur_chunk = self.current_chunk_footer.get();
+    /// Reset this bump allocator, except for the last `keep` bytes of the
+    /// current chunk, which are left allocated and untouched.
+    ///
+    /// `keep` must be at most the current chunk's capacity.
+    unsafe fn reset_keeping_top(&mut self, keep: usize) {
+        self.run_drop_list();
+        if self.current_chunk_footer.get().as_ref().is_empty() {
+            return;
+        }
 
-            // Deallocate all chunks except the current one
-            let prev_chunk = cur_chunk.as_ref().prev.replace(EMPTY_CHUNK.get());
-            dealloc_chunk_list(prev_chunk);
+        let mut cur_chunk = self.current_chunk_footer.get();
+        let defer_frees = self.chunk_policy.defer_frees;
+        let zero = self.reset_policy() == ResetPolicy::Zero;
+
+        // Only walk the chunks if there is something to do with them.
+        if zero || cfg!(any(feature = "asan", feature = "debug_poison")) {
+            let mut footer = cur_chunk;
+            while !footer.as_ref().is_empty() {
+                let data = footer.as_ref().data;
+                let mut len = footer.as_ptr() as usize - data.as_ptr() as usize;
+                let mut footer_keep = 0;
+                if footer == cur_chunk {
+                    len -= keep;
+                    footer_keep = keep;
+                }
+                asan_unpoison(data.as_ptr(), len);
+                if zero {
+                    footer.as_ref().zero_used_bytes(footer_keep);
+                }
+                poison_freed(data.as_ptr(), len);
+                if defer_frees && footer != cur_chunk {
+                    asan_poison(data.as_ptr(), len);
+                }
+                footer = footer.as_ref().prev.get();
+            }
+        }
 
-            // Reset the bump finger to the end of the chunk.
-            cur_chunk.as_ref().ptr.set(cur_chunk.cast());
+        // Deallocate, or set aside, all chunks except the current one.
+        let prev_chunk = cur_chunk.as_ref().prev.replace(EMPTY_CHUNK.get());
+        if defer_frees {
+            self.spare_chunks.push_chain(prev_chunk);
+        } else {
+            dealloc_chunk_list(prev_chunk, &self.backing);
+        }
 
-            // Reset the allocated size of the chunk.
-            cur_chunk.as_mut().allocated_bytes = cur_chunk.as_ref().layout.size();
+        event!(
+            trace,
+            "bumpalo: reset arena, retaining a chunk of {} bytes",
+            cur_chunk.as_ref().layout.size()
+        );
 
-            debug_assert!(
-                self.current_chunk_footer
-                    .get()
-                    .as_ref()
-                    .prev
-                    .get()
-                    .as_ref()
-                    .is_empty(),
-                "We should only have a single chunk"
-            );
-            debug_assert_eq!(
-                self.current_chunk_footer.get().as_ref().ptr.get(),
-                self.current_chunk_footer.get().cast(),
-                "Our chunk's bump finger should be reset to the start of its allocation"
-            );
-        }
+        // Reset the bump finger to the end of the chunk, below the kept
+        // bytes, and the front bump finger to the start of the chunk.
+        let end = cur_chunk.cast::<u8>().as_ptr().sub(keep);
+        cur_chunk.as_ref().ptr.set(NonNull::new_unchecked(end));
+        cur_chunk.as_ref().front.set(cur_chunk.as_ref().data);
+
+        // Let the backing allocator release the memory of the now unused
+        // part of the chunk.
+        let data = cur_chunk.as_ref().data;
+        let len = end as usize - data.as_ptr() as usize;
+        self.backing.discard(data, len);
+        asan_poison(data.as_ptr(), len);
+
+        // Reset the allocated size of the chunk.
+        cur_chunk.as_mut().allocated_bytes = cur_chunk.as_ref().layout.size() - FOOTER_SIZE;
+
+        debug_assert!(
+            self.current_chunk_footer
+                .get()
+                .as_ref()
+                .prev
+                .get()
+                .as_ref()
+                .is_empty(),
+            "We should only have a single chunk"
+        );
+        debug_assert_eq!(
+            self.current_chunk_footer.get().as_ref().ptr.get().as_ptr(),
+            end,
+            "Our chunk's bump finger should be reset to the start of its allocation"
+        );
     }
 
-    /// Allocate an object in this `Bump` and return an exclusive reference to
-    /// it.
+    /// Run a phase of allocations, then reset this bump allocator while
+    /// keeping the phase's result.
     ///
-    /// ## Panics
+    /// `phase` allocates whatever it needs in the arena and returns a
+    /// reference to its result, which is usually the most recent allocation.
+    /// Everything else that was allocated in the arena, during the phase and
+    /// before it, is reset just like with [`reset`](Bump::reset), and the
+    /// result is moved to the very end of the chunk that the arena keeps, so
+    /// that it does not get in the way of later allocations.
     ///
-    /// Panics if reserving space for `T` fails.
+    /// If the result is the most recent allocation, it is moved within the
+    /// arena, without copying it anywhere else. Otherwise it is moved out of
+    /// the arena and allocated again after the reset.
+    ///
+    /// The result is returned in a [`Detached`] guard, which borrows the arena
+    /// mutably. When the guard is dropped, it drops the result and resets the
+    /// arena again, so that the result's memory can be reused. Use
+    /// [`Detached::leak`] to keep the result allocated until the arena's next
+    /// reset instead, or [`Detached::into_inner`] to move it out of the arena.
+    /// As with other allocations, the `Drop` implementations of the values
+    /// that are reset are never run.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the result is not the most recent allocation and allocating
+    /// it again after the reset fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    ///
+    /// use bumpalo::Detached;
+    ///
+    /// let sum = bump.detach_last_allocation(|bump| {
+    ///     // Lots of scratch allocations...
+    ///     let squares = bump.alloc_slice_fill_with(10_000, |i| (i * i) as u64);
+    ///     // ...and one small result.
+    ///     bump.alloc(squares.iter().sum::<u64>())
+    /// });
+    /// assert_eq!(*sum, 333_283_335_000);
+    ///
+    /// // Only the result is still allocated.
+    /// let used: usize = unsafe { Detached::bump(&sum).iter_allocated_chunks_raw() }
+    ///     .map(|(_, len)| len)
+    ///     .sum();
+    /// assert_eq!(used, 8);
+    ///
+    /// // Dropping the guard gives the result's memory back.
+    /// drop(sum);
+    /// let used: usize = unsafe { bump.iter_allocated_chunks_raw() }.map(|(_, len)| len).sum();
+    /// assert_eq!(used, 0);
+    /// ```
+    pub fn detach_last_allocation<T, F>(&mut self, phase: F) -> Detached<'_, T, A>
+    where
+        F: for<'b> FnOnce(&'b Bump<A>) -> &'b mut T,
+    {
+        let value = NonNull::from(phase(self));
+        // Drop any leaked pinned values before moving `value` over them.
+        self.run_drop_list();
+        unsafe {
+            if mem::size_of::<T>() == 0 {
+                self.reset();
+                return Detached::new(self, value);
+            }
+
+            if !self.is_last_allocation(value.cast()) {
+                let value = ptr::read(value.as_ptr());
+                self.reset();
+                let value = NonNull::from(self.alloc(value));
+                return Detached::new(self, value);
+            }
+
+            // The value is in the current chunk, so it fits at its end. Move
+            // it there before anything else is poisoned or discarded.
+            let layout = self
+                .padded_layout(Layout::new::<T>())
+                .unwrap_or_else(allocation_size_overflow);
+            let end = self.current_chunk_footer.get().cast::<u8>().as_ptr();
+            let new_ptr = round_mut_ptr_down_to(end.sub(layout.size()), layout.align());
+            debug_assert!(value.as_ptr() as *mut u8 <= new_ptr);
+            ptr::copy(value.as_ptr(), new_ptr as *mut T, 1);
+
+            self.reset_keeping_top(end as usize - new_ptr as usize);
+            Detached::new(self, NonNull::new_unchecked(new_ptr as *mut T))
+        }
+    }
+
+    /// Reset this bump allocator, like [`reset`](Bump::reset), while keeping
+    /// at most `bytes` of chunk capacity around.
+    ///
+    /// This is meant for frame allocators that are reset once per frame: a
+    /// single spiky frame grows the arena, but the arena shouldn't hold on to
+    /// all of that memory forever.
+    ///
+    /// If the chunk that `reset` would keep has a capacity of at most `bytes`,
+    /// this is the same as `reset`. Otherwise every chunk is freed, and they
+    /// are replaced with a single new chunk with a capacity of at most `bytes`,
+    /// so that the next frame's allocations land in one appropriately-sized
+    /// chunk, rather than in a series of small chunks that grow again from
+    /// the default size. If allocating that chunk fails, or would exceed the
+    /// allocation limit, the arena is left without any chunks, just like a
+    /// new arena.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    ///
+    /// // A spiky frame.
+    /// bump.alloc_slice_fill_copy(1 << 20, 0_u8);
+    /// bump.reset_and_shrink_to(64 * 1024);
+    /// assert!(bump.chunk_capacity() <= 64 * 1024);
+    ///
+    /// // A regular frame fits in the kept chunk.
+    /// let capacity = bump.chunk_capacity();
+    /// bump.alloc_slice_fill_copy(1000, 0_u8);
+    /// assert_eq!(bump.chunk_capacity(), capacity - 1000);
+    /// ```
+    pub fn reset_and_shrink_to(&mut self, bytes: usize) {
+        let kept_capacity = unsafe {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            if footer.is_empty() {
+                0
+            } else {
+                footer.layout.size() - FOOTER_SIZE
+            }
+        };
+
+        self.reset();
+        self.release_spare_chunks();
+        if kept_capacity <= bytes {
+            return;
+        }
+
+        unsafe {
+            // `reset` left a single chunk, which is too big to keep.
+            let footer = self.current_chunk_footer.replace(EMPTY_CHUNK.get());
+            dealloc_chunk_list(footer, &self.backing);
+
+            // Coalesce into a single chunk with the largest allocator-friendly
+            // capacity that is at most `bytes`.
+            let layout = Layout::from_size_align_unchecked(0, 1);
+            let mut size = bytes;
+            while size > 0 {
+                let details = match Self::new_chunk_memory_details(
+                    Some(size),
+                    layout,
+                    self.chunk_policy.chunk_align,
+                ) {
+                    Some(details) => details,
+                    None => return,
+                };
+                if details.new_size_without_footer <= bytes {
+                    if Self::chunk_fits_under_limit(self.allocation_limit_remaining(), details) {
+                        if let Some(footer) =
+                            self.new_chunk(details, layout, EMPTY_CHUNK.get(), false)
+                        {
+                            self.current_chunk_footer.set(footer);
+                        }
+                    }
+                    return;
+                }
+                size /= 2;
+            }
+        }
+    }
+
+    /// Allocate an object in this `Bump` and return an exclusive reference to
+    /// it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
     ///
     /// ## Example
     ///
@@ -832,6 +2835,96 @@ impl Bump {
         self.try_alloc_with(|| val)
     }
 
+    /// Allocate space for an object in this `Bump`, without initializing it,
+    /// and return an exclusive reference to it.
+    ///
+    /// The object can be initialized later, with [`MaybeUninit::write`], for
+    /// example.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_uninit::<u64>();
+    /// let x: &mut u64 = x.write(42);
+    /// assert_eq!(*x, 42);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_uninit<T>(&self) -> &mut mem::MaybeUninit<T> {
+        self.try_alloc_uninit().unwrap_or_else(|_| oom())
+    }
+
+    /// Try to allocate space for an object in this `Bump`, without
+    /// initializing it, and return an exclusive reference to it.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_uninit::<u64>().unwrap();
+    /// assert_eq!(*x.write(42), 42);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_uninit<T>(&self) -> Result<&mut mem::MaybeUninit<T>, AllocErr> {
+        let dst = self.try_alloc_layout_for::<mem::MaybeUninit<T>>(Layout::new::<T>())?;
+        unsafe { Ok(&mut *dst.as_ptr()) }
+    }
+
+    /// Allocate space for a slice of `len` objects in this `Bump`, without
+    /// initializing them, and return an exclusive reference to it.
+    ///
+    /// The slice can be initialized later, element by element, or all at once
+    /// with the functions in the [`uninit`] module.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let xs = bump.alloc_uninit_slice::<u32>(3);
+    /// let xs: &mut [u32] = bumpalo::uninit::write_slice_copy(xs, &[1, 2, 3]);
+    /// assert_eq!(xs, [1, 2, 3]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_uninit_slice<T>(&self, len: usize) -> &mut [mem::MaybeUninit<T>] {
+        self.try_alloc_uninit_slice(len).unwrap_or_else(|_| oom())
+    }
+
+    /// Try to allocate space for a slice of `len` objects in this `Bump`,
+    /// without initializing them, and return an exclusive reference to it.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let xs = bump.try_alloc_uninit_slice::<u32>(3).unwrap();
+    /// assert_eq!(xs.len(), 3);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_uninit_slice<T>(
+        &self,
+        len: usize,
+    ) -> Result<&mut [mem::MaybeUninit<T>], AllocErr> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+        let dst = self.try_alloc_layout_for::<mem::MaybeUninit<T>>(layout)?;
+        unsafe { Ok(slice::from_raw_parts_mut(dst.as_ptr(), len)) }
+    }
+
     /// Pre-allocate space for an object in this `Bump`, initializes it using
     /// the closure, then returns an exclusive reference to it.
     ///
@@ -877,8 +2970,10 @@ impl Bump {
         let layout = Layout::new::<T>();
 
         unsafe {
-            let p = self.alloc_layout(layout);
-            let p = p.as_ptr() as *mut T;
+            let p = self
+                .try_alloc_layout_for::<T>(layout)
+                .unwrap_or_else(|_| oom());
+            let p = p.as_ptr();
             inner_writer(p, f);
             &mut *p
         }
@@ -929,8 +3024,8 @@ impl Bump {
         //SAFETY: Self-contained:
         // `p` is allocated for `T` and then a `T` is written.
         let layout = Layout::new::<T>();
-        let p = self.try_alloc_layout(layout)?;
-        let p = p.as_ptr() as *mut T;
+        let p = self.try_alloc_layout_for::<T>(layout)?;
+        let p = p.as_ptr();
 
         unsafe {
             inner_writer(p, f);
@@ -998,39 +3093,8 @@ impl Bump {
             }),
             Err(e) => unsafe {
                 // If this result was the last allocation in this arena, we can
-                // reclaim its space. In fact, sometimes we can do even better
-                // than simply calling `dealloc` on the result pointer: we can
-                // reclaim any alignment padding we might have added (which
-                // `dealloc` cannot do) if we didn't allocate a new chunk for
-                // this result.
-                if self.is_last_allocation(inner_result_ptr.cast()) {
-                    let current_footer_p = self.current_chunk_footer.get();
-                    let current_ptr = &current_footer_p.as_ref().ptr;
-                    if current_footer_p == rewind_footer {
-                        // It's still the same chunk, so reset the bump pointer
-                        // to its original value upon entry to this method
-                        // (reclaiming any alignment padding we may have
-                        // added).
-                        current_ptr.set(rewind_ptr);
-                    } else {
-                        // We allocated a new chunk for this result.
-                        //
-                        // We know the result is the only allocation in this
-                        // chunk: Any additional allocations since the start of
-                        // this method could only have happened when running
-                        // the initializer function, which is called *after*
-                        // reserving space for this result. Therefore, since we
-                        // already determined via the check above that this
-                        // result was the last allocation, there must not have
-                        // been any other allocations, and this result is the
-                        // only allocation in this chunk.
-                        //
-                        // Because this is the only allocation in this chunk,
-                        // we can reset the chunk's bump finger to the start of
-                        // the chunk.
-                        current_ptr.set(current_footer_p.as_ref().data);
-                    }
-                }
+                // reclaim its space.
+                self.rewind_failed_init(inner_result_ptr.cast(), rewind_footer, rewind_ptr);
                 //SAFETY:
                 // As we received `E` semantically by value from `f`, we can
                 // just copy that value here as long as we avoid a double-drop
@@ -1106,39 +3170,8 @@ impl Bump {
             }),
             Err(e) => unsafe {
                 // If this result was the last allocation in this arena, we can
-                // reclaim its space. In fact, sometimes we can do even better
-                // than simply calling `dealloc` on the result pointer: we can
-                // reclaim any alignment padding we might have added (which
-                // `dealloc` cannot do) if we didn't allocate a new chunk for
-                // this result.
-                if self.is_last_allocation(inner_result_ptr.cast()) {
-                    let current_footer_p = self.current_chunk_footer.get();
-                    let current_ptr = &current_footer_p.as_ref().ptr;
-                    if current_footer_p == rewind_footer {
-                        // It's still the same chunk, so reset the bump pointer
-                        // to its original value upon entry to this method
-                        // (reclaiming any alignment padding we may have
-                        // added).
-                        current_ptr.set(rewind_ptr);
-                    } else {
-                        // We allocated a new chunk for this result.
-                        //
-                        // We know the result is the only allocation in this
-                        // chunk: Any additional allocations since the start of
-                        // this method could only have happened when running
-                        // the initializer function, which is called *after*
-                        // reserving space for this result. Therefore, since we
-                        // already determined via the check above that this
-                        // result was the last allocation, there must not have
-                        // been any other allocations, and this result is the
-                        // only allocation in this chunk.
-                        //
-                        // Because this is the only allocation in this chunk,
-                        // we can reset the chunk's bump finger to the start of
-                        // the chunk.
-                        current_ptr.set(current_footer_p.as_ref().data);
-                    }
-                }
+                // reclaim its space.
+                self.rewind_failed_init(inner_result_ptr.cast(), rewind_footer, rewind_ptr);
                 //SAFETY:
                 // As we received `E` semantically by value from `f`, we can
                 // just copy that value here as long as we avoid a double-drop
@@ -1152,138 +3185,178 @@ impl Bump {
         }
     }
 
-    /// `Copy` a slice into this `Bump` and return an exclusive reference to
-    /// the copy.
+    /// Allocate space for an object with the given `Layout`, and initialize
+    /// it in place with the closure, which may fail.
+    ///
+    /// The closure is given a pointer to the uninitialized allocation. If it
+    /// returns [`Err`], an allocator rewind is *attempted*, as for the
+    /// [`_try_with`](#fallible-initialization-the-_try_with-method-suffix)
+    /// methods, and the error is returned. Unlike those methods, the value is
+    /// written directly into the arena by the closure rather than returned by
+    /// value from it, and no space is used for a [`Result`].
+    ///
+    /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+    /// [`Err`]: https://doc.rust-lang.org/std/result/enum.Result.html#variant.Err
+    ///
+    /// ## Errors
+    ///
+    /// Iff the allocation succeeds but `f` fails, that error is forwarded by
+    /// value.
     ///
     /// ## Panics
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Panics if reserving space matching `layout` fails.
     ///
     /// ## Example
     ///
     /// ```
+    /// use std::alloc::Layout;
+    ///
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_slice_copy(&[1, 2, 3]);
-    /// assert_eq!(x, &[1, 2, 3]);
+    /// let layout = Layout::new::<[u32; 4]>();
+    ///
+    /// let p = bump.alloc_init(layout, |p| {
+    ///     let p = p.cast::<u32>().as_ptr();
+    ///     for i in 0..4 {
+    ///         unsafe { p.add(i).write(i as u32) };
+    ///     }
+    ///     Ok::<_, ()>(())
+    /// })?;
+    /// let xs = unsafe { p.cast::<[u32; 4]>().as_ref() };
+    /// assert_eq!(xs, &[0, 1, 2, 3]);
+    ///
+    /// let r = bump.alloc_init(layout, |_| Err("not today"));
+    /// assert_eq!(r, Err("not today"));
+    /// # Result::<_, ()>::Ok(())
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_copy<T>(&self, src: &[T]) -> &mut [T]
+    pub fn alloc_init<F, E>(&self, layout: Layout, f: F) -> Result<NonNull<u8>, E>
     where
-        T: Copy,
+        F: FnOnce(NonNull<u8>) -> Result<(), E>,
     {
-        let layout = Layout::for_value(src);
-        let dst = self.alloc_layout(layout).cast::<T>();
-
-        unsafe {
-            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
-            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+        match self.try_alloc_init(layout, f) {
+            Ok(p) => Ok(p),
+            Err(AllocOrInitError::Alloc(_)) => oom(),
+            Err(AllocOrInitError::Init(e)) => Err(e),
         }
     }
 
-    /// `Clone` a slice into this `Bump` and return an exclusive reference to
-    /// the clone. Prefer [`alloc_slice_copy`](#method.alloc_slice_copy) if `T` is `Copy`.
+    /// Attempt to allocate space for an object with the given `Layout`, and
+    /// initialize it in place with the closure, which may fail.
     ///
-    /// ## Panics
+    /// Iff the allocation fails, the closure is not run. See
+    /// [`alloc_init`](Bump::alloc_init) for details.
     ///
-    /// Panics if reserving space for the slice fails.
+    /// ## Errors
+    ///
+    /// Errors with the [`Alloc`](`AllocOrInitError::Alloc`) variant iff
+    /// reserving space matching `layout` fails.
+    ///
+    /// Iff the allocation succeeds but `f` fails, that error is forwarded by
+    /// value inside the [`Init`](`AllocOrInitError::Init`) variant.
     ///
     /// ## Example
     ///
     /// ```
-    /// #[derive(Clone, Debug, Eq, PartialEq)]
-    /// struct Sheep {
-    ///     name: String,
-    /// }
-    ///
-    /// let originals = [
-    ///     Sheep { name: "Alice".into() },
-    ///     Sheep { name: "Bob".into() },
-    ///     Sheep { name: "Cathy".into() },
-    /// ];
+    /// use std::alloc::Layout;
     ///
     /// let bump = bumpalo::Bump::new();
-    /// let clones = bump.alloc_slice_clone(&originals);
-    /// assert_eq!(originals, clones);
+    /// let p = bump.try_alloc_init(Layout::new::<u64>(), |p| {
+    ///     unsafe { p.cast::<u64>().as_ptr().write(42) };
+    ///     Ok::<_, ()>(())
+    /// })?;
+    /// assert_eq!(unsafe { *p.cast::<u64>().as_ptr() }, 42);
+    /// # Result::<_, bumpalo::AllocOrInitError<()>>::Ok(())
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_clone<T>(&self, src: &[T]) -> &mut [T]
+    pub fn try_alloc_init<F, E>(
+        &self,
+        layout: Layout,
+        f: F,
+    ) -> Result<NonNull<u8>, AllocOrInitError<E>>
     where
-        T: Clone,
+        F: FnOnce(NonNull<u8>) -> Result<(), E>,
     {
-        let layout = Layout::for_value(src);
-        let dst = self.alloc_layout(layout).cast::<T>();
-
-        unsafe {
-            for (i, val) in src.iter().cloned().enumerate() {
-                ptr::write(dst.as_ptr().add(i), val);
+        let rewind_footer = self.current_chunk_footer.get();
+        let rewind_ptr = unsafe { rewind_footer.as_ref() }.ptr.get();
+        let p = self.try_alloc_layout(layout)?;
+        match f(p) {
+            Ok(()) => Ok(p),
+            Err(e) => {
+                unsafe {
+                    poison_freed(p.as_ptr(), layout.size());
+                    asan_poison(p.as_ptr(), layout.size());
+                    self.rewind_failed_init(p, rewind_footer, rewind_ptr);
+                }
+                Err(AllocOrInitError::Init(e))
             }
-
-            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
         }
     }
 
-    /// `Copy` a string slice into this `Bump` and return an exclusive reference to it.
+    /// `Copy` a slice into this `Bump` and return an exclusive reference to
+    /// the copy.
     ///
     /// ## Panics
     ///
-    /// Panics if reserving space for the string fails.
+    /// Panics if reserving space for the slice fails.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let hello = bump.alloc_str("hello world");
-    /// assert_eq!("hello world", hello);
+    /// let x = bump.alloc_slice_copy(&[1, 2, 3]);
+    /// assert_eq!(x, &[1, 2, 3]);
     /// ```
     #[inline(always)]
-    pub fn alloc_str(&self, src: &str) -> &mut str {
-        let buffer = self.alloc_slice_copy(src.as_bytes());
-        unsafe {
-            // This is OK, because it already came in as str, so it is guaranteed to be utf8
-            str::from_utf8_unchecked_mut(buffer)
-        }
+    pub fn alloc_slice_copy<T>(&self, src: &[T]) -> &mut [T]
+    where
+        T: Copy,
+    {
+        self.try_alloc_slice_copy(src).unwrap_or_else(|_| oom())
     }
 
-    /// Allocates a new slice of size `len` into this `Bump` and returns an
-    /// exclusive reference to the copy.
-    ///
-    /// The elements of the slice are initialized using the supplied closure.
-    /// The closure argument is the position in the slice.
+    /// Try to `Copy` a slice into this `Bump` and return an exclusive
+    /// reference to the copy.
     ///
-    /// ## Panics
+    /// ## Errors
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Errors if reserving space for the slice fails.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_slice_fill_with(5, |i| 5 * (i + 1));
-    /// assert_eq!(x, &[5, 10, 15, 20, 25]);
+    /// let x = bump.try_alloc_slice_copy(&[1, 2, 3]);
+    /// assert_eq!(x, Ok(&mut [1, 2, 3][..]));
+    ///
+    /// let limited = bumpalo::Bump::new();
+    /// limited.set_allocation_limit(Some(0));
+    /// assert!(limited.try_alloc_slice_copy(&[1, 2, 3]).is_err());
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_fill_with<T, F>(&self, len: usize, mut f: F) -> &mut [T]
+    pub fn try_alloc_slice_copy<T>(&self, src: &[T]) -> Result<&mut [T], AllocErr>
     where
-        F: FnMut(usize) -> T,
+        T: Copy,
     {
-        let layout = Layout::array::<T>(len).unwrap_or_else(|_| oom());
-        let dst = self.alloc_layout(layout).cast::<T>();
+        let layout = Layout::for_value(src);
+        let dst = match self.try_alloc_bulk_copy_dst(layout) {
+            Some(dst) => dst.cast::<T>(),
+            None => self.try_alloc_layout_for::<T>(layout)?,
+        };
 
         unsafe {
-            for i in 0..len {
-                ptr::write(dst.as_ptr().add(i), f(i));
-            }
-
-            let result = slice::from_raw_parts_mut(dst.as_ptr(), len);
-            debug_assert_eq!(Layout::for_value(result), layout);
-            result
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            Ok(slice::from_raw_parts_mut(dst.as_ptr(), src.len()))
         }
     }
 
-    /// Allocates a new slice of size `len` into this `Bump` and returns an
-    /// exclusive reference to the copy.
+    /// `Copy` a slice into this `Bump`, followed by room for `extra` more
+    /// objects in the same allocation, and return exclusive references to the
+    /// copy and to the uninitialized room behind it.
     ///
-    /// All elements of the slice are initialized to `value`.
+    /// This lets a copied slice be extended in place, without allocating
+    /// again: the tail directly follows the copy in memory, and can be
+    /// initialized with the functions in the [`uninit`] module.
     ///
     /// ## Panics
     ///
@@ -1293,616 +3366,4064 @@ impl Bump {
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_slice_fill_copy(5, 42);
-    /// assert_eq!(x, &[42, 42, 42, 42, 42]);
+    /// let (head, tail) = bump.alloc_slice_copy_uninit_tail(&[1, 2, 3], 2);
+    /// assert_eq!(head, [1, 2, 3]);
+    /// assert_eq!(tail.len(), 2);
+    ///
+    /// let tail = bumpalo::uninit::write_slice_copy(tail, &[4, 5]);
+    /// assert_eq!(tail, [4, 5]);
+    /// assert_eq!(head.as_ptr().wrapping_add(3), tail.as_ptr());
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_fill_copy<T: Copy>(&self, len: usize, value: T) -> &mut [T] {
-        self.alloc_slice_fill_with(len, |_| value)
+    pub fn alloc_slice_copy_uninit_tail<T>(
+        &self,
+        src: &[T],
+        extra: usize,
+    ) -> (&mut [T], &mut [mem::MaybeUninit<T>])
+    where
+        T: Copy,
+    {
+        self.try_alloc_slice_copy_uninit_tail(src, extra)
+            .unwrap_or_else(|_| oom())
     }
 
-    /// Allocates a new slice of size `len` slice into this `Bump` and return an
-    /// exclusive reference to the copy.
-    ///
-    /// All elements of the slice are initialized to `value.clone()`.
+    /// Try to `Copy` a slice into this `Bump`, followed by room for `extra`
+    /// more objects in the same allocation, and return exclusive references
+    /// to the copy and to the uninitialized room behind it.
     ///
-    /// ## Panics
+    /// ## Errors
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Errors if reserving space for the slice fails.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let s: String = "Hello Bump!".to_string();
-    /// let x: &[String] = bump.alloc_slice_fill_clone(2, &s);
-    /// assert_eq!(x.len(), 2);
-    /// assert_eq!(&x[0], &s);
-    /// assert_eq!(&x[1], &s);
+    /// let (head, tail) = bump.try_alloc_slice_copy_uninit_tail(&[1, 2], 1).unwrap();
+    /// assert_eq!((head.len(), tail.len()), (2, 1));
+    ///
+    /// assert!(bump.try_alloc_slice_copy_uninit_tail(&[1_u8], usize::MAX).is_err());
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_fill_clone<T: Clone>(&self, len: usize, value: &T) -> &mut [T] {
-        self.alloc_slice_fill_with(len, |_| value.clone())
+    pub fn try_alloc_slice_copy_uninit_tail<T>(
+        &self,
+        src: &[T],
+        extra: usize,
+    ) -> Result<(&mut [T], &mut [mem::MaybeUninit<T>]), AllocErr>
+    where
+        T: Copy,
+    {
+        let len = src
+            .len()
+            .checked_add(extra)
+            .ok_or(AllocErr::SIZE_OVERFLOW)?;
+        let layout = Layout::array::<T>(len).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+        let dst = self.try_alloc_layout_for::<T>(layout)?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            let tail = dst.as_ptr().add(src.len()) as *mut mem::MaybeUninit<T>;
+            Ok((
+                slice::from_raw_parts_mut(dst.as_ptr(), src.len()),
+                slice::from_raw_parts_mut(tail, extra),
+            ))
+        }
     }
 
-    /// Allocates a new slice of size `len` slice into this `Bump` and return an
-    /// exclusive reference to the copy.
-    ///
-    /// The elements are initialized using the supplied iterator.
+    /// Try to allocate the destination of a large bulk copy of `layout`
+    /// aligned to a cache line, within the current chunk.
     ///
-    /// ## Panics
+    /// `memcpy` is measurably faster for multi-kilobyte copies when its
+    /// destination is cache line aligned, since none of its stores then
+    /// straddle two lines. The padding that this costs is at most a cache
+    /// line, which is small next to the copy itself. Returns `None` for small
+    /// copies, and when the current chunk doesn't have room for the padding,
+    /// so that this never allocates a new chunk that a naturally aligned
+    /// allocation wouldn't have needed.
+    #[inline]
+    fn try_alloc_bulk_copy_dst(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if layout.size() < BULK_COPY_THRESHOLD || layout.align() >= CACHE_LINE_SIZE {
+            return None;
+        }
+        let aligned = Layout::from_size_align(layout.size(), CACHE_LINE_SIZE).ok()?;
+        self.alloc_layout_in_current_chunk(aligned)
+    }
+
+    /// `Clone` a slice into this `Bump` and return an exclusive reference to
+    /// the clone. Prefer [`alloc_slice_copy`](#method.alloc_slice_copy) if `T` is `Copy`.
     ///
-    /// Panics if reserving space for the slice fails, or if the supplied
-    /// iterator returns fewer elements than it promised.
+    /// If cloning any element panics, the elements that were already cloned
+    /// are dropped before the panic propagates.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails, or if cloning any
+    /// element panics.
     ///
     /// ## Example
     ///
     /// ```
+    /// #[derive(Clone, Debug, Eq, PartialEq)]
+    /// struct Sheep {
+    ///     name: String,
+    /// }
+    ///
+    /// let originals = [
+    ///     Sheep { name: "Alice".into() },
+    ///     Sheep { name: "Bob".into() },
+    ///     Sheep { name: "Cathy".into() },
+    /// ];
+    ///
     /// let bump = bumpalo::Bump::new();
-    /// let x: &[i32] = bump.alloc_slice_fill_iter([2, 3, 5].iter().cloned().map(|i| i * i));
-    /// assert_eq!(x, [4, 9, 25]);
+    /// let clones = bump.alloc_slice_clone(&originals);
+    /// assert_eq!(originals, clones);
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_fill_iter<T, I>(&self, iter: I) -> &mut [T]
+    pub fn alloc_slice_clone<T>(&self, src: &[T]) -> &mut [T]
     where
-        I: IntoIterator<Item = T>,
-        I::IntoIter: ExactSizeIterator,
+        T: Clone,
     {
-        let mut iter = iter.into_iter();
-        self.alloc_slice_fill_with(iter.len(), |_| {
-            iter.next().expect("Iterator supplied too few elements")
-        })
+        let mut iter = src.iter();
+        self.alloc_slice_fill_with(src.len(), |_| iter.next().unwrap().clone())
     }
 
-    /// Allocates a new slice of size `len` slice into this `Bump` and return an
-    /// exclusive reference to the copy.
-    ///
-    /// All elements of the slice are initialized to [`T::default()`].
+    /// Try to `Clone` a slice into this `Bump` and return an exclusive
+    /// reference to the clone. Prefer
+    /// [`try_alloc_slice_copy`](#method.try_alloc_slice_copy) if `T` is
+    /// `Copy`.
     ///
-    /// [`T::default()`]: https://doc.rust-lang.org/std/default/trait.Default.html#tymethod.default
+    /// If cloning any element panics, the elements that were already cloned
+    /// are dropped before the panic propagates.
     ///
-    /// ## Panics
+    /// ## Errors
     ///
-    /// Panics if reserving space for the slice fails.
+    /// Errors if reserving space for the slice fails. Nothing is cloned in
+    /// that case.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let x = bump.alloc_slice_fill_default::<u32>(5);
-    /// assert_eq!(x, &[0, 0, 0, 0, 0]);
+    /// let names = [String::from("Alice"), String::from("Bob")];
+    /// let clones = bump.try_alloc_slice_clone(&names).unwrap();
+    /// assert_eq!(clones, names);
     /// ```
     #[inline(always)]
-    pub fn alloc_slice_fill_default<T: Default>(&self, len: usize) -> &mut [T] {
-        self.alloc_slice_fill_with(len, |_| T::default())
+    pub fn try_alloc_slice_clone<T>(&self, src: &[T]) -> Result<&mut [T], AllocErr>
+    where
+        T: Clone,
+    {
+        let mut iter = src.iter();
+        self.try_alloc_slice_fill_with(src.len(), |_| iter.next().unwrap().clone())
     }
 
-    /// Allocate space for an object with the given `Layout`.
+    /// Allocate an object in this `Bump` on cache lines of its own, and return
+    /// an exclusive reference to it.
     ///
-    /// The returned pointer points at uninitialized memory, and should be
-    /// initialized with
-    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    /// The object is aligned to the arena's
+    /// [cache line size](Bump::cache_line_size), and its allocation is padded
+    /// to a multiple of it, so no other allocation in the arena shares a cache
+    /// line with it. This avoids false sharing when objects that are written
+    /// by different threads, e.g. per-thread counters handed to scoped
+    /// threads, are allocated next to each other.
     ///
-    /// # Panics
+    /// ## Panics
     ///
-    /// Panics if reserving space matching `layout` fails.
-    #[inline(always)]
-    pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
-        self.try_alloc_layout(layout).unwrap_or_else(|_| oom())
+    /// Panics if reserving space for the object fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let counters: Vec<&AtomicU64> = (0..4)
+    ///     .map(|_| &*bump.alloc_cache_aligned(AtomicU64::new(0)))
+    ///     .collect();
+    ///
+    /// std::thread::scope(|s| {
+    ///     for counter in &counters {
+    ///         s.spawn(move || {
+    ///             for _ in 0..1000 {
+    ///                 counter.fetch_add(1, Ordering::Relaxed);
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    /// assert!(counters.iter().all(|c| c.load(Ordering::Relaxed) == 1000));
+    /// ```
+    #[inline]
+    pub fn alloc_cache_aligned<T>(&self, val: T) -> &mut T {
+        let layout = self.cache_aligned_layout(Layout::new::<T>());
+        unsafe {
+            let p = self.alloc_layout(layout).cast::<T>();
+            ptr::write(p.as_ptr(), val);
+            &mut *p.as_ptr()
+        }
     }
 
-    /// Attempts to allocate space for an object with the given `Layout` or else returns
-    /// an `Err`.
+    /// `Copy` a slice into this `Bump` on cache lines of its own, and return
+    /// an exclusive reference to the copy.
     ///
-    /// The returned pointer points at uninitialized memory, and should be
-    /// initialized with
-    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    /// Like [`alloc_cache_aligned`](Bump::alloc_cache_aligned), the copy is
+    /// aligned to the arena's cache line size, and padded to a multiple of
+    /// it. The elements themselves are not padded, so they may share cache
+    /// lines with each other, but not with other allocations.
     ///
-    /// # Errors
+    /// ## Panics
     ///
-    /// Errors if reserving space matching `layout` fails.
-    #[inline(always)]
-    pub fn try_alloc_layout(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
-        if let Some(p) = self.try_alloc_layout_fast(layout) {
-            Ok(p)
-        } else {
-            self.alloc_layout_slow(layout).ok_or(AllocErr)
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_cache_aligned(&[1_u32, 2, 3]);
+    /// assert_eq!(x, [1, 2, 3]);
+    /// assert_eq!(x.as_ptr() as usize % bump.cache_line_size(), 0);
+    /// ```
+    #[inline]
+    pub fn alloc_slice_cache_aligned<T>(&self, src: &[T]) -> &mut [T]
+    where
+        T: Copy,
+    {
+        let layout = self.cache_aligned_layout(Layout::for_value(src));
+        unsafe {
+            let dst = self.alloc_layout(layout).cast::<T>();
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
         }
     }
 
+    /// Align `layout` to this arena's cache line size, and pad its size to a
+    /// multiple of it.
+    fn cache_aligned_layout(&self, layout: Layout) -> Layout {
+        let align = layout.align().max(self.chunk_policy.cache_align);
+        round_up_to(layout.size(), align)
+            .and_then(|size| Layout::from_size_align(size, align).ok())
+            .unwrap_or_else(allocation_size_overflow)
+    }
+
+    /// `Copy` a string slice into this `Bump` and return an exclusive reference to it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the string fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let hello = bump.alloc_str("hello world");
+    /// assert_eq!("hello world", hello);
+    /// ```
     #[inline(always)]
-    fn try_alloc_layout_fast(&self, layout: Layout) -> Option<NonNull<u8>> {
-        // We don't need to check for ZSTs here since they will automatically
-        // be handled properly: the pointer will be bumped by zero bytes,
-        // modulo alignment. This keeps the fast path optimized for non-ZSTs,
-        // which are much more common.
+    pub fn alloc_str(&self, src: &str) -> &mut str {
+        let buffer = self.alloc_slice_copy(src.as_bytes());
         unsafe {
-            let footer = self.current_chunk_footer.get();
-            let footer = footer.as_ref();
-            let ptr = footer.ptr.get().as_ptr();
-            let start = footer.data.as_ptr();
-            debug_assert!(start <= ptr);
-            debug_assert!(ptr as *const u8 <= footer as *const _ as *const u8);
-
-            if (ptr as usize) < layout.size() {
-                return None;
-            }
-
-            let ptr = ptr.wrapping_sub(layout.size());
-            let aligned_ptr = round_mut_ptr_down_to(ptr, layout.align());
-
-            if aligned_ptr >= start {
-                let aligned_ptr = NonNull::new_unchecked(aligned_ptr);
-                footer.ptr.set(aligned_ptr);
-                Some(aligned_ptr)
-            } else {
-                None
-            }
+            // This is OK, because it already came in as str, so it is guaranteed to be utf8
+            str::from_utf8_unchecked_mut(buffer)
         }
     }
 
-    /// Gets the remaining capacity in the current chunk (in bytes).
+    /// Try to `Copy` a string slice into this `Bump` and return an exclusive
+    /// reference to it.
     ///
-    /// ## Example
+    /// ## Errors
     ///
-    /// ```
-    /// use bumpalo::Bump;
+    /// Errors if reserving space for the string fails.
     ///
-    /// let bump = Bump::with_capacity(100);
+    /// ## Example
     ///
-    /// let capacity = bump.chunk_capacity();
-    /// assert!(capacity >= 100);
     /// ```
-    pub fn chunk_capacity(&self) -> usize {
-        let current_footer = self.current_chunk_footer.get();
-        let current_footer = unsafe { current_footer.as_ref() };
-
-        current_footer.ptr.get().as_ptr() as usize - current_footer.data.as_ptr() as usize
-    }
-
-    /// Slow path allocation for when we need to allocate a new chunk from the
-    /// parent bump set because there isn't enough room in our current chunk.
-    #[inline(never)]
-    #[cold]
-    fn alloc_layout_slow(&self, layout: Layout) -> Option<NonNull<u8>> {
+    /// let bump = bumpalo::Bump::new();
+    /// let hello = bump.try_alloc_str("hello world").unwrap();
+    /// assert_eq!("hello world", hello);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_str(&self, src: &str) -> Result<&mut str, AllocErr> {
+        let buffer = self.try_alloc_slice_copy(src.as_bytes())?;
         unsafe {
-            let size = layout.size();
-            let allocation_limit_remaining = self.allocation_limit_remaining();
-
-            // Get a new chunk from the global allocator.
-            let current_footer = self.current_chunk_footer.get();
-            let current_layout = current_footer.as_ref().layout;
-
-            // By default, we want our new chunk to be about twice as big
-            // as the previous chunk. If the global allocator refuses it,
-            // we try to divide it by half until it works or the requested
-            // size is smaller than the default footer size.
-            let min_new_chunk_size = layout.size().max(DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER);
-            let mut base_size = (current_layout.size() - FOOTER_SIZE)
-                .checked_mul(2)?
-                .max(min_new_chunk_size);
-            let chunk_memory_details = iter::from_fn(|| {
-                let bypass_min_chunk_size_for_small_limits = matches!(self.allocation_limit(), Some(limit) if layout.size() < limit
-                            && base_size >= layout.size()
-                            && limit < DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER
-                            && self.allocated_bytes() == 0);
-
-                if base_size >= min_new_chunk_size || bypass_min_chunk_size_for_small_limits {
-                    let size = base_size;
-                    base_size /= 2;
-                    Bump::new_chunk_memory_details(Some(size), layout)
-                } else {
-                    None
-                }
-            });
-
-            let new_footer = chunk_memory_details
-                .filter_map(|chunk_memory_details| {
-                    if Bump::chunk_fits_under_limit(
-                        allocation_limit_remaining,
-                        chunk_memory_details,
-                    ) {
-                        Bump::new_chunk(chunk_memory_details, layout, current_footer)
-                    } else {
-                        None
-                    }
-                })
-                .next()?;
-
-            debug_assert_eq!(
-                new_footer.as_ref().data.as_ptr() as usize % layout.align(),
-                0
-            );
-
-            // Set the new chunk as our new current chunk.
-            self.current_chunk_footer.set(new_footer);
-
-            let new_footer = new_footer.as_ref();
-
-            // Move the bump ptr finger down to allocate room for `val`. We know
-            // this can't overflow because we successfully allocated a chunk of
-            // at least the requested size.
-            let mut ptr = new_footer.ptr.get().as_ptr().sub(size);
-            // Round the pointer down to the requested alignment.
-            ptr = round_mut_ptr_down_to(ptr, layout.align());
-            debug_assert!(
-                ptr as *const _ <= new_footer,
-                "{:p} <= {:p}",
-                ptr,
-                new_footer
-            );
-            let ptr = NonNull::new_unchecked(ptr);
-            new_footer.ptr.set(ptr);
-
-            // Return a pointer to the freshly allocated region in this chunk.
-            Some(ptr)
+            // This is OK, because it already came in as str, so it is guaranteed to be utf8
+            Ok(str::from_utf8_unchecked_mut(buffer))
         }
     }
 
-    /// Returns an iterator over each chunk of allocated memory that
-    /// this arena has bump allocated into.
+    /// Validate that `bytes` are UTF-8, and copy them into this `Bump` as a
+    /// string slice.
     ///
-    /// The chunks are returned ordered by allocation time, with the most
-    /// recently allocated chunk being returned first, and the least recently
-    /// allocated chunk being returned last.
+    /// Nothing is allocated if the bytes are not valid UTF-8.
     ///
-    /// The values inside each chunk are also ordered by allocation time, with
-    /// the most recent allocation being earlier in the slice, and the least
-    /// recent allocation being towards the end of the slice.
+    /// ## Errors
     ///
-    /// ## Safety
+    /// Errors if `bytes` are not valid UTF-8.
     ///
-    /// Because this method takes `&mut self`, we know that the bump arena
-    /// reference is unique and therefore there aren't any active references to
-    /// any of the objects we've allocated in it either. This potential aliasing
-    /// of exclusive references is one common footgun for unsafe code that we
-    /// don't need to worry about here.
+    /// ## Panics
     ///
-    /// However, there could be regions of uninitialized memory used as padding
-    /// between allocations, which is why this iterator has items of type
-    /// `[MaybeUninit<u8>]`, instead of simply `[u8]`.
+    /// Panics if reserving space for the string fails.
     ///
-    /// The only way to guarantee that there is no padding between allocations
-    /// or within allocated objects is if all of these properties hold:
+    /// ## Example
     ///
-    /// 1. Every object allocated in this arena has the same alignment,
-    ///    and that alignment is at most 16.
-    /// 2. Every object's size is a multiple of its alignment.
-    /// 3. None of the objects allocated in this arena contain any internal
-    ///    padding.
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let hello = bump.alloc_str_from_utf8(b"hello").unwrap();
+    /// assert_eq!(hello, "hello");
+    /// assert!(bump.alloc_str_from_utf8(b"\xff").is_err());
+    /// ```
+    #[inline]
+    pub fn alloc_str_from_utf8(&self, bytes: &[u8]) -> Result<&mut str, str::Utf8Error> {
+        let src = str::from_utf8(bytes)?;
+        Ok(self.alloc_str(src))
+    }
+
+    /// Allocate the UTF-8 encoding of a `char` in this `Bump`, and return it
+    /// as a string slice.
     ///
-    /// If you want to use this `iter_allocated_chunks` method, it is *your*
-    /// responsibility to ensure that these properties hold before calling
-    /// `MaybeUninit::assume_init` or otherwise reading the returned values.
+    /// ## Panics
     ///
-    /// Finally, you must also ensure that any values allocated into the bump
-    /// arena have not had their `Drop` implementations called on them,
-    /// e.g. after dropping a [`bumpalo::boxed::Box<T>`][crate::boxed::Box].
+    /// Panics if reserving space for the string fails.
     ///
     /// ## Example
     ///
     /// ```
-    /// let mut bump = bumpalo::Bump::new();
-    ///
-    /// // Allocate a bunch of `i32`s in this bump arena, potentially causing
-    /// // additional memory chunks to be reserved.
-    /// for i in 0..10000 {
-    ///     bump.alloc(i);
-    /// }
+    /// let bump = bumpalo::Bump::new();
+    /// let s = bump.alloc_char('ß');
+    /// assert_eq!(s, "ß");
+    /// assert_eq!(s.len(), 2);
+    /// ```
+    #[inline]
+    pub fn alloc_char(&self, c: char) -> &mut str {
+        let buffer = self.alloc_slice_fill_copy(c.len_utf8(), 0);
+        c.encode_utf8(buffer)
+    }
+
+    /// Copy a string slice into this `Bump` with every character converted
+    /// to lowercase, and return an exclusive reference to the copy.
     ///
-    /// // Iterate over each chunk we've bump allocated into. This is safe
-    /// // because we have only allocated `i32`s in this arena, which fulfills
-    /// // the above requirements.
-    /// for ch in bump.iter_allocated_chunks() {
-    ///     println!("Used a chunk that is {} bytes long", ch.len());
-    ///     println!("The first byte is {:?}", unsafe {
-    ///         ch[0].assume_init()
-    ///     });
-    /// }
+    /// The copy's length is computed up front, so exactly as many bytes as it
+    /// needs are allocated, without any intermediate `String`. Characters are
+    /// converted one at a time with [`char::to_lowercase`], so unlike
+    /// `str::to_lowercase`, a final capital sigma becomes `'σ'` rather than
+    /// `'ς'`.
     ///
-    /// // Within a chunk, allocations are ordered from most recent to least
-    /// // recent. If we allocated 'a', then 'b', then 'c', when we iterate
-    /// // through the chunk's data, we get them in the order 'c', then 'b',
-    /// // then 'a'.
+    /// ## Panics
     ///
-    /// bump.reset();
-    /// bump.alloc(b'a');
-    /// bump.alloc(b'b');
-    /// bump.alloc(b'c');
+    /// Panics if reserving space for the string fails.
     ///
-    /// assert_eq!(bump.iter_allocated_chunks().count(), 1);
-    /// let chunk = bump.iter_allocated_chunks().nth(0).unwrap();
-    /// assert_eq!(chunk.len(), 3);
+    /// ## Example
     ///
-    /// // Safe because we've only allocated `u8`s in this arena, which
-    /// // fulfills the above requirements.
-    /// unsafe {
-    ///     assert_eq!(chunk[0].assume_init(), b'c');
-    ///     assert_eq!(chunk[1].assume_init(), b'b');
-    ///     assert_eq!(chunk[2].assume_init(), b'a');
-    /// }
     /// ```
-    pub fn iter_allocated_chunks(&mut self) -> ChunkIter<'_> {
-        // SAFE: Ensured by mutable borrow of `self`.
-        let raw = unsafe { self.iter_allocated_chunks_raw() };
-        ChunkIter {
-            raw,
-            bump: PhantomData,
-        }
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(bump.alloc_str_lowercase("Hello, WORLD"), "hello, world");
+    /// assert_eq!(bump.alloc_str_lowercase("İ"), "i\u{307}");
+    /// ```
+    pub fn alloc_str_lowercase(&self, src: &str) -> &mut str {
+        self.alloc_str_mapped(src, char::to_lowercase)
     }
 
-    /// Returns an iterator over raw pointers to chunks of allocated memory that
-    /// this arena has bump allocated into.
+    /// Copy a string slice into this `Bump` with every character converted
+    /// to uppercase, and return an exclusive reference to the copy.
     ///
-    /// This is an unsafe version of [`iter_allocated_chunks()`](Bump::iter_allocated_chunks),
-    /// with the caller responsible for safe usage of the returned pointers as
-    /// well as ensuring that the iterator is not invalidated by new
-    /// allocations.
+    /// The copy's length is computed up front, so exactly as many bytes as it
+    /// needs are allocated, without any intermediate `String`. Characters are
+    /// converted one at a time with [`char::to_uppercase`].
     ///
-    /// ## Safety
+    /// ## Panics
     ///
-    /// Allocations from this arena must not be performed while the returned
-    /// iterator is alive. If reading the chunk data (or casting to a reference)
-    /// the caller must ensure that there exist no mutable references to
-    /// previously allocated data.
+    /// Panics if reserving space for the string fails.
     ///
-    /// In addition, all of the caveats when reading the chunk data from
-    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks) still apply.
-    pub unsafe fn iter_allocated_chunks_raw(&self) -> ChunkRawIter<'_> {
-        ChunkRawIter {
-            footer: self.current_chunk_footer.get(),
-            bump: PhantomData,
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(bump.alloc_str_uppercase("Hello, world"), "HELLO, WORLD");
+    /// assert_eq!(bump.alloc_str_uppercase("straße"), "STRASSE");
+    /// ```
+    pub fn alloc_str_uppercase(&self, src: &str) -> &mut str {
+        self.alloc_str_mapped(src, char::to_uppercase)
+    }
+
+    fn alloc_str_mapped<F, I>(&self, src: &str, map: F) -> &mut str
+    where
+        F: Fn(char) -> I,
+        I: Iterator<Item = char>,
+    {
+        let len = src
+            .chars()
+            .flat_map(&map)
+            .map(char::len_utf8)
+            .fold(0_usize, |len, n| {
+                len.checked_add(n).unwrap_or_else(allocation_size_overflow)
+            });
+        let buffer = self.alloc_slice_fill_copy(len, 0_u8);
+        let mut pos = 0;
+        for c in src.chars().flat_map(&map) {
+            pos += c.encode_utf8(&mut buffer[pos..]).len();
+        }
+        debug_assert_eq!(pos, len);
+        unsafe {
+            // Every byte was written by `encode_utf8`.
+            str::from_utf8_unchecked_mut(buffer)
         }
     }
 
-    /// Calculates the number of bytes currently allocated across all chunks in
-    /// this bump arena.
+    /// Allocates a new slice of size `len` into this `Bump` and returns an
+    /// exclusive reference to the copy.
     ///
-    /// If you allocate types of different alignments or types with
-    /// larger-than-typical alignment in the same arena, some padding
-    /// bytes might get allocated in the bump arena. Note that those padding
-    /// bytes will add to this method's resulting sum, so you cannot rely
-    /// on it only counting the sum of the sizes of the things
-    /// you've allocated in the arena.
+    /// The elements of the slice are initialized using the supplied closure.
+    /// The closure argument is the position in the slice.
     ///
-    /// The allocated bytes do not include the size of bumpalo's metadata,
-    /// so the amount of memory requested from the Rust allocator is higher
-    /// than the returned value.
+    /// If the closure panics, the elements that were already initialized are
+    /// dropped before the panic propagates. The space reserved for the slice
+    /// is not reclaimed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails, or if the closure
+    /// panics.
     ///
     /// ## Example
     ///
     /// ```
     /// let bump = bumpalo::Bump::new();
-    /// let _x = bump.alloc_slice_fill_default::<u32>(5);
-    /// let bytes = bump.allocated_bytes();
-    /// assert!(bytes >= core::mem::size_of::<u32>() * 5);
+    /// let x = bump.alloc_slice_fill_with(5, |i| 5 * (i + 1));
+    /// assert_eq!(x, &[5, 10, 15, 20, 25]);
     /// ```
-    pub fn allocated_bytes(&self) -> usize {
-        let footer = self.current_chunk_footer.get();
-
-        unsafe { footer.as_ref().allocated_bytes }
+    #[inline(always)]
+    pub fn alloc_slice_fill_with<T, F>(&self, len: usize, f: F) -> &mut [T]
+    where
+        F: FnMut(usize) -> T,
+    {
+        self.try_alloc_slice_fill_with(len, f)
+            .unwrap_or_else(|_| oom())
     }
 
-    /// Calculates the number of bytes requested from the Rust allocator for this `Bump`.
+    /// Try to allocate a new slice of size `len` into this `Bump` and return
+    /// an exclusive reference to it.
     ///
-    /// This number is equal to the [`allocated_bytes()`](Self::allocated_bytes) plus
-    /// the size of the bump metadata.
-    pub fn allocated_bytes_including_metadata(&self) -> usize {
-        let metadata_size =
-            unsafe { self.iter_allocated_chunks_raw().count() * mem::size_of::<ChunkFooter>() };
-        self.allocated_bytes() + metadata_size
+    /// The elements of the slice are initialized using the supplied closure.
+    /// The closure argument is the position in the slice.
+    ///
+    /// If the closure panics, the elements that were already initialized are
+    /// dropped before the panic propagates. The space reserved for the slice
+    /// is not reclaimed.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails. The closure is not
+    /// called in that case.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the closure panics.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_fill_with(5, |i| 5 * (i + 1)).unwrap();
+    /// assert_eq!(x, &[5, 10, 15, 20, 25]);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_fill_with<T, F>(&self, len: usize, f: F) -> Result<&mut [T], AllocErr>
+    where
+        F: FnMut(usize) -> T,
+    {
+        let dst = self.try_alloc_uninit_slice(len)?;
+        Ok(uninit::write_slice_with(dst, f))
     }
 
-    #[inline]
-    unsafe fn is_last_allocation(&self, ptr: NonNull<u8>) -> bool {
-        let footer = self.current_chunk_footer.get();
-        let footer = footer.as_ref();
-        footer.ptr.get() == ptr
+    /// Allocates a new slice of size `len` into this `Bump` and returns an
+    /// exclusive reference to the copy.
+    ///
+    /// All elements of the slice are initialized to `value`.
+    ///
+    /// The slice is filled with a plain store loop, with no per-element
+    /// bookkeeping. In optimized builds, fills of byte-sized types, and of
+    /// constants whose bytes are all the same, such as `0_u64`, compile down
+    /// to a single `memset`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_fill_copy(5, 42);
+    /// assert_eq!(x, &[42, 42, 42, 42, 42]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_copy<T: Copy>(&self, len: usize, value: T) -> &mut [T] {
+        self.try_alloc_slice_fill_copy(len, value)
+            .unwrap_or_else(|_| oom())
     }
 
-    #[inline]
-    unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
-        // If the pointer is the last allocation we made, we can reuse the bytes,
-        // otherwise they are simply leaked -- at least until somebody calls reset().
-        if self.is_last_allocation(ptr) {
-            let ptr = self.current_chunk_footer.get().as_ref().ptr.get();
-            let ptr = NonNull::new_unchecked(ptr.as_ptr().add(layout.size()));
-            self.current_chunk_footer.get().as_ref().ptr.set(ptr);
+    /// Try to allocate a new slice of size `len` into this `Bump` and return
+    /// an exclusive reference to it.
+    ///
+    /// All elements of the slice are initialized to `value`.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_fill_copy(5, 42).unwrap();
+    /// assert_eq!(x, &[42, 42, 42, 42, 42]);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_fill_copy<T: Copy>(
+        &self,
+        len: usize,
+        value: T,
+    ) -> Result<&mut [T], AllocErr> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+        let dst = self.try_alloc_layout_for::<T>(layout)?;
+
+        // Copying `value` can't panic, so unlike `try_alloc_slice_fill_with`,
+        // there's no partially initialized prefix to keep track of, and the
+        // loop is simple enough for LLVM to recognize fills.
+        unsafe {
+            let dst = dst.as_ptr();
+            for i in 0..len {
+                ptr::write(dst.add(i), value);
+            }
+            Ok(slice::from_raw_parts_mut(dst, len))
         }
     }
 
-    #[inline]
-    unsafe fn shrink(
+    /// Allocates a new slice of size `len` slice into this `Bump` and return an
+    /// exclusive reference to the copy.
+    ///
+    /// All elements of the slice are initialized to `value.clone()`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let s: String = "Hello Bump!".to_string();
+    /// let x: &[String] = bump.alloc_slice_fill_clone(2, &s);
+    /// assert_eq!(x.len(), 2);
+    /// assert_eq!(&x[0], &s);
+    /// assert_eq!(&x[1], &s);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_clone<T: Clone>(&self, len: usize, value: &T) -> &mut [T] {
+        self.alloc_slice_fill_with(len, |_| value.clone())
+    }
+
+    /// Try to allocate a new slice of size `len` into this `Bump` and return
+    /// an exclusive reference to it.
+    ///
+    /// All elements of the slice are initialized to `value.clone()`.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let s: String = "Hello Bump!".to_string();
+    /// let x: &[String] = bump.try_alloc_slice_fill_clone(2, &s).unwrap();
+    /// assert_eq!(x, [s.clone(), s]);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_fill_clone<T: Clone>(
         &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<u8>, AllocErr> {
-        // If the new layout demands greater alignment than the old layout has,
-        // then either
-        //
-        // 1. the pointer happens to satisfy the new layout's alignment, so we
-        //    got lucky and can return the pointer as-is, or
-        //
-        // 2. the pointer is not aligned to the new layout's demanded alignment,
-        //    and we are unlucky.
-        //
-        // In the case of (2), to successfully "shrink" the allocation, we would
-        // have to allocate a whole new region for the new layout, without being
-        // able to free the old region. That is unacceptable, so simply return
-        // an allocation failure error instead.
-        if old_layout.align() < new_layout.align() {
-            if is_pointer_aligned_to(ptr.as_ptr(), new_layout.align()) {
-                return Ok(ptr);
-            } else {
-                return Err(AllocErr);
+        len: usize,
+        value: &T,
+    ) -> Result<&mut [T], AllocErr> {
+        self.try_alloc_slice_fill_with(len, |_| value.clone())
+    }
+
+    /// Allocates a new slice of size `len` slice into this `Bump` and return an
+    /// exclusive reference to the copy.
+    ///
+    /// The elements are initialized using the supplied iterator.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails, or if the supplied
+    /// iterator returns fewer elements than it promised.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x: &[i32] = bump.alloc_slice_fill_iter([2, 3, 5].iter().cloned().map(|i| i * i));
+    /// assert_eq!(x, [4, 9, 25]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_iter<T, I>(&self, iter: I) -> &mut [T]
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+        self.alloc_slice_fill_with(iter.len(), |_| {
+            iter.next().expect("Iterator supplied too few elements")
+        })
+    }
+
+    /// Try to allocate a new slice of size `len` into this `Bump` and return
+    /// an exclusive reference to it.
+    ///
+    /// The elements are initialized using the supplied iterator.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails. The iterator is not
+    /// advanced in that case.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the supplied iterator returns fewer elements than it
+    /// promised.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x: &[i32] = bump
+    ///     .try_alloc_slice_fill_iter([2, 3, 5].iter().map(|i| i * i))
+    ///     .unwrap();
+    /// assert_eq!(x, [4, 9, 25]);
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_fill_iter<T, I>(&self, iter: I) -> Result<&mut [T], AllocErr>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+        self.try_alloc_slice_fill_with(iter.len(), |_| {
+            iter.next().expect("Iterator supplied too few elements")
+        })
+    }
+
+    /// Allocates a new slice into this `Bump`, filled with every element of
+    /// an iterator whose length is not known up front, and returns an
+    /// exclusive reference to it.
+    ///
+    /// Space is reserved in blocks, starting from the iterator's lower size
+    /// hint and doubling whenever a block fills up. While the block is the
+    /// last allocation in the arena it grows in place; afterwards, the unused
+    /// tail of the final block is returned to the arena. The resulting slice
+    /// has exactly as many elements as the iterator produced, without first
+    /// collecting them into a `std::vec::Vec` to learn the length.
+    ///
+    /// If the iterator panics, the elements that were already produced are
+    /// dropped before the panic propagates.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let sevens = bump.alloc_slice_fill_iter_partial((0..100).filter(|i| i % 7 == 0));
+    /// assert_eq!(sevens.len(), 15);
+    /// assert_eq!(sevens[..3], [0, 7, 14]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_iter_partial<T, I>(&self, iter: I) -> &mut [T]
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_alloc_slice_fill_iter_partial(iter)
+            .unwrap_or_else(|_| oom())
+    }
+
+    /// Try to allocate a new slice into this `Bump`, filled with every element
+    /// of an iterator whose length is not known up front, and return an
+    /// exclusive reference to it.
+    ///
+    /// See [`alloc_slice_fill_iter_partial`](Bump::alloc_slice_fill_iter_partial)
+    /// for how space is reserved.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails. The elements that were
+    /// already produced are dropped in that case, and the iterator is not
+    /// advanced any further.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let words = bump
+    ///     .try_alloc_slice_fill_iter_partial("a bc def".split(' '))
+    ///     .unwrap();
+    /// assert_eq!(words, ["a", "bc", "def"]);
+    ///
+    /// let limited = bumpalo::Bump::new();
+    /// limited.set_allocation_limit(Some(0));
+    /// assert!(limited.try_alloc_slice_fill_iter_partial(0..10_u64).is_err());
+    /// ```
+    pub fn try_alloc_slice_fill_iter_partial<T, I>(&self, iter: I) -> Result<&mut [T], AllocErr>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        // Drops the already-initialized prefix of the block if the iterator
+        // panics or growing the block fails.
+        struct Guard<T> {
+            dst: NonNull<T>,
+            initialized: usize,
+        }
+
+        impl<T> Drop for Guard<T> {
+            fn drop(&mut self) {
+                unsafe {
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        self.dst.as_ptr(),
+                        self.initialized,
+                    ));
+                }
+            }
+        }
+
+        let iter = iter.into_iter();
+
+        if mem::size_of::<T>() == 0 {
+            let mut len = 0_usize;
+            for value in iter {
+                mem::forget(value);
+                len = len.checked_add(1).ok_or(AllocErr::SIZE_OVERFLOW)?;
+            }
+            return Ok(unsafe { slice::from_raw_parts_mut(NonNull::dangling().as_ptr(), len) });
+        }
+
+        let mut cap = iter.size_hint().0.max(4);
+        let mut layout = Layout::array::<T>(cap).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+        let mut guard = Guard {
+            dst: self.try_alloc_layout(layout)?.cast::<T>(),
+            initialized: 0,
+        };
+
+        for value in iter {
+            if guard.initialized == cap {
+                let new_cap = cap.checked_mul(2).ok_or(AllocErr::SIZE_OVERFLOW)?;
+                let new_layout =
+                    Layout::array::<T>(new_cap).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+                guard.dst = unsafe { self.grow(guard.dst.cast(), layout, new_layout)?.cast() };
+                cap = new_cap;
+                layout = new_layout;
+            }
+            unsafe { ptr::write(guard.dst.as_ptr().add(guard.initialized), value) };
+            guard.initialized += 1;
+        }
+
+        let len = guard.initialized;
+        let dst = guard.dst;
+        mem::forget(guard);
+
+        unsafe {
+            let dst = if len == 0 {
+                self.dealloc(dst.cast(), layout);
+                NonNull::dangling()
+            } else {
+                let new_layout = Layout::array::<T>(len).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+                self.shrink(dst.cast(), layout, new_layout)?.cast()
+            };
+            Ok(slice::from_raw_parts_mut(dst.as_ptr(), len))
+        }
+    }
+
+    /// Allocates a new slice of size `len` slice into this `Bump` and return an
+    /// exclusive reference to the copy.
+    ///
+    /// All elements of the slice are initialized to [`T::default()`].
+    ///
+    /// [`T::default()`]: https://doc.rust-lang.org/std/default/trait.Default.html#tymethod.default
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_slice_fill_default::<u32>(5);
+    /// assert_eq!(x, &[0, 0, 0, 0, 0]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_fill_default<T: Default>(&self, len: usize) -> &mut [T] {
+        self.alloc_slice_fill_with(len, |_| T::default())
+    }
+
+    /// Try to allocate a new slice of size `len` into this `Bump` and return
+    /// an exclusive reference to it.
+    ///
+    /// All elements of the slice are initialized to [`T::default()`].
+    ///
+    /// [`T::default()`]: https://doc.rust-lang.org/std/default/trait.Default.html#tymethod.default
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.try_alloc_slice_fill_default::<u32>(5).unwrap();
+    /// assert_eq!(x, &[0, 0, 0, 0, 0]);
+    ///
+    /// let limited = bumpalo::Bump::new();
+    /// limited.set_allocation_limit(Some(0));
+    /// assert!(limited.try_alloc_slice_fill_default::<u32>(5).is_err());
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc_slice_fill_default<T: Default>(
+        &self,
+        len: usize,
+    ) -> Result<&mut [T], AllocErr> {
+        self.try_alloc_slice_fill_with(len, |_| T::default())
+    }
+
+    /// Allocates a new slice of size `len` into this `Bump` and returns an
+    /// exclusive reference to it, with every element initialized to
+    /// [`T::default()`].
+    ///
+    /// This is an alias for
+    /// [`alloc_slice_fill_default`](Bump::alloc_slice_fill_default).
+    ///
+    /// [`T::default()`]: https://doc.rust-lang.org/std/default/trait.Default.html#tymethod.default
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// #[derive(Default, Debug, PartialEq)]
+    /// struct Config {
+    ///     verbose: bool,
+    ///     name: String,
+    /// }
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let configs = bump.alloc_slice_default::<Config>(3);
+    /// assert_eq!(configs.len(), 3);
+    /// assert!(configs.iter().all(|c| *c == Config::default()));
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_default<T: Default>(&self, len: usize) -> &mut [T] {
+        self.alloc_slice_fill_default(len)
+    }
+
+    /// Allocates a header value followed by a trailing slice in one
+    /// contiguous allocation, and returns exclusive references to both.
+    ///
+    /// The slice is initialized from the supplied iterator, and is placed
+    /// directly after the header (plus any padding needed to align `U`). This
+    /// is the "header + variable-length payload" layout that is common for
+    /// nodes in compilers and interpreters.
+    ///
+    /// If the iterator panics, the header and the elements that were already
+    /// initialized are dropped before the panic propagates. The space reserved
+    /// for the allocation is not reclaimed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the allocation fails, or if the supplied
+    /// iterator returns fewer elements than it promised.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// struct Node {
+    ///     id: u32,
+    /// }
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let (node, edges) = bump.alloc_header_with_slice(Node { id: 7 }, [1_u64, 2, 3]);
+    /// assert_eq!(node.id, 7);
+    /// assert_eq!(edges, [1, 2, 3]);
+    ///
+    /// // The slice immediately follows the header.
+    /// let node_end = (node as *mut Node as usize) + std::mem::size_of::<Node>();
+    /// assert!(edges.as_ptr() as usize >= node_end);
+    /// assert!(edges.as_ptr() as usize - node_end < std::mem::align_of::<u64>());
+    /// ```
+    #[inline(always)]
+    pub fn alloc_header_with_slice<H, U, I>(&self, header: H, iter: I) -> (&mut H, &mut [U])
+    where
+        I: IntoIterator<Item = U>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        // Drops the header and the already-initialized prefix of the slice if
+        // the iterator panics.
+        struct Guard<H, U> {
+            header: NonNull<H>,
+            dst: NonNull<U>,
+            initialized: usize,
+        }
+
+        impl<H, U> Drop for Guard<H, U> {
+            fn drop(&mut self) {
+                unsafe {
+                    ptr::drop_in_place(self.header.as_ptr());
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        self.dst.as_ptr(),
+                        self.initialized,
+                    ));
+                }
+            }
+        }
+
+        let mut iter = iter.into_iter();
+        let len = iter.len();
+
+        let (layout, offset) = Layout::array::<U>(len)
+            .and_then(|slice| Layout::new::<H>().extend(slice))
+            .unwrap_or_else(|_| oom());
+
+        let (header_ptr, dst) = if layout.size() == 0 {
+            // Both `H` and `U` are zero-sized, so don't touch the arena.
+            (NonNull::<H>::dangling(), NonNull::<U>::dangling())
+        } else {
+            let p = self.alloc_layout(layout);
+            unsafe {
+                let dst = if mem::size_of::<U>() == 0 {
+                    NonNull::dangling()
+                } else {
+                    NonNull::new_unchecked(p.as_ptr().add(offset)).cast::<U>()
+                };
+                (p.cast::<H>(), dst)
+            }
+        };
+
+        unsafe {
+            ptr::write(header_ptr.as_ptr(), header);
+
+            let mut guard = Guard {
+                header: header_ptr,
+                dst,
+                initialized: 0,
+            };
+            for i in 0..len {
+                let value = iter.next().expect("Iterator supplied too few elements");
+                ptr::write(dst.as_ptr().add(i), value);
+                guard.initialized += 1;
+            }
+            mem::forget(guard);
+
+            (
+                &mut *header_ptr.as_ptr(),
+                slice::from_raw_parts_mut(dst.as_ptr(), len),
+            )
+        }
+    }
+
+    /// Allocate space for an object with the given `Layout`.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        self.try_alloc_layout(layout).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempts to allocate space for an object with the given `Layout` or else returns
+    /// an `Err`.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn try_alloc_layout(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let p = if let Some(p) = self.try_alloc_layout_fast(layout) {
+            p
+        } else {
+            self.alloc_layout_slow(layout, false)?
+        };
+        unsafe {
+            poison_fresh(p.as_ptr(), layout.size());
+        }
+        self.trace_alloc(p, layout, false);
+        Ok(p)
+    }
+
+    /// Allocate space for an object with the given `Layout`, but only if it
+    /// fits in the current chunk.
+    ///
+    /// Returns `None` instead of allocating a new chunk, so this never calls
+    /// into the backing allocator. This is useful for callers with latency
+    /// constraints that would rather fall back to another strategy than take
+    /// the allocation slow path at an inconvenient moment. Use
+    /// [`reserve_bytes`](Bump::reserve_bytes) to make room ahead of time.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::with_capacity(64);
+    /// let layout = Layout::new::<[u64; 4]>();
+    ///
+    /// // Fits in the initial chunk.
+    /// assert!(bump.alloc_layout_in_current_chunk(layout).is_some());
+    ///
+    /// // Doesn't, and no new chunk is allocated for it.
+    /// let big = Layout::new::<[u64; 1024]>();
+    /// let allocated = bump.allocated_bytes();
+    /// assert!(bump.alloc_layout_in_current_chunk(big).is_none());
+    /// assert_eq!(bump.allocated_bytes(), allocated);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_layout_in_current_chunk(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let p = self.try_alloc_layout_fast(layout)?;
+        unsafe {
+            poison_fresh(p.as_ptr(), layout.size());
+        }
+        self.trace_alloc(p, layout, false);
+        Some(p)
+    }
+
+    /// Allocate an object in this `Bump` without checking whether the current
+    /// chunk has room for it, and return an exclusive reference to it.
+    ///
+    /// This skips the capacity check and the slow path of
+    /// [`alloc`](Bump::alloc), and is meant for hot loops that have already
+    /// made room with [`reserve_bytes`](Bump::reserve_bytes).
+    ///
+    /// ## Safety
+    ///
+    /// The current chunk must have at least `size_of::<T>()` bytes of free
+    /// capacity, plus any padding needed to align the allocation for `T`. See
+    /// [`chunk_capacity`](Bump::chunk_capacity).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.reserve_bytes(100 * std::mem::size_of::<u64>());
+    ///
+    /// for i in 0..100_u64 {
+    ///     // Safety: every `u64` is 8-byte aligned and 8 bytes large, so there
+    ///     // is no padding and the 100 of them fit in the reserved space.
+    ///     let x = unsafe { bump.alloc_unchecked(i) };
+    ///     assert_eq!(*x, i);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub unsafe fn alloc_unchecked<T>(&self, val: T) -> &mut T {
+        let p = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            self.alloc_layout_unchecked(Layout::new::<T>()).cast::<T>()
+        };
+        let p = p.as_ptr();
+        ptr::write(p, val);
+        &mut *p
+    }
+
+    /// Allocate space for an object with the given `Layout` without checking
+    /// whether the current chunk has room for it.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// ## Safety
+    ///
+    /// The current chunk must have at least `layout.size()` bytes of free
+    /// capacity, plus any padding needed to align the allocation to
+    /// `layout.align()`. See [`chunk_capacity`](Bump::chunk_capacity) and
+    /// [`reserve_bytes`](Bump::reserve_bytes).
+    #[inline(always)]
+    pub unsafe fn alloc_layout_unchecked(&self, layout: Layout) -> NonNull<u8> {
+        let footer = self.current_chunk_footer.get();
+        let footer = footer.as_ref();
+        let ptr = footer.ptr.get().as_ptr();
+        let min_align = self.chunk_policy.alloc_align;
+        let size = (layout.size() + min_align - 1) & !(min_align - 1);
+        debug_assert!(
+            (ptr as usize) >= size,
+            "alloc_layout_unchecked: not enough capacity in the current chunk"
+        );
+
+        debug_assert!(is_pointer_aligned_to(ptr, min_align));
+        let aligned_ptr = if layout.align() <= min_align {
+            ptr.sub(size)
+        } else {
+            round_mut_ptr_down_to(ptr.sub(size), layout.align())
+        };
+        debug_assert!(
+            aligned_ptr >= footer.front.get().as_ptr(),
+            "alloc_layout_unchecked: not enough capacity in the current chunk"
+        );
+
+        let aligned_ptr = NonNull::new_unchecked(aligned_ptr);
+        footer.ptr.set(aligned_ptr);
+        asan_unpoison(
+            aligned_ptr.as_ptr(),
+            ptr as usize - aligned_ptr.as_ptr() as usize,
+        );
+        self.trace_alloc(aligned_ptr, layout, false);
+        aligned_ptr
+    }
+
+    /// Attempts to allocate space for `layout`, which must be the layout of a
+    /// `T` or of a slice of `T`s.
+    ///
+    /// If `T` is a zero-sized type, no space is reserved and the bump pointer
+    /// is left untouched. Instead, a dangling pointer aligned for `T` is
+    /// returned. Because `T`'s size is known at compile time, this check is
+    /// free.
+    #[inline(always)]
+    fn try_alloc_layout_for<T>(&self, layout: Layout) -> Result<NonNull<T>, AllocErr> {
+        if mem::size_of::<T>() == 0 {
+            debug_assert_eq!(layout.size(), 0);
+            return Ok(NonNull::dangling());
+        }
+        self.try_alloc_layout(layout).map(NonNull::cast)
+    }
+
+    /// Allocate space for an object with the given `Layout`, and zero it.
+    ///
+    /// This is like [`alloc_layout`](Bump::alloc_layout), except the
+    /// returned pointer points at memory that has been initialized to all
+    /// zero bytes. When the allocation requires a fresh chunk, that chunk is
+    /// requested already zeroed from the global allocator, avoiding an extra
+    /// `memset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserving space matching `layout` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let layout = Layout::new::<[u32; 4]>();
+    /// let p = bump.alloc_layout_zeroed(layout);
+    /// let array = unsafe { &*(p.as_ptr() as *const [u32; 4]) };
+    /// assert_eq!(array, &[0, 0, 0, 0]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_layout_zeroed(&self, layout: Layout) -> NonNull<u8> {
+        self.try_alloc_layout_zeroed(layout)
+            .unwrap_or_else(|_| oom())
+    }
+
+    /// Attempts to allocate space for an object with the given `Layout`, and
+    /// zero it, or else returns an `Err`.
+    ///
+    /// See [`alloc_layout_zeroed`](Bump::alloc_layout_zeroed) for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn try_alloc_layout_zeroed(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let p = if let Some(p) = self.try_alloc_layout_fast(layout) {
+            unsafe {
+                ptr::write_bytes(p.as_ptr(), 0, layout.size());
+            }
+            p
+        } else {
+            // Memory in a freshly allocated chunk is already zeroed.
+            self.alloc_layout_slow(layout, true)?
+        };
+        self.trace_alloc(p, layout, false);
+        Ok(p)
+    }
+
+    /// Allocate space for an object with the given `Layout`, and return it
+    /// along with its offset in this arena.
+    ///
+    /// The offset numbers the arena's bytes as if all of its chunks were laid
+    /// out one after another, oldest first, so it stays the same when the
+    /// arena allocates new chunks. Passing it to
+    /// [`resolve_offset`](Bump::resolve_offset) gives back the pointer. This
+    /// lets data structures in the arena refer to each other with 32-bit
+    /// offsets rather than pointers, e.g. to serialize them.
+    ///
+    /// Zero-sized layouts are given a byte of space, so that every offset
+    /// resolves to exactly one allocation.
+    ///
+    /// Offsets are only valid until the arena is reset, or the allocation is
+    /// otherwise freed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space matching `layout` fails, or if the offset
+    /// doesn't fit in a `u32`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let (p, offset) = bump.alloc_layout_with_offset_tracking(Layout::new::<u64>());
+    /// unsafe { p.cast::<u64>().as_ptr().write(42) };
+    ///
+    /// // Allocate enough to fill up a few more chunks.
+    /// for _ in 0..1000 {
+    ///     bump.alloc([0_u8; 100]);
+    /// }
+    ///
+    /// let q = bump.resolve_offset(offset).unwrap();
+    /// assert_eq!(p, q);
+    /// assert_eq!(unsafe { *q.cast::<u64>().as_ptr() }, 42);
+    /// ```
+    #[inline]
+    pub fn alloc_layout_with_offset_tracking(&self, layout: Layout) -> (NonNull<u8>, u32) {
+        self.try_alloc_layout_with_offset_tracking(layout)
+            .unwrap_or_else(|_| oom())
+    }
+
+    /// Attempts to allocate space for an object with the given `Layout`, and
+    /// return it along with its offset in this arena, or else returns an
+    /// `Err`.
+    ///
+    /// See [`alloc_layout_with_offset_tracking`](Bump::alloc_layout_with_offset_tracking)
+    /// for details.
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space matching `layout` fails, or with
+    /// [`AllocErrKind::SizeOverflow`] if the offset doesn't fit in a `u32`.
+    pub fn try_alloc_layout_with_offset_tracking(
+        &self,
+        layout: Layout,
+    ) -> Result<(NonNull<u8>, u32), AllocErr> {
+        let layout = layout_from_size_align(layout.size().max(1), layout.align())?;
+        let p = self.try_alloc_layout(layout)?;
+        unsafe {
+            // The allocation is always in the current chunk, whether or not
+            // it needed a new one.
+            let footer = self.current_chunk_footer.get().as_ref();
+            let base = footer.prev.get().as_ref().allocated_bytes;
+            let offset = base + (p.as_ptr() as usize - footer.data.as_ptr() as usize);
+            match u32::try_from(offset) {
+                Ok(offset) => Ok((p, offset)),
+                Err(_) => {
+                    self.dealloc(p, layout);
+                    Err(AllocErr::SIZE_OVERFLOW)
+                }
+            }
+        }
+    }
+
+    /// Returns a pointer to the allocation at `offset`, as returned by
+    /// [`alloc_layout_with_offset_tracking`](Bump::alloc_layout_with_offset_tracking).
+    ///
+    /// Returns `None` if `offset` is past the end of this arena's chunks. An
+    /// offset that wasn't returned by this arena, or that was invalidated by
+    /// resetting it, may resolve to a pointer to arbitrary memory in the
+    /// arena.
+    ///
+    /// This walks the arena's chunks from newest to oldest, so resolving
+    /// offsets of older allocations takes longer.
+    pub fn resolve_offset(&self, offset: u32) -> Option<NonNull<u8>> {
+        let offset = offset as usize;
+        let mut footer = self.current_chunk_footer.get();
+        unsafe {
+            while !footer.as_ref().is_empty() {
+                let f = footer.as_ref();
+                let base = f.prev.get().as_ref().allocated_bytes;
+                if offset >= base {
+                    let size = footer.as_ptr() as usize - f.data.as_ptr() as usize;
+                    let in_chunk = offset - base;
+                    return if in_chunk < size {
+                        Some(NonNull::new_unchecked(f.data.as_ptr().add(in_chunk)))
+                    } else {
+                        None
+                    };
+                }
+                footer = f.prev.get();
+            }
+        }
+        None
+    }
+
+    /// Allocates a new slice of size `len` into this `Bump`, with all of its
+    /// bytes initialized to zero, and returns an exclusive reference to it.
+    ///
+    /// ## Safety
+    ///
+    /// The all-zero bit pattern must be a valid value of `T`. This is the case
+    /// for integers and floats, for example, but not for references or
+    /// `NonNull`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = unsafe { bump.alloc_slice_zeroed::<u64>(5) };
+    /// assert_eq!(x, &[0, 0, 0, 0, 0]);
+    /// ```
+    #[inline(always)]
+    pub unsafe fn alloc_slice_zeroed<T>(&self, len: usize) -> &mut [T] {
+        if mem::size_of::<T>() == 0 {
+            return slice::from_raw_parts_mut(NonNull::dangling().as_ptr(), len);
+        }
+
+        let layout = Layout::array::<T>(len).unwrap_or_else(|_| oom());
+        let dst = self.alloc_layout_zeroed(layout).cast::<T>();
+        slice::from_raw_parts_mut(dst.as_ptr(), len)
+    }
+
+    /// Allocates a zeroed `T` into this `Bump`, and returns an exclusive
+    /// reference to it.
+    ///
+    /// This is a safe version of
+    /// [`alloc_layout_zeroed`](Bump::alloc_layout_zeroed) for types that
+    /// implement [`bytemuck::Zeroable`]. Requires the `bytemuck` Cargo
+    /// feature.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the value fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let x = bump.alloc_zeroed_pod::<[u32; 4]>();
+    /// assert_eq!(x, &[0, 0, 0, 0]);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline(always)]
+    pub fn alloc_zeroed_pod<T: bytemuck::Zeroable>(&self) -> &mut T {
+        self.try_alloc_zeroed_pod().unwrap_or_else(|_| oom())
+    }
+
+    /// Tries to allocate a zeroed `T` into this `Bump`, and returns an
+    /// exclusive reference to it.
+    ///
+    /// Requires the `bytemuck` Cargo feature.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the value fails.
+    #[cfg(feature = "bytemuck")]
+    #[inline(always)]
+    pub fn try_alloc_zeroed_pod<T: bytemuck::Zeroable>(&self) -> Result<&mut T, AllocErr> {
+        let p = self.try_alloc_layout_zeroed(Layout::new::<T>())?;
+        // Safety: `Zeroable` guarantees that all zero bytes are a valid `T`.
+        unsafe { Ok(&mut *p.cast::<T>().as_ptr()) }
+    }
+
+    /// Allocates a slice of `len` zeroed `T`s into this `Bump`, and returns an
+    /// exclusive reference to it.
+    ///
+    /// This is a safe version of [`alloc_slice_zeroed`](Bump::alloc_slice_zeroed)
+    /// for types that implement [`bytemuck::Zeroable`]. Requires the
+    /// `bytemuck` Cargo feature.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let xs = bump.alloc_slice_zeroed_pod::<f32>(3);
+    /// assert_eq!(xs, [0.0; 3]);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline(always)]
+    pub fn alloc_slice_zeroed_pod<T: bytemuck::Zeroable>(&self, len: usize) -> &mut [T] {
+        // Safety: `Zeroable` guarantees that all zero bytes are a valid `T`.
+        unsafe { self.alloc_slice_zeroed(len) }
+    }
+
+    /// Copies the bytes of `src` into this `Bump` as a slice of `T`s, and
+    /// returns an exclusive reference to it.
+    ///
+    /// Unlike [`bytemuck::cast_slice`], this never fails because of
+    /// alignment: the copy is aligned for `T`, however `src` is aligned.
+    /// Requires the `bytemuck` Cargo feature.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the size of `src` in bytes is not a multiple of the size of
+    /// `T`, like [`bytemuck::cast_slice`] does, or if reserving space for the
+    /// slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let bytes = [1_u8, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0];
+    /// // Possibly misaligned for `u32`.
+    /// let words: &mut [u32] = bump.alloc_slice_cast(&bytes[..8]);
+    /// assert_eq!(words, [u32::from_ne_bytes([1, 0, 0, 0]), u32::from_ne_bytes([2, 0, 0, 0])]);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn alloc_slice_cast<T: bytemuck::Pod, U: bytemuck::Pod>(&self, src: &[U]) -> &mut [T] {
+        match self.alloc_slice_try_cast(src) {
+            Ok(dst) => dst,
+            Err(e) => panic!("alloc_slice_cast: {}", e),
+        }
+    }
+
+    /// Copies the bytes of `src` into this `Bump` as a slice of `T`s, and
+    /// returns an exclusive reference to it, or an error if they don't make
+    /// up a whole number of `T`s.
+    ///
+    /// This is the arena counterpart of [`bytemuck::try_cast_slice`]. See
+    /// [`alloc_slice_cast`](Bump::alloc_slice_cast) for details. Requires the
+    /// `bytemuck` Cargo feature.
+    ///
+    /// ## Errors
+    ///
+    /// Errors with [`bytemuck::PodCastError::OutputSliceWouldHaveSlop`] if
+    /// the size of `src` in bytes is not a multiple of the size of `T`, and
+    /// with [`bytemuck::PodCastError::SizeMismatch`] if `T` is zero-sized but
+    /// `src` is not empty.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bytemuck::PodCastError;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let halves = bump.alloc_slice_try_cast::<u16, u8>(&[1, 2, 3, 4]).unwrap();
+    /// assert_eq!(halves.len(), 2);
+    ///
+    /// let err = bump.alloc_slice_try_cast::<u16, u8>(&[1, 2, 3]).unwrap_err();
+    /// assert_eq!(err, PodCastError::OutputSliceWouldHaveSlop);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn alloc_slice_try_cast<T: bytemuck::Pod, U: bytemuck::Pod>(
+        &self,
+        src: &[U],
+    ) -> Result<&mut [T], bytemuck::PodCastError> {
+        let bytes: &[u8] = bytemuck::cast_slice(src);
+        if mem::size_of::<T>() == 0 {
+            return if bytes.is_empty() {
+                Ok(&mut [])
+            } else {
+                Err(bytemuck::PodCastError::SizeMismatch)
+            };
+        }
+        if bytes.len() % mem::size_of::<T>() != 0 {
+            return Err(bytemuck::PodCastError::OutputSliceWouldHaveSlop);
+        }
+
+        let len = bytes.len() / mem::size_of::<T>();
+        let dst = self.alloc_uninit_slice::<T>(len);
+        // Safety: `dst` has room for exactly `bytes.len()` bytes, and any
+        // bytes are a valid `T`, since it is `Pod`.
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), dst.as_mut_ptr().cast::<u8>(), bytes.len());
+            Ok(slice::from_raw_parts_mut(dst.as_mut_ptr().cast::<T>(), len))
+        }
+    }
+
+    #[inline(always)]
+    fn try_alloc_layout_fast(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // We don't need to check for ZSTs here since they will automatically
+        // be handled properly: the pointer will be bumped by zero bytes,
+        // modulo alignment. This keeps the fast path optimized for non-ZSTs,
+        // which are much more common.
+        unsafe {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            let ptr = footer.ptr.get().as_ptr();
+            let start = footer.front.get().as_ptr();
+            debug_assert!(start <= ptr);
+            debug_assert!(ptr as *const u8 <= footer as *const _ as *const u8);
+
+            // Pad the allocation to the arena's minimum alignment. This can't
+            // overflow, since `layout.size()` is at most `isize::MAX`, and
+            // keeps the bump pointer aligned to it, so that only allocations
+            // that need more alignment than that have to round it down.
+            let min_align = self.chunk_policy.alloc_align;
+            let size = (layout.size() + min_align - 1) & !(min_align - 1);
+            debug_assert!(is_pointer_aligned_to(ptr, min_align));
+
+            if (ptr as usize) < size {
+                return None;
+            }
+
+            let end = ptr;
+            let ptr = ptr.wrapping_sub(size);
+            let aligned_ptr = if layout.align() <= min_align {
+                ptr
+            } else {
+                round_mut_ptr_down_to(ptr, layout.align())
+            };
+
+            if aligned_ptr >= start {
+                let aligned_ptr = NonNull::new_unchecked(aligned_ptr);
+                footer.ptr.set(aligned_ptr);
+                // Unpoison the alignment padding too, so that the allocated
+                // part of the chunk stays one contiguous, addressable region.
+                asan_unpoison(
+                    aligned_ptr.as_ptr(),
+                    end as usize - aligned_ptr.as_ptr() as usize,
+                );
+                Some(aligned_ptr)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Allocate an object at the front of this `Bump`'s current chunk and
+    /// return an exclusive reference to it.
+    ///
+    /// Regular allocations are bumped downwards from the end of a chunk. Front
+    /// allocations are instead bumped upwards from the start of the chunk,
+    /// and share the chunk's free space with regular allocations. This makes
+    /// it possible to keep two kinds of data in one arena, e.g. short-lived
+    /// scratch data at the front, and long-lived results at the back, and to
+    /// release the scratch data with [`reset_front`](Bump::reset_front)
+    /// without disturbing the results.
+    ///
+    /// Front allocations are not visited by
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks), and cannot be
+    /// reallocated or deallocated through the `Bump`'s allocator APIs. They
+    /// are included when the arena's bytes are copied, e.g. by
+    /// [`copy_allocated_bytes_to`](Bump::copy_allocated_bytes_to).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    ///
+    /// for frame in 0..3_u32 {
+    ///     // Short-lived scratch data goes at the front.
+    ///     let scratch = bump.alloc_front([frame; 16]);
+    ///     let sum: u32 = scratch.iter().sum();
+    ///
+    ///     // Long-lived results go at the back.
+    ///     bump.alloc(sum);
+    ///
+    ///     // Release the scratch data, leaving the results in place.
+    ///     bump.reset_front();
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn alloc_front<T>(&self, val: T) -> &mut T {
+        let p = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            self.alloc_front_layout(Layout::new::<T>()).cast::<T>()
+        };
+        let p = p.as_ptr();
+
+        unsafe {
+            ptr::write(p, val);
+            &mut *p
+        }
+    }
+
+    /// Allocate space for an object with the given `Layout` at the front of
+    /// this `Bump`'s current chunk.
+    ///
+    /// See [`alloc_front`](Bump::alloc_front) for details on front allocations.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn alloc_front_layout(&self, layout: Layout) -> NonNull<u8> {
+        self.try_alloc_front_layout(layout)
+            .unwrap_or_else(|_| oom())
+    }
+
+    /// Attempts to allocate space for an object with the given `Layout` at the
+    /// front of this `Bump`'s current chunk, or else returns an `Err`.
+    ///
+    /// See [`alloc_front`](Bump::alloc_front) for details on front allocations.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn try_alloc_front_layout(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let p = if let Some(p) = self.try_alloc_front_layout_fast(layout) {
+            p
+        } else {
+            self.alloc_front_layout_slow(layout)?
+        };
+        unsafe {
+            poison_fresh(p.as_ptr(), layout.size());
+        }
+        self.trace_alloc(p, layout, true);
+        Ok(p)
+    }
+
+    #[inline(always)]
+    fn try_alloc_front_layout_fast(&self, layout: Layout) -> Option<NonNull<u8>> {
+        unsafe {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            let front = footer.front.get().as_ptr();
+            let end = footer.ptr.get().as_ptr();
+            debug_assert!(footer.data.as_ptr() <= front);
+            debug_assert!(front <= end);
+
+            let padding = (front as usize).wrapping_neg() & (layout.align() - 1);
+            let available = end as usize - front as usize;
+            if available < padding || available - padding < layout.size() {
+                return None;
+            }
+
+            let aligned_ptr = front.add(padding);
+            footer
+                .front
+                .set(NonNull::new_unchecked(aligned_ptr.add(layout.size())));
+            asan_unpoison(front, padding + layout.size());
+            Some(NonNull::new_unchecked(aligned_ptr))
+        }
+    }
+
+    /// Slow path for front allocation, for when there isn't enough room in our
+    /// current chunk.
+    #[inline(never)]
+    #[cold]
+    fn alloc_front_layout_slow(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        unsafe {
+            self.replace_current_chunk(layout, false)?;
+
+            // The new chunk's data is aligned to at least `layout.align()` and
+            // has room for at least `layout.size()` bytes.
+            let p = self.try_alloc_front_layout_fast(layout);
+            debug_assert!(p.is_some());
+            p.ok_or(AllocErr::OUT_OF_MEMORY)
+        }
+    }
+
+    /// Reset the front allocations in this arena.
+    ///
+    /// Performs mass deallocation on everything allocated with
+    /// [`alloc_front`](Bump::alloc_front) and friends, while leaving regular
+    /// allocations untouched. Does not run any `Drop` implementations on
+    /// deallocated objects.
+    ///
+    /// Unlike [`reset`](Bump::reset), this does not return any chunks to the
+    /// global allocator.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::with_capacity(100);
+    /// let capacity = bump.chunk_capacity();
+    ///
+    /// bump.alloc_front([0_u8; 50]);
+    /// assert_eq!(bump.chunk_capacity(), capacity - 50);
+    ///
+    /// bump.reset_front();
+    /// assert_eq!(bump.chunk_capacity(), capacity);
+    /// ```
+    pub fn reset_front(&mut self) {
+        // Takes `&mut self` so `self` must be unique and there can't be any
+        // borrows active that would get invalidated by resetting.
+        let mut footer = self.current_chunk_footer.get();
+        unsafe {
+            while !footer.as_ref().is_empty() {
+                let f = footer.as_ref();
+                let len = f.front.get().as_ptr() as usize - f.data.as_ptr() as usize;
+                poison_freed(f.data.as_ptr(), len);
+                asan_poison(f.data.as_ptr(), len);
+                f.front.set(f.data);
+                footer = f.prev.get();
+            }
+        }
+    }
+
+    /// Run `f` with a view of this arena that is specialized for a burst of
+    /// allocations that all share the alignment `ALIGN`.
+    ///
+    /// The bump pointer is rounded down to `ALIGN` once, upon entry, and every
+    /// allocation made through the [`AlignedBump`] has its size rounded up to
+    /// a multiple of `ALIGN`, or of the arena's
+    /// [minimum alignment](Bump::with_min_align) if that is greater. This keeps the bump pointer aligned between
+    /// allocations, so that the allocation fast path can skip the
+    /// per-allocation alignment fixup that [`Bump::alloc`] has to perform. For
+    /// homogeneous allocation loops, e.g. allocating many `u64`s, this removes
+    /// work from the hottest path.
+    ///
+    /// Allocations whose alignment is greater than `ALIGN` are still
+    /// supported, they just don't get to take the specialized fast path.
+    ///
+    /// This method takes `&mut self` so that no other allocations can be
+    /// interleaved with the ones made through the `AlignedBump` while it is
+    /// alive, since they could leave the bump pointer misaligned. References
+    /// returned from the `AlignedBump` may outlive `f`, however.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ALIGN` is not a power of two, or if it is greater than 16.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    ///
+    /// let values = bump.with_align::<8, _>(|aligned| {
+    ///     let mut values = vec![];
+    ///     for i in 0..100_u64 {
+    ///         values.push(aligned.alloc(i));
+    ///     }
+    ///     values
+    /// });
+    ///
+    /// for (i, v) in values.into_iter().enumerate() {
+    ///     assert_eq!(*v, i as u64);
+    /// }
+    /// ```
+    pub fn with_align<'a, const ALIGN: usize, R>(
+        &'a mut self,
+        f: impl FnOnce(&AlignedBump<'a, ALIGN, A>) -> R,
+    ) -> R {
+        assert!(
+            ALIGN.is_power_of_two() && ALIGN <= CHUNK_ALIGN,
+            "`ALIGN` must be a power of two that is at most {}",
+            CHUNK_ALIGN
+        );
+
+        // The arena's own allocations rely on the bump pointer staying aligned
+        // to its minimum alignment, so keep to that, too, if it is greater.
+        let align = ALIGN.max(self.chunk_policy.alloc_align);
+
+        // Round the bump pointer down once up front. This can only fail to
+        // stay within the chunk's free region when there are fewer than
+        // `align` bytes left in it, in which case there is no room to bump
+        // into anyways.
+        self.round_bump_ptr_down_to(align);
+        let result = f(&AlignedBump { bump: self, align });
+        // Allocations through the `AlignedBump` keep the bump pointer aligned
+        // to `align`, but make sure the arena can rely on it regardless.
+        self.round_bump_ptr_down_to(self.chunk_policy.alloc_align);
+        result
+    }
+
+    /// Round the current chunk's bump pointer down to `align`, unless that
+    /// would leave the chunk's free region.
+    fn round_bump_ptr_down_to(&self, align: usize) {
+        unsafe {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            let ptr = round_mut_ptr_down_to(footer.ptr.get().as_ptr(), align);
+            if ptr >= footer.front.get().as_ptr() {
+                let end = footer.ptr.replace(NonNull::new_unchecked(ptr));
+                asan_unpoison(ptr, end.as_ptr() as usize - ptr as usize);
+            }
+        }
+    }
+
+    /// Create a [`BumpWriter`](writer::BumpWriter) that appends bytes to a
+    /// growable allocation in this arena.
+    ///
+    /// The writer implements [`core::fmt::Write`], and `std::io::Write` when
+    /// the `std` Cargo feature is enabled. Once done writing, turn it into a
+    /// `&[u8]` or `&str` that lives as long as the arena. See the
+    /// [`writer`] module for more details.
+    ///
+    /// This does not allocate until something is written.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// let mut w = bump.writer();
+    /// for i in 0..3 {
+    ///     write!(w, "[{}]", i).unwrap();
+    /// }
+    /// assert_eq!(w.into_bump_str().unwrap(), "[0][1][2]");
+    /// ```
+    #[inline]
+    pub fn writer(&self) -> writer::BumpWriter<'_, A> {
+        writer::BumpWriter::new_in(self)
+    }
+
+    /// Allocate a slice of `len` bytes in this arena, and fill it from
+    /// `reader`.
+    ///
+    /// The bytes are read directly into arena memory, without an
+    /// intermediate buffer.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the slice fails, with an error of kind
+    /// [`std::io::ErrorKind::OutOfMemory`], or if reading from `reader` fails,
+    /// as with [`std::io::Read::read_exact`]. The allocation is given back to
+    /// the arena in that case, if it is still the most recent one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let mut reader: &[u8] = b"hello, world";
+    ///
+    /// let hello = bump.alloc_from_reader(&mut reader, 5).unwrap();
+    /// assert_eq!(hello, b"hello");
+    /// assert_eq!(reader, b", world");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn alloc_from_reader<R: std::io::Read + ?Sized>(
+        &self,
+        reader: &mut R,
+        len: usize,
+    ) -> std::io::Result<&mut [u8]> {
+        let layout = Layout::array::<u8>(len)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+        let ptr = self
+            .try_alloc_layout(layout)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+        unsafe {
+            ptr::write_bytes(ptr.as_ptr(), 0, len);
+            let bytes = slice::from_raw_parts_mut(ptr.as_ptr(), len);
+            match reader.read_exact(bytes) {
+                Ok(()) => Ok(bytes),
+                Err(e) => {
+                    self.dealloc(ptr, layout);
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Read everything from `reader` until EOF into a slice in this arena,
+    /// like [`std::io::Read::read_to_end`].
+    ///
+    /// The slice grows in place while it is the arena's most recent
+    /// allocation, and the bytes are read directly into arena memory,
+    /// without an intermediate buffer. Unused capacity is given back to the
+    /// arena at the end. See [`Bump::writer`] for the underlying mechanism.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if growing the slice fails, with an error of kind
+    /// [`std::io::ErrorKind::OutOfMemory`], or if reading from `reader` fails.
+    /// Reads that fail with [`std::io::ErrorKind::Interrupted`] are retried.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let mut reader: &[u8] = &[7; 1000];
+    ///
+    /// let bytes = bump.alloc_read_to_end(&mut reader).unwrap();
+    /// assert_eq!(bytes.len(), 1000);
+    /// assert!(bytes.iter().all(|&b| b == 7));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn alloc_read_to_end<R: std::io::Read + ?Sized>(
+        &self,
+        reader: &mut R,
+    ) -> std::io::Result<&mut [u8]> {
+        let mut writer = self.writer();
+        writer.read_to_end(reader)?;
+        Ok(writer.into_bump_slice())
+    }
+
+    /// Gets the number of bytes that the current chunk can hold in total,
+    /// whether they are allocated already or not.
+    ///
+    /// Unlike [`allocated_bytes`](Bump::allocated_bytes), this leaves out the
+    /// chunk's footer and the rounding done for the backing allocator, so for
+    /// a new arena it is the number of bytes that can be allocated before
+    /// the arena needs another chunk. It is zero if the arena has no chunks
+    /// yet.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_usable_capacity(100);
+    /// let usable = bump.usable_capacity();
+    /// assert_eq!(bump.chunk_capacity(), usable);
+    ///
+    /// bump.alloc(1_u64);
+    /// assert_eq!(bump.usable_capacity(), usable);
+    /// assert_eq!(bump.chunk_capacity(), usable - 8);
+    /// ```
+    pub fn usable_capacity(&self) -> usize {
+        let footer = self.current_chunk_footer.get();
+        unsafe { footer.as_ref() }.usage(true).size
+    }
+
+    /// Gets the remaining capacity in the current chunk (in bytes).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    ///
+    /// let bump = Bump::with_capacity(100);
+    ///
+    /// let capacity = bump.chunk_capacity();
+    /// assert!(capacity >= 100);
+    /// ```
+    pub fn chunk_capacity(&self) -> usize {
+        let current_footer = self.current_chunk_footer.get();
+        let current_footer = unsafe { current_footer.as_ref() };
+
+        current_footer.ptr.get().as_ptr() as usize - current_footer.front.get().as_ptr() as usize
+    }
+
+    /// Gets the number of bytes that can still be allocated in the current
+    /// chunk before allocation takes the slow path and allocates a new chunk.
+    ///
+    /// This is the same as [`chunk_capacity`](Bump::chunk_capacity). Padding
+    /// for the alignment of future allocations also comes out of it, so
+    /// allocations totalling slightly fewer bytes may already spill over into
+    /// a new chunk. Use [`reserve_bytes`](Bump::reserve_bytes) to make sure
+    /// that a given number of bytes is available.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(100);
+    ///
+    /// let before = bump.remaining_capacity();
+    /// bump.alloc([0_u8; 10]);
+    /// assert_eq!(bump.remaining_capacity(), before - 10);
+    /// ```
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.chunk_capacity()
+    }
+
+    /// Gets the number of bytes allocated in the current chunk, including
+    /// front allocations and any padding between allocations.
+    ///
+    /// Together with [`remaining_capacity`](Bump::remaining_capacity), this
+    /// adds up to the size of the current chunk.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(bump.allocated_bytes_in_current_chunk(), 0);
+    ///
+    /// bump.alloc(1_u64);
+    /// bump.alloc_front(1_u32);
+    /// assert_eq!(bump.allocated_bytes_in_current_chunk(), 12);
+    /// ```
+    pub fn allocated_bytes_in_current_chunk(&self) -> usize {
+        let footer = unsafe { self.current_chunk_footer.get().as_ref() };
+        footer.usage(true).used()
+    }
+
+    /// Returns an iterator over the usage of each chunk in this arena.
+    ///
+    /// The chunks are returned ordered by allocation time, with the most
+    /// recently allocated chunk, from which allocations are currently served,
+    /// being returned first.
+    ///
+    /// Unlike [`iter_allocated_chunks`](Bump::iter_allocated_chunks), this does
+    /// not give access to the chunks' contents, so it only needs a shared
+    /// borrow of the arena.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// for i in 0..1000_u64 {
+    ///     bump.alloc(i);
+    /// }
+    ///
+    /// let usage: Vec<_> = bump.iter_chunk_usage().collect();
+    /// assert!(usage.len() > 1);
+    ///
+    /// // Only the current chunk has room left.
+    /// for chunk in &usage[1..] {
+    ///     assert!(chunk.remaining() < 8);
+    /// }
+    ///
+    /// let total: usize = usage.iter().map(|chunk| chunk.size()).sum();
+    /// assert_eq!(total, bump.allocated_bytes());
+    /// ```
+    pub fn iter_chunk_usage(&self) -> ChunkUsageIter<'_> {
+        ChunkUsageIter {
+            footer: self.current_chunk_footer.get(),
+            current: &self.current_chunk_footer,
+        }
+    }
+
+    /// Returns the number of chunks that this arena currently holds.
+    ///
+    /// This is the same as `self.iter_chunk_usage().count()`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// assert_eq!(bump.chunk_count(), 0);
+    ///
+    /// bump.alloc(1_u64);
+    /// assert_eq!(bump.chunk_count(), 1);
+    /// ```
+    pub fn chunk_count(&self) -> usize {
+        self.iter_chunk_usage().count()
+    }
+
+    /// Returns the total capacity, in bytes, of the spare chunks that
+    /// [`reset`](Bump::reset) set aside because this arena
+    /// [defers chunk frees](BumpBuilder::defer_chunk_frees).
+    ///
+    /// Spare chunks are not included in [`chunk_count`](Bump::chunk_count) or
+    /// [`allocated_bytes`](Bump::allocated_bytes). This is always zero for
+    /// arenas that free chunks eagerly.
+    pub fn spare_capacity(&self) -> usize {
+        self.spare_chunks.capacity.get()
+    }
+
+    /// Returns all spare chunks that [`reset`](Bump::reset) set aside to the
+    /// backing allocator.
+    ///
+    /// This takes time proportional to the number of spare chunks, and is
+    /// meant to be called when that latency does not matter, e.g. between
+    /// requests or frames. See [`BumpBuilder::defer_chunk_frees`].
+    pub fn release_spare_chunks(&self) {
+        unsafe {
+            while let Some(footer) = self.spare_chunks.pop() {
+                dealloc_chunk_list(footer, &self.backing);
+            }
+        }
+    }
+
+    /// Ensures that the current chunk has at least `additional` bytes of free
+    /// capacity, allocating a new chunk up front if it does not.
+    ///
+    /// After this returns, allocations totalling at most `additional` bytes,
+    /// including any padding needed to satisfy their alignment, are served
+    /// from the current chunk without taking the slow path that allocates new
+    /// chunks. This is useful before a burst of small allocations whose total
+    /// size is known ahead of time.
+    ///
+    /// If a new chunk is needed, any free space left in the current chunk is
+    /// abandoned, just like when an allocation does not fit.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating a new chunk fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.reserve_bytes(4096);
+    /// assert!(bump.chunk_capacity() >= 4096);
+    ///
+    /// let allocated = bump.allocated_bytes();
+    /// for i in 0..512_u64 {
+    ///     bump.alloc(i);
+    /// }
+    /// // No new chunks were needed.
+    /// assert_eq!(bump.allocated_bytes(), allocated);
+    /// ```
+    pub fn reserve_bytes(&self, additional: usize) {
+        self.try_reserve_bytes(additional).unwrap_or_else(|_| oom())
+    }
+
+    /// Ensures that the current chunk has at least `additional` bytes of free
+    /// capacity, allocating a new chunk up front if it does not, or else
+    /// returns an `Err`.
+    ///
+    /// See [`reserve_bytes`](Bump::reserve_bytes) for details.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating a new chunk fails, for example because it would
+    /// exceed the arena's allocation limit.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(1024));
+    ///
+    /// assert!(bump.try_reserve_bytes(512).is_ok());
+    /// assert!(bump.chunk_capacity() >= 512);
+    ///
+    /// assert!(bump.try_reserve_bytes(1 << 20).is_err());
+    /// ```
+    pub fn try_reserve_bytes(&self, additional: usize) -> Result<(), AllocErr> {
+        if self.chunk_capacity() >= additional {
+            return Ok(());
+        }
+
+        let layout = layout_from_size_align(additional, 1)?;
+        unsafe { self.replace_current_chunk(layout, false).map(|_| ()) }
+    }
+
+    /// Split the free space of the current chunk into `n` disjoint
+    /// [`SubBump`](split::SubBump) allocators, e.g. to allocate on `n`
+    /// threads at once.
+    ///
+    /// The free space is divided evenly, and all of it is taken from the
+    /// arena right away: the arena's own allocations go to a new chunk from
+    /// now on. Anything the sub-allocators allocate stays in the arena until
+    /// it is reset, and space they leave unused is lost until then, too,
+    /// unless it is given back with [`merge`](Bump::merge).
+    /// Reserve room with [`reserve_bytes`](Bump::reserve_bytes) first, since
+    /// the sub-allocators cannot grow. Their allocations are not reported to
+    /// a trace hook.
+    ///
+    /// If `n` is zero, no sub-allocators are returned and nothing is taken.
+    ///
+    /// See the [`split`] module for an example with threads.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.reserve_bytes(1024);
+    ///
+    /// let subs: Vec<_> = bump.split_remaining(2).collect();
+    /// let a = subs[0].alloc(1_u32);
+    /// let b = subs[1].alloc(2_u32);
+    /// assert_eq!((*a, *b), (1, 2));
+    ///
+    /// // The arena itself allocates elsewhere now.
+    /// assert_eq!(bump.chunk_capacity(), 0);
+    /// ```
+    pub fn split_remaining(&self, n: usize) -> split::SplitRemaining<'_> {
+        unsafe {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            let end = footer.ptr.get();
+            // Start at the front rounded up to the minimum alignment, so that
+            // the bump pointer stays aligned to it.
+            let front = footer.front.get().as_ptr();
+            let padding = front.align_offset(self.chunk_policy.alloc_align);
+            let start = if padding <= end.as_ptr() as usize - front as usize {
+                NonNull::new_unchecked(front.add(padding))
+            } else {
+                end
+            };
+            // Nothing to take from an already full chunk, which might be the
+            // shared, immutable empty chunk.
+            if n > 0 && start != end {
+                footer.ptr.set(start);
+            }
+            split::SplitRemaining::new(start, end, n)
+        }
+    }
+
+    /// Give the space that a [`SubBump`](split::SubBump) from
+    /// [`split_remaining`](Bump::split_remaining) left unused back to this
+    /// arena, so that the arena can allocate in it again.
+    ///
+    /// The sub-allocator's free space sits below its allocations, so it can
+    /// only be given back if it borders the arena's own free space: the
+    /// arena must not have moved on to a new chunk since the split, and all
+    /// the sub-allocators handed out before `sub` must have been merged
+    /// already, each of them without allocating anything. So the first one
+    /// can be merged as long as the arena is still on the same chunk.
+    ///
+    /// Returns whether any space was given back. Values that `sub` allocated
+    /// stay where they are either way.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::with_capacity(1024);
+    ///
+    /// let mut subs = bump.split_remaining(2);
+    /// let first = subs.next().unwrap();
+    /// let x = first.alloc(1_u32);
+    /// assert_eq!(bump.chunk_capacity(), 0);
+    ///
+    /// assert!(bump.merge(first));
+    /// assert!(bump.chunk_capacity() > 0);
+    /// let y = bump.alloc(2_u32);
+    /// assert_eq!((*x, *y), (1, 2));
+    ///
+    /// // The first sub-allocator allocated something, which now sits between
+    /// // the arena's free space and the second one's.
+    /// assert!(!bump.merge(subs.next().unwrap()));
+    /// ```
+    pub fn merge(&self, sub: split::SubBump<'_>) -> bool {
+        let (start, free_end) = sub.free_space();
+        if start == free_end {
+            // Nothing to give back, and `start` might be in the shared,
+            // immutable empty chunk.
+            return false;
+        }
+        unsafe {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            // The arena's bump pointer only ever sits exactly at the start of
+            // a sub-allocator that came from its current chunk, so this also
+            // rejects sub-allocators from other arenas and older chunks.
+            if footer.ptr.get() != start {
+                return false;
+            }
+            // Keep the bump pointer aligned to the minimum alignment. Since
+            // `start` already is, this can't go below it.
+            let ptr = round_mut_ptr_down_to(free_end.as_ptr(), self.chunk_policy.alloc_align);
+            debug_assert!(ptr >= start.as_ptr());
+            footer.ptr.set(NonNull::new_unchecked(ptr));
+        }
+        true
+    }
+
+    /// Check whether allocating `layout` would fail because of the
+    /// allocation limit or an absurd size, without allocating anything.
+    ///
+    /// This is [`preflight`](Bump::preflight) for a single layout. See there
+    /// for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(1024));
+    ///
+    /// assert!(bump.can_allocate(Layout::new::<[u8; 512]>()));
+    /// assert!(!bump.can_allocate(Layout::new::<[u8; 4096]>()));
+    /// ```
+    pub fn can_allocate(&self, layout: Layout) -> bool {
+        self.preflight(slice::from_ref(&layout)).is_ok()
+    }
+
+    /// Check whether allocating all of `layouts`, in order, would fail
+    /// because of the allocation limit or an absurd size, without allocating
+    /// anything.
+    ///
+    /// This lets batch jobs fail fast, before doing any partial work. The
+    /// check follows the arena's chunk sizing, as if nothing else were
+    /// allocated in between, and accounts for the worst-case alignment
+    /// padding of each allocation. It can't predict whether the backing
+    /// allocator has the memory, though, nor does it run the
+    /// [limit hook](Bump::set_limit_hook), so a successful check is no
+    /// guarantee that the allocations succeed.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if the layouts don't fit in the current chunk and in new chunks
+    /// under the allocation limit, or if their sizes overflow.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::alloc::Layout;
+    ///
+    /// let bump = bumpalo::Bump::new();
+    /// bump.set_allocation_limit(Some(64 * 1024));
+    ///
+    /// let batch = vec![Layout::new::<[u64; 1024]>(); 4];
+    /// assert!(bump.preflight(&batch).is_ok());
+    ///
+    /// let too_big = vec![Layout::new::<[u64; 1024]>(); 16];
+    /// assert!(bump.preflight(&too_big).is_err());
+    /// ```
+    pub fn preflight(&self, layouts: &[Layout]) -> Result<(), AllocErr> {
+        let alloc_align = self.chunk_policy.alloc_align;
+        let footer = unsafe { self.current_chunk_footer.get().as_ref() };
+        let mut chunk_size = footer.layout.size() - FOOTER_SIZE;
+        let mut free = self.chunk_capacity();
+        let mut allocated_bytes = self.allocated_bytes();
+        let mut limit_remaining = self.allocation_limit_remaining();
+
+        for layout in layouts {
+            let layout = self.padded_layout(*layout).ok_or(AllocErr::SIZE_OVERFLOW)?;
+            let needed = layout
+                .size()
+                .checked_add(layout.align().saturating_sub(alloc_align))
+                .ok_or(AllocErr::SIZE_OVERFLOW)?;
+            if needed <= free {
+                free -= needed;
+                continue;
+            }
+
+            let details = self
+                .new_chunk_sizes(layout, chunk_size, allocated_bytes)
+                .find(|details| Self::chunk_fits_under_limit(limit_remaining, *details))
+                // With a limit, even a size too large to describe a chunk
+                // would exceed it.
+                .ok_or(match limit_remaining {
+                    Some(_) => AllocErr::LIMIT_EXCEEDED,
+                    None => AllocErr::SIZE_OVERFLOW,
+                })?;
+            chunk_size = details.new_size_without_footer;
+            free = chunk_size.saturating_sub(needed);
+            allocated_bytes += chunk_size;
+            if let Some(remaining) = &mut limit_remaining {
+                *remaining -= chunk_size;
+            }
+        }
+        Ok(())
+    }
+
+    /// Slow path allocation for when we need to allocate a new chunk from the
+    /// parent bump set because there isn't enough room in our current chunk.
+    ///
+    /// If `zeroed` is true, the new chunk is zero-initialized.
+    #[inline(never)]
+    #[cold]
+    fn alloc_layout_slow(&self, layout: Layout, zeroed: bool) -> Result<NonNull<u8>, AllocErr> {
+        let layout = self.padded_layout(layout).ok_or(AllocErr::SIZE_OVERFLOW)?;
+        unsafe {
+            let new_footer = self.replace_current_chunk(layout, zeroed)?;
+            Ok(Self::alloc_in_new_chunk(new_footer.as_ref(), layout))
+        }
+    }
+
+    /// Bump allocate `layout` in a chunk that was just allocated with enough
+    /// room for it.
+    #[inline(always)]
+    unsafe fn alloc_in_new_chunk(new_footer: &ChunkFooter, layout: Layout) -> NonNull<u8> {
+        // Move the bump ptr finger down to allocate room for `val`. We know
+        // this can't overflow because we successfully allocated a chunk of
+        // at least the requested size.
+        let mut ptr = new_footer.ptr.get().as_ptr().sub(layout.size());
+        // Round the pointer down to the requested alignment.
+        ptr = round_mut_ptr_down_to(ptr, layout.align());
+        debug_assert!(
+            ptr as *const _ <= new_footer,
+            "{:p} <= {:p}",
+            ptr,
+            new_footer
+        );
+        let ptr = NonNull::new_unchecked(ptr);
+        new_footer.ptr.set(ptr);
+        asan_unpoison(
+            ptr.as_ptr(),
+            new_footer as *const ChunkFooter as usize - ptr.as_ptr() as usize,
+        );
+
+        // Return a pointer to the freshly allocated region in this chunk.
+        ptr
+    }
+
+    /// Allocate a new chunk with enough room for `layout` from the global
+    /// allocator, and make it our current chunk.
+    ///
+    /// If `zeroed` is true, the new chunk is zero-initialized.
+    ///
+    /// Errors with [`AllocErrKind::LimitExceeded`] if the allocation limit kept
+    /// the chunk from being allocated.
+    unsafe fn replace_current_chunk(
+        &self,
+        layout: Layout,
+        zeroed: bool,
+    ) -> Result<NonNull<ChunkFooter>, AllocErr> {
+        if !zeroed {
+            if let Some(new_footer) = self.reuse_spare_chunk(layout) {
+                self.current_chunk_footer.set(new_footer);
+                return Ok(new_footer);
+            }
+        }
+
+        let current_footer = self.current_chunk_footer.get();
+
+        let mut ignore_limit = false;
+        let new_footer = loop {
+            let allocation_limit_remaining = if ignore_limit {
+                None
+            } else {
+                self.allocation_limit_remaining()
+            };
+            let err = match self.new_chunk_for(
+                layout,
+                zeroed,
+                current_footer,
+                allocation_limit_remaining,
+            ) {
+                Ok(new_footer) => break new_footer,
+                Err(err) => err,
+            };
+
+            // Give the limit hook a chance to intervene, but only if the limit
+            // is what kept us from allocating a new chunk.
+            if err == AllocErr::LIMIT_EXCEEDED {
+                match self.run_limit_hook(layout) {
+                    LimitDecision::Retry => continue,
+                    LimitDecision::Allow => {
+                        ignore_limit = true;
+                        continue;
+                    }
+                    LimitDecision::Deny => {}
+                }
+            }
+
+            event!(
+                warn,
+                "bumpalo: out of memory: failed to allocate a chunk for a {}-byte allocation (align {})",
+                layout.size(),
+                layout.align()
+            );
+            return Err(err);
+        };
+
+        debug_assert_eq!(
+            new_footer.as_ref().data.as_ptr() as usize % layout.align(),
+            0
+        );
+
+        // Set the new chunk as our new current chunk.
+        self.current_chunk_footer.set(new_footer);
+
+        Ok(new_footer)
+    }
+
+    /// Take the most recently set aside spare chunk, if `layout` fits in it
+    /// and it fits under the allocation limit, and link it after the current
+    /// chunk.
+    ///
+    /// A spare that is too small for `layout` is freed, since the spares that
+    /// follow it are likely smaller still.
+    unsafe fn reuse_spare_chunk(&self, layout: Layout) -> Option<NonNull<ChunkFooter>> {
+        let head = self.spare_chunks.head.get();
+        let h = head.as_ref();
+        if h.is_empty() {
+            return None;
+        }
+
+        let capacity = h.layout.size() - FOOTER_SIZE;
+        if layout.size() > capacity || layout.align() > h.layout.align() {
+            let footer = self.spare_chunks.pop()?;
+            dealloc_chunk_list(footer, &self.backing);
+            return None;
+        }
+        let fits_under_limit = self
+            .allocation_limit_remaining()
+            .map_or(true, |limit_left| limit_left >= capacity);
+        if !fits_under_limit {
+            return None;
+        }
+
+        let mut footer = self.spare_chunks.pop()?;
+        let end = footer.cast::<u8>();
+        let prev = self.current_chunk_footer.get();
+        let f = footer.as_mut();
+        f.prev.set(prev);
+        f.ptr.set(end);
+        f.front.set(f.data);
+        f.allocated_bytes = prev.as_ref().allocated_bytes + capacity;
+
+        event!(trace, "bumpalo: reused a spare chunk of {} bytes", capacity);
+
+        Some(footer)
+    }
+
+    /// Allocate a new chunk with enough room for `layout`, following `prev`,
+    /// that fits within `allocation_limit_remaining`.
+    ///
+    /// Errors with [`AllocErrKind::LimitExceeded`] if the allocation limit ruled
+    /// out a chunk size, and with [`AllocErrKind::SizeOverflow`] if no chunk size
+    /// could describe a chunk for `layout` at all.
+    unsafe fn new_chunk_for(
+        &self,
+        layout: Layout,
+        zeroed: bool,
+        prev: NonNull<ChunkFooter>,
+        allocation_limit_remaining: Option<usize>,
+    ) -> Result<NonNull<ChunkFooter>, AllocErr> {
+        let current_size = prev.as_ref().layout.size() - FOOTER_SIZE;
+        let mut err = AllocErr::SIZE_OVERFLOW;
+        for chunk_memory_details in
+            self.new_chunk_sizes(layout, current_size, self.allocated_bytes())
+        {
+            if !Self::chunk_fits_under_limit(allocation_limit_remaining, chunk_memory_details) {
+                err = AllocErr::LIMIT_EXCEEDED;
+            } else if let Some(footer) = self.new_chunk(chunk_memory_details, layout, prev, zeroed)
+            {
+                return Ok(footer);
+            } else if err == AllocErr::SIZE_OVERFLOW {
+                err = AllocErr::OUT_OF_MEMORY;
+            }
+        }
+        Err(err)
+    }
+
+    /// The sizes of the chunks to try to allocate, largest first, to make
+    /// room for `layout` after a chunk with `current_size` usable bytes, when
+    /// the arena has allocated `allocated_bytes` so far.
+    fn new_chunk_sizes(
+        &self,
+        layout: Layout,
+        current_size: usize,
+        allocated_bytes: usize,
+    ) -> impl Iterator<Item = NewChunkMemoryDetails> + '_ {
+        // By default, we want our new chunk to be about twice as big
+        // as the previous chunk, or as the chunk policy dictates. If the
+        // global allocator refuses it, we try to divide it by half until it
+        // works or the requested size is smaller than the default footer
+        // size.
+        let ChunkPolicy {
+            growth_factor,
+            max_chunk_size,
+            chunk_align: min_align,
+            alloc_align: _,
+            defer_frees: _,
+            cache_align: _,
+            #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
+                size_class_limit: _,
+        } = self.chunk_policy;
+        let default_chunk_size = match max_chunk_size {
+            Some(max) => DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER.min(max),
+            None => DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER,
+        };
+        let min_new_chunk_size = layout.size().max(default_chunk_size);
+        let policy_size = self
+            .growth_policy()
+            .and_then(|policy| policy.next_chunk_size(current_size, layout));
+        let grown_size = match (policy_size, max_chunk_size) {
+            (Some(size), _) => size,
+            (None, Some(max)) => current_size.saturating_mul(growth_factor).min(max),
+            (None, None) => current_size.saturating_mul(growth_factor),
+        };
+        let mut base_size = grown_size.max(min_new_chunk_size);
+        iter::from_fn(move || loop {
+            // `base_size` must stay positive, or halving it would never
+            // stop for zero-sized layouts.
+            let bypass_min_chunk_size_for_small_limits = matches!(self.allocation_limit(), Some(limit) if layout.size() < limit
+                        && base_size >= layout.size().max(1)
+                        && limit < DEFAULT_CHUNK_SIZE_WITHOUT_FOOTER
+                        && allocated_bytes == 0);
+
+            if base_size >= min_new_chunk_size || bypass_min_chunk_size_for_small_limits {
+                let size = base_size;
+                base_size /= 2;
+                // Sizes too large to describe a chunk at all are skipped like
+                // sizes that the backing allocator refuses.
+                if let Some(details) = Self::new_chunk_memory_details(Some(size), layout, min_align)
+                    .filter(|details| layout_from_size_align(details.size, details.align).is_ok())
+                {
+                    return Some(details);
+                }
+            } else {
+                return None;
+            }
+        })
+    }
+
+    /// Returns an iterator over each chunk of allocated memory that
+    /// this arena has bump allocated into.
+    ///
+    /// The chunks are returned ordered by allocation time, with the most
+    /// recently allocated chunk being returned first, and the least recently
+    /// allocated chunk being returned last.
+    ///
+    /// The values inside each chunk are also ordered by allocation time, with
+    /// the most recent allocation being earlier in the slice, and the least
+    /// recent allocation being towards the end of the slice.
+    ///
+    /// Each slice only covers a chunk's regular allocations: front
+    /// allocations (see [`alloc_front`](Bump::alloc_front)) are at the other
+    /// end of the chunk, and are not included.
+    ///
+    /// ## Safety
+    ///
+    /// Because this method takes `&mut self`, we know that the bump arena
+    /// reference is unique and therefore there aren't any active references to
+    /// any of the objects we've allocated in it either. This potential aliasing
+    /// of exclusive references is one common footgun for unsafe code that we
+    /// don't need to worry about here.
+    ///
+    /// However, there could be regions of uninitialized memory used as padding
+    /// between allocations, which is why this iterator has items of type
+    /// `[MaybeUninit<u8>]`, instead of simply `[u8]`.
+    ///
+    /// The only way to guarantee that there is no padding between allocations
+    /// or within allocated objects is if all of these properties hold:
+    ///
+    /// 1. Every object allocated in this arena has the same alignment,
+    ///    and that alignment is at most 16.
+    /// 2. Every object's size is a multiple of its alignment.
+    /// 3. None of the objects allocated in this arena contain any internal
+    ///    padding.
+    ///
+    /// If you want to use this `iter_allocated_chunks` method, it is *your*
+    /// responsibility to ensure that these properties hold before calling
+    /// `MaybeUninit::assume_init` or otherwise reading the returned values.
+    ///
+    /// Finally, you must also ensure that any values allocated into the bump
+    /// arena have not had their `Drop` implementations called on them,
+    /// e.g. after dropping a [`bumpalo::boxed::Box<T>`][crate::boxed::Box].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    ///
+    /// // Allocate a bunch of `i32`s in this bump arena, potentially causing
+    /// // additional memory chunks to be reserved.
+    /// for i in 0..10000 {
+    ///     bump.alloc(i);
+    /// }
+    ///
+    /// // Iterate over each chunk we've bump allocated into. This is safe
+    /// // because we have only allocated `i32`s in this arena, which fulfills
+    /// // the above requirements.
+    /// for ch in bump.iter_allocated_chunks() {
+    ///     println!("Used a chunk that is {} bytes long", ch.len());
+    ///     println!("The first byte is {:?}", unsafe {
+    ///         ch[0].assume_init()
+    ///     });
+    /// }
+    ///
+    /// // Within a chunk, allocations are ordered from most recent to least
+    /// // recent. If we allocated 'a', then 'b', then 'c', when we iterate
+    /// // through the chunk's data, we get them in the order 'c', then 'b',
+    /// // then 'a'.
+    ///
+    /// bump.reset();
+    /// bump.alloc(b'a');
+    /// bump.alloc(b'b');
+    /// bump.alloc(b'c');
+    ///
+    /// assert_eq!(bump.iter_allocated_chunks().count(), 1);
+    /// let chunk = bump.iter_allocated_chunks().nth(0).unwrap();
+    /// assert_eq!(chunk.len(), 3);
+    ///
+    /// // Safe because we've only allocated `u8`s in this arena, which
+    /// // fulfills the above requirements.
+    /// unsafe {
+    ///     assert_eq!(chunk[0].assume_init(), b'c');
+    ///     assert_eq!(chunk[1].assume_init(), b'b');
+    ///     assert_eq!(chunk[2].assume_init(), b'a');
+    /// }
+    /// ```
+    pub fn iter_allocated_chunks(&mut self) -> ChunkIter<'_> {
+        // SAFE: Ensured by mutable borrow of `self`.
+        let raw = unsafe { self.iter_allocated_chunks_raw() };
+        ChunkIter {
+            raw,
+            bump: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over raw pointers to chunks of allocated memory that
+    /// this arena has bump allocated into.
+    ///
+    /// This is an unsafe version of [`iter_allocated_chunks()`](Bump::iter_allocated_chunks),
+    /// with the caller responsible for safe usage of the returned pointers as
+    /// well as ensuring that the iterator is not invalidated by new
+    /// allocations.
+    ///
+    /// ## Safety
+    ///
+    /// Allocations from this arena must not be performed while the returned
+    /// iterator is alive. If reading the chunk data (or casting to a reference)
+    /// the caller must ensure that there exist no mutable references to
+    /// previously allocated data.
+    ///
+    /// In addition, all of the caveats when reading the chunk data from
+    /// [`iter_allocated_chunks()`](Bump::iter_allocated_chunks) still apply.
+    pub unsafe fn iter_allocated_chunks_raw(&self) -> ChunkRawIter<'_> {
+        ChunkRawIter {
+            footer: self.current_chunk_footer.get(),
+            bump: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over every value allocated in this arena, for
+    /// arenas that only hold values of a single type `T`.
+    ///
+    /// This is a typed view of
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks): the values are
+    /// returned ordered by allocation time, most recent first.
+    ///
+    /// Before iterating, every chunk is checked to be a whole number of
+    /// properly aligned `T`s. This catches many, but not all, violations of
+    /// the safety requirements below.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `T` is zero-sized or has an alignment greater than 16, or if
+    /// any chunk's allocated bytes are not a properly aligned, whole number of
+    /// `T`s.
+    ///
+    /// ## Safety
+    ///
+    /// Every allocation in this arena, apart from front allocations (see
+    /// [`alloc_front`](Bump::alloc_front)), must be a `T`, or a slice of `T`s,
+    /// that has not been dropped, e.g. via a
+    /// [`bumpalo::boxed::Box<T>`][crate::boxed::Box].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// for i in 0..10_000_u64 {
+    ///     bump.alloc(i);
+    /// }
+    ///
+    /// // Safe because we have only allocated `u64`s in this arena.
+    /// let items = unsafe { bump.iter_allocated_items::<u64>() };
+    /// assert!(items.copied().eq((0..10_000).rev()));
+    /// ```
+    pub unsafe fn iter_allocated_items<T>(&mut self) -> AllocatedItemsIter<'_, T> {
+        let size = mem::size_of::<T>();
+        let align = mem::align_of::<T>();
+        assert!(size != 0, "zero-sized types are not supported");
+        assert!(
+            align <= SUPPORTED_ITER_ALIGNMENT,
+            "alignments greater than {} are not supported",
+            SUPPORTED_ITER_ALIGNMENT
+        );
+        for (ptr, len) in self.iter_allocated_chunks_raw() {
+            assert!(
+                ptr as usize % align == 0 && len % size == 0,
+                "chunk is not a whole number of aligned values"
+            );
+        }
+        AllocatedItemsIter {
+            chunks: self.iter_allocated_chunks_raw(),
+            items: [].iter(),
+        }
+    }
+
+    /// Append the allocated bytes of every chunk in this arena to `out`, and
+    /// return a map from addresses in the arena to offsets in `out`.
+    ///
+    /// The chunks are copied in allocation order, least recently allocated
+    /// chunk first, so that the output is laid out in a predictable way. This
+    /// makes it possible to build a data structure in the arena that refers to
+    /// its parts with offsets rather than pointers, and then cache it on disk
+    /// or ship it elsewhere as a single contiguous buffer. Use the returned
+    /// [`AllocatedBytesMap`] to translate pointers into the arena into
+    /// offsets into `out`.
+    ///
+    /// Front allocations (see [`alloc_front`](Bump::alloc_front)) are copied
+    /// too, each chunk's just before its regular allocations.
+    ///
+    /// ## Safety
+    ///
+    /// Every allocated byte in the arena must be initialized; in particular,
+    /// there must not be any padding bytes in or between allocations. See
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks) for the
+    /// conditions under which this is the case.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if growing `out` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// let hello = bump.alloc_slice_copy(b"hello") as *const [u8];
+    /// let world = bump.alloc_slice_copy(b"world") as *const [u8];
+    ///
+    /// let mut out = Vec::new();
+    /// // Safe because we've only allocated `u8`s in this arena, so there is
+    /// // no uninitialized padding.
+    /// let map = unsafe { bump.copy_allocated_bytes_to(&mut out) };
+    ///
+    /// let hello = map.offset_of(hello).unwrap();
+    /// let world = map.offset_of(world).unwrap();
+    /// assert_eq!(&out[hello..hello + 5], b"hello");
+    /// assert_eq!(&out[world..world + 5], b"world");
+    /// ```
+    pub unsafe fn copy_allocated_bytes_to(
+        &mut self,
+        out: &mut core_alloc::vec::Vec<u8>,
+    ) -> AllocatedBytesMap {
+        let mut regions = self.allocated_regions_oldest_first();
+        let total = regions.iter().map(|r| r.len).sum();
+        out.reserve(total);
+        for region in &mut regions {
+            region.offset = out.len();
+            out.extend_from_slice(slice::from_raw_parts(
+                region.address as *const u8,
+                region.len,
+            ));
+        }
+
+        AllocatedBytesMap { regions }
+    }
+
+    /// The allocated bytes of every chunk, least recently allocated chunk
+    /// first, with their offsets still to be filled in. A chunk's front
+    /// allocations, if any, come right before its regular allocations.
+    unsafe fn allocated_regions_oldest_first(&mut self) -> core_alloc::vec::Vec<CopiedRegion> {
+        let mut regions = core_alloc::vec::Vec::new();
+        let mut footer = self.current_chunk_footer.get();
+        while !footer.as_ref().is_empty() {
+            let f = footer.as_ref();
+            let (ptr, len) = f.as_raw_parts();
+            regions.push(CopiedRegion {
+                address: ptr as usize,
+                offset: 0,
+                len,
+            });
+            let (front, front_len) = f.front_raw_parts();
+            if front_len != 0 {
+                regions.push(CopiedRegion {
+                    address: front as usize,
+                    offset: 0,
+                    len: front_len,
+                });
+            }
+            footer = f.prev.get();
+        }
+        regions.reverse();
+        regions
+    }
+
+    /// Calculates the number of bytes currently allocated across all chunks in
+    /// this bump arena.
+    ///
+    /// If you allocate types of different alignments or types with
+    /// larger-than-typical alignment in the same arena, some padding
+    /// bytes might get allocated in the bump arena. Note that those padding
+    /// bytes will add to this method's resulting sum, so you cannot rely
+    /// on it only counting the sum of the sizes of the things
+    /// you've allocated in the arena.
+    ///
+    /// The allocated bytes do not include the size of bumpalo's metadata,
+    /// so the amount of memory requested from the Rust allocator is higher
+    /// than the returned value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let _x = bump.alloc_slice_fill_default::<u32>(5);
+    /// let bytes = bump.allocated_bytes();
+    /// assert!(bytes >= core::mem::size_of::<u32>() * 5);
+    /// ```
+    pub fn allocated_bytes(&self) -> usize {
+        let footer = self.current_chunk_footer.get();
+
+        unsafe { footer.as_ref().allocated_bytes }
+    }
+
+    /// Calculates the number of bytes requested from the Rust allocator for this `Bump`.
+    ///
+    /// This number is equal to the [`allocated_bytes()`](Self::allocated_bytes) plus
+    /// the size of the bump metadata.
+    pub fn allocated_bytes_including_metadata(&self) -> usize {
+        let metadata_size =
+            unsafe { self.iter_allocated_chunks_raw().count() * mem::size_of::<ChunkFooter>() };
+        self.allocated_bytes() + metadata_size
+    }
+
+    /// Takes a snapshot of this arena's memory usage, e.g. to report it as
+    /// metrics.
+    ///
+    /// With the `serde` Cargo feature, the snapshot can be serialized, to emit
+    /// it into structured logs as a whole.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.alloc_slice_fill_default::<u64>(10);
+    ///
+    /// let stats = bump.stats();
+    /// assert_eq!(stats.chunk_count(), 1);
+    /// assert_eq!(stats.used_bytes(), 80);
+    /// assert_eq!(stats.allocated_bytes(), bump.allocated_bytes());
+    /// ```
+    pub fn stats(&self) -> BumpStats {
+        BumpStats {
+            allocated_bytes: self.allocated_bytes(),
+            allocated_bytes_including_metadata: self.allocated_bytes_including_metadata(),
+            spare_capacity: self.spare_capacity(),
+            allocation_limit: self.allocation_limit(),
+            chunks: self.iter_chunk_usage().collect(),
+        }
+    }
+
+    /// Returns `true` if `ptr` points into memory that was allocated in this
+    /// arena, and hasn't been freed by resetting it.
+    ///
+    /// This checks the allocated parts of each of the arena's chunks, e.g. to
+    /// assert that a data structure only refers to values in its own arena.
+    /// Bytes that were handed back with `dealloc` or a shrinking `realloc`
+    /// count as allocated, unless a later allocation made them the free part
+    /// of the current chunk again. Pointers to allocations of zero-sized
+    /// types are dangling, and not in the arena.
+    ///
+    /// This walks the arena's chunks, so it takes time linear in their
+    /// number.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let other = bumpalo::Bump::new();
+    ///
+    /// let x = bump.alloc(1_u32);
+    /// let y = other.alloc(2_u32);
+    ///
+    /// assert!(bump.contains_ptr(x as *const u32 as *const u8));
+    /// assert!(!bump.contains_ptr(y as *const u32 as *const u8));
+    /// ```
+    pub fn contains_ptr(&self, ptr: *const u8) -> bool {
+        let addr = ptr as usize;
+        let mut footer = self.current_chunk_footer.get();
+        unsafe {
+            while !footer.as_ref().is_empty() {
+                let f = footer.as_ref();
+                let back = f.ptr.get().as_ptr() as usize..footer.as_ptr() as usize;
+                let front = f.data.as_ptr() as usize..f.front.get().as_ptr() as usize;
+                if back.contains(&addr) || front.contains(&addr) {
+                    return true;
+                }
+                footer = f.prev.get();
+            }
+        }
+        false
+    }
+
+    /// Undo an allocation whose initialization failed, if it is still the
+    /// last allocation in this arena. `rewind_footer` and `rewind_ptr` are
+    /// the current chunk and its bump pointer from before the allocation.
+    unsafe fn rewind_failed_init(
+        &self,
+        ptr: NonNull<u8>,
+        rewind_footer: NonNull<ChunkFooter>,
+        rewind_ptr: NonNull<u8>,
+    ) {
+        // Sometimes we can do even better than simply calling `dealloc` on
+        // the pointer: we can reclaim any alignment padding we might have
+        // added (which `dealloc` cannot do) if we didn't allocate a new chunk
+        // for this allocation.
+        if self.is_last_allocation(ptr) {
+            let current_footer_p = self.current_chunk_footer.get();
+            let current_ptr = &current_footer_p.as_ref().ptr;
+            if current_footer_p == rewind_footer {
+                // It's still the same chunk, so reset the bump pointer to its
+                // original value from before the allocation (reclaiming any
+                // alignment padding we may have added).
+                current_ptr.set(rewind_ptr);
+            } else {
+                // We allocated a new chunk for this allocation.
+                //
+                // We know it is the only allocation in this chunk: Any
+                // additional allocations since could only have happened when
+                // running the initializer function, which is called *after*
+                // reserving space. Therefore, since we already determined via
+                // the check above that this was the last allocation, there
+                // must not have been any other allocations, and this is the
+                // only allocation in this chunk.
+                //
+                // Because this is the only allocation in this chunk, we can
+                // reset the chunk's bump finger to the end of the chunk, where
+                // it started.
+                current_ptr.set(current_footer_p.cast());
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn is_last_allocation(&self, ptr: NonNull<u8>) -> bool {
+        let footer = self.current_chunk_footer.get();
+        let footer = footer.as_ref();
+        footer.ptr.get() == ptr
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+        poison_freed(ptr.as_ptr(), layout.size());
+        asan_poison(ptr.as_ptr(), layout.size());
+
+        // If the pointer is the last allocation we made, we can reuse the bytes,
+        // otherwise they are simply leaked -- at least until somebody calls reset().
+        if self.is_last_allocation(ptr) {
+            // The allocation took up its size padded to the minimum
+            // alignment, and giving all of that back keeps the bump pointer
+            // aligned to it.
+            let min_align = self.chunk_policy.alloc_align;
+            let size = (layout.size() + min_align - 1) & !(min_align - 1);
+            let ptr = self.current_chunk_footer.get().as_ref().ptr.get();
+            let ptr = NonNull::new_unchecked(ptr.as_ptr().add(size));
+            self.current_chunk_footer.get().as_ref().ptr.set(ptr);
+        }
+    }
+
+    #[inline]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocErr> {
+        // If the new layout demands greater alignment than the old layout has,
+        // then either
+        //
+        // 1. the pointer happens to satisfy the new layout's alignment, so we
+        //    got lucky and can return the pointer as-is, or
+        //
+        // 2. the pointer is not aligned to the new layout's demanded alignment,
+        //    and we are unlucky.
+        //
+        // In the case of (2), to successfully "shrink" the allocation, we would
+        // have to allocate a whole new region for the new layout, without being
+        // able to free the old region. That is unacceptable, so simply return
+        // an allocation failure error instead.
+        if old_layout.align() < new_layout.align() {
+            if is_pointer_aligned_to(ptr.as_ptr(), new_layout.align()) {
+                return Ok(ptr);
+            } else {
+                return Err(AllocErr::OUT_OF_MEMORY);
+            }
+        }
+
+        debug_assert!(is_pointer_aligned_to(ptr.as_ptr(), new_layout.align()));
+
+        let old_size = old_layout.size();
+        let new_size = new_layout.size();
+
+        // This is how much space we would *actually* reclaim while satisfying
+        // the requested alignment.
+        let delta = round_down_to(
+            old_size - new_size,
+            new_layout.align().max(self.chunk_policy.alloc_align),
+        );
+
+        if delta != 0 && self.is_last_allocation(ptr) {
+            let footer = self.current_chunk_footer.get();
+            let footer = footer.as_ref();
+
+            // NB: new_ptr is aligned, because ptr *has to* be aligned, and we
+            // made sure delta is aligned.
+            let new_ptr = NonNull::new_unchecked(footer.ptr.get().as_ptr().add(delta));
+            footer.ptr.set(new_ptr);
+
+            // The free end of the chunk is below the allocation, so the bytes
+            // we keep move up to the end of the old region, and the released
+            // bytes are at its start. The two ranges overlap when less than
+            // half of the allocation is released.
+            ptr::copy(ptr.as_ptr(), new_ptr.as_ptr(), new_size);
+            poison_freed(ptr.as_ptr(), delta);
+            asan_poison(ptr.as_ptr(), delta);
+
+            return Ok(new_ptr);
+        }
+
+        // If this wasn't the last allocation, the released bytes can't be
+        // reused until the arena is reset, so simply return the old pointer
+        // as-is.
+        Ok(ptr)
+    }
+
+    #[inline]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocErr> {
+        let old_size = old_layout.size();
+        let new_size = new_layout.size();
+
+        if self.is_last_allocation(ptr) {
+            // Try to allocate the delta size within this same block so we can
+            // reuse the currently allocated space. Aligning the delta to the
+            // stricter of the two alignments aligns the grown allocation too,
+            // since it starts at the delta and ends where the old one did.
+            let delta = new_size - old_size;
+            let align = old_layout.align().max(new_layout.align());
+            if let Some(p) = self.try_alloc_layout_fast(layout_from_size_align(delta, align)?) {
+                ptr::copy(ptr.as_ptr(), p.as_ptr(), old_size);
+                return Ok(p);
+            }
+
+            if let Some(p) = self.grow_into_new_chunk(ptr, old_layout, new_layout) {
+                return Ok(p);
+            }
+        }
+
+        // Fallback: do a fresh allocation and copy the existing data into it.
+        let new_ptr = self.try_alloc_layout(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_size);
+        Ok(new_ptr)
+    }
+
+    /// Grow `ptr`, the last allocation, into a new chunk when it is the only
+    /// allocation in the current chunk, and free the old chunk afterwards.
+    ///
+    /// Otherwise, a buffer that repeatedly outgrows its chunk, like a `Vec`
+    /// being pushed onto, would leave every chunk it has outgrown behind,
+    /// holding nothing but a stale copy of its contents until the arena is
+    /// reset or dropped.
+    ///
+    /// Returns `None`, without changing anything, if the chunk holds other
+    /// allocations or a new chunk cannot be allocated. The caller falls back
+    /// to a regular allocation in that case, which also gives the allocation
+    /// limit hook its chance to run.
+    #[cold]
+    unsafe fn grow_into_new_chunk(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<u8>> {
+        let old_footer = self.current_chunk_footer.get();
+        let old = old_footer.as_ref();
+        let padded_old_layout = self.padded_layout(old_layout)?;
+        let padded_layout = self.padded_layout(new_layout)?;
+
+        // The allocation has to span everything from the bump finger to the
+        // end of the chunk, and nothing may have been allocated at the front.
+        if old.is_empty()
+            || old.front.get() != old.data
+            || ptr.as_ptr().add(padded_old_layout.size()) != old_footer.as_ptr().cast::<u8>()
+        {
+            return None;
+        }
+
+        // Size the new chunk as if it followed the old one, so that growth
+        // stays geometric, but link it to the old chunk's predecessor, since
+        // the old chunk is about to go away.
+        let mut new_footer = self
+            .new_chunk_for(
+                padded_layout,
+                false,
+                old_footer,
+                self.allocation_limit_remaining(),
+            )
+            .ok()?;
+        let prev = old.prev.get();
+        new_footer.as_ref().prev.set(prev);
+        new_footer.as_mut().allocated_bytes -= old.allocated_bytes - prev.as_ref().allocated_bytes;
+        self.current_chunk_footer.set(new_footer);
+
+        // Copy while the old chunk is still around, then free it.
+        let new_ptr = Self::alloc_in_new_chunk(new_footer.as_ref(), padded_layout);
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+        if self.reset_policy() == ResetPolicy::Zero {
+            old.zero_used_bytes(0);
+        }
+        old.prev.set(EMPTY_CHUNK.get());
+        dealloc_chunk_list(old_footer, &self.backing);
+
+        Some(new_ptr)
+    }
+}
+
+impl<A: BackingAllocator + Clone> Bump<A> {
+    /// Consume this arena, transferring ownership of each of its chunks of
+    /// memory to an [`OwnedChunkBytes`] handle.
+    ///
+    /// Unlike the references returned from allocation methods, the resulting
+    /// handles do not borrow the `Bump`, and are `Send`. This allows the
+    /// contents of a finished arena to be shipped to other threads, or written
+    /// out elsewhere, without copying them into new buffers first. Each chunk's
+    /// memory is deallocated when its handle is dropped, through a clone of
+    /// the arena's backing allocator, and is zeroed first if the arena's
+    /// [reset policy](Bump::set_reset_policy) is [`ResetPolicy::Zero`].
+    ///
+    /// The chunks are returned in the same order as
+    /// [`iter_allocated_chunks`](Bump::iter_allocated_chunks): most recently
+    /// allocated first. Chunks that do not contain any allocations are still
+    /// returned, with an empty used-bytes range. A chunk's front allocations
+    /// (see [`alloc_front`](Bump::alloc_front)) are available through
+    /// [`OwnedChunkBytes::front`].
+    ///
+    /// No `Drop` implementations are run for values allocated in this arena.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the returned `Vec` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.alloc_slice_copy(b"hello");
+    ///
+    /// let chunks = bump.into_bytes_chunks();
+    /// assert_eq!(chunks.len(), 1);
+    ///
+    /// let handle = std::thread::spawn(move || {
+    ///     // Safe because we've only allocated `u8`s in this arena, so there
+    ///     // is no uninitialized padding.
+    ///     unsafe { chunks[0].as_bytes().to_vec() }
+    /// });
+    /// assert_eq!(handle.join().unwrap(), b"hello");
+    /// ```
+    pub fn into_bytes_chunks(mut self) -> core_alloc::vec::Vec<OwnedChunkBytes<A>> {
+        self.run_drop_list();
+        // Reserve space up front, so that we can't panic after we've taken
+        // ownership of the chunks away from `self`.
+        let num_chunks = unsafe { self.iter_allocated_chunks_raw().count() };
+        let mut chunks = core_alloc::vec::Vec::with_capacity(num_chunks);
+        self.release_spare_chunks();
+
+        let zero = self.reset_policy() == ResetPolicy::Zero;
+        let mut footer = self.current_chunk_footer.get();
+        let this = mem::ManuallyDrop::new(self);
+        // Safety: `this` is never used or dropped again.
+        let backing = unsafe { ptr::read(&this.backing) };
+
+        unsafe {
+            while !footer.as_ref().is_empty() {
+                let f = footer.as_ref();
+                let (ptr, len) = f.as_raw_parts();
+                let (_, front_len) = f.front_raw_parts();
+                chunks.push(OwnedChunkBytes {
+                    data: f.data,
+                    layout: f.layout,
+                    ptr: NonNull::new_unchecked(ptr as *mut u8),
+                    len,
+                    front_len,
+                    zero,
+                    backing: backing.clone(),
+                });
+                footer = f.prev.get();
+            }
+        }
+
+        debug_assert_eq!(chunks.len(), num_chunks);
+        chunks
+    }
+}
+
+/// The result of a phase of allocations, detached from the rest of the arena
+/// by [`Bump::detach_last_allocation`].
+///
+/// The guard dereferences to the result. When it is dropped, it drops the
+/// result and resets the arena, so that the result's memory can be reused.
+/// While the guard is alive, [`Detached::bump`] can allocate in the arena
+/// below the result.
+pub struct Detached<'a, T, A: BackingAllocator = Global> {
+    bump: &'a mut Bump<A>,
+    value: NonNull<T>,
+    _value: PhantomData<T>,
+}
+
+impl<'a, T, A: BackingAllocator> Detached<'a, T, A> {
+    /// ## Safety
+    ///
+    /// `value` must point to a valid `T` that is allocated in `bump`, and that
+    /// nothing else refers to.
+    unsafe fn new(bump: &'a mut Bump<A>, value: NonNull<T>) -> Detached<'a, T, A> {
+        Detached {
+            bump,
+            value,
+            _value: PhantomData,
+        }
+    }
+
+    /// Returns the arena that the result is allocated in.
+    ///
+    /// Everything that is allocated through it is reset along with the result
+    /// when the guard is dropped.
+    #[inline]
+    pub fn bump<'b>(this: &'b Detached<'a, T, A>) -> &'b Bump<A> {
+        this.bump
+    }
+
+    /// Keeps the result allocated until the arena is reset next, and returns
+    /// an exclusive reference to it. Its `Drop` implementation is never run.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, Detached};
+    ///
+    /// let mut bump = Bump::new();
+    /// let x = Detached::leak(bump.detach_last_allocation(|bump| bump.alloc(5)));
+    /// *x += 1;
+    /// assert_eq!(*x, 6);
+    /// ```
+    #[inline]
+    pub fn leak(this: Detached<'a, T, A>) -> &'a mut T {
+        let this = mem::ManuallyDrop::new(this);
+        unsafe { &mut *this.value.as_ptr() }
+    }
+
+    /// Moves the result out of the arena, and resets the arena.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, Detached};
+    ///
+    /// let mut bump = Bump::new();
+    /// let s = bump.detach_last_allocation(|bump| bump.alloc(String::from("hi")));
+    /// assert_eq!(Detached::into_inner(s), "hi");
+    /// ```
+    pub fn into_inner(this: Detached<'a, T, A>) -> T {
+        let this = mem::ManuallyDrop::new(this);
+        // Safety: `this` is never used or dropped again.
+        unsafe {
+            let value = ptr::read(this.value.as_ptr());
+            ptr::read(&this.bump).reset();
+            value
+        }
+    }
+}
+
+impl<T, A: BackingAllocator> core::ops::Deref for Detached<'_, T, A> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T, A: BackingAllocator> core::ops::DerefMut for Detached<'_, T, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<T, A: BackingAllocator> Drop for Detached<'_, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.value.as_ptr());
+        }
+        self.bump.reset();
+    }
+}
+
+impl<T: fmt::Debug, A: BackingAllocator> fmt::Debug for Detached<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// An iterator over each chunk of allocated memory that
+/// an arena has bump allocated into.
+///
+/// The chunks are returned ordered by allocation time, with the most recently
+/// allocated chunk being returned first.
+///
+/// The values inside each chunk are also ordered by allocation time, with the most
+/// recent allocation being earlier in the slice.
+///
+/// This struct is created by the [`iter_allocated_chunks`] method on
+/// [`Bump`]. See that function for a safety description regarding reading from the returned items.
+///
+/// [`Bump`]: struct.Bump.html
+/// [`iter_allocated_chunks`]: struct.Bump.html#method.iter_allocated_chunks
+#[derive(Debug)]
+pub struct ChunkIter<'a> {
+    raw: ChunkRawIter<'a>,
+    bump: PhantomData<&'a mut Bump>,
+}
+
+impl<'a> Iterator for ChunkIter<'a> {
+    type Item = &'a [mem::MaybeUninit<u8>];
+    fn next(&mut self) -> Option<&'a [mem::MaybeUninit<u8>]> {
+        unsafe {
+            let (ptr, len) = self.raw.next()?;
+            let slice = slice::from_raw_parts(ptr as *const mem::MaybeUninit<u8>, len);
+            Some(slice)
+        }
+    }
+}
+
+impl<'a> iter::FusedIterator for ChunkIter<'a> {}
+
+/// An iterator over every value allocated in an arena that only holds values
+/// of a single type.
+///
+/// This struct is created by the [`iter_allocated_items`] method on
+/// [`Bump`]. See that function for more details.
+///
+/// [`Bump`]: struct.Bump.html
+/// [`iter_allocated_items`]: struct.Bump.html#method.iter_allocated_items
+#[derive(Debug)]
+pub struct AllocatedItemsIter<'a, T> {
+    chunks: ChunkRawIter<'a>,
+    items: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for AllocatedItemsIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(item);
+            }
+            let (ptr, len) = self.chunks.next()?;
+            // SAFE: validated by `iter_allocated_items`, whose caller
+            // guarantees that the chunk only holds live `T`s.
+            self.items =
+                unsafe { slice::from_raw_parts(ptr as *const T, len / mem::size_of::<T>()).iter() };
+        }
+    }
+}
+
+impl<T> iter::FusedIterator for AllocatedItemsIter<'_, T> {}
+
+/// An iterator over raw pointers to chunks of allocated memory that this
+/// arena has bump allocated into.
+///
+/// See [`ChunkIter`] for details regarding the returned chunks.
+///
+/// This struct is created by the [`iter_allocated_chunks_raw`] method on
+/// [`Bump`]. See that function for a safety description regarding reading from
+/// the returned items.
+///
+/// [`Bump`]: struct.Bump.html
+/// [`iter_allocated_chunks_raw`]: struct.Bump.html#method.iter_allocated_chunks_raw
+#[derive(Debug)]
+pub struct ChunkRawIter<'a> {
+    footer: NonNull<ChunkFooter>,
+    bump: PhantomData<&'a Bump>,
+}
+
+impl Iterator for ChunkRawIter<'_> {
+    type Item = (*mut u8, usize);
+    fn next(&mut self) -> Option<(*mut u8, usize)> {
+        unsafe {
+            let foot = self.footer.as_ref();
+            if foot.is_empty() {
+                return None;
+            }
+            let (ptr, len) = foot.as_raw_parts();
+            self.footer = foot.prev.get();
+            Some((ptr as *mut u8, len))
+        }
+    }
+}
+
+impl iter::FusedIterator for ChunkRawIter<'_> {}
+
+/// An iterator over the usage of each chunk in an arena.
+///
+/// This struct is created by the [`iter_chunk_usage`] method on [`Bump`]. See
+/// that function for more details.
+///
+/// [`Bump`]: struct.Bump.html
+/// [`iter_chunk_usage`]: struct.Bump.html#method.iter_chunk_usage
+#[derive(Debug)]
+pub struct ChunkUsageIter<'a> {
+    footer: NonNull<ChunkFooter>,
+    current: &'a CurrentChunk,
+}
+
+impl Iterator for ChunkUsageIter<'_> {
+    type Item = ChunkUsage;
+    fn next(&mut self) -> Option<ChunkUsage> {
+        // Chunks are only deallocated through `&mut Bump`, so every chunk
+        // stays alive while this iterator borrows the arena, even if new
+        // chunks are allocated in the meantime.
+        unsafe {
+            let foot = self.footer.as_ref();
+            if foot.is_empty() {
+                return None;
+            }
+            let is_current = self.footer == self.current.get();
+            self.footer = foot.prev.get();
+            Some(foot.usage(is_current))
+        }
+    }
+}
+
+impl iter::FusedIterator for ChunkUsageIter<'_> {}
+
+/// How much of a chunk of an arena is in use.
+///
+/// This is returned by [`Bump::iter_chunk_usage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkUsage {
+    size: usize,
+    used: usize,
+    is_current: bool,
+}
+
+impl ChunkUsage {
+    /// The number of bytes in this chunk that can be allocated, not counting
+    /// bumpalo's metadata.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of bytes in this chunk that are allocated, including any
+    /// padding between allocations.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// The number of bytes in this chunk that are still free.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.size - self.used
+    }
+
+    /// Whether this is the arena's current chunk, from which new allocations
+    /// are served.
+    ///
+    /// The free space of every other chunk is never allocated from again
+    /// until the arena is reset, so it counts towards fragmentation.
+    #[inline]
+    pub fn is_current(&self) -> bool {
+        self.is_current
+    }
+}
+
+/// A snapshot of an arena's memory usage.
+///
+/// This is returned by [`Bump::stats`], and serializable with the `serde`
+/// Cargo feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BumpStats {
+    allocated_bytes: usize,
+    allocated_bytes_including_metadata: usize,
+    spare_capacity: usize,
+    allocation_limit: Option<usize>,
+    chunks: core_alloc::vec::Vec<ChunkUsage>,
+}
+
+impl BumpStats {
+    /// The total size of the arena's chunks, as reported by
+    /// [`Bump::allocated_bytes`].
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocated_bytes
+    }
+
+    /// The number of bytes requested from the backing allocator, as reported
+    /// by [`Bump::allocated_bytes_including_metadata`].
+    #[inline]
+    pub fn allocated_bytes_including_metadata(&self) -> usize {
+        self.allocated_bytes_including_metadata
+    }
+
+    /// The number of bytes allocated across all chunks, including any padding
+    /// between allocations.
+    pub fn used_bytes(&self) -> usize {
+        self.chunks.iter().map(ChunkUsage::used).sum()
+    }
+
+    /// The number of chunks that the arena held.
+    #[inline]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// The total capacity of the arena's spare chunks, as reported by
+    /// [`Bump::spare_capacity`].
+    #[inline]
+    pub fn spare_capacity(&self) -> usize {
+        self.spare_capacity
+    }
+
+    /// The arena's allocation limit, if any.
+    #[inline]
+    pub fn allocation_limit(&self) -> Option<usize> {
+        self.allocation_limit
+    }
+
+    /// The usage of each chunk, with the current chunk first, as returned by
+    /// [`Bump::iter_chunk_usage`].
+    #[inline]
+    pub fn chunks(&self) -> &[ChunkUsage] {
+        &self.chunks
+    }
+}
+
+/// An owned chunk of memory that used to belong to a [`Bump`] arena.
+///
+/// The handle owns the whole chunk allocation, and deallocates it when
+/// dropped. It dereferences to the chunk's used bytes: the region that
+/// allocations were bump allocated into. The chunk's front allocations are
+/// available through [`front`](OwnedChunkBytes::front).
+///
+/// This struct is created by the [`into_bytes_chunks`] method on [`Bump`]. The
+/// same caveats regarding padding and uninitialized memory that apply to
+/// [`iter_allocated_chunks`] apply to the used bytes of an `OwnedChunkBytes`.
+///
+/// [`Bump`]: struct.Bump.html
+/// [`into_bytes_chunks`]: struct.Bump.html#method.into_bytes_chunks
+/// [`iter_allocated_chunks`]: struct.Bump.html#method.iter_allocated_chunks
+#[derive(Debug)]
+pub struct OwnedChunkBytes<A: BackingAllocator = Global> {
+    // The start and layout of the whole chunk allocation.
+    data: NonNull<u8>,
+    layout: Layout,
+
+    // The start and length of the used region within the chunk.
+    ptr: NonNull<u8>,
+    len: usize,
+
+    // The length of the front allocations at the start of the chunk.
+    front_len: usize,
+
+    // Whether to zero the used bytes before deallocating the chunk.
+    zero: bool,
+
+    backing: A,
+}
+
+// `OwnedChunkBytes` uniquely owns its chunk, and nothing else can alias the
+// chunk's memory once the `Bump` it came from has been consumed.
+unsafe impl<A: BackingAllocator + Send> Send for OwnedChunkBytes<A> {}
+unsafe impl<A: BackingAllocator + Sync> Sync for OwnedChunkBytes<A> {}
+
+impl<A: BackingAllocator> OwnedChunkBytes<A> {
+    /// Get the number of used bytes in this chunk.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes were used in this chunk.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a raw pointer to the start of this chunk's used bytes.
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Get this chunk's used bytes as a slice of initialized bytes.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that none of the used bytes are uninitialized
+    /// padding. See [`Bump::iter_allocated_chunks`] for the conditions under
+    /// which this is the case.
+    pub unsafe fn as_bytes(&self) -> &[u8] {
+        slice::from_raw_parts(self.ptr.as_ptr(), self.len)
+    }
+
+    /// Get the bytes of this chunk's front allocations (see
+    /// [`Bump::alloc_front`]), oldest allocation first.
+    ///
+    /// The same caveats regarding padding and uninitialized memory apply as
+    /// for the used bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// bump.alloc_front(*b"front");
+    /// bump.alloc(*b"back");
+    ///
+    /// let chunks = bump.into_bytes_chunks();
+    /// // Safe because we've only allocated `u8`s in this arena.
+    /// unsafe {
+    ///     assert_eq!(chunks[0].as_bytes(), b"back");
+    ///     assert_eq!(&*(chunks[0].front() as *const _ as *const [u8]), b"front");
+    /// }
+    /// ```
+    pub fn front(&self) -> &[mem::MaybeUninit<u8>] {
+        unsafe {
+            slice::from_raw_parts(
+                self.data.as_ptr() as *const mem::MaybeUninit<u8>,
+                self.front_len,
+            )
+        }
+    }
+}
+
+impl<A: BackingAllocator> core::ops::Deref for OwnedChunkBytes<A> {
+    type Target = [mem::MaybeUninit<u8>];
+
+    fn deref(&self) -> &[mem::MaybeUninit<u8>] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr() as *const mem::MaybeUninit<u8>, self.len) }
+    }
+}
+
+impl<A: BackingAllocator> Drop for OwnedChunkBytes<A> {
+    fn drop(&mut self) {
+        unsafe {
+            let data = self.data.as_ptr();
+            asan_unpoison(data, self.ptr.as_ptr() as usize - data as usize);
+            if self.zero {
+                zero_volatile(data, self.front_len);
+                zero_volatile(self.ptr.as_ptr(), self.len);
             }
+            self.backing.deallocate(self.data, self.layout);
         }
+    }
+}
 
-        debug_assert!(is_pointer_aligned_to(ptr.as_ptr(), new_layout.align()));
+/// A map from addresses in a [`Bump`] arena to offsets in a buffer that the
+/// arena's allocated bytes were copied into.
+///
+/// This struct is created by the [`copy_allocated_bytes_to`] method on
+/// [`Bump`]. See that method for more details.
+///
+/// [`Bump`]: struct.Bump.html
+/// [`copy_allocated_bytes_to`]: struct.Bump.html#method.copy_allocated_bytes_to
+#[derive(Clone, Debug)]
+pub struct AllocatedBytesMap {
+    regions: core_alloc::vec::Vec<CopiedRegion>,
+}
 
-        let old_size = old_layout.size();
-        let new_size = new_layout.size();
+/// One chunk's worth of allocated bytes, or of front allocations, that were
+/// copied out of a [`Bump`] arena.
+///
+/// See [`AllocatedBytesMap`] for more details.
+///
+/// [`Bump`]: struct.Bump.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CopiedRegion {
+    address: usize,
+    offset: usize,
+    len: usize,
+}
 
-        // This is how much space we would *actually* reclaim while satisfying
-        // the requested alignment.
-        let delta = round_down_to(old_size - new_size, new_layout.align());
+impl AllocatedBytesMap {
+    /// Get the copied regions, in the order they were copied: one per chunk,
+    /// and one more for each chunk with front allocations.
+    pub fn regions(&self) -> &[CopiedRegion] {
+        &self.regions
+    }
 
-        if self.is_last_allocation(ptr)
-                // Only reclaim the excess space (which requires a copy) if it
-                // is worth it: we are actually going to recover "enough" space
-                // and we can do a non-overlapping copy.
-                //
-                // We do `(old_size + 1) / 2` so division rounds up rather than
-                // down. Consider when:
-                //
-                //     old_size = 5
-                //     new_size = 3
-                //
-                // If we do not take care to round up, this will result in:
-                //
-                //     delta = 2
-                //     (old_size / 2) = (5 / 2) = 2
-                //
-                // And the the check will succeed even though we are have
-                // overlapping ranges:
-                //
-                //     |--------old-allocation-------|
-                //     |------from-------|
-                //                 |-------to--------|
-                //     +-----+-----+-----+-----+-----+
-                //     |  a  |  b  |  c  |  .  |  .  |
-                //     +-----+-----+-----+-----+-----+
-                //
-                // But we MUST NOT have overlapping ranges because we use
-                // `copy_nonoverlapping` below! Therefore, we round the division
-                // up to avoid this issue.
-                && delta >= (old_size + 1) / 2
-        {
-            let footer = self.current_chunk_footer.get();
-            let footer = footer.as_ref();
+    /// Translate a pointer into the arena into an offset in the buffer the
+    /// arena's bytes were copied into.
+    ///
+    /// Returns `None` if `ptr` does not point into the allocated bytes of any
+    /// of the copied chunks.
+    pub fn offset_of<T: ?Sized>(&self, ptr: *const T) -> Option<usize> {
+        let address = ptr as *const u8 as usize;
+        self.regions.iter().find_map(|r| {
+            let delta = address.checked_sub(r.address)?;
+            if delta < r.len {
+                Some(r.offset + delta)
+            } else {
+                None
+            }
+        })
+    }
+}
 
-            // NB: new_ptr is aligned, because ptr *has to* be aligned, and we
-            // made sure delta is aligned.
-            let new_ptr = NonNull::new_unchecked(footer.ptr.get().as_ptr().add(delta));
-            footer.ptr.set(new_ptr);
+/// A map from addresses in a [`Bump`] arena to the copies of their bytes in
+/// another arena.
+///
+/// This struct is created by [`Bump::clone_from_bump`] and its variants. See
+/// that function for more details.
+#[derive(Clone, Debug)]
+pub struct RelocationMap {
+    base: NonNull<u8>,
+    bytes: AllocatedBytesMap,
+}
 
-            // NB: we know it is non-overlapping because of the size check
-            // in the `if` condition.
-            ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), new_size);
+impl RelocationMap {
+    /// Get the copied regions, one per non-empty chunk of the source arena
+    /// and one more for each chunk with front allocations, in the order they
+    /// were copied.
+    ///
+    /// Each region's [`offset`](CopiedRegion::offset) is relative to the
+    /// start of the copied bytes in the new arena.
+    pub fn regions(&self) -> &[CopiedRegion] {
+        self.bytes.regions()
+    }
 
-            return Ok(new_ptr);
-        }
+    /// Translate a pointer into the source arena into the offset of its copy,
+    /// relative to the start of the copied bytes in the new arena.
+    ///
+    /// Returns `None` if `ptr` does not point into the allocated bytes of any
+    /// of the copied chunks.
+    pub fn offset_of<T: ?Sized>(&self, ptr: *const T) -> Option<usize> {
+        self.bytes.offset_of(ptr)
+    }
 
-        // If this wasn't the last allocation, or shrinking wasn't worth it,
-        // simply return the old pointer as-is.
-        Ok(ptr)
+    /// Translate a pointer into the source arena into a pointer to its copy
+    /// in the new arena.
+    ///
+    /// Returns `None` if `ptr` does not point into the allocated bytes of any
+    /// of the copied chunks. The returned pointer is only valid for as long
+    /// as the new arena is not reset or dropped.
+    pub fn relocate<T>(&self, ptr: *const T) -> Option<*mut T> {
+        let offset = self.offset_of(ptr)?;
+        Some(self.base.as_ptr().wrapping_add(offset).cast::<T>())
     }
+}
 
-    #[inline]
-    unsafe fn grow(
-        &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<u8>, AllocErr> {
-        let old_size = old_layout.size();
-        let new_size = new_layout.size();
-        let align_is_compatible = old_layout.align() >= new_layout.align();
+impl CopiedRegion {
+    /// The address in the arena that this region's bytes were copied from.
+    pub fn address(&self) -> usize {
+        self.address
+    }
 
-        if align_is_compatible && self.is_last_allocation(ptr) {
-            // Try to allocate the delta size within this same block so we can
-            // reuse the currently allocated space.
-            let delta = new_size - old_size;
-            if let Some(p) =
-                self.try_alloc_layout_fast(layout_from_size_align(delta, old_layout.align())?)
-            {
-                ptr::copy(ptr.as_ptr(), p.as_ptr(), old_size);
-                return Ok(p);
-            }
-        }
+    /// The offset in the output buffer that this region's bytes were copied
+    /// to.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
 
-        // Fallback: do a fresh allocation and copy the existing data into it.
-        let new_ptr = self.try_alloc_layout(new_layout)?;
-        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_size);
-        Ok(new_ptr)
+    /// The number of bytes in this region.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 }
 
-/// An iterator over each chunk of allocated memory that
-/// an arena has bump allocated into.
-///
-/// The chunks are returned ordered by allocation time, with the most recently
-/// allocated chunk being returned first.
+/// A view of a [`Bump`] arena that is specialized for allocations sharing the
+/// alignment `ALIGN`.
 ///
-/// The values inside each chunk are also ordered by allocation time, with the most
-/// recent allocation being earlier in the slice.
+/// Between allocations made through an `AlignedBump`, the underlying arena's
+/// bump pointer is always aligned to `ALIGN`, or to the arena's minimum
+/// alignment if that is greater (or the current chunk has no capacity left at
+/// all). Allocation sizes are rounded up to a multiple of that alignment to
+/// maintain this invariant, which lets the fast path skip per-allocation
+/// alignment fixups.
 ///
-/// This struct is created by the [`iter_allocated_chunks`] method on
-/// [`Bump`]. See that function for a safety description regarding reading from the returned items.
+/// This struct is created by the [`with_align`] method on [`Bump`]. See that
+/// method for more details.
 ///
 /// [`Bump`]: struct.Bump.html
-/// [`iter_allocated_chunks`]: struct.Bump.html#method.iter_allocated_chunks
+/// [`with_align`]: struct.Bump.html#method.with_align
 #[derive(Debug)]
-pub struct ChunkIter<'a> {
-    raw: ChunkRawIter<'a>,
-    bump: PhantomData<&'a mut Bump>,
+pub struct AlignedBump<'a, const ALIGN: usize, A: BackingAllocator = Global> {
+    bump: &'a Bump<A>,
+    // The greater of `ALIGN` and the arena's minimum alignment.
+    align: usize,
 }
 
-impl<'a> Iterator for ChunkIter<'a> {
-    type Item = &'a [mem::MaybeUninit<u8>];
-    fn next(&mut self) -> Option<&'a [mem::MaybeUninit<u8>]> {
+impl<'a, const ALIGN: usize, A: BackingAllocator> AlignedBump<'a, ALIGN, A> {
+    /// Allocate an object in the underlying arena and return an exclusive
+    /// reference to it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// let x = bump.with_align::<4, _>(|aligned| aligned.alloc(42_u32));
+    /// assert_eq!(*x, 42);
+    /// ```
+    #[inline(always)]
+    pub fn alloc<T>(&self, val: T) -> &'a mut T {
+        self.alloc_with(|| val)
+    }
+
+    /// Try to allocate an object in the underlying arena and return an
+    /// exclusive reference to it.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// let x = bump.with_align::<4, _>(|aligned| aligned.try_alloc(42_u32));
+    /// assert_eq!(x, Ok(&mut 42));
+    /// ```
+    #[inline(always)]
+    pub fn try_alloc<T>(&self, val: T) -> Result<&'a mut T, AllocErr> {
+        self.try_alloc_with(|| val)
+    }
+
+    /// Pre-allocate space for an object in the underlying arena, initializes
+    /// it using the closure, then returns an exclusive reference to it.
+    ///
+    /// See [`Bump::alloc_with`] for details.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for `T` fails.
+    #[inline(always)]
+    pub fn alloc_with<F, T>(&self, f: F) -> &'a mut T
+    where
+        F: FnOnce() -> T,
+    {
+        self.try_alloc_with(f).unwrap_or_else(|_| oom())
+    }
+
+    /// Tries to pre-allocate space for an object in the underlying arena,
+    /// initializes it using the closure, then returns an exclusive reference
+    /// to it.
+    ///
+    /// See [`Bump::try_alloc_with`] for details.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for `T` fails.
+    #[inline(always)]
+    pub fn try_alloc_with<F, T>(&self, f: F) -> Result<&'a mut T, AllocErr>
+    where
+        F: FnOnce() -> T,
+    {
+        let p = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            self.try_alloc_layout(Layout::new::<T>())?.cast::<T>()
+        };
+        let p = p.as_ptr();
+
         unsafe {
-            let (ptr, len) = self.raw.next()?;
-            let slice = slice::from_raw_parts(ptr as *const mem::MaybeUninit<u8>, len);
-            Some(slice)
+            ptr::write(p, f());
+            Ok(&mut *p)
         }
     }
-}
 
-impl<'a> iter::FusedIterator for ChunkIter<'a> {}
+    /// `Copy` a slice into the underlying arena and return an exclusive
+    /// reference to the copy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the slice fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut bump = bumpalo::Bump::new();
+    /// let x = bump.with_align::<8, _>(|aligned| aligned.alloc_slice_copy(&[1_u64, 2, 3]));
+    /// assert_eq!(x, &[1, 2, 3]);
+    /// ```
+    #[inline(always)]
+    pub fn alloc_slice_copy<T>(&self, src: &[T]) -> &'a mut [T]
+    where
+        T: Copy,
+    {
+        let dst = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            self.alloc_layout(Layout::for_value(src)).cast::<T>()
+        };
 
-/// An iterator over raw pointers to chunks of allocated memory that this
-/// arena has bump allocated into.
-///
-/// See [`ChunkIter`] for details regarding the returned chunks.
-///
-/// This struct is created by the [`iter_allocated_chunks_raw`] method on
-/// [`Bump`]. See that function for a safety description regarding reading from
-/// the returned items.
-///
-/// [`Bump`]: struct.Bump.html
-/// [`iter_allocated_chunks_raw`]: struct.Bump.html#method.iter_allocated_chunks_raw
-#[derive(Debug)]
-pub struct ChunkRawIter<'a> {
-    footer: NonNull<ChunkFooter>,
-    bump: PhantomData<&'a Bump>,
-}
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+        }
+    }
 
-impl Iterator for ChunkRawIter<'_> {
-    type Item = (*mut u8, usize);
-    fn next(&mut self) -> Option<(*mut u8, usize)> {
+    /// Allocate space for an object with the given `Layout`.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// # Panics
+    ///
+    /// Panics if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        self.try_alloc_layout(layout).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempts to allocate space for an object with the given `Layout` or
+    /// else returns an `Err`.
+    ///
+    /// The returned pointer points at uninitialized memory, and should be
+    /// initialized with
+    /// [`std::ptr::write`](https://doc.rust-lang.org/std/ptr/fn.write.html).
+    ///
+    /// # Errors
+    ///
+    /// Errors if reserving space matching `layout` fails.
+    #[inline(always)]
+    pub fn try_alloc_layout(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let size = round_up_to(layout.size(), self.align).ok_or(AllocErr::SIZE_OVERFLOW)?;
+        let fast = if layout.align() <= self.align && size != 0 {
+            self.try_alloc_layout_fast(size)
+        } else {
+            None
+        };
+        let p = match fast {
+            Some(p) => p,
+            None => {
+                // Either we need a new chunk, the layout demands greater
+                // alignment than `align`, or this is a zero-sized allocation
+                // that may land in the canonical empty chunk. In all of these
+                // cases, let the arena handle alignment for us. Because the
+                // size is a multiple of `align` and the alignment is at least
+                // `align`, the bump pointer remains aligned to `align`
+                // afterwards.
+                let padded = layout_from_size_align(size, layout.align().max(self.align))?;
+                match self.bump.try_alloc_layout_fast(padded) {
+                    Some(p) => p,
+                    None => self.bump.alloc_layout_slow(padded, false)?,
+                }
+            }
+        };
         unsafe {
-            let foot = self.footer.as_ref();
-            if foot.is_empty() {
+            poison_fresh(p.as_ptr(), size);
+        }
+        self.bump.trace_alloc(p, layout, false);
+        Ok(p)
+    }
+
+    #[inline(always)]
+    fn try_alloc_layout_fast(&self, size: usize) -> Option<NonNull<u8>> {
+        unsafe {
+            let footer = self.bump.current_chunk_footer.get();
+            let footer = footer.as_ref();
+            let ptr = footer.ptr.get().as_ptr();
+            let start = footer.front.get().as_ptr();
+            debug_assert!(start <= ptr);
+
+            if (ptr as usize - start as usize) < size {
                 return None;
             }
-            let (ptr, len) = foot.as_raw_parts();
-            self.footer = foot.prev.get();
-            Some((ptr as *mut u8, len))
+
+            // No need to round the pointer down: it is already aligned to
+            // `align`, and `size` is a multiple of `align`.
+            debug_assert!(is_pointer_aligned_to(ptr, self.align));
+            let ptr = NonNull::new_unchecked(ptr.sub(size));
+            footer.ptr.set(ptr);
+            asan_unpoison(ptr.as_ptr(), size);
+            Some(ptr)
         }
     }
 }
 
-impl iter::FusedIterator for ChunkRawIter<'_> {}
-
 #[inline(never)]
 #[cold]
 fn oom() -> ! {
     panic!("out of memory")
 }
 
-unsafe impl<'a> alloc::Alloc for &'a Bump {
+unsafe impl<A: BackingAllocator> alloc::Alloc for &Bump<A> {
     #[inline(always)]
     unsafe fn alloc(&mut self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
         self.try_alloc_layout(layout)
@@ -1910,7 +7431,7 @@ unsafe impl<'a> alloc::Alloc for &'a Bump {
 
     #[inline]
     unsafe fn dealloc(&mut self, ptr: NonNull<u8>, layout: Layout) {
-        Bump::dealloc(self, ptr, layout);
+        Bump::<A>::dealloc(self, ptr, layout);
     }
 
     #[inline]
@@ -1928,17 +7449,18 @@ unsafe impl<'a> alloc::Alloc for &'a Bump {
 
         let new_layout = layout_from_size_align(new_size, layout.align())?;
         if new_size <= old_size {
-            self.shrink(ptr, layout, new_layout)
+            Bump::<A>::shrink(self, ptr, layout, new_layout)
         } else {
-            self.grow(ptr, layout, new_layout)
+            Bump::<A>::grow(self, ptr, layout, new_layout)
         }
     }
 }
 
 #[cfg(any(feature = "allocator_api", feature = "allocator-api2"))]
-unsafe impl<'a> Allocator for &'a Bump {
+unsafe impl<A: BackingAllocator> Allocator for &Bump<A> {
     #[inline]
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = self.size_class_layout(layout);
         self.try_alloc_layout(layout)
             .map(|p| unsafe {
                 NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(p.as_ptr(), layout.size()))
@@ -1948,7 +7470,7 @@ unsafe impl<'a> Allocator for &'a Bump {
 
     #[inline]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
-        Bump::dealloc(self, ptr, layout)
+        Bump::<A>::dealloc(self, ptr, self.size_class_layout(layout))
     }
 
     #[inline]
@@ -1958,7 +7480,9 @@ unsafe impl<'a> Allocator for &'a Bump {
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        Bump::shrink(self, ptr, old_layout, new_layout)
+        let old_layout = self.size_class_layout(old_layout);
+        let new_layout = self.size_class_layout(new_layout);
+        Bump::<A>::shrink(self, ptr, old_layout, new_layout)
             .map(|p| unsafe {
                 NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(p.as_ptr(), new_layout.size()))
             })
@@ -1972,7 +7496,20 @@ unsafe impl<'a> Allocator for &'a Bump {
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        Bump::grow(self, ptr, old_layout, new_layout)
+        let old_layout = self.size_class_layout(old_layout);
+        let new_layout = self.size_class_layout(new_layout);
+
+        // Still within the block's size class, so there is nothing to do.
+        if new_layout.size() <= old_layout.size()
+            && is_pointer_aligned_to(ptr.as_ptr(), new_layout.align())
+        {
+            return Ok(NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(
+                ptr.as_ptr(),
+                old_layout.size(),
+            )));
+        }
+
+        Bump::<A>::grow(self, ptr, old_layout, new_layout)
             .map(|p| unsafe {
                 NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(p.as_ptr(), new_layout.size()))
             })
@@ -2001,8 +7538,8 @@ mod tests {
 
     // Uses private type `ChunkFooter`.
     #[test]
-    fn chunk_footer_is_five_words() {
-        assert_eq!(mem::size_of::<ChunkFooter>(), mem::size_of::<usize>() * 6);
+    fn chunk_footer_is_seven_words() {
+        assert_eq!(mem::size_of::<ChunkFooter>(), mem::size_of::<usize>() * 7);
     }
 
     // Uses private `alloc` module.
@@ -2014,18 +7551,29 @@ mod tests {
             const CAPACITY: usize = 1024 - OVERHEAD;
             let mut b = Bump::with_capacity(CAPACITY);
 
-            // `realloc` doesn't shrink allocations that aren't "worth it".
+            // `realloc` shrinks the last allocation, even when the kept bytes
+            // overlap their old location.
             let layout = Layout::from_size_align(100, 1).unwrap();
             let p = b.alloc_layout(layout);
             let q = (&b).realloc(p, layout, 51).unwrap();
-            assert_eq!(p, q);
+            assert_eq!(q.as_ptr() as usize, p.as_ptr() as usize + 49);
+            b.reset();
+
+            // `realloc` shrinks the last allocation when the kept bytes don't
+            // overlap their old location.
+            let layout = Layout::from_size_align(100, 1).unwrap();
+            let p = b.alloc_layout(layout);
+            let q = (&b).realloc(p, layout, 50).unwrap();
+            assert_eq!(q.as_ptr() as usize, p.as_ptr() as usize + 50);
             b.reset();
 
-            // `realloc` will shrink allocations that are "worth it".
+            // `realloc` doesn't move allocations that aren't the last one when
+            // shrinking them.
             let layout = Layout::from_size_align(100, 1).unwrap();
             let p = b.alloc_layout(layout);
+            let _ = b.alloc_layout(layout);
             let q = (&b).realloc(p, layout, 50).unwrap();
-            assert!(p != q);
+            assert_eq!(p, q);
             b.reset();
 
             // `realloc` will reuse the last allocation when growing.
@@ -0,0 +1,126 @@
+//! Chunks mapped directly from the operating system.
+//!
+//! This module is only available on Unix platforms, when the `mmap` Cargo
+//! feature is enabled. It provides [`MmapAllocator`], a [`BackingAllocator`]
+//! that maps every chunk as anonymous memory with its own `mmap` call, and
+//! places an inaccessible guard page right after it.
+//!
+//! Because a `Bump` bump allocates downwards from the end of each chunk, the
+//! guard page sits right after the chunk's first allocations: writing past
+//! the end of the chunk faults immediately instead of silently corrupting
+//! whatever memory happens to follow it. And when the arena is
+//! [reset](crate::Bump::reset), the pages of the chunk it keeps are handed
+//! back to the operating system with `madvise(MADV_DONTNEED)`, without
+//! unmapping them.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::{mmap::MmapAllocator, Bump};
+//!
+//! let mut bump = Bump::new_in(MmapAllocator);
+//! let xs = bump.alloc_slice_fill_copy(100_000, 1_u8);
+//! assert_eq!(xs.iter().map(|&x| x as usize).sum::<usize>(), 100_000);
+//!
+//! // Return the kept chunk's pages to the OS.
+//! bump.reset();
+//! ```
+
+use crate::BackingAllocator;
+use core::alloc::Layout;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A [`BackingAllocator`] that maps each chunk directly from the operating
+/// system, followed by a guard page.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MmapAllocator;
+
+impl MmapAllocator {
+    /// Get the operating system's page size.
+    pub fn page_size() -> usize {
+        static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+        match PAGE_SIZE.load(Ordering::Relaxed) {
+            0 => {
+                let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+                let size = if size > 0 { size as usize } else { 4096 };
+                PAGE_SIZE.store(size, Ordering::Relaxed);
+                size
+            }
+            size => size,
+        }
+    }
+
+    /// The size of the mapping for a chunk with the given layout, not
+    /// counting its guard page.
+    fn mapped_len(layout: Layout) -> Option<usize> {
+        let page = Self::page_size();
+        layout.size().checked_add(page - 1).map(|n| n & !(page - 1))
+    }
+}
+
+unsafe impl BackingAllocator for MmapAllocator {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let page = Self::page_size();
+        if layout.align() > page {
+            return None;
+        }
+        let len = Self::mapped_len(layout)?;
+        let total = len.checked_add(page)?;
+
+        unsafe {
+            let base = libc::mmap(
+                ptr::null_mut(),
+                total,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if base == libc::MAP_FAILED {
+                return None;
+            }
+            let base = base.cast::<u8>();
+
+            let guard = base.add(len);
+            if libc::mprotect(guard.cast(), page, libc::PROT_NONE) != 0 {
+                libc::munmap(base.cast(), total);
+                return None;
+            }
+
+            // Place the chunk so that it ends (modulo its alignment) right at
+            // the guard page.
+            let start = (len - layout.size()) & !(layout.align() - 1);
+            NonNull::new(base.add(start))
+        }
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // Fresh anonymous mappings are always zeroed.
+        self.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        let page = Self::page_size();
+        let base = ptr.as_ptr() as usize & !(page - 1);
+        let total = Self::mapped_len(layout).unwrap() + page;
+        let result = libc::munmap(base as *mut libc::c_void, total);
+        debug_assert_eq!(result, 0);
+    }
+
+    unsafe fn discard(&self, ptr: NonNull<u8>, len: usize) {
+        // Only whole pages can be discarded, so shrink the region to the
+        // pages it fully covers.
+        let page = Self::page_size();
+        let start = (ptr.as_ptr() as usize + page - 1) & !(page - 1);
+        let end = (ptr.as_ptr() as usize + len) & !(page - 1);
+        if start < end {
+            let result =
+                libc::madvise(start as *mut libc::c_void, end - start, libc::MADV_DONTNEED);
+            debug_assert_eq!(result, 0);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Object pools that recycle memory in an arena.
+//!
+//! Memory allocated in a [`Bump`] is only reclaimed when the arena is reset or
+//! dropped, so a loop that keeps allocating and discarding objects grows the
+//! arena without bound. An [`ObjectPool<T>`] keeps a free list of slots for
+//! `T`s in the arena: returning an object to the pool makes its slot available
+//! to the next allocation from the pool, so the arena only grows to the
+//! largest number of objects that are alive at the same time. The slots
+//! themselves are never freed individually, and are released along with the
+//! rest of the arena.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::{pool::ObjectPool, Bump};
+//!
+//! let bump = Bump::new();
+//! let pool = ObjectPool::new_in(&bump);
+//!
+//! for i in 0..1000 {
+//!     let a = pool.alloc([i; 16]);
+//!     let b = pool.alloc([i + 1; 16]);
+//!     assert_eq!(a[0] + 1, b[0]);
+//!     // `a` and `b` are returned to the pool here.
+//! }
+//!
+//! // Only two slots were ever allocated.
+//! assert_eq!(pool.capacity(), 2);
+//! ```
+
+use crate::{AllocErr, Bump};
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+
+/// A slot in the pool holds either a live value, or a link to the next free
+/// slot.
+union Slot<T> {
+    value: ManuallyDrop<T>,
+    next: Option<NonNull<Slot<T>>>,
+}
+
+/// A pool of reusable slots for values of type `T`, allocated in a [`Bump`]
+/// arena.
+///
+/// Allocating from the pool returns a [`Pooled`] smart pointer, which drops
+/// the value and returns its slot to the pool when it is dropped.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct ObjectPool<'bump, T> {
+    bump: &'bump Bump,
+    // The head of the free list.
+    free: Cell<Option<NonNull<Slot<T>>>>,
+    // The number of slots allocated in the arena, and the number of those
+    // that hold live values.
+    capacity: Cell<usize>,
+    len: Cell<usize>,
+    marker: PhantomData<T>,
+}
+
+impl<'bump, T> ObjectPool<'bump, T> {
+    /// Construct a new, empty pool that allocates its slots in `bump`.
+    ///
+    /// This does not allocate.
+    pub fn new_in(bump: &'bump Bump) -> ObjectPool<'bump, T> {
+        ObjectPool {
+            bump,
+            free: Cell::new(None),
+            capacity: Cell::new(0),
+            len: Cell::new(0),
+            marker: PhantomData,
+        }
+    }
+
+    /// Construct a new pool with `capacity` free slots, allocated in `bump`
+    /// up front.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the slots fails.
+    pub fn with_capacity_in(capacity: usize, bump: &'bump Bump) -> ObjectPool<'bump, T> {
+        let pool = ObjectPool::new_in(bump);
+        pool.reserve(capacity);
+        pool
+    }
+
+    /// Get the arena that this pool allocates its slots in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump {
+        self.bump
+    }
+
+    /// Get the number of values currently allocated from this pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Are there no values currently allocated from this pool?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of slots that this pool has allocated in its arena,
+    /// both occupied and free.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity.get()
+    }
+
+    /// Make sure that at least `additional` free slots are available, so
+    /// that the next `additional` allocations from this pool don't allocate
+    /// in the arena.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the slots fails.
+    pub fn reserve(&self, additional: usize) {
+        let free = self.capacity() - self.len();
+        if additional <= free {
+            return;
+        }
+        let slots = self
+            .bump
+            .alloc_slice_fill_with(additional - free, |_| Slot { next: None });
+        for slot in slots.iter_mut() {
+            self.push_free(NonNull::from(slot));
+        }
+        self.capacity.set(self.capacity() + (additional - free));
+    }
+
+    /// Allocate a value in this pool.
+    ///
+    /// Reuses a free slot if there is one, and allocates a new slot in the
+    /// arena otherwise.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating a new slot fails.
+    #[inline]
+    pub fn alloc(&self, value: T) -> Pooled<'_, 'bump, T> {
+        let slot = match self.pop_free() {
+            Some(slot) => slot,
+            None => {
+                self.capacity.set(self.capacity() + 1);
+                NonNull::from(self.bump.alloc(Slot { next: None }))
+            }
+        };
+        self.fill(slot, value)
+    }
+
+    /// Try to allocate a value in this pool.
+    ///
+    /// Reuses a free slot if there is one, and allocates a new slot in the
+    /// arena otherwise.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating a new slot fails. The value is dropped in that
+    /// case.
+    #[inline]
+    pub fn try_alloc(&self, value: T) -> Result<Pooled<'_, 'bump, T>, AllocErr> {
+        let slot = match self.pop_free() {
+            Some(slot) => slot,
+            None => {
+                let slot = self.bump.try_alloc(Slot { next: None })?;
+                self.capacity.set(self.capacity() + 1);
+                NonNull::from(slot)
+            }
+        };
+        Ok(self.fill(slot, value))
+    }
+
+    #[inline]
+    fn fill(&self, slot: NonNull<Slot<T>>, value: T) -> Pooled<'_, 'bump, T> {
+        unsafe {
+            ptr::write(
+                slot.as_ptr(),
+                Slot {
+                    value: ManuallyDrop::new(value),
+                },
+            );
+        }
+        self.len.set(self.len() + 1);
+        Pooled { pool: self, slot }
+    }
+
+    #[inline]
+    fn pop_free(&self) -> Option<NonNull<Slot<T>>> {
+        let slot = self.free.get()?;
+        self.free.set(unsafe { slot.as_ref().next });
+        Some(slot)
+    }
+
+    #[inline]
+    fn push_free(&self, slot: NonNull<Slot<T>>) {
+        unsafe {
+            ptr::write(
+                slot.as_ptr(),
+                Slot {
+                    next: self.free.get(),
+                },
+            );
+        }
+        self.free.set(Some(slot));
+    }
+}
+
+impl<T> fmt::Debug for ObjectPool<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectPool")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// A value allocated from an [`ObjectPool`].
+///
+/// Dereferences to the value. Dropping it drops the value and returns its
+/// slot to the pool.
+pub struct Pooled<'a, 'bump, T> {
+    pool: &'a ObjectPool<'bump, T>,
+    slot: NonNull<Slot<T>>,
+}
+
+impl<'a, 'bump, T> Pooled<'a, 'bump, T> {
+    /// Move the value out, returning its slot to the pool.
+    ///
+    /// This is an associated function, rather than a method, so that it
+    /// doesn't shadow methods of `T`.
+    pub fn into_inner(this: Pooled<'a, 'bump, T>) -> T {
+        let this = ManuallyDrop::new(this);
+        unsafe {
+            let value = ManuallyDrop::into_inner(ptr::read(&this.slot.as_ref().value));
+            this.release();
+            value
+        }
+    }
+
+    // Return the slot to the pool. Its value must have been dropped or moved
+    // out already.
+    unsafe fn release(&self) {
+        self.pool.len.set(self.pool.len() - 1);
+        self.pool.push_free(self.slot);
+    }
+}
+
+impl<T> Drop for Pooled<'_, '_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ManuallyDrop::drop(&mut self.slot.as_mut().value);
+            self.release();
+        }
+    }
+}
+
+impl<T> Deref for Pooled<'_, '_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &self.slot.as_ref().value }
+    }
+}
+
+impl<T> DerefMut for Pooled<'_, '_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut self.slot.as_mut().value }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pooled<'_, '_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
@@ -0,0 +1,323 @@
+//! Offset-based allocation for relocatable arenas.
+//!
+//! References into a [`Bump`] are absolute addresses, so a data structure
+//! built out of them cannot be copied to another place in memory, written to
+//! disk, or memory-mapped back in later. [`RelativeBump`] instead hands out
+//! [`BumpOffset<T>`] handles: 32-bit offsets that are resolved against the
+//! arena (or a byte-for-byte copy of it) on access.
+//!
+//! A `RelativeBump` is backed by a single chunk of memory, whose size is fixed
+//! when the arena is created, and never allocates any more chunks. Offsets are
+//! measured backwards from the end of that chunk, which is where bump
+//! allocation starts. This means that the bytes from the most recent
+//! allocation up to the end of the chunk,
+//! [`RelativeBump::used_bytes`], are a self-contained image of everything
+//! allocated so far, and offsets stay valid as the arena grows.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::relative::{BumpOffset, RelativeBump};
+//!
+//! #[derive(Clone, Copy)]
+//! struct Node {
+//!     value: u32,
+//!     next: Option<BumpOffset<Node>>,
+//! }
+//!
+//! let arena = RelativeBump::with_capacity(1024);
+//! let mut next = None;
+//! for value in 0..3 {
+//!     next = Some(arena.alloc_rel(Node { value, next }));
+//! }
+//!
+//! // Walk the list.
+//! let mut values = vec![];
+//! while let Some(offset) = next {
+//!     // Safety: all offsets were returned by `alloc_rel` on this arena.
+//!     let node = unsafe { arena.get(offset) };
+//!     values.push(node.value);
+//!     next = node.next;
+//! }
+//! assert_eq!(values, [2, 1, 0]);
+//! ```
+
+use crate::{oom, AllocErr, Bump};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::slice;
+
+/// An offset-based handle to a `T` allocated in a [`RelativeBump`].
+///
+/// Unlike a reference, a `BumpOffset` does not encode an absolute address,
+/// so structures that contain `BumpOffset`s remain valid when the arena's
+/// bytes are moved elsewhere. A `BumpOffset` is always four bytes large.
+///
+/// See the [module-level documentation](self) for more details.
+#[repr(transparent)]
+pub struct BumpOffset<T> {
+    offset: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> BumpOffset<T> {
+    /// Create a handle from its raw offset, as returned by
+    /// [`to_raw`](BumpOffset::to_raw).
+    #[inline]
+    pub fn from_raw(offset: u32) -> Self {
+        BumpOffset {
+            offset,
+            marker: PhantomData,
+        }
+    }
+
+    /// Get this handle's raw offset: the distance in bytes from the start of
+    /// the `T` to the end of its arena's chunk.
+    #[inline]
+    pub fn to_raw(self) -> u32 {
+        self.offset
+    }
+
+    /// Resolve this handle against a copy of the used bytes of the
+    /// [`RelativeBump`] it was allocated in.
+    ///
+    /// `bytes` must end exactly where the arena's chunk ended; a copy of
+    /// [`RelativeBump::used_bytes`] taken after this handle was allocated
+    /// fulfils that.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `T` would not lie within `bytes`, or if it would not be
+    /// properly aligned. Since `bytes` is an arbitrary byte buffer, callers
+    /// must make sure that its end has at least the same alignment as every
+    /// type they resolve in it.
+    ///
+    /// ## Safety
+    ///
+    /// This handle must have been returned by
+    /// [`alloc_rel`](RelativeBump::alloc_rel) on the arena that `bytes` is a
+    /// copy of, and that `T` must not contain any absolute pointers or
+    /// references.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::relative::RelativeBump;
+    ///
+    /// let arena = RelativeBump::with_capacity(64);
+    /// let x = arena.alloc_rel(0x1234_5678_u32);
+    ///
+    /// // Copy the arena's bytes into a buffer whose end is aligned for `u32`.
+    /// let used = unsafe { arena.used_bytes() };
+    /// let mut copy = vec![0_u32; 16];
+    /// let copy: &mut [u8] = unsafe {
+    ///     std::slice::from_raw_parts_mut(copy.as_mut_ptr().cast(), 64)
+    /// };
+    /// let start = copy.len() - used.len();
+    /// copy[start..].copy_from_slice(used);
+    ///
+    /// assert_eq!(unsafe { *x.get_in(copy) }, 0x1234_5678);
+    /// ```
+    pub unsafe fn get_in(self, bytes: &[u8]) -> &T {
+        let offset = self.offset as usize;
+        assert!(
+            mem::size_of::<T>() <= offset && offset <= bytes.len(),
+            "offset out of bounds"
+        );
+        let p = bytes.as_ptr().add(bytes.len() - offset);
+        assert_eq!(p as usize % mem::align_of::<T>(), 0, "misaligned offset");
+        &*(p as *const T)
+    }
+}
+
+impl<T> Clone for BumpOffset<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BumpOffset<T> {}
+
+impl<T> PartialEq for BumpOffset<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<T> Eq for BumpOffset<T> {}
+
+impl<T> Hash for BumpOffset<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for BumpOffset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BumpOffset").field(&self.offset).finish()
+    }
+}
+
+/// A single-chunk arena that hands out [`BumpOffset`] handles instead of
+/// references.
+///
+/// See the [module-level documentation](self) for more details.
+#[derive(Debug)]
+pub struct RelativeBump {
+    bump: Bump,
+    // The end of the arena's only chunk, which offsets are measured from.
+    end: NonNull<u8>,
+}
+
+impl RelativeBump {
+    /// Construct a new relative arena with room for at least `capacity`
+    /// bytes.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the arena's chunk fails, or if `capacity` does
+    /// not fit in a `u32`.
+    pub fn with_capacity(capacity: usize) -> RelativeBump {
+        RelativeBump::try_with_capacity(capacity).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempt to construct a new relative arena with room for at least
+    /// `capacity` bytes.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if allocating the arena's chunk fails, or if `capacity` does
+    /// not fit in a `u32`.
+    pub fn try_with_capacity(capacity: usize) -> Result<RelativeBump, AllocErr> {
+        if capacity > u32::MAX as usize {
+            return Err(AllocErr::SIZE_OVERFLOW);
+        }
+        let bump = Bump::try_with_capacity(capacity)?;
+        if bump.chunk_capacity() > u32::MAX as usize {
+            return Err(AllocErr::SIZE_OVERFLOW);
+        }
+        // Never allocate a second chunk.
+        bump.set_allocation_limit(Some(bump.allocated_bytes()));
+        let end = bump.current_chunk_footer.get().cast::<u8>();
+        Ok(RelativeBump { bump, end })
+    }
+
+    /// Get the number of bytes this arena can hold in total.
+    ///
+    /// This may be a little larger than the capacity that was requested.
+    pub fn capacity(&self) -> usize {
+        self.used_len() + self.bump.chunk_capacity()
+    }
+
+    /// Get the number of bytes allocated in this arena so far, including any
+    /// padding.
+    pub fn used_len(&self) -> usize {
+        let footer = unsafe { self.bump.current_chunk_footer.get().as_ref() };
+        self.end.as_ptr() as usize - footer.ptr.get().as_ptr() as usize
+    }
+
+    /// Allocate an object in this arena and return a handle to it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the arena is full.
+    #[inline]
+    pub fn alloc_rel<T>(&self, val: T) -> BumpOffset<T> {
+        self.try_alloc_rel(val).unwrap_or_else(|_| oom())
+    }
+
+    /// Attempt to allocate an object in this arena and return a handle to it.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if the arena is full. The value is dropped in that case.
+    #[inline]
+    pub fn try_alloc_rel<T>(&self, val: T) -> Result<BumpOffset<T>, AllocErr> {
+        // Give zero-sized values a place in the chunk too, so that their
+        // offsets can be resolved like any other.
+        let layout =
+            core::alloc::Layout::from_size_align(mem::size_of::<T>().max(1), mem::align_of::<T>())
+                .map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+        let p = self.bump.try_alloc_layout(layout)?;
+        let offset = self.end.as_ptr() as usize - p.as_ptr() as usize;
+        debug_assert!(offset <= self.capacity());
+        unsafe {
+            ptr::write(p.as_ptr().cast::<T>(), val);
+        }
+        Ok(BumpOffset::from_raw(offset as u32))
+    }
+
+    /// Resolve a handle into a shared reference.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `T` would not lie within this arena's used bytes.
+    ///
+    /// ## Safety
+    ///
+    /// `offset` must have been returned by [`alloc_rel`](Self::alloc_rel) on
+    /// this arena, or on an arena that this one's bytes were copied from.
+    #[inline]
+    pub unsafe fn get<T>(&self, offset: BumpOffset<T>) -> &T {
+        &*self.resolve(offset)
+    }
+
+    /// Resolve a handle into an exclusive reference.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `T` would not lie within this arena's used bytes.
+    ///
+    /// ## Safety
+    ///
+    /// `offset` must have been returned by [`alloc_rel`](Self::alloc_rel) on
+    /// this arena, or on an arena that this one's bytes were copied from.
+    #[inline]
+    pub unsafe fn get_mut<T>(&mut self, offset: BumpOffset<T>) -> &mut T {
+        &mut *self.resolve(offset)
+    }
+
+    fn resolve<T>(&self, offset: BumpOffset<T>) -> *mut T {
+        let offset = offset.offset as usize;
+        assert!(
+            mem::size_of::<T>() <= offset && offset <= self.used_len(),
+            "offset out of bounds"
+        );
+        self.end.as_ptr().wrapping_sub(offset).cast::<T>()
+    }
+
+    /// Get the bytes allocated in this arena so far, from the most recent
+    /// allocation up to the end of the chunk.
+    ///
+    /// Offsets are measured from the end of this slice, so a copy of it can
+    /// be used to resolve handles with [`BumpOffset::get_in`].
+    ///
+    /// ## Safety
+    ///
+    /// Every allocated byte must be initialized; in particular, there must
+    /// not be any padding bytes in or between allocations. See
+    /// [`Bump::iter_allocated_chunks`] for the conditions under which this is
+    /// the case.
+    pub unsafe fn used_bytes(&self) -> &[u8] {
+        let len = self.used_len();
+        slice::from_raw_parts(self.end.as_ptr().sub(len), len)
+    }
+
+    /// Reset this arena, making all of its capacity available again.
+    ///
+    /// Handles that were allocated before the reset must not be resolved
+    /// afterwards.
+    pub fn reset(&mut self) {
+        self.bump.reset();
+        debug_assert_eq!(self.bump.current_chunk_footer.get().cast::<u8>(), self.end);
+    }
+}
+
+// `RelativeBump` owns its chunk, and `end` merely points into it.
+unsafe impl Send for RelativeBump {}
@@ -0,0 +1,203 @@
+//! Double-buffered scratch arenas.
+//!
+//! Iterative algorithms often build each iteration's data from the previous
+//! iteration's data, and then never look at the older data again: a
+//! simulation step reads the last frame's state to produce the next one, a
+//! worklist algorithm turns the current frontier into the next. A
+//! [`ScratchPair`] owns two arenas for this pattern. Each iteration allocates
+//! into the [current](ScratchPair::current) arena while the
+//! [previous](ScratchPair::previous) one still holds the last iteration's
+//! data. [`swap_and_reset`](ScratchPair::swap_and_reset) then makes the
+//! current arena the previous one, and resets the other arena to become the
+//! new current one, reusing its memory.
+//!
+//! `swap_and_reset` takes `&mut self`, so references into the arenas can't
+//! be held across it. [`iterate`](ScratchPair::iterate) runs the whole loop
+//! instead, handing each iteration the previous iteration's result.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::scratch::ScratchPair;
+//!
+//! // Run an elementary cellular automaton, where each generation of cells is
+//! // computed from the previous one.
+//! let mut pair = ScratchPair::new();
+//! let first = [0_u8, 0, 0, 1, 0, 0, 0];
+//! let last = pair.iterate(&first[..], 3, |cells, bump| {
+//!     bump.alloc_slice_fill_with(cells.len(), |i| {
+//!         let left = if i == 0 { 0 } else { cells[i - 1] };
+//!         let right = cells.get(i + 1).copied().unwrap_or(0);
+//!         left ^ right
+//!     })
+//! });
+//! assert_eq!(last, [1, 0, 1, 0, 1, 0, 1]);
+//!
+//! // Only two generations were ever kept around.
+//! assert!(pair.allocated_bytes() < 1 << 12);
+//! ```
+
+use crate::{BackingAllocator, Bump, Global};
+use core::fmt;
+
+/// Two arenas that take turns holding the current and the previous
+/// iteration's data.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct ScratchPair<A: BackingAllocator = Global> {
+    bumps: [Bump<A>; 2],
+    // The index of the current arena in `bumps`.
+    current: usize,
+}
+
+impl ScratchPair {
+    /// Construct a new pair of empty arenas.
+    ///
+    /// This does not allocate.
+    pub fn new() -> ScratchPair {
+        ScratchPair::from_bumps(Bump::new(), Bump::new())
+    }
+
+    /// Construct a new pair of arenas that each have at least `capacity`
+    /// bytes of capacity up front.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating either arena's initial chunk fails.
+    pub fn with_capacity(capacity: usize) -> ScratchPair {
+        ScratchPair::from_bumps(Bump::with_capacity(capacity), Bump::with_capacity(capacity))
+    }
+}
+
+impl Default for ScratchPair {
+    fn default() -> ScratchPair {
+        ScratchPair::new()
+    }
+}
+
+impl<A: BackingAllocator> ScratchPair<A> {
+    /// Construct a pair out of two existing arenas, with `current` as the
+    /// current arena and `previous` as the previous one.
+    ///
+    /// Neither arena is reset, so anything already allocated in `previous`
+    /// is readable until the second call to
+    /// [`swap_and_reset`](ScratchPair::swap_and_reset).
+    pub fn from_bumps(current: Bump<A>, previous: Bump<A>) -> ScratchPair<A> {
+        ScratchPair {
+            bumps: [current, previous],
+            current: 0,
+        }
+    }
+
+    /// Returns the arena that the current iteration allocates into.
+    #[inline]
+    pub fn current(&self) -> &Bump<A> {
+        &self.bumps[self.current]
+    }
+
+    /// Returns the arena that holds the previous iteration's data.
+    #[inline]
+    pub fn previous(&self) -> &Bump<A> {
+        &self.bumps[self.current ^ 1]
+    }
+
+    /// Returns exclusive references to the current and the previous arena,
+    /// in that order, e.g. to iterate over their chunks.
+    #[inline]
+    pub fn both_mut(&mut self) -> (&mut Bump<A>, &mut Bump<A>) {
+        let [a, b] = &mut self.bumps;
+        if self.current == 0 {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Make the current arena the previous one, and reset the previous arena
+    /// to become the new current one.
+    ///
+    /// Everything allocated in the current arena stays readable through
+    /// [`previous`](ScratchPair::previous), and everything allocated in the
+    /// previous arena is discarded. See [`Bump::reset`] for how the reset
+    /// arena keeps its memory.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::scratch::ScratchPair;
+    ///
+    /// let mut pair = ScratchPair::new();
+    /// pair.current().alloc_str("built this iteration");
+    /// let used = pair.current().allocated_bytes();
+    ///
+    /// pair.swap_and_reset();
+    /// assert_eq!(pair.previous().allocated_bytes(), used);
+    /// assert_eq!(pair.current().allocated_bytes_in_current_chunk(), 0);
+    /// ```
+    pub fn swap_and_reset(&mut self) {
+        self.current ^= 1;
+        self.bumps[self.current].reset();
+    }
+
+    /// Run `iterations` iterations of a loop that computes each iteration's
+    /// result from the previous one's, and return the last result.
+    ///
+    /// Each iteration swaps and resets the arenas, as with
+    /// [`swap_and_reset`](ScratchPair::swap_and_reset), and calls `f` with
+    /// the previous result and the current arena to allocate the next result
+    /// in. The first iteration gets `init`, and if `iterations` is zero,
+    /// `init` is returned.
+    ///
+    /// Results have to be references to `T`, which can't borrow from the
+    /// arenas in turn, since each one only lives until the iteration after
+    /// next.
+    ///
+    /// See the [module-level documentation](self) for an example.
+    pub fn iterate<'a, T, F>(&'a mut self, init: &'a T, iterations: usize, mut f: F) -> &'a T
+    where
+        T: ?Sized,
+        F: for<'p, 'c> FnMut(&'p T, &'c Bump<A>) -> &'c T,
+    {
+        let mut prev: *const T = init;
+        for _ in 0..iterations {
+            // This resets the arena that holds the result from two
+            // iterations ago, which `f` no longer has access to.
+            self.swap_and_reset();
+            // Safety: `prev` is either `init` or the previous iteration's
+            // result, which `f` allocated in what is now the previous arena,
+            // and which hasn't been reset since. `f` can't keep the
+            // reference, nor anything else that borrows from the arenas, so
+            // nothing reads from or frees the result while `f` runs.
+            prev = f(unsafe { &*prev }, self.current());
+        }
+        // Safety: as above, and the current arena isn't reset while `self`
+        // is borrowed.
+        unsafe { &*prev }
+    }
+
+    /// Returns the total number of bytes allocated by both arenas, as
+    /// reported by [`Bump::allocated_bytes`].
+    pub fn allocated_bytes(&self) -> usize {
+        self.bumps[0].allocated_bytes() + self.bumps[1].allocated_bytes()
+    }
+
+    /// Consume the pair, returning its current and previous arena, in that
+    /// order.
+    pub fn into_bumps(self) -> (Bump<A>, Bump<A>) {
+        let [a, b] = self.bumps;
+        if self.current == 0 {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+}
+
+impl<A: BackingAllocator + fmt::Debug> fmt::Debug for ScratchPair<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScratchPair")
+            .field("current", self.current())
+            .field("previous", self.previous())
+            .finish()
+    }
+}
@@ -0,0 +1,160 @@
+//! Deserializing directly into arena-allocated types.
+//!
+//! Serde's [`Deserialize`] trait has no way to thread a [`Bump`] through to
+//! the values being built, so the bump collections cannot implement it. This
+//! module provides [`DeserializeIn`], which is implemented for
+//! [`collections::Vec`][crate::collections::Vec],
+//! [`collections::String`][crate::collections::String], and
+//! [`boxed::Box`][crate::boxed::Box] (when the respective features are
+//! enabled), as well as for every type that implements `Deserialize`. This
+//! lets nested structures such as a `Vec<'bump, Box<'bump, T>>` be built
+//! without any intermediate allocations in the global heap.
+//!
+//! ## Example
+//!
+//! ```
+//! # #[cfg(feature = "collections")]
+//! # {
+//! use bumpalo::{collections::Vec, serde::DeserializeIn, Bump};
+//!
+//! let bump = Bump::new();
+//! let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+//! let v = Vec::<u32>::deserialize_in(&mut de, &bump).unwrap();
+//! assert_eq!(v, [1, 2, 3]);
+//! # }
+//! ```
+//!
+//! When a [`DeserializeSeed`] is needed instead, for example to deserialize
+//! the elements of a sequence by hand, use [`DeserializeInSeed`].
+//!
+//! This module also implements [`Serialize`] for [`BumpStats`] snapshots of
+//! an arena's memory usage, so that they can be emitted into structured logs.
+
+use crate::{Bump, BumpStats, ChunkUsage};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// A data structure that can be deserialized into a [`Bump`] arena.
+///
+/// This is the arena-aware counterpart of serde's [`Deserialize`]. See the
+/// [module-level documentation](self) for more details.
+pub trait DeserializeIn<'de, 'bump>: Sized {
+    /// Deserialize a value from the given deserializer, allocating any
+    /// storage it needs inside `bump`.
+    fn deserialize_in<D>(deserializer: D, bump: &'bump Bump) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+impl<'de, 'bump, T> DeserializeIn<'de, 'bump> for T
+where
+    T: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize_in<D>(deserializer: D, _bump: &'bump Bump) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// A [`DeserializeSeed`] that deserializes a `T` into a [`Bump`] arena via
+/// its [`DeserializeIn`] implementation.
+///
+/// ## Example
+///
+/// ```
+/// # #[cfg(feature = "collections")]
+/// # {
+/// use bumpalo::{collections::String, serde::DeserializeInSeed, Bump};
+/// use serde::de::DeserializeSeed;
+///
+/// let bump = Bump::new();
+/// let mut de = serde_json::Deserializer::from_str("\"hello\"");
+/// let s: String = DeserializeInSeed::new(&bump).deserialize(&mut de).unwrap();
+/// assert_eq!(s, "hello");
+/// # }
+/// ```
+pub struct DeserializeInSeed<'bump, T> {
+    bump: &'bump Bump,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'bump, T> DeserializeInSeed<'bump, T> {
+    /// Construct a new seed that allocates inside `bump`.
+    #[inline]
+    pub fn new(bump: &'bump Bump) -> Self {
+        DeserializeInSeed {
+            bump,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'bump, T> Clone for DeserializeInSeed<'bump, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'bump, T> Copy for DeserializeInSeed<'bump, T> {}
+
+impl<'bump, T> fmt::Debug for DeserializeInSeed<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeserializeInSeed")
+            .field("bump", &self.bump)
+            .finish()
+    }
+}
+
+impl<'de, 'bump, T> DeserializeSeed<'de> for DeserializeInSeed<'bump, T>
+where
+    T: DeserializeIn<'de, 'bump>,
+{
+    type Value = T;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_in(deserializer, self.bump)
+    }
+}
+
+impl Serialize for BumpStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("BumpStats", 7)?;
+        state.serialize_field("allocated_bytes", &self.allocated_bytes())?;
+        state.serialize_field(
+            "allocated_bytes_including_metadata",
+            &self.allocated_bytes_including_metadata(),
+        )?;
+        state.serialize_field("used_bytes", &self.used_bytes())?;
+        state.serialize_field("chunk_count", &self.chunk_count())?;
+        state.serialize_field("spare_capacity", &self.spare_capacity())?;
+        state.serialize_field("allocation_limit", &self.allocation_limit())?;
+        state.serialize_field("chunks", self.chunks())?;
+        state.end()
+    }
+}
+
+impl Serialize for ChunkUsage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("ChunkUsage", 3)?;
+        state.serialize_field("size", &self.size())?;
+        state.serialize_field("used", &self.used())?;
+        state.serialize_field("is_current", &self.is_current())?;
+        state.end()
+    }
+}
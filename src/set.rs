@@ -0,0 +1,270 @@
+//! Families of arenas that recycle each other's chunks.
+//!
+//! Each [`Bump`] allocates its chunks from its backing allocator, and gives
+//! them back when it is dropped, or when it is reset and frees all but its
+//! last chunk. A program that creates many short-lived arenas, e.g. one per
+//! request, thus spends time allocating and freeing the same chunk sizes
+//! over and over. A [`BumpSet`] is a backing allocator that keeps the chunks
+//! its member arenas free, up to a configurable number of bytes, and hands
+//! them to the next member that asks for a chunk of the same size.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::set::BumpSet;
+//!
+//! let set = BumpSet::new();
+//!
+//! for request in 0..3 {
+//!     let bump = set.new_bump();
+//!     bump.alloc_slice_fill_copy(1000, request);
+//!     // `bump`'s chunks go back to `set` here, and the next iteration's
+//!     // arena reuses them instead of calling into the global allocator.
+//! }
+//!
+//! assert!(set.retained_bytes() > 0);
+//! ```
+
+use crate::{BackingAllocator, Bump, Global};
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+use core::mem;
+use core::ptr::{self, NonNull};
+
+/// The header written into the start of each retained chunk, linking it into
+/// the set's free list.
+struct FreeChunk {
+    next: Cell<Option<NonNull<FreeChunk>>>,
+    layout: Layout,
+}
+
+/// A backing allocator shared by a family of arenas, that recycles the chunks
+/// they free.
+///
+/// Create member arenas with [`new_bump`](BumpSet::new_bump), or with
+/// [`Bump::new_in(&set)`](Bump::new_in) and friends. When a member frees a
+/// chunk, the set keeps it, as long as it retains no more than its
+/// [`max_retained_bytes`](BumpSet::max_retained_bytes) in total. Otherwise
+/// the chunk is returned to the set's own backing allocator. When a member
+/// needs a new chunk, a retained chunk of exactly the same size and
+/// alignment is reused if there is one.
+///
+/// Retained chunks are returned to the backing allocator when the set is
+/// dropped, or with [`release`](BumpSet::release).
+///
+/// See the [module-level documentation](self) for more details.
+pub struct BumpSet<A: BackingAllocator = Global> {
+    // The most recently freed chunk, which is reused first.
+    free: Cell<Option<NonNull<FreeChunk>>>,
+    retained_bytes: Cell<usize>,
+    max_retained_bytes: Cell<usize>,
+    backing: A,
+}
+
+// The retained chunks are owned by the set, so it can be sent to another
+// thread whenever its backing allocator can.
+unsafe impl<A: BackingAllocator + Send> Send for BumpSet<A> {}
+
+impl BumpSet {
+    /// Construct a new set that retains every chunk its members free, and
+    /// allocates new chunks from the global allocator.
+    pub fn new() -> BumpSet {
+        BumpSet::new_in(Global)
+    }
+
+    /// Construct a new set that retains at most `bytes` bytes of chunks, and
+    /// allocates new chunks from the global allocator.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::set::BumpSet;
+    ///
+    /// let set = BumpSet::with_max_retained_bytes(0);
+    /// drop(set.new_bump().alloc(1));
+    /// assert_eq!(set.retained_bytes(), 0);
+    /// ```
+    pub fn with_max_retained_bytes(bytes: usize) -> BumpSet {
+        BumpSet::with_max_retained_bytes_in(bytes, Global)
+    }
+}
+
+impl Default for BumpSet {
+    fn default() -> BumpSet {
+        BumpSet::new()
+    }
+}
+
+impl<A: BackingAllocator> BumpSet<A> {
+    /// Construct a new set that retains every chunk its members free, and
+    /// allocates new chunks from the given backing allocator.
+    pub fn new_in(backing: A) -> BumpSet<A> {
+        BumpSet::with_max_retained_bytes_in(usize::MAX, backing)
+    }
+
+    /// Construct a new set that retains at most `bytes` bytes of chunks, and
+    /// allocates new chunks from the given backing allocator.
+    pub fn with_max_retained_bytes_in(bytes: usize, backing: A) -> BumpSet<A> {
+        BumpSet {
+            free: Cell::new(None),
+            retained_bytes: Cell::new(0),
+            max_retained_bytes: Cell::new(bytes),
+            backing,
+        }
+    }
+
+    /// Construct a new, empty member arena.
+    ///
+    /// This does not allocate. The arena's first chunk comes from the set
+    /// when the arena is first allocated in.
+    pub fn new_bump(&self) -> Bump<&BumpSet<A>> {
+        Bump::new_in(self)
+    }
+
+    /// Returns the backing allocator that this set allocates new chunks
+    /// from.
+    pub fn backing(&self) -> &A {
+        &self.backing
+    }
+
+    /// Returns the total size of the chunks that this set is currently
+    /// retaining for reuse.
+    pub fn retained_bytes(&self) -> usize {
+        self.retained_bytes.get()
+    }
+
+    /// Returns the maximum total size of the chunks that this set retains.
+    pub fn max_retained_bytes(&self) -> usize {
+        self.max_retained_bytes.get()
+    }
+
+    /// Set the maximum total size of the chunks that this set retains.
+    ///
+    /// If the set currently retains more than `bytes` bytes, chunks are
+    /// returned to the backing allocator until it doesn't, most recently
+    /// freed first.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::set::BumpSet;
+    ///
+    /// let set = BumpSet::new();
+    /// drop(set.new_bump().alloc(1));
+    /// assert!(set.retained_bytes() > 0);
+    ///
+    /// set.set_max_retained_bytes(0);
+    /// assert_eq!(set.retained_bytes(), 0);
+    /// ```
+    pub fn set_max_retained_bytes(&self, bytes: usize) {
+        self.max_retained_bytes.set(bytes);
+        while self.retained_bytes.get() > bytes {
+            match self.pop_free() {
+                Some((ptr, layout)) => unsafe { self.backing.deallocate(ptr, layout) },
+                None => break,
+            }
+        }
+    }
+
+    /// Return all retained chunks to the backing allocator.
+    ///
+    /// Chunks that members free afterwards are retained again, up to
+    /// [`max_retained_bytes`](BumpSet::max_retained_bytes).
+    pub fn release(&self) {
+        while let Some((ptr, layout)) = self.pop_free() {
+            unsafe { self.backing.deallocate(ptr, layout) }
+        }
+    }
+
+    fn pop_free(&self) -> Option<(NonNull<u8>, Layout)> {
+        let chunk = self.free.get()?;
+        unsafe {
+            let FreeChunk { next, layout } = ptr::read(chunk.as_ptr());
+            self.free.set(next.get());
+            self.retained_bytes
+                .set(self.retained_bytes.get() - layout.size());
+            Some((chunk.cast(), layout))
+        }
+    }
+
+    /// Unlink and return a retained chunk with exactly the given layout.
+    fn take_free(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let mut link = &self.free;
+        // Safety: every chunk in the free list starts with a valid
+        // `FreeChunk` header, and the list is only modified here and in
+        // `pop_free` and `deallocate`.
+        unsafe {
+            while let Some(chunk) = link.get() {
+                let header = &*(chunk.as_ptr() as *const FreeChunk);
+                if header.layout == layout {
+                    link.set(header.next.get());
+                    self.retained_bytes
+                        .set(self.retained_bytes.get() - layout.size());
+                    return Some(chunk.cast());
+                }
+                link = &header.next;
+            }
+        }
+        None
+    }
+}
+
+impl<A: BackingAllocator> Drop for BumpSet<A> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl<A: BackingAllocator + fmt::Debug> fmt::Debug for BumpSet<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpSet")
+            .field("retained_bytes", &self.retained_bytes.get())
+            .field("max_retained_bytes", &self.max_retained_bytes.get())
+            .field("backing", &self.backing)
+            .finish()
+    }
+}
+
+unsafe impl<A: BackingAllocator> BackingAllocator for BumpSet<A> {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.take_free(layout)
+            .or_else(|| self.backing.allocate(layout))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        match self.take_free(layout) {
+            Some(ptr) => {
+                unsafe { ptr::write_bytes(ptr.as_ptr(), 0, layout.size()) };
+                Some(ptr)
+            }
+            None => self.backing.allocate_zeroed(layout),
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        let fits_header = layout.size() >= mem::size_of::<FreeChunk>()
+            && layout.align() >= mem::align_of::<FreeChunk>();
+        let retained = self.retained_bytes.get();
+        let within_limit = layout.size() <= self.max_retained_bytes.get().saturating_sub(retained);
+        if !fits_header || !within_limit {
+            return self.backing.deallocate(ptr, layout);
+        }
+
+        let chunk = ptr.cast::<FreeChunk>();
+        ptr::write(
+            chunk.as_ptr(),
+            FreeChunk {
+                next: Cell::new(self.free.get()),
+                layout,
+            },
+        );
+        self.free.set(Some(chunk));
+        self.retained_bytes.set(retained + layout.size());
+    }
+
+    #[inline]
+    unsafe fn discard(&self, ptr: NonNull<u8>, len: usize) {
+        self.backing.discard(ptr, len)
+    }
+}
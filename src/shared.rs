@@ -0,0 +1,310 @@
+//! Owning, reference-counted allocator handles to an arena.
+//!
+//! `&Bump` implements `Allocator`, so collections that are generic over their
+//! allocator can allocate in an arena. The collections then borrow the arena,
+//! though, which gets in the way of storing them next to it, e.g. in the same
+//! struct, or of keeping them around for an unknown amount of time. The
+//! handles in this module own the arena instead, and implement `Allocator`
+//! themselves:
+//!
+//! * [`SharedBump`] keeps the arena in an `Rc`, for use on a single thread.
+//!
+//! * [`SyncBump`] keeps the arena in an `Arc<Mutex<_>>`, so that its clones
+//!   can be sent to, and allocate on, other threads. This requires the `std`
+//!   Cargo feature.
+//!
+//! Every clone of a handle allocates in the same arena, which is dropped,
+//! freeing all of its memory, once the last clone is.
+//!
+//! This module is only available with the `allocator_api` or
+//! `allocator-api2` Cargo features.
+//!
+//! ## Example
+//!
+//! ```
+//! # #[cfg(feature = "allocator-api2")]
+//! # {
+//! use allocator_api2::alloc::{Allocator, Layout};
+//! use bumpalo::shared::SharedBump;
+//! use std::ptr::NonNull;
+//!
+//! // A struct that owns its allocator handle, so it doesn't borrow an arena
+//! // that lives elsewhere.
+//! struct Interner<A: Allocator> {
+//!     alloc: A,
+//!     strings: Vec<NonNull<[u8]>>,
+//! }
+//!
+//! fn interner() -> Interner<SharedBump> {
+//!     Interner { alloc: SharedBump::new(), strings: vec![] }
+//! }
+//!
+//! let mut interner = interner();
+//! let block = interner.alloc.allocate(Layout::new::<[u8; 16]>()).unwrap();
+//! interner.strings.push(block);
+//! assert!(interner.alloc.allocated_bytes() >= 16);
+//! # }
+//! ```
+
+use crate::{AllocError, Allocator, BackingAllocator, Bump, Global};
+use core::fmt;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core_alloc::alloc::Layout;
+use core_alloc::rc::Rc;
+
+/// A reference-counted handle to an arena, that allocates in it.
+///
+/// Cloning the handle only bumps the reference count, and every clone
+/// allocates in the same arena. The arena is dropped with the last clone.
+/// The handle dereferences to the arena, for its regular allocation methods.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct SharedBump<A: BackingAllocator = Global> {
+    bump: Rc<Bump<A>>,
+}
+
+impl SharedBump {
+    /// Construct a handle to a new, empty arena.
+    ///
+    /// This does not allocate any chunks for the arena.
+    pub fn new() -> SharedBump {
+        SharedBump::from(Bump::new())
+    }
+}
+
+impl Default for SharedBump {
+    fn default() -> SharedBump {
+        SharedBump::new()
+    }
+}
+
+impl<A: BackingAllocator> SharedBump<A> {
+    /// Take back the arena, if this is its last handle.
+    ///
+    /// Otherwise, returns the handle in the `Err`.
+    pub fn try_unwrap(this: SharedBump<A>) -> Result<Bump<A>, SharedBump<A>> {
+        Rc::try_unwrap(this.bump).map_err(|bump| SharedBump { bump })
+    }
+
+    /// Returns the number of handles to this arena.
+    pub fn handle_count(this: &SharedBump<A>) -> usize {
+        Rc::strong_count(&this.bump)
+    }
+}
+
+impl<A: BackingAllocator> From<Bump<A>> for SharedBump<A> {
+    fn from(bump: Bump<A>) -> SharedBump<A> {
+        SharedBump {
+            bump: Rc::new(bump),
+        }
+    }
+}
+
+impl<A: BackingAllocator> Clone for SharedBump<A> {
+    fn clone(&self) -> SharedBump<A> {
+        SharedBump {
+            bump: Rc::clone(&self.bump),
+        }
+    }
+}
+
+impl<A: BackingAllocator> Deref for SharedBump<A> {
+    type Target = Bump<A>;
+
+    #[inline]
+    fn deref(&self) -> &Bump<A> {
+        &self.bump
+    }
+}
+
+impl<A: BackingAllocator + fmt::Debug> fmt::Debug for SharedBump<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedBump").field(&*self.bump).finish()
+    }
+}
+
+// Clones of the handle share the arena, so they can free each other's
+// allocations, and the arena's memory stays valid until the last one is
+// dropped.
+unsafe impl<A: BackingAllocator> Allocator for SharedBump<A> {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Allocator::allocate(&&*self.bump, layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Allocator::deallocate(&&*self.bump, ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Allocator::shrink(&&*self.bump, ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Allocator::grow(&&*self.bump, ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Allocator::grow_zeroed(&&*self.bump, ptr, old_layout, new_layout)
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::sync::SyncBump;
+
+#[cfg(feature = "std")]
+mod sync {
+    use super::*;
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    /// A thread-safe, reference-counted handle to an arena, that allocates
+    /// in it.
+    ///
+    /// Like [`SharedBump`], but the arena is kept in an `Arc<Mutex<_>>`, so
+    /// the handle is `Send` and `Sync` when the arena's backing allocator is
+    /// `Send`. Every allocation locks the arena, so contended use is slow;
+    /// this is meant for handing collections between threads, rather than for
+    /// allocating on many threads at once.
+    ///
+    /// See the [module-level documentation](self) for more details.
+    pub struct SyncBump<A: BackingAllocator = Global> {
+        bump: Arc<Mutex<Bump<A>>>,
+    }
+
+    impl SyncBump {
+        /// Construct a handle to a new, empty arena.
+        ///
+        /// This does not allocate any chunks for the arena.
+        pub fn new() -> SyncBump {
+            SyncBump::from(Bump::new())
+        }
+    }
+
+    impl Default for SyncBump {
+        fn default() -> SyncBump {
+            SyncBump::new()
+        }
+    }
+
+    impl<A: BackingAllocator> SyncBump<A> {
+        /// Run `f` with the arena locked, e.g. to use its regular allocation
+        /// methods.
+        ///
+        /// The closure's return value cannot borrow from the arena. A panic
+        /// while the arena is locked doesn't poison it, since allocating
+        /// leaves the arena in a consistent state even when it panics.
+        ///
+        /// ## Example
+        ///
+        /// ```
+        /// use bumpalo::shared::SyncBump;
+        ///
+        /// let bump = SyncBump::new();
+        /// let len = bump.with_bump(|bump| bump.alloc_str("hello").len());
+        /// assert_eq!(len, 5);
+        /// ```
+        pub fn with_bump<R>(&self, f: impl FnOnce(&Bump<A>) -> R) -> R {
+            f(&self.bump.lock().unwrap_or_else(PoisonError::into_inner))
+        }
+
+        /// Take back the arena, if this is its last handle.
+        ///
+        /// Otherwise, returns the handle in the `Err`.
+        pub fn try_unwrap(this: SyncBump<A>) -> Result<Bump<A>, SyncBump<A>> {
+            Arc::try_unwrap(this.bump)
+                .map(|bump| bump.into_inner().unwrap_or_else(PoisonError::into_inner))
+                .map_err(|bump| SyncBump { bump })
+        }
+
+        /// Returns the number of handles to this arena.
+        pub fn handle_count(this: &SyncBump<A>) -> usize {
+            Arc::strong_count(&this.bump)
+        }
+    }
+
+    impl<A: BackingAllocator> From<Bump<A>> for SyncBump<A> {
+        fn from(bump: Bump<A>) -> SyncBump<A> {
+            SyncBump {
+                bump: Arc::new(Mutex::new(bump)),
+            }
+        }
+    }
+
+    impl<A: BackingAllocator> Clone for SyncBump<A> {
+        fn clone(&self) -> SyncBump<A> {
+            SyncBump {
+                bump: Arc::clone(&self.bump),
+            }
+        }
+    }
+
+    impl<A: BackingAllocator + fmt::Debug> fmt::Debug for SyncBump<A> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.with_bump(|bump| f.debug_tuple("SyncBump").field(bump).finish())
+        }
+    }
+
+    // As for `SharedBump`, and the lock makes sure that only one thread at a
+    // time uses the arena.
+    unsafe impl<A: BackingAllocator> Allocator for SyncBump<A> {
+        #[inline]
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.with_bump(|bump| Allocator::allocate(&bump, layout))
+        }
+
+        #[inline]
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.with_bump(|bump| Allocator::deallocate(&bump, ptr, layout))
+        }
+
+        #[inline]
+        unsafe fn shrink(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            self.with_bump(|bump| Allocator::shrink(&bump, ptr, old_layout, new_layout))
+        }
+
+        #[inline]
+        unsafe fn grow(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            self.with_bump(|bump| Allocator::grow(&bump, ptr, old_layout, new_layout))
+        }
+
+        #[inline]
+        unsafe fn grow_zeroed(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            self.with_bump(|bump| Allocator::grow_zeroed(&bump, ptr, old_layout, new_layout))
+        }
+    }
+}
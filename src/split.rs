@@ -0,0 +1,279 @@
+//! Splitting an arena's free space between threads.
+//!
+//! A [`Bump`] is `!Sync`, so only one thread at a time can allocate in it.
+//! [`Bump::split_remaining`] carves the free space of the arena's current
+//! chunk into disjoint [`SubBump`]s instead, which are `Send`, so that each
+//! can be moved to a different (scoped) thread and allocated in without any
+//! synchronization. Their allocations live in the arena, and stay there until
+//! it is reset, like the arena's own.
+//!
+//! A `SubBump` never allocates more memory: it fails once its share of the
+//! chunk is used up. Reserve enough room in the arena before splitting it,
+//! e.g. with [`Bump::with_capacity`] or [`Bump::reserve_bytes`].
+//!
+//! Once the threads are done, [`Bump::merge`] gives the space that a
+//! `SubBump` left unused back to the arena, as long as that space borders the
+//! arena's own free space. Merging the `SubBump`s in the order they were
+//! handed out gives back the unused space of the first one, and of every one
+//! after it while those before it were left completely unused.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::Bump;
+//!
+//! let bump = Bump::with_capacity(64 * 1024);
+//! let mut results = vec![];
+//!
+//! std::thread::scope(|s| {
+//!     let handles: Vec<_> = bump
+//!         .split_remaining(4)
+//!         .enumerate()
+//!         .map(|(i, sub)| s.spawn(move || &*sub.alloc_slice_copy(&[i; 100])))
+//!         .collect();
+//!     for handle in handles {
+//!         results.push(handle.join().unwrap());
+//!     }
+//! });
+//!
+//! for (i, slice) in results.iter().enumerate() {
+//!     assert!(slice.iter().all(|&x| x == i));
+//! }
+//! ```
+//!
+//! [`Bump`]: crate::Bump
+//! [`Bump::split_remaining`]: crate::Bump::split_remaining
+//! [`Bump::with_capacity`]: crate::Bump::with_capacity
+//! [`Bump::reserve_bytes`]: crate::Bump::reserve_bytes
+//! [`Bump::merge`]: crate::Bump::merge
+
+use crate::{asan_unpoison, oom, poison_fresh, round_mut_ptr_down_to, AllocErr};
+use core::cell::Cell;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::slice;
+use core::str;
+use core_alloc::alloc::Layout;
+
+/// A bump allocator for a share of an arena's current chunk.
+///
+/// This is returned by [`Bump::split_remaining`](crate::Bump::split_remaining).
+/// Like a [`Bump`](crate::Bump), it does not run `Drop` implementations of
+/// the values allocated in it.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct SubBump<'a> {
+    start: NonNull<u8>,
+    end: NonNull<u8>,
+    // Allocations are bumped downwards from `end`, and this is always in
+    // `start..=end`.
+    ptr: Cell<NonNull<u8>>,
+    _bump: PhantomData<&'a mut [u8]>,
+}
+
+// A `SubBump` has exclusive access to its part of the chunk, which the arena
+// will not touch again until it is reset, and that needs `&mut Bump`.
+unsafe impl Send for SubBump<'_> {}
+
+impl<'a> SubBump<'a> {
+    /// Allocate an object in this sub-allocator and return an exclusive
+    /// reference to it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the sub-allocator doesn't have room for `val`.
+    #[inline]
+    pub fn alloc<T>(&self, val: T) -> &'a mut T {
+        self.try_alloc(val).unwrap_or_else(|_| oom())
+    }
+
+    /// Try to allocate an object in this sub-allocator and return an
+    /// exclusive reference to it.
+    ///
+    /// ## Errors
+    ///
+    /// Errors with [`AllocErrKind::OutOfMemory`](crate::AllocErrKind::OutOfMemory) if the sub-allocator doesn't
+    /// have room for `val`.
+    #[inline]
+    pub fn try_alloc<T>(&self, val: T) -> Result<&'a mut T, AllocErr> {
+        let p = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            self.try_alloc_layout(Layout::new::<T>())?.cast::<T>()
+        };
+        unsafe {
+            ptr::write(p.as_ptr(), val);
+            Ok(&mut *p.as_ptr())
+        }
+    }
+
+    /// `Copy` a slice into this sub-allocator and return an exclusive
+    /// reference to the copy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the sub-allocator doesn't have room for the slice.
+    #[inline]
+    pub fn alloc_slice_copy<T: Copy>(&self, src: &[T]) -> &'a mut [T] {
+        let dst = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            self.alloc_layout(Layout::for_value(src)).cast::<T>()
+        };
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.as_ptr(), src.len());
+            slice::from_raw_parts_mut(dst.as_ptr(), src.len())
+        }
+    }
+
+    /// Copy a string slice into this sub-allocator and return an exclusive
+    /// reference to the copy.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the sub-allocator doesn't have room for the string.
+    #[inline]
+    pub fn alloc_str(&self, src: &str) -> &'a mut str {
+        let buffer = self.alloc_slice_copy(src.as_bytes());
+        unsafe {
+            // This is OK, because it already came in as str, so it is guaranteed to be utf8
+            str::from_utf8_unchecked_mut(buffer)
+        }
+    }
+
+    /// Allocate space for an object with the given `Layout`.
+    ///
+    /// The returned pointer points at uninitialized memory.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the sub-allocator doesn't have room for `layout`.
+    #[inline]
+    pub fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        self.try_alloc_layout(layout).unwrap_or_else(|_| oom())
+    }
+
+    /// Try to allocate space for an object with the given `Layout`.
+    ///
+    /// The returned pointer points at uninitialized memory.
+    ///
+    /// ## Errors
+    ///
+    /// Errors with [`AllocErrKind::OutOfMemory`](crate::AllocErrKind::OutOfMemory) if the sub-allocator doesn't
+    /// have room for `layout`.
+    #[inline]
+    pub fn try_alloc_layout(&self, layout: Layout) -> Result<NonNull<u8>, AllocErr> {
+        let ptr = self.ptr.get().as_ptr();
+        let available = ptr as usize - self.start.as_ptr() as usize;
+        if available < layout.size() {
+            return Err(AllocErr::OUT_OF_MEMORY);
+        }
+        let aligned = round_mut_ptr_down_to(ptr.wrapping_sub(layout.size()), layout.align());
+        if (aligned as usize) < self.start.as_ptr() as usize {
+            return Err(AllocErr::OUT_OF_MEMORY);
+        }
+        unsafe {
+            let aligned = NonNull::new_unchecked(aligned);
+            self.ptr.set(aligned);
+            asan_unpoison(aligned.as_ptr(), ptr as usize - aligned.as_ptr() as usize);
+            poison_fresh(aligned.as_ptr(), layout.size());
+            Ok(aligned)
+        }
+    }
+
+    /// Returns the number of bytes this sub-allocator started out with.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.end.as_ptr() as usize - self.start.as_ptr() as usize
+    }
+
+    /// Returns the number of bytes that are still free in this
+    /// sub-allocator, some of which may go to alignment padding.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.ptr.get().as_ptr() as usize - self.start.as_ptr() as usize
+    }
+    /// The start of this sub-allocator's part of the chunk, and where its
+    /// free space ends.
+    pub(crate) fn free_space(&self) -> (NonNull<u8>, NonNull<u8>) {
+        (self.start, self.ptr.get())
+    }
+}
+
+impl fmt::Debug for SubBump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubBump")
+            .field("capacity", &self.capacity())
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+/// An iterator over the [`SubBump`]s that an arena's free space was split
+/// into.
+///
+/// This struct is created by the
+/// [`split_remaining`](crate::Bump::split_remaining) method on
+/// [`Bump`](crate::Bump). See that function for more details.
+#[derive(Debug)]
+pub struct SplitRemaining<'a> {
+    next: NonNull<u8>,
+    end: NonNull<u8>,
+    part: usize,
+    count: usize,
+    _bump: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> SplitRemaining<'a> {
+    /// Split `start..end` into `count` parts.
+    pub(crate) unsafe fn new(start: NonNull<u8>, end: NonNull<u8>, count: usize) -> Self {
+        let len = end.as_ptr() as usize - start.as_ptr() as usize;
+        SplitRemaining {
+            next: start,
+            end,
+            part: len.checked_div(count).unwrap_or(0),
+            count,
+            _bump: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for SplitRemaining<'a> {
+    type Item = SubBump<'a>;
+
+    fn next(&mut self) -> Option<SubBump<'a>> {
+        if self.count == 0 {
+            return None;
+        }
+        self.count -= 1;
+        let start = self.next;
+        // The last part also gets what's left over from dividing evenly.
+        let end = if self.count == 0 {
+            self.end
+        } else {
+            unsafe { NonNull::new_unchecked(start.as_ptr().add(self.part)) }
+        };
+        self.next = end;
+        Some(SubBump {
+            start,
+            end,
+            ptr: Cell::new(end),
+            _bump: PhantomData,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count, Some(self.count))
+    }
+}
+
+impl ExactSizeIterator for SplitRemaining<'_> {}
+
+impl FusedIterator for SplitRemaining<'_> {}
+
+// Like `SubBump`, the iterator only hands out parts of the chunk that the
+// arena won't touch.
+unsafe impl Send for SplitRemaining<'_> {}
@@ -0,0 +1,249 @@
+//! Sharing arena allocations across threads.
+//!
+//! A [`Bump`] is `!Sync`, so other threads can't allocate in it. They can
+//! still read what it has allocated, as long as the arena outlives them,
+//! which scoped threads guarantee. An [`ArcRef`] is an atomically
+//! reference-counted handle to a value in an arena, for when several threads
+//! need to share ownership of it: the value is dropped once the last handle
+//! is dropped. Its memory is not freed until the arena is.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::{Bump, sync::ArcRef};
+//!
+//! let bump = Bump::new();
+//! let words = ArcRef::new_in(vec!["hello", "world"], &bump);
+//!
+//! std::thread::scope(|s| {
+//!     for _ in 0..4 {
+//!         let words = words.clone();
+//!         s.spawn(move || assert_eq!(words.len(), 2));
+//!     }
+//! });
+//!
+//! assert_eq!(ArcRef::strong_count(&words), 1);
+//! ```
+
+use crate::{BackingAllocator, Bump};
+use core::borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{self, AtomicUsize, Ordering};
+
+// Clones past this count are refused, well before the count could overflow.
+const MAX_COUNT: usize = isize::MAX as usize;
+
+struct ArcInner<T: ?Sized> {
+    count: AtomicUsize,
+    value: T,
+}
+
+/// An atomically reference-counted, shared handle to a value in a [`Bump`].
+///
+/// Cloning an `ArcRef` only bumps its reference count. The value is dropped
+/// when the last clone is dropped, but its memory stays allocated until the
+/// arena is dropped or reset. An `ArcRef<T>` is `Send` and `Sync` when `T` is
+/// both, just like `std::sync::Arc<T>`.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct ArcRef<'bump, T> {
+    ptr: NonNull<ArcInner<T>>,
+    // The value lives in an arena borrowed for `'bump`, and is dropped by
+    // the last handle.
+    marker: PhantomData<(&'bump (), ArcInner<T>)>,
+}
+
+unsafe impl<'bump, T: Send + Sync> Send for ArcRef<'bump, T> {}
+unsafe impl<'bump, T: Send + Sync> Sync for ArcRef<'bump, T> {}
+
+impl<'bump, T> ArcRef<'bump, T> {
+    /// Allocates `value` in `bump`, returning the first handle to it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the value fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, sync::ArcRef};
+    ///
+    /// let bump = Bump::new();
+    /// let x = ArcRef::new_in(5, &bump);
+    /// assert_eq!(*x, 5);
+    /// ```
+    pub fn new_in<A: BackingAllocator>(value: T, bump: &'bump Bump<A>) -> ArcRef<'bump, T> {
+        let inner = bump.alloc(ArcInner {
+            count: AtomicUsize::new(1),
+            value,
+        });
+        ArcRef {
+            ptr: NonNull::from(inner),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn inner(&self) -> &ArcInner<T> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns the number of handles to this value.
+    ///
+    /// Other threads may clone or drop handles at any time, so the result
+    /// can be out of date as soon as it is returned.
+    #[inline]
+    pub fn strong_count(this: &ArcRef<'bump, T>) -> usize {
+        this.inner().count.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if both handles point to the same value.
+    #[inline]
+    pub fn ptr_eq(this: &ArcRef<'bump, T>, other: &ArcRef<'bump, T>) -> bool {
+        this.ptr == other.ptr
+    }
+
+    /// Returns a mutable reference to the value, if there are no other
+    /// handles to it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, sync::ArcRef};
+    ///
+    /// let bump = Bump::new();
+    /// let mut x = ArcRef::new_in(5, &bump);
+    /// *ArcRef::get_mut(&mut x).unwrap() += 1;
+    ///
+    /// let y = x.clone();
+    /// assert!(ArcRef::get_mut(&mut x).is_none());
+    /// assert_eq!(*y, 6);
+    /// ```
+    pub fn get_mut<'a>(this: &'a mut ArcRef<'bump, T>) -> Option<&'a mut T> {
+        // Synchronize with the `Release` decrements of other handles, so
+        // that their reads of the value happen before our writes.
+        if this.inner().count.load(Ordering::Acquire) == 1 {
+            unsafe { Some(&mut (*this.ptr.as_ptr()).value) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value, if this is its only handle. Otherwise, returns the
+    /// handle.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::{Bump, sync::ArcRef};
+    ///
+    /// let bump = Bump::new();
+    /// let x = ArcRef::new_in(String::from("hi"), &bump);
+    /// let y = x.clone();
+    ///
+    /// let x = ArcRef::try_unwrap(x).unwrap_err();
+    /// drop(y);
+    /// assert_eq!(ArcRef::try_unwrap(x).unwrap(), "hi");
+    /// ```
+    pub fn try_unwrap(this: ArcRef<'bump, T>) -> Result<T, ArcRef<'bump, T>> {
+        if this
+            .inner()
+            .count
+            .compare_exchange(1, 0, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(this);
+        }
+        atomic::fence(Ordering::Acquire);
+        let value = unsafe { ptr::read(&this.inner().value) };
+        core::mem::forget(this);
+        Ok(value)
+    }
+}
+
+impl<'bump, T> Clone for ArcRef<'bump, T> {
+    fn clone(&self) -> Self {
+        // Like `std::sync::Arc`, new handles are only made from existing
+        // ones, so no synchronization is needed to increment the count.
+        let old = self.inner().count.fetch_add(1, Ordering::Relaxed);
+        if old > MAX_COUNT {
+            self.inner().count.fetch_sub(1, Ordering::Relaxed);
+            panic!("too many clones of a bumpalo::sync::ArcRef");
+        }
+        ArcRef {
+            ptr: self.ptr,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'bump, T> Drop for ArcRef<'bump, T> {
+    fn drop(&mut self) {
+        if self.inner().count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        // Synchronize with the other handles' decrements, so that all their
+        // uses of the value happen before it is dropped.
+        atomic::fence(Ordering::Acquire);
+        unsafe { ptr::drop_in_place(&mut (*self.ptr.as_ptr()).value) }
+    }
+}
+
+impl<'bump, T> Deref for ArcRef<'bump, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<'bump, T> AsRef<T> for ArcRef<'bump, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'bump, T> borrow::Borrow<T> for ArcRef<'bump, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'bump, T: fmt::Debug> fmt::Debug for ArcRef<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'bump, T: fmt::Display> fmt::Display for ArcRef<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'bump, T> fmt::Pointer for ArcRef<'bump, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ptr: *const T = &**self;
+        fmt::Pointer::fmt(&ptr, f)
+    }
+}
+
+impl<'a, 'b, T: PartialEq> PartialEq<ArcRef<'b, T>> for ArcRef<'a, T> {
+    #[inline]
+    fn eq(&self, other: &ArcRef<'b, T>) -> bool {
+        **self == **other
+    }
+}
+
+impl<'bump, T: Eq> Eq for ArcRef<'bump, T> {}
+
+impl<'bump, T: Hash> Hash for ArcRef<'bump, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
@@ -0,0 +1,108 @@
+//! A per-thread arena for scratch allocations.
+//!
+//! [`with_bump`] gives its closure access to an arena that belongs to the
+//! current thread. Calls can be nested, and nested calls share the same
+//! arena. When the outermost call returns, the arena is reset, so its memory
+//! is reused by the next call instead of accumulating over the life of the
+//! thread.
+//!
+//! The closure's return value cannot borrow from the arena, which is what
+//! makes resetting it on the way out safe. Copy anything that needs to
+//! outlive the call out of the arena before returning it.
+//!
+//! This module is only available with the `std` Cargo feature.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::thread_local::with_bump;
+//!
+//! fn render(items: &[u32]) -> String {
+//!     with_bump(|bump| {
+//!         let parts = bump.alloc_slice_fill_iter(items.iter().map(|i| i * 2));
+//!         format!("{:?}", parts)
+//!     })
+//! }
+//!
+//! assert_eq!(render(&[1, 2, 3]), "[2, 4, 6]");
+//! ```
+
+use crate::Bump;
+use std::cell::{Cell, UnsafeCell};
+
+struct ThreadArena {
+    bump: UnsafeCell<Bump>,
+    // The number of `with_bump` calls currently running on this thread.
+    depth: Cell<usize>,
+}
+
+std::thread_local! {
+    static ARENA: ThreadArena = const {
+        ThreadArena {
+            bump: UnsafeCell::new(Bump::empty()),
+            depth: Cell::new(0),
+        }
+    };
+}
+
+/// Run `f` with this thread's arena.
+///
+/// Nested calls on the same thread see the same arena, including everything
+/// allocated by the calls they are nested in. Once the outermost call
+/// returns, or unwinds, the arena is [reset](Bump::reset).
+///
+/// ## Panics
+///
+/// Panics if called while the thread's thread-local storage is being torn
+/// down, e.g. from the destructor of another thread-local.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::thread_local::with_bump;
+///
+/// let n = with_bump(|outer| {
+///     let a = outer.alloc(1);
+///     let b = with_bump(|inner| *inner.alloc(2));
+///     // `a` is still valid: only the outermost call resets the arena.
+///     *a + b
+/// });
+/// assert_eq!(n, 3);
+///
+/// // The arena was reset when the outermost call returned.
+/// with_bump(|bump| assert_eq!(bump.allocated_bytes_in_current_chunk(), 0));
+/// ```
+pub fn with_bump<F, R>(f: F) -> R
+where
+    F: for<'b> FnOnce(&'b Bump) -> R,
+{
+    ARENA.with(|arena| {
+        let _guard = DepthGuard::enter(arena);
+        // Safety: the arena is only mutated by `DepthGuard::drop` once the
+        // depth is back at zero. At that point, every closure that was given
+        // a reference has returned, and because `R` cannot name the
+        // closure's lifetime, none of those references outlive it.
+        f(unsafe { &*arena.bump.get() })
+    })
+}
+
+struct DepthGuard<'a> {
+    arena: &'a ThreadArena,
+}
+
+impl<'a> DepthGuard<'a> {
+    fn enter(arena: &'a ThreadArena) -> DepthGuard<'a> {
+        arena.depth.set(arena.depth.get() + 1);
+        DepthGuard { arena }
+    }
+}
+
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        let depth = self.arena.depth.get() - 1;
+        self.arena.depth.set(depth);
+        if depth == 0 {
+            unsafe { (*self.arena.bump.get()).reset() }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Arenas that hold values of a single type and run their destructors.
+//!
+//! A [`Bump`] never runs `Drop` for the values allocated in it. That is what
+//! makes it fast, but it means that values that own other resources, e.g. a
+//! `std::vec::Vec` or a file handle, leak those resources when the arena is
+//! dropped or reset. A [`TypedBump<T>`] only allocates values of one type
+//! `T`, and drops all of them when it is dropped or reset, while still using
+//! `Bump`'s chunks of memory for the allocations themselves.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::typed::TypedBump;
+//!
+//! let arena = TypedBump::new();
+//! let a = arena.alloc(vec![1, 2, 3]);
+//! let b = arena.alloc(vec![4, 5, 6]);
+//! a.extend_from_slice(b);
+//! assert_eq!(*a, [1, 2, 3, 4, 5, 6]);
+//!
+//! // Both vectors' heap buffers are freed here.
+//! drop(arena);
+//! ```
+
+use crate::{AllocErr, Bump};
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::slice;
+
+/// An arena that only allocates values of type `T`, and drops them all when
+/// it is dropped or reset.
+///
+/// Values are dropped in reverse allocation order: most recently allocated
+/// first.
+///
+/// See the [module-level documentation](self) for more details.
+pub struct TypedBump<T> {
+    bump: Bump,
+    // The number of `T`s allocated in `bump`. Zero-sized `T`s don't take up
+    // any space in its chunks, so this is the only record of them.
+    len: Cell<usize>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for TypedBump<T> {
+    fn default() -> Self {
+        TypedBump::new()
+    }
+}
+
+impl<T> fmt::Debug for TypedBump<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedBump")
+            .field("len", &self.len.get())
+            .field("bump", &self.bump)
+            .finish()
+    }
+}
+
+impl<T> TypedBump<T> {
+    /// Construct a new, empty typed arena.
+    ///
+    /// This does not allocate.
+    pub fn new() -> TypedBump<T> {
+        TypedBump::from_bump(Bump::new())
+    }
+
+    /// Construct a new typed arena with room for at least `capacity` values
+    /// in its first chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if allocating the initial capacity fails.
+    pub fn with_capacity(capacity: usize) -> TypedBump<T> {
+        let bytes = capacity
+            .checked_mul(mem::size_of::<T>())
+            .unwrap_or_else(|| crate::oom());
+        TypedBump::from_bump(Bump::with_capacity(bytes))
+    }
+
+    fn from_bump(bump: Bump) -> TypedBump<T> {
+        TypedBump {
+            bump,
+            len: Cell::new(0),
+            marker: PhantomData,
+        }
+    }
+
+    /// Allocate a value in this arena, returning an exclusive reference to
+    /// it.
+    ///
+    /// The value is dropped when the arena is dropped or reset.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if reserving space for the value fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::typed::TypedBump;
+    ///
+    /// let arena = TypedBump::new();
+    /// let s = arena.alloc(String::from("hello"));
+    /// s.push_str(", world");
+    /// assert_eq!(s, "hello, world");
+    /// ```
+    #[inline]
+    pub fn alloc(&self, val: T) -> &mut T {
+        let val = self.bump.alloc(val);
+        self.len.set(self.len.get() + 1);
+        val
+    }
+
+    /// Try to allocate a value in this arena, returning an exclusive
+    /// reference to it.
+    ///
+    /// The value is dropped when the arena is dropped or reset.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if reserving space for the value fails. The value is dropped in
+    /// that case.
+    #[inline]
+    pub fn try_alloc(&self, val: T) -> Result<&mut T, AllocErr> {
+        let val = self.bump.try_alloc(val)?;
+        self.len.set(self.len.get() + 1);
+        Ok(val)
+    }
+
+    /// Get the number of values allocated in this arena.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if no values have been allocated in this arena.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of bytes this arena has allocated for its values, as
+    /// with [`Bump::allocated_bytes`].
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+
+    /// Set the allocation limit of the underlying arena, as with
+    /// [`Bump::set_allocation_limit`].
+    #[inline]
+    pub fn set_allocation_limit(&self, limit: Option<usize>) {
+        self.bump.set_allocation_limit(limit)
+    }
+
+    /// Drop every value allocated in this arena, and reset it so that its
+    /// memory can be reused, as with [`Bump::reset`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bumpalo::typed::TypedBump;
+    /// use std::rc::Rc;
+    ///
+    /// let shared = Rc::new(());
+    /// let mut arena = TypedBump::new();
+    /// for _ in 0..10 {
+    ///     arena.alloc(shared.clone());
+    /// }
+    /// assert_eq!(Rc::strong_count(&shared), 11);
+    ///
+    /// arena.reset();
+    /// assert!(arena.is_empty());
+    /// assert_eq!(Rc::strong_count(&shared), 1);
+    /// ```
+    pub fn reset(&mut self) {
+        // Reset the arena even if a destructor panics, so that the values
+        // are leaked rather than dropped a second time later.
+        struct ResetOnDrop<'a>(&'a mut Bump);
+
+        impl Drop for ResetOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.reset();
+            }
+        }
+
+        let len = self.len.replace(0);
+        let bump = ResetOnDrop(&mut self.bump);
+        if !mem::needs_drop::<T>() {
+            return;
+        }
+
+        unsafe {
+            if mem::size_of::<T>() == 0 {
+                for _ in 0..len {
+                    ptr::drop_in_place(NonNull::<T>::dangling().as_ptr());
+                }
+                return;
+            }
+
+            // Every allocation in the arena is a `T`, so each chunk's
+            // allocated bytes are an array of `T`s, most recent first. The
+            // only padding is between the least recent `T` and the end of
+            // the chunk, when `T` is more aligned than the chunk, and it is
+            // always smaller than a `T`.
+            for (ptr, bytes) in bump.0.iter_allocated_chunks_raw() {
+                let values =
+                    slice::from_raw_parts_mut(ptr.cast::<T>(), bytes / mem::size_of::<T>());
+                ptr::drop_in_place(values);
+            }
+        }
+    }
+}
+
+impl<T> Drop for TypedBump<T> {
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
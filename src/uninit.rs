@@ -0,0 +1,166 @@
+//! Initializing uninitialized arena allocations.
+//!
+//! [`Bump::alloc_uninit`] and [`Bump::alloc_uninit_slice`] reserve space in an
+//! arena without initializing it, for when the value isn't ready yet at the
+//! time of allocation. A single value is then initialized with
+//! [`MaybeUninit::write`], which returns an exclusive reference to the
+//! initialized value. The functions in this module do the same for slices.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::{uninit, Bump};
+//!
+//! let bump = Bump::new();
+//!
+//! let x = bump.alloc_uninit::<u32>();
+//! let x: &mut u32 = x.write(42);
+//! assert_eq!(*x, 42);
+//!
+//! let xs = bump.alloc_uninit_slice::<u32>(3);
+//! let xs: &mut [u32] = uninit::write_slice_with(xs, |i| i as u32 * 10);
+//! assert_eq!(xs, [0, 10, 20]);
+//! ```
+//!
+//! [`Bump::alloc_uninit`]: crate::Bump::alloc_uninit
+//! [`Bump::alloc_uninit_slice`]: crate::Bump::alloc_uninit_slice
+
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+/// Initialize `dst` by copying the elements of `src` into it, and return an
+/// exclusive reference to the initialized slice.
+///
+/// ## Panics
+///
+/// Panics if `dst` and `src` have different lengths.
+///
+/// ## Example
+///
+/// ```
+/// let bump = bumpalo::Bump::new();
+/// let xs = bump.alloc_uninit_slice(3);
+/// let xs = bumpalo::uninit::write_slice_copy(xs, &[1, 2, 3]);
+/// assert_eq!(xs, [1, 2, 3]);
+/// ```
+#[inline]
+pub fn write_slice_copy<'a, T: Copy>(dst: &'a mut [MaybeUninit<T>], src: &[T]) -> &'a mut [T] {
+    assert_eq!(
+        dst.len(),
+        src.len(),
+        "destination and source slices have different lengths"
+    );
+    unsafe {
+        ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr().cast::<T>(), src.len());
+        assume_init_slice_mut(dst)
+    }
+}
+
+/// Initialize `dst` by cloning the elements of `src` into it, and return an
+/// exclusive reference to the initialized slice.
+///
+/// If cloning an element panics, the elements that were already cloned are
+/// dropped before the panic propagates.
+///
+/// ## Panics
+///
+/// Panics if `dst` and `src` have different lengths, or if cloning an element
+/// panics.
+///
+/// ## Example
+///
+/// ```
+/// let bump = bumpalo::Bump::new();
+/// let xs = bump.alloc_uninit_slice(2);
+/// let xs = bumpalo::uninit::write_slice_clone(xs, &["a".to_string(), "b".to_string()]);
+/// assert_eq!(xs, ["a", "b"]);
+/// ```
+#[inline]
+pub fn write_slice_clone<'a, T: Clone>(dst: &'a mut [MaybeUninit<T>], src: &[T]) -> &'a mut [T] {
+    assert_eq!(
+        dst.len(),
+        src.len(),
+        "destination and source slices have different lengths"
+    );
+    write_slice_with(dst, |i| src[i].clone())
+}
+
+/// Initialize `dst` with the values returned by `f`, and return an exclusive
+/// reference to the initialized slice.
+///
+/// The closure argument is the position in the slice. If the closure panics,
+/// the elements that were already initialized are dropped before the panic
+/// propagates.
+///
+/// ## Panics
+///
+/// Panics if the closure panics.
+///
+/// ## Example
+///
+/// ```
+/// let bump = bumpalo::Bump::new();
+/// let xs = bump.alloc_uninit_slice(5);
+/// let xs = bumpalo::uninit::write_slice_with(xs, |i| 5 * (i + 1));
+/// assert_eq!(xs, [5, 10, 15, 20, 25]);
+/// ```
+#[inline(always)]
+pub fn write_slice_with<T, F>(dst: &mut [MaybeUninit<T>], mut f: F) -> &mut [T]
+where
+    F: FnMut(usize) -> T,
+{
+    // Drops the already-initialized prefix of the slice if `f` panics.
+    struct Guard<T> {
+        dst: *mut T,
+        initialized: usize,
+    }
+
+    impl<T> Drop for Guard<T> {
+        fn drop(&mut self) {
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.dst, self.initialized));
+            }
+        }
+    }
+
+    // Write through one raw pointer for the whole loop, which the guard
+    // shares, rather than reborrowing `dst`, which would invalidate it.
+    let len = dst.len();
+    let mut guard = Guard {
+        dst: dst.as_mut_ptr().cast::<T>(),
+        initialized: 0,
+    };
+    for i in 0..len {
+        unsafe {
+            ptr::write(guard.dst.add(i), f(i));
+        }
+        guard.initialized += 1;
+    }
+    let dst = guard.dst;
+    mem::forget(guard);
+
+    unsafe { slice::from_raw_parts_mut(dst, len) }
+}
+
+/// Get an exclusive reference to the contents of `slice`, which has been
+/// initialized.
+///
+/// ## Safety
+///
+/// Every element of `slice` must have been initialized.
+///
+/// ## Example
+///
+/// ```
+/// let bump = bumpalo::Bump::new();
+/// let xs = bump.alloc_uninit_slice::<u8>(2);
+/// xs[0].write(1);
+/// xs[1].write(2);
+/// let xs = unsafe { bumpalo::uninit::assume_init_slice_mut(xs) };
+/// assert_eq!(xs, [1, 2]);
+/// ```
+#[inline(always)]
+pub unsafe fn assume_init_slice_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    slice::from_raw_parts_mut(slice.as_mut_ptr().cast::<T>(), slice.len())
+}
@@ -0,0 +1,363 @@
+//! Writing formatted output and byte streams directly into an arena.
+//!
+//! A [`BumpWriter`] appends bytes to a single allocation in a [`Bump`], growing
+//! it as needed, and is finally turned into a `&'bump [u8]` or `&'bump str`
+//! that lives as long as the arena. It implements [`core::fmt::Write`], and
+//! `std::io::Write` when the `std` Cargo feature is enabled, so existing
+//! formatters and serializers can emit their output straight into the arena,
+//! without going through an intermediate heap buffer.
+//!
+//! While the writer's buffer is the arena's most recent allocation, it grows
+//! in place. If other allocations are made in the arena in the meantime, the
+//! buffer is copied to a new allocation when it next grows.
+//!
+//! ## Example
+//!
+//! ```
+//! use bumpalo::Bump;
+//! use std::fmt::Write;
+//!
+//! let bump = Bump::new();
+//!
+//! let mut w = bump.writer();
+//! write!(w, "{} + {} = {}", 1, 2, 1 + 2).unwrap();
+//! let s: &str = w.into_bump_str().unwrap();
+//!
+//! assert_eq!(s, "1 + 2 = 3");
+//! ```
+//!
+//! When all the output can be produced by a single format string, the
+//! [`write!`](crate::write!) macro creates the writer, writes to it, and
+//! finalizes it in one step:
+//!
+//! ```
+//! use bumpalo::Bump;
+//!
+//! let bump = Bump::new();
+//! let s: &str = bumpalo::write!(in &bump, "{} + {} = {}", 1, 2, 1 + 2);
+//! assert_eq!(s, "1 + 2 = 3");
+//! ```
+
+use crate::{oom, AllocErr, BackingAllocator, Bump, Global};
+use core::alloc::Layout;
+use core::fmt;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::slice;
+use core::str;
+
+/// Format a string into an arena, like the [`format!`] macro, and return it as
+/// a `&'bump mut str` that lives as long as the arena.
+///
+/// The string is written with a [`BumpWriter`], so this works without the
+/// `collections` or `std` Cargo features. Without the leading `in $bump`, this
+/// is the same as [`core::write!`], so importing it does not break existing
+/// uses of that macro.
+///
+/// [`format!`]: https://doc.rust-lang.org/std/macro.format.html
+///
+/// ## Panics
+///
+/// Panics if allocating the string fails, or if a formatting trait
+/// implementation returns an error.
+///
+/// ## Example
+///
+/// ```
+/// use bumpalo::Bump;
+///
+/// let bump = Bump::new();
+/// let who = "World";
+/// let s: &str = bumpalo::write!(in &bump, "Hello, {}!", who);
+/// assert_eq!(s, "Hello, World!");
+///
+/// // Without `in`, this is `core::write!`.
+/// use core::fmt::Write;
+/// let mut w = bump.writer();
+/// bumpalo::write!(w, "{}", 42).unwrap();
+/// assert_eq!(w.as_bytes(), b"42");
+/// ```
+#[macro_export]
+macro_rules! write {
+    ( in $bump:expr, $($arg:tt)* ) => {
+        $crate::writer::__write_in($bump, ::core::format_args!($($arg)*))
+    };
+
+    ( $($arg:tt)* ) => {
+        ::core::write!($($arg)*)
+    };
+}
+
+#[doc(hidden)]
+pub fn __write_in<'bump, A: BackingAllocator>(
+    bump: &'bump Bump<A>,
+    args: fmt::Arguments<'_>,
+) -> &'bump mut str {
+    if let Some(s) = args.as_str() {
+        return bump.alloc_str(s);
+    }
+    // Report allocation failures as such, rather than as formatting errors.
+    struct Infallible<'a, 'bump, A: BackingAllocator>(&'a mut BumpWriter<'bump, A>);
+
+    impl<A: BackingAllocator> fmt::Write for Infallible<'_, '_, A> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.extend_from_slice(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    let mut w = bump.writer();
+    fmt::Write::write_fmt(&mut Infallible(&mut w), args)
+        .expect("a formatting trait implementation returned an error");
+    // Everything was written through `fmt::Write`, so it is valid UTF-8.
+    w.into_bump_str().unwrap()
+}
+
+/// A growable byte buffer in a [`Bump`] arena that implements
+/// [`core::fmt::Write`] and, with the `std` Cargo feature, `std::io::Write`.
+///
+/// Created with [`Bump::writer`]. See the [module-level
+/// documentation](self) for more details.
+///
+/// If the writer is dropped without being finalized with
+/// [`into_bump_slice`](BumpWriter::into_bump_slice) or
+/// [`into_bump_str`](BumpWriter::into_bump_str), its buffer is given back to
+/// the arena, when it is still the arena's most recent allocation.
+pub struct BumpWriter<'bump, A: BackingAllocator = Global> {
+    bump: &'bump Bump<A>,
+    ptr: NonNull<u8>,
+    len: usize,
+    cap: usize,
+}
+
+impl<'bump, A: BackingAllocator> BumpWriter<'bump, A> {
+    pub(crate) fn new_in(bump: &'bump Bump<A>) -> BumpWriter<'bump, A> {
+        BumpWriter {
+            bump,
+            ptr: NonNull::dangling(),
+            len: 0,
+            cap: 0,
+        }
+    }
+
+    /// Returns a shared reference to the arena this writer allocates in.
+    #[inline]
+    pub fn bump(&self) -> &'bump Bump<A> {
+        self.bump
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been written yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes the writer can hold without growing its
+    /// buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns the bytes written so far.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Appends all of `bytes` to the buffer.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if growing the buffer fails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let mut w = bump.writer();
+    /// w.extend_from_slice(b"abc");
+    /// w.extend_from_slice(b"def");
+    /// assert_eq!(w.as_bytes(), b"abcdef");
+    /// ```
+    #[inline]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if self.try_extend_from_slice(bytes).is_err() {
+            oom()
+        }
+    }
+
+    /// Appends all of `bytes` to the buffer.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if growing the buffer fails, in which case nothing is written.
+    pub fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), AllocErr> {
+        if self.cap - self.len < bytes.len() {
+            self.grow(bytes.len())?;
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), self.ptr.as_ptr().add(self.len), bytes.len());
+        }
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    #[cold]
+    fn grow(&mut self, additional: usize) -> Result<(), AllocErr> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(AllocErr::SIZE_OVERFLOW)?;
+        let new_cap = required.max(self.cap * 2).max(8);
+        let new_layout =
+            Layout::from_size_align(new_cap, 1).map_err(|_| AllocErr::SIZE_OVERFLOW)?;
+        self.ptr = unsafe {
+            if self.cap == 0 {
+                self.bump.try_alloc_layout(new_layout)?
+            } else {
+                let old_layout = Layout::from_size_align_unchecked(self.cap, 1);
+                self.bump.grow(self.ptr, old_layout, new_layout)?
+            }
+        };
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    /// Read everything from `reader` until EOF, appending it to the buffer.
+    /// Returns the number of bytes read.
+    ///
+    /// The buffer's spare capacity is read into directly, so no intermediate
+    /// buffer is needed.
+    #[cfg(feature = "std")]
+    pub(crate) fn read_to_end<R: std::io::Read + ?Sized>(
+        &mut self,
+        reader: &mut R,
+    ) -> std::io::Result<usize> {
+        let start = self.len;
+        // Bytes in `len..initialized` are already zeroed, and can be handed
+        // to `reader` as they are.
+        let mut initialized = self.len;
+        loop {
+            if self.len == self.cap {
+                self.grow(self.cap.max(32))
+                    .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+            }
+            let spare = unsafe {
+                if initialized < self.cap {
+                    let uninit = self.ptr.as_ptr().add(initialized);
+                    ptr::write_bytes(uninit, 0, self.cap - initialized);
+                    initialized = self.cap;
+                }
+                slice::from_raw_parts_mut(self.ptr.as_ptr().add(self.len), self.cap - self.len)
+            };
+            match reader.read(spare) {
+                Ok(0) => return Ok(self.len - start),
+                Ok(n) => {
+                    assert!(n <= spare.len(), "reader returned an invalid length");
+                    self.len += n;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Finish writing, and return the written bytes as a slice that lives as
+    /// long as the arena.
+    ///
+    /// Unused capacity is given back to the arena, when the buffer is still
+    /// the arena's most recent allocation.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let bump = bumpalo::Bump::new();
+    /// let mut w = bump.writer();
+    /// w.extend_from_slice(&[1, 2, 3]);
+    /// let bytes: &mut [u8] = w.into_bump_slice();
+    /// assert_eq!(bytes, [1, 2, 3]);
+    /// ```
+    pub fn into_bump_slice(self) -> &'bump mut [u8] {
+        let mut ptr = self.ptr;
+        if self.len < self.cap {
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(self.cap, 1);
+                let new_layout = Layout::from_size_align_unchecked(self.len, 1);
+                // Shrinking without raising the alignment can't fail.
+                ptr = self.bump.shrink(ptr, old_layout, new_layout).unwrap_or(ptr);
+            }
+        }
+        let len = self.len;
+        mem::forget(self);
+        unsafe { slice::from_raw_parts_mut(ptr.as_ptr(), len) }
+    }
+
+    /// Finish writing, and return the written bytes as a string slice that
+    /// lives as long as the arena.
+    ///
+    /// Everything written through [`core::fmt::Write`] is valid UTF-8, but
+    /// bytes written in other ways might not be.
+    ///
+    /// ## Errors
+    ///
+    /// Errors if the written bytes are not valid UTF-8. They are left in the
+    /// arena in that case.
+    pub fn into_bump_str(self) -> Result<&'bump mut str, str::Utf8Error> {
+        str::from_utf8_mut(self.into_bump_slice())
+    }
+}
+
+impl<'bump, A: BackingAllocator> Drop for BumpWriter<'bump, A> {
+    fn drop(&mut self) {
+        if self.cap != 0 {
+            unsafe {
+                let layout = Layout::from_size_align_unchecked(self.cap, 1);
+                self.bump.dealloc(self.ptr, layout);
+            }
+        }
+    }
+}
+
+impl<'bump, A: BackingAllocator> fmt::Debug for BumpWriter<'bump, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpWriter")
+            .field("len", &self.len)
+            .field("capacity", &self.cap)
+            .finish()
+    }
+}
+
+impl<'bump, A: BackingAllocator> fmt::Write for BumpWriter<'bump, A> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_extend_from_slice(s.as_bytes())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'bump, A: BackingAllocator> std::io::Write for BumpWriter<'bump, A> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.try_extend_from_slice(buf)
+            .map_err(|_| std::io::ErrorKind::OutOfMemory.into())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
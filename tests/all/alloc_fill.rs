@@ -1,7 +1,9 @@
 use bumpalo::Bump;
 use std::alloc::Layout;
+use std::cell::Cell;
 use std::cmp;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 
 #[test]
 fn alloc_slice_fill_zero() {
@@ -18,15 +20,17 @@ fn alloc_slice_fill_zero() {
     b.alloc_slice_fill_copy(0, 42u64);
     b.alloc_slice_fill_clone(0, &"hello".to_string());
     b.alloc_slice_fill_default::<String>(0);
+    // Allocating a ZST doesn't touch the arena at all.
     let ptr2 = b.alloc(MyZeroSizedType);
+    assert_eq!(ptr2 as *mut _ as usize, mem::align_of::<MyZeroSizedType>());
+
+    // But the zero-length slices still aligned the bump pointer.
     let alignment = cmp::max(mem::align_of::<u64>(), mem::align_of::<String>());
+    let ptr3 = b.alloc_layout(layout);
     assert_eq!(
         ptr1.as_ptr() as usize & !(alignment - 1),
-        ptr2 as *mut _ as usize
+        ptr3.as_ptr() as usize + 1
     );
-
-    let ptr3 = b.alloc_layout(layout);
-    assert_eq!(ptr2 as *mut _ as usize, ptr3.as_ptr() as usize + 1);
 }
 
 #[test]
@@ -36,3 +40,158 @@ fn alloc_slice_overflow() {
 
     b.alloc_slice_fill_default::<u64>(usize::max_value());
 }
+
+#[test]
+fn alloc_slice_default() {
+    let b = Bump::new();
+    let xs = b.alloc_slice_default::<String>(3);
+    assert_eq!(xs, &["", "", ""]);
+}
+
+#[derive(Debug)]
+struct DropCounter<'a>(&'a Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn alloc_slice_fill_with_drops_initialized_on_panic() {
+    let b = Bump::new();
+    let drops = Cell::new(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.alloc_slice_fill_with(10, |i| {
+            if i == 5 {
+                panic!("oops");
+            }
+            DropCounter(&drops)
+        });
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn alloc_slice_clone_drops_initialized_on_panic() {
+    struct PanicOnClone<'a>(usize, DropCounter<'a>);
+
+    impl Clone for PanicOnClone<'_> {
+        fn clone(&self) -> Self {
+            if self.0 == 3 {
+                panic!("oops");
+            }
+            PanicOnClone(self.0, DropCounter(self.1 .0))
+        }
+    }
+
+    let b = Bump::new();
+    let drops = Cell::new(0);
+    let originals: Vec<_> = (0..10)
+        .map(|i| PanicOnClone(i, DropCounter(&drops)))
+        .collect();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.alloc_slice_clone(&originals);
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 3);
+    drop(originals);
+    assert_eq!(drops.get(), 13);
+}
+
+#[test]
+fn alloc_slice_fill_copy_values() {
+    let b = Bump::new();
+
+    let bytes = b.alloc_slice_fill_copy(10_000, 0xab_u8);
+    assert!(bytes.iter().all(|&x| x == 0xab));
+
+    let words = b.alloc_slice_fill_copy(1000, 0x0102_0304_0506_0708_u64);
+    assert!(words.iter().all(|&x| x == 0x0102_0304_0506_0708));
+
+    // Types with padding bytes are filled element by element.
+    let pairs = b.alloc_slice_fill_copy(100, (1_u8, 2_u32));
+    assert!(pairs.iter().all(|&p| p == (1, 2)));
+
+    let uninit = b.alloc_slice_fill_copy(100, mem::MaybeUninit::<u16>::uninit());
+    assert_eq!(uninit.len(), 100);
+
+    let zsts = b.alloc_slice_fill_copy(1 << 20, ());
+    assert_eq!(zsts.len(), 1 << 20);
+}
+
+#[test]
+fn try_alloc_slice_fill_copy_overflow() {
+    let b = Bump::new();
+    assert!(b.try_alloc_slice_fill_copy(usize::MAX, 0_u64).is_err());
+}
+
+#[test]
+fn alloc_slice_fill_iter_partial_unknown_length() {
+    let b = Bump::new();
+
+    let odds = b.alloc_slice_fill_iter_partial((0..10_000_u32).filter(|i| i % 2 == 1));
+    assert_eq!(odds.len(), 5_000);
+    assert!(odds.iter().copied().eq((0..10_000).filter(|i| i % 2 == 1)));
+
+    let empty = b.alloc_slice_fill_iter_partial(std::iter::empty::<String>());
+    assert!(empty.is_empty());
+
+    let zsts = b.alloc_slice_fill_iter_partial((0..1000).map(|_| ()));
+    assert_eq!(zsts.len(), 1000);
+}
+
+#[test]
+fn alloc_slice_fill_iter_partial_returns_unused_tail() {
+    let b = Bump::with_capacity(4096);
+    let before = b.remaining_capacity();
+
+    // Over-reserve by the size hint, then produce fewer elements.
+    let xs = b.alloc_slice_fill_iter_partial((0..100_u64).take_while(|&i| i < 10));
+    assert_eq!(xs, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(before - b.remaining_capacity(), 10 * mem::size_of::<u64>());
+
+    let empty = b.alloc_slice_fill_iter_partial((0..100_u64).filter(|_| false));
+    assert!(empty.is_empty());
+    assert_eq!(before - b.remaining_capacity(), 10 * mem::size_of::<u64>());
+}
+
+#[test]
+fn alloc_slice_fill_iter_partial_drops_initialized_on_panic() {
+    let b = Bump::new();
+    let drops = Cell::new(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        b.alloc_slice_fill_iter_partial((0..100).map(|i| {
+            if i == 50 {
+                panic!("oops");
+            }
+            DropCounter(&drops)
+        }));
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 50);
+}
+
+#[test]
+fn try_alloc_slice_fill_iter_partial_limit() {
+    let b = Bump::with_capacity(1024);
+    let drops = Cell::new(0);
+    b.set_allocation_limit(Some(b.allocated_bytes()));
+
+    // `filter` hides the length, so the first block is small and has to grow.
+    let produced = Cell::new(0);
+    let result = b.try_alloc_slice_fill_iter_partial((0..10_000).filter(|_| true).map(|_| {
+        produced.set(produced.get() + 1);
+        DropCounter(&drops)
+    }));
+    assert!(result.is_err());
+    assert!(produced.get() > 4);
+    assert_eq!(drops.get(), produced.get());
+}
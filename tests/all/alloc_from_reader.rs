@@ -0,0 +1,106 @@
+#![cfg(feature = "std")]
+
+use bumpalo::Bump;
+use std::io::{self, Read};
+
+/// A reader that returns at most `chunk` bytes per `read`, and is interrupted
+/// before every successful read.
+struct Trickle<'a> {
+    data: &'a [u8],
+    chunk: usize,
+    interrupt: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let n = buf.len().min(self.chunk).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+struct Failing;
+
+impl Read for Failing {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "oops"))
+    }
+}
+
+#[test]
+fn alloc_from_reader_exact() {
+    let bump = Bump::new();
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = Trickle {
+        data: &data,
+        chunk: 7,
+        interrupt: false,
+    };
+    let bytes = bump.alloc_from_reader(&mut reader, 200).unwrap();
+    assert_eq!(bytes, &data[..200]);
+    assert_eq!(reader.data, &data[200..]);
+}
+
+#[test]
+fn alloc_from_reader_unexpected_eof() {
+    let bump = Bump::with_capacity(1024);
+    let capacity = bump.chunk_capacity();
+    let mut reader: &[u8] = b"short";
+    let err = bump.alloc_from_reader(&mut reader, 10).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(bump.chunk_capacity(), capacity);
+}
+
+#[test]
+fn alloc_from_reader_out_of_memory() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    let mut reader: &[u8] = b"hello";
+    let err = bump.alloc_from_reader(&mut reader, 5).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::OutOfMemory);
+}
+
+#[test]
+fn alloc_read_to_end() {
+    let bump = Bump::new();
+    let data: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+    let mut reader = Trickle {
+        data: &data,
+        chunk: 333,
+        interrupt: false,
+    };
+    let bytes = bump.alloc_read_to_end(&mut reader).unwrap();
+    assert_eq!(bytes, &data[..]);
+}
+
+#[test]
+fn alloc_read_to_end_empty() {
+    let bump = Bump::new();
+    let mut reader: &[u8] = &[];
+    let bytes = bump.alloc_read_to_end(&mut reader).unwrap();
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn alloc_read_to_end_gives_back_unused_capacity() {
+    let bump = Bump::with_capacity(4096);
+    let capacity = bump.chunk_capacity();
+    let mut reader: &[u8] = &[1; 100];
+    let bytes = bump.alloc_read_to_end(&mut reader).unwrap();
+    assert_eq!(bytes.len(), 100);
+    assert_eq!(bump.chunk_capacity(), capacity - 100);
+}
+
+#[test]
+fn alloc_read_to_end_error() {
+    let bump = Bump::with_capacity(1024);
+    let capacity = bump.chunk_capacity();
+    let err = bump.alloc_read_to_end(&mut Failing).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert_eq!(bump.chunk_capacity(), capacity);
+}
@@ -0,0 +1,65 @@
+use bumpalo::Bump;
+use std::alloc::Layout;
+
+#[test]
+fn front_and_back_allocations_dont_overlap() {
+    let bump = Bump::new();
+    let mut ranges = vec![];
+
+    for i in 0..1000_u64 {
+        let front = bump.alloc_front(i) as *mut u64 as usize;
+        let back = bump.alloc(i as u32) as *mut u32 as usize;
+        ranges.push((front, front + 8));
+        ranges.push((back, back + 4));
+    }
+
+    ranges.sort();
+    for w in ranges.windows(2) {
+        assert!(w[0].1 <= w[1].0);
+    }
+}
+
+#[test]
+fn front_allocations_are_aligned() {
+    let bump = Bump::new();
+    for align in [1, 2, 4, 8, 16, 32, 64] {
+        bump.alloc_front(1_u8);
+        let layout = Layout::from_size_align(3, align).unwrap();
+        let p = bump.alloc_front_layout(layout);
+        assert_eq!(p.as_ptr() as usize % align, 0);
+    }
+}
+
+#[test]
+fn reset_front_keeps_back_allocations() {
+    let mut bump = Bump::with_capacity(1024);
+    let capacity = bump.chunk_capacity();
+
+    let back = bump.alloc_slice_copy(&[1, 2, 3]) as *mut [i32];
+    let remaining = bump.chunk_capacity();
+    bump.alloc_front([0_u8; 100]);
+    assert_eq!(bump.chunk_capacity(), remaining - 100);
+
+    bump.reset_front();
+    assert_eq!(bump.chunk_capacity(), remaining);
+    assert_eq!(unsafe { &*back }, &[1, 2, 3]);
+
+    bump.reset();
+    assert_eq!(bump.chunk_capacity(), capacity);
+}
+
+#[test]
+fn front_allocation_allocates_new_chunks() {
+    let mut bump = Bump::with_capacity(64);
+    for i in 0..10_000_u32 {
+        assert_eq!(*bump.alloc_front(i), i);
+    }
+    assert!(bump.iter_allocated_chunks().count() > 1);
+}
+
+#[test]
+fn try_alloc_front_layout_respects_allocation_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    assert!(bump.try_alloc_front_layout(Layout::new::<u64>()).is_err());
+}
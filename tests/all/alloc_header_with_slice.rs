@@ -0,0 +1,100 @@
+use bumpalo::Bump;
+use std::cell::Cell;
+use std::mem;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    tag: u8,
+    count: u32,
+}
+
+#[test]
+fn header_and_slice_are_contiguous() {
+    let bump = Bump::new();
+    let (header, edges) =
+        bump.alloc_header_with_slice(Header { tag: 1, count: 3 }, [10_u64, 20, 30]);
+
+    assert_eq!(*header, Header { tag: 1, count: 3 });
+    assert_eq!(edges, [10, 20, 30]);
+
+    let header_start = header as *mut Header as usize;
+    let edges_start = edges.as_ptr() as usize;
+    assert_eq!(header_start % mem::align_of::<Header>(), 0);
+    assert_eq!(edges_start % mem::align_of::<u64>(), 0);
+    assert!(edges_start >= header_start + mem::size_of::<Header>());
+    assert!(edges_start - (header_start + mem::size_of::<Header>()) < mem::align_of::<u64>());
+}
+
+#[test]
+fn empty_slice() {
+    let bump = Bump::new();
+    let (header, edges) =
+        bump.alloc_header_with_slice(Header { tag: 2, count: 0 }, std::iter::empty::<u16>());
+    assert_eq!(*header, Header { tag: 2, count: 0 });
+    assert!(edges.is_empty());
+}
+
+#[test]
+fn zero_sized_header_and_elements() {
+    let bump = Bump::new();
+    let before = bump.allocated_bytes();
+    let (_, units) = bump.alloc_header_with_slice((), vec![(); 5]);
+    assert_eq!(units.len(), 5);
+    assert_eq!(bump.allocated_bytes(), before);
+
+    let (header, units) = bump.alloc_header_with_slice(42_u32, vec![(); 5]);
+    assert_eq!(*header, 42);
+    assert_eq!(units.len(), 5);
+}
+
+#[test]
+fn drops_header_and_prefix_on_panic() {
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        bump.alloc_header_with_slice(
+            DropCounter(&drops),
+            (0..5).map(|i| {
+                if i == 3 {
+                    panic!("boom");
+                }
+                DropCounter(&drops)
+            }),
+        );
+    }));
+    assert!(result.is_err());
+    // The header plus the three initialized elements.
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+#[should_panic(expected = "Iterator supplied too few elements")]
+fn too_few_elements() {
+    struct Liar;
+
+    impl Iterator for Liar {
+        type Item = u32;
+        fn next(&mut self) -> Option<u32> {
+            None
+        }
+    }
+
+    impl ExactSizeIterator for Liar {
+        fn len(&self) -> usize {
+            3
+        }
+    }
+
+    let bump = Bump::new();
+    bump.alloc_header_with_slice(0_u8, Liar);
+}
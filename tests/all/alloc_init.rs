@@ -0,0 +1,67 @@
+use bumpalo::{AllocOrInitError, Bump};
+use std::alloc::Layout;
+
+#[test]
+fn initializes_in_place() {
+    let bump = Bump::new();
+    let layout = Layout::array::<u16>(8).unwrap();
+    let p = bump
+        .alloc_init(layout, |p| {
+            let p = p.cast::<u16>().as_ptr();
+            for i in 0..8 {
+                unsafe { p.add(i).write(i as u16 * 3) };
+            }
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert_eq!(p.as_ptr() as usize % layout.align(), 0);
+    let xs = unsafe { std::slice::from_raw_parts(p.cast::<u16>().as_ptr(), 8) };
+    assert_eq!(xs, [0, 3, 6, 9, 12, 15, 18, 21]);
+}
+
+#[test]
+fn error_rewinds_last_allocation() {
+    let bump = Bump::new();
+    bump.alloc(1_u8);
+    let before = bump.allocated_bytes_in_current_chunk();
+
+    let r = bump.alloc_init(Layout::new::<u64>(), |_| Err("nope"));
+    assert_eq!(r, Err("nope"));
+    // The alignment padding is reclaimed too.
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), before);
+}
+
+#[test]
+fn error_rewinds_allocation_in_new_chunk() {
+    let bump = Bump::with_capacity(64);
+    bump.alloc(1_u8);
+    let chunks = bump.iter_chunk_usage().count();
+
+    let layout = Layout::from_size_align(4096, 8).unwrap();
+    let r = bump.try_alloc_init(layout, |_| Err(()));
+    assert!(matches!(r, Err(AllocOrInitError::Init(()))));
+    assert_eq!(bump.iter_chunk_usage().count(), chunks + 1);
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), 0);
+}
+
+#[test]
+fn error_after_other_allocations_does_not_rewind() {
+    let bump = Bump::new();
+    let r = bump.alloc_init(Layout::new::<u64>(), |_| {
+        bump.alloc(7_u32);
+        Err(())
+    });
+    assert!(r.is_err());
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), 12);
+}
+
+#[test]
+fn allocation_failure_skips_closure() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    let layout = Layout::new::<[u8; 4096]>();
+    let r = bump.try_alloc_init(layout, |_| -> Result<(), ()> {
+        panic!("closure should not run")
+    });
+    assert!(matches!(r, Err(AllocOrInitError::Alloc(_))));
+}
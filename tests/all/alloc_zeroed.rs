@@ -0,0 +1,49 @@
+use bumpalo::Bump;
+use std::alloc::Layout;
+
+fn assert_zeroed(p: std::ptr::NonNull<u8>, len: usize) {
+    let bytes = unsafe { std::slice::from_raw_parts(p.as_ptr(), len) };
+    assert!(bytes.iter().all(|b| *b == 0));
+}
+
+#[test]
+fn alloc_layout_zeroed_reused_memory() {
+    let mut bump = Bump::with_capacity(1024);
+    bump.alloc_slice_fill_copy(512, 0xff_u8);
+    bump.reset();
+
+    let layout = Layout::from_size_align(512, 8).unwrap();
+    let p = bump.alloc_layout_zeroed(layout);
+    assert_eq!(p.as_ptr() as usize % 8, 0);
+    assert_zeroed(p, 512);
+}
+
+#[test]
+fn alloc_layout_zeroed_new_chunks() {
+    let bump = Bump::new();
+    for size in [1, 100, 1000, 10_000, 100_000] {
+        let layout = Layout::from_size_align(size, 16).unwrap();
+        let p = bump.try_alloc_layout_zeroed(layout).unwrap();
+        assert_eq!(p.as_ptr() as usize % 16, 0);
+        assert_zeroed(p, size);
+    }
+}
+
+#[test]
+fn alloc_slice_zeroed() {
+    let bump = Bump::new();
+    bump.alloc_slice_fill_copy(100, u64::MAX);
+    let xs = unsafe { bump.alloc_slice_zeroed::<u64>(100) };
+    assert_eq!(xs, &[0; 100][..]);
+
+    let empty = unsafe { bump.alloc_slice_zeroed::<u32>(0) };
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn try_alloc_layout_zeroed_respects_allocation_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    let layout = Layout::from_size_align(16, 1).unwrap();
+    assert!(bump.try_alloc_layout_zeroed(layout).is_err());
+}
@@ -1,4 +1,6 @@
-use bumpalo::Bump;
+use crate::quickcheck;
+use bumpalo::{AllocErr, AllocErrKind, AllocOrInitError, Bump, LimitDecision};
+use std::alloc::Layout;
 
 #[test]
 fn allocation_limit_trivial() {
@@ -89,3 +91,316 @@ fn small_allocation_limit() {
     bump.set_allocation_limit(Some(64));
     assert!(bump.try_alloc([0; 1]).is_ok());
 }
+
+#[test]
+fn try_alloc_slice_methods_respect_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+
+    let calls = std::cell::Cell::new(0);
+    assert!(bump.try_alloc_slice_copy(&[1, 2, 3]).is_err());
+    assert!(bump.try_alloc_slice_clone(&[String::new()]).is_err());
+    assert!(bump.try_alloc_str("hello").is_err());
+    assert!(bump
+        .try_alloc_slice_fill_with(3, |i| {
+            calls.set(calls.get() + 1);
+            i
+        })
+        .is_err());
+    assert!(bump.try_alloc_slice_fill_copy(3, 0_u8).is_err());
+    assert!(bump.try_alloc_slice_fill_clone(3, &String::new()).is_err());
+    assert!(bump.try_alloc_slice_fill_iter(0..3).is_err());
+    assert!(bump.try_alloc_slice_fill_default::<u32>(3).is_err());
+    assert_eq!(calls.get(), 0);
+
+    // Empty slices never need any space.
+    assert_eq!(bump.try_alloc_slice_copy::<u8>(&[]), Ok(&mut [][..]));
+
+    bump.set_allocation_limit(None);
+    assert_eq!(bump.try_alloc_slice_fill_iter(0..3), Ok(&mut [0, 1, 2][..]));
+    assert_eq!(bump.try_alloc_str("hello").map(|s| &*s), Ok("hello"));
+}
+
+#[test]
+fn try_alloc_slice_overflowing_layout_is_an_error() {
+    let bump = Bump::new();
+    assert!(bump
+        .try_alloc_slice_fill_default::<u64>(usize::MAX)
+        .is_err());
+}
+
+thread_local! {
+    static HOOK_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+fn hook_calls() -> usize {
+    HOOK_CALLS.with(|c| c.get())
+}
+
+fn count_hook_call() {
+    HOOK_CALLS.with(|c| c.set(c.get() + 1));
+}
+
+#[test]
+fn limit_hook_deny() {
+    fn deny(_: &Bump, layout: Layout) -> LimitDecision {
+        assert_eq!(layout, Layout::new::<[u8; 100]>());
+        count_hook_call();
+        LimitDecision::Deny
+    }
+
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_limit_hook(Some(deny));
+    assert!(bump.limit_hook().is_some());
+
+    let before = hook_calls();
+    assert!(bump.try_alloc([0_u8; 100]).is_err());
+    assert_eq!(hook_calls(), before + 1);
+}
+
+#[test]
+fn limit_hook_allow() {
+    fn allow(_: &Bump, _: Layout) -> LimitDecision {
+        LimitDecision::Allow
+    }
+
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_limit_hook(Some(allow));
+    assert!(bump.try_alloc([0_u8; 100]).is_ok());
+    assert_eq!(bump.allocation_limit(), Some(0));
+}
+
+#[test]
+fn limit_hook_retry_after_raising_limit() {
+    fn raise(bump: &Bump, layout: Layout) -> LimitDecision {
+        count_hook_call();
+        bump.set_allocation_limit(Some(layout.size() * 2));
+        LimitDecision::Retry
+    }
+
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_limit_hook(Some(raise));
+
+    let before = hook_calls();
+    assert!(bump.try_alloc([0_u8; 100]).is_ok());
+    assert_eq!(hook_calls(), before + 1);
+    assert_eq!(bump.allocation_limit(), Some(200));
+}
+
+#[test]
+fn limit_hook_not_called_for_other_failures() {
+    fn allow(_: &Bump, _: Layout) -> LimitDecision {
+        count_hook_call();
+        LimitDecision::Allow
+    }
+
+    // Within the limit, so allocations don't need the hook.
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(1 << 20));
+    bump.set_limit_hook(Some(allow));
+    let before = hook_calls();
+    assert!(bump.try_alloc([0_u8; 100]).is_ok());
+    assert_eq!(hook_calls(), before);
+}
+
+#[test]
+fn limit_hook_is_not_reentrant() {
+    fn nested(bump: &Bump, _: Layout) -> LimitDecision {
+        count_hook_call();
+        // This allocation is over the limit too, but must not call the hook
+        // recursively.
+        assert!(bump.try_alloc([0_u8; 1000]).is_err());
+        LimitDecision::Deny
+    }
+
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_limit_hook(Some(nested));
+
+    let before = hook_calls();
+    assert!(bump.try_alloc([0_u8; 100]).is_err());
+    assert_eq!(hook_calls(), before + 1);
+
+    // The hook is called again for later allocations.
+    assert!(bump.try_alloc([0_u8; 100]).is_err());
+    assert_eq!(hook_calls(), before + 2);
+
+    bump.set_limit_hook(None);
+    assert!(bump.limit_hook().is_none());
+    assert!(bump.try_alloc([0_u8; 100]).is_err());
+    assert_eq!(hook_calls(), before + 2);
+}
+
+#[test]
+fn preflight_does_not_allocate() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(1 << 20));
+
+    let layouts = vec![Layout::new::<[u8; 1000]>(); 100];
+    assert!(bump.preflight(&layouts).is_ok());
+    assert!(bump.can_allocate(Layout::new::<[u8; 1 << 19]>()));
+    assert!(!bump.can_allocate(Layout::new::<[u8; 1 << 21]>()));
+    assert_eq!(bump.allocated_bytes(), 0);
+
+    let too_many = vec![Layout::new::<[u8; 1000]>(); 2000];
+    assert!(bump.preflight(&too_many).is_err());
+    assert_eq!(bump.allocated_bytes(), 0);
+}
+
+#[test]
+fn preflight_rejects_absurd_layouts() {
+    let bump = Bump::new();
+    let huge = Layout::from_size_align(isize::MAX as usize - 4095, 4096).unwrap();
+    assert!(!bump.can_allocate(huge));
+    assert!(bump.preflight(&[huge, huge]).is_err());
+}
+
+quickcheck! {
+    fn preflight_success_means_the_limit_allows_the_batch(
+        limit: u16,
+        sizes: Vec<(u16, u8)>
+    ) -> bool {
+        let layouts: Vec<Layout> = sizes
+            .iter()
+            .map(|&(size, align)| {
+                Layout::from_size_align(size as usize, 1 << (align % 8)).unwrap()
+            })
+            .collect();
+
+        let bump = Bump::new();
+        bump.set_allocation_limit(Some(limit as usize * 4));
+
+        if bump.preflight(&layouts).is_err() {
+            return true;
+        }
+        layouts.iter().all(|layout| bump.try_alloc_layout(*layout).is_ok())
+    }
+}
+
+#[test]
+fn errors_tell_the_limit_apart_from_overflow() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(1024));
+    assert_eq!(
+        bump.try_alloc([0_u8; 2048]),
+        Err(AllocErr::from(AllocErrKind::LimitExceeded))
+    );
+    assert_eq!(
+        bump.try_alloc_uninit_slice::<u64>(usize::MAX).err(),
+        Some(AllocErr::from(AllocErrKind::SizeOverflow))
+    );
+
+    // Without a limit, a layout too large for any chunk is an overflow.
+    let unlimited = Bump::new();
+    let huge = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+    assert_eq!(
+        unlimited.try_alloc_layout(huge),
+        Err(AllocErr::from(AllocErrKind::SizeOverflow))
+    );
+    assert_eq!(
+        unlimited.preflight(&[huge]),
+        Err(AllocErr::from(AllocErrKind::SizeOverflow))
+    );
+    assert_eq!(
+        bump.try_reserve_bytes(4096),
+        Err(AllocErr::from(AllocErrKind::LimitExceeded))
+    );
+    assert_eq!(
+        bump.preflight(&[Layout::new::<[u8; 4096]>()]),
+        Err(AllocErr::from(AllocErrKind::LimitExceeded))
+    );
+    assert_eq!(
+        bump.try_alloc_try_with(|| Ok::<_, ()>([0_u8; 2048])),
+        Err(AllocOrInitError::Alloc(AllocErr::from(
+            AllocErrKind::LimitExceeded
+        )))
+    );
+
+    let err = bumpalo::BumpBuilder::new()
+        .initial_chunk_size(4096)
+        .allocation_limit(Some(1024))
+        .try_build()
+        .err();
+    assert_eq!(err, Some(AllocErr::from(AllocErrKind::LimitExceeded)));
+}
+
+#[test]
+fn limit_hook_denial_is_a_limit_error() {
+    fn deny(_: &Bump, _: Layout) -> LimitDecision {
+        LimitDecision::Deny
+    }
+
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    bump.set_limit_hook(Some(deny));
+    assert_eq!(
+        bump.try_alloc(1_u8),
+        Err(AllocErr::from(AllocErrKind::LimitExceeded))
+    );
+}
+
+#[test]
+fn alloc_err_kind() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    let err = bump.try_alloc(1_u8).unwrap_err();
+    assert_eq!(err.kind(), AllocErrKind::LimitExceeded);
+    assert_eq!(AllocErr::new(err.kind()), err);
+
+    // `AllocErr` used to be a unit struct.
+    let unit: AllocErr = AllocErr;
+    assert_eq!(unit.kind(), AllocErrKind::OutOfMemory);
+}
+
+#[cfg(any(feature = "std", feature = "core_error"))]
+#[test]
+fn alloc_errors_implement_error() {
+    use std::error::Error;
+
+    let err: Box<dyn Error> = Box::new(AllocErr::from(AllocErrKind::LimitExceeded));
+    assert_eq!(
+        err.to_string(),
+        "memory allocation failed: allocation limit exceeded"
+    );
+
+    #[derive(Debug)]
+    struct InitFailed;
+    impl std::fmt::Display for InitFailed {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("init failed")
+        }
+    }
+    impl Error for InitFailed {}
+
+    let err = AllocOrInitError::Init(InitFailed);
+    assert_eq!(err.source().unwrap().to_string(), "init failed");
+    let err = AllocOrInitError::<InitFailed>::Alloc(AllocErr::from(AllocErrKind::OutOfMemory));
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "memory allocation failed"
+    );
+}
+
+#[test]
+fn zero_sized_over_aligned_layouts_under_a_small_limit_terminate() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(4));
+    let layout = Layout::from_size_align(0, 128).unwrap();
+    // Only checks that these return at all.
+    let _ = bump.preflight(&[layout]);
+    let _ = bump.try_alloc_layout(layout);
+}
+
+#[cfg(feature = "core_error")]
+#[test]
+fn alloc_errors_implement_core_error() {
+    fn assert_core_error<E: core::error::Error>(_: &E) {}
+
+    assert_core_error(&AllocErr::from(AllocErrKind::OutOfMemory));
+    assert_core_error(&AllocOrInitError::<AllocErr>::Init(AllocErr::from(
+        AllocErrKind::SizeOverflow,
+    )));
+}
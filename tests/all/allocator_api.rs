@@ -145,6 +145,30 @@ fn allocator_grow_zeroed() {
     assert_eq!(unsafe { p.as_ref() }, [42, 42, 42, 42, 0, 0, 0, 0]);
 }
 
+#[test]
+fn allocator_grow_align_increase_in_place() {
+    let ref bump = Bump::new();
+
+    // Make a 1-aligned, 3-byte allocation below another one.
+    let ptr = bump.alloc_layout(Layout::from_size_align(32, 64).unwrap());
+    let first_layout = Layout::from_size_align(3, 1).unwrap();
+    let p = bump.allocate(first_layout).unwrap().cast::<u8>();
+    unsafe { p.as_ptr().copy_from_nonoverlapping([1, 2, 3].as_ptr(), 3) };
+    let allocated = bump.allocated_bytes();
+
+    // Growing to a stricter alignment extends the old allocation in place,
+    // rather than making a fresh allocation below it.
+    let old_end = p.as_ptr() as usize + 3;
+    let second_layout = Layout::from_size_align(64, 16).unwrap();
+    let p = unsafe { bump.grow(p, first_layout, second_layout) }.unwrap();
+    assert_eq!(bump.allocated_bytes(), allocated);
+    assert!(is_pointer_aligned_to(p, 16));
+    let new_end = p.as_ptr() as *mut u8 as usize + 64;
+    assert!(new_end > old_end - 16 && new_end <= old_end);
+    assert!(new_end <= ptr.as_ptr() as usize);
+    assert_eq!(unsafe { &p.as_ref()[..3] }, [1, 2, 3]);
+}
+
 quickcheck! {
     fn allocator_grow_align_increase(layouts: Vec<(usize, usize)>) -> bool {
         let mut layouts: Vec<_> = layouts.into_iter().map(|(size, align)| {
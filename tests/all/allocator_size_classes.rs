@@ -0,0 +1,102 @@
+#![cfg(all(feature = "allocator-api2", not(feature = "allocator_api")))]
+
+use allocator_api2::alloc::{Allocator, Layout};
+use bumpalo::Bump;
+
+fn layout(size: usize) -> Layout {
+    Layout::from_size_align(size, 1).unwrap()
+}
+
+#[test]
+fn disabled_by_default() {
+    let bump = Bump::new();
+    assert_eq!(bump.allocator_size_classes(), 0);
+    let block = (&bump).allocate(layout(10)).unwrap();
+    assert_eq!(block.len(), 10);
+}
+
+#[test]
+fn small_allocations_are_rounded_up() {
+    let bump = Bump::builder().allocator_size_classes(64).build();
+    assert_eq!(bump.allocator_size_classes(), 64);
+
+    for (size, rounded) in [(1, 1), (3, 4), (10, 16), (33, 64), (64, 64), (65, 65)] {
+        let block = (&bump).allocate(layout(size)).unwrap();
+        assert_eq!(block.len(), rounded);
+    }
+}
+
+#[test]
+fn grow_within_size_class_does_not_move() {
+    let bump = Bump::builder().allocator_size_classes(256).build();
+    let a = (&bump).allocate(layout(10)).unwrap().cast::<u8>();
+    unsafe { a.as_ptr().write_bytes(7, 10) };
+
+    // Make sure `a` isn't the last allocation anymore.
+    (&bump).allocate(layout(10)).unwrap();
+
+    unsafe {
+        let grown = (&bump).grow(a, layout(10), layout(16)).unwrap();
+        assert_eq!(grown.cast::<u8>(), a);
+        assert_eq!(grown.len(), 16);
+
+        // Leaving the size class copies, as usual.
+        let moved = (&bump).grow(a, layout(16), layout(17)).unwrap();
+        assert_ne!(moved.cast::<u8>(), a);
+        assert_eq!(moved.len(), 32);
+        assert_eq!(&moved.as_ref()[..10], &[7; 10]);
+    }
+}
+
+#[test]
+fn deallocating_with_any_fitting_size_frees_the_whole_block() {
+    let bump = Bump::builder().allocator_size_classes(64).build();
+    (&bump).allocate(layout(1)).unwrap();
+
+    for size in 9..=16 {
+        let p = (&bump).allocate(layout(9)).unwrap().cast::<u8>();
+        unsafe { (&bump).deallocate(p, layout(size)) };
+        let q = (&bump).allocate(layout(16)).unwrap().cast::<u8>();
+        assert_eq!(p, q);
+        unsafe { (&bump).deallocate(q, layout(16)) };
+    }
+}
+
+#[test]
+fn interleaved_growth() {
+    let bump = Bump::builder().allocator_size_classes(1024).build();
+    let mut blocks: Vec<_> = (0..8_u8)
+        .map(|i| {
+            let p = (&bump).allocate(layout(1)).unwrap().cast::<u8>();
+            unsafe { p.as_ptr().write(i) };
+            (p, 1)
+        })
+        .collect();
+
+    let mut moves = 0;
+    for _ in 0..200 {
+        for (i, (p, len)) in blocks.iter_mut().enumerate() {
+            let grown = unsafe { (&bump).grow(*p, layout(*len), layout(*len + 1)) };
+            let grown = grown.unwrap().cast::<u8>();
+            if grown != *p {
+                moves += 1;
+            }
+            unsafe { grown.as_ptr().add(*len).write(i as u8) };
+            *p = grown;
+            *len += 1;
+        }
+    }
+
+    // Each block only moved when it outgrew a size class.
+    assert!(moves <= 8 * 8, "{} moves", moves);
+    for (i, (p, len)) in blocks.iter().enumerate() {
+        let bytes = unsafe { std::slice::from_raw_parts(p.as_ptr(), *len) };
+        assert!(bytes.iter().all(|b| *b == i as u8));
+    }
+}
+
+#[test]
+#[should_panic(expected = "size class limit must be zero or a power of two")]
+fn non_power_of_two_limit() {
+    Bump::builder().allocator_size_classes(100);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "arbitrary")]
+#![cfg(feature = "collections")]
+#![cfg(feature = "boxed")]
+
+use arbitrary::Unstructured;
+use bumpalo::arbitrary::ArbitraryIn;
+use bumpalo::collections::{String, Vec};
+use bumpalo::{boxed::Box, Bump};
+
+#[test]
+fn vec_of_primitives() {
+    let bump = Bump::new();
+    let mut u = Unstructured::new(&[1, 0x34, 0x12, 1, 0x78, 0x56, 0, 0xff]);
+    let v = Vec::<u16>::arbitrary_in(&mut u, &bump).unwrap();
+    assert_eq!(v, [0x1234, 0x5678]);
+    assert_eq!(v.bump() as *const Bump, &bump as *const Bump);
+    assert_eq!(u.len(), 1);
+
+    // An exhausted input generates an empty vector.
+    let v = Vec::<u16>::arbitrary_in(&mut Unstructured::new(&[]), &bump).unwrap();
+    assert!(v.is_empty());
+}
+
+#[test]
+fn take_rest() {
+    let bump = Bump::new();
+    let u = Unstructured::new(&[1, 2, 3, 4]);
+    let v = Vec::<u8>::arbitrary_take_rest_in(u, &bump).unwrap();
+    assert_eq!(v, [1, 2, 3, 4]);
+
+    let u = Unstructured::new(b"hello");
+    let s = String::arbitrary_take_rest_in(u, &bump).unwrap();
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn nested() {
+    let bump = Bump::new();
+    let data = [1, 1, 7, 1, 8, 0, 1, 0, 0];
+    let mut u = Unstructured::new(&data);
+    let v = Vec::<Box<Vec<u8>>>::arbitrary_in(&mut u, &bump).unwrap();
+    assert_eq!(v.len(), 2);
+    assert_eq!(**v[0], [7, 8]);
+    assert!(v[1].is_empty());
+}
+
+#[test]
+fn fuzz_like_input_never_panics() {
+    let bump = Bump::new();
+    for seed in 0..200_u32 {
+        let data: std::vec::Vec<u8> = (0..64)
+            .map(|i| (seed.wrapping_mul(31).wrapping_add(i * 17) % 251) as u8)
+            .collect();
+        let mut u = Unstructured::new(&data);
+        let _ = Vec::<String>::arbitrary_in(&mut u, &bump);
+        let _ = Box::<Vec<i64>>::arbitrary_in(&mut u, &bump);
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "asan")]
+
+// These tests only link when built with `-Zsanitizer=address`.
+
+use bumpalo::Bump;
+
+extern "C" {
+    fn __asan_address_is_poisoned(addr: *const u8) -> i32;
+}
+
+fn is_poisoned(p: *const u8) -> bool {
+    unsafe { __asan_address_is_poisoned(p) != 0 }
+}
+
+#[test]
+fn allocations_are_addressable() {
+    let bump = Bump::with_capacity(1024);
+    let x = bump.alloc([1_u64; 4]);
+    let p = x.as_ptr() as *const u8;
+    assert!((0..32).all(|i| !is_poisoned(p.wrapping_add(i))));
+    // The free space right below the allocation is not.
+    assert!(is_poisoned(p.wrapping_sub(1)));
+}
+
+#[test]
+fn reset_poisons_allocations() {
+    let mut bump = Bump::with_capacity(1024);
+    let p = bump.alloc([1_u64; 4]).as_ptr() as *const u8;
+    bump.reset();
+    assert!((0..32).all(|i| is_poisoned(p.wrapping_add(i))));
+
+    let q = bump.alloc([2_u64; 4]).as_ptr() as *const u8;
+    assert!((0..32).all(|i| !is_poisoned(q.wrapping_add(i))));
+}
+
+#[test]
+fn reset_front_poisons_front_allocations() {
+    let mut bump = Bump::with_capacity(1024);
+    let p = bump.alloc_front([1_u64; 4]).as_ptr() as *const u8;
+    assert!(!is_poisoned(p));
+    bump.reset_front();
+    assert!(is_poisoned(p));
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn dealloc_poisons_memory() {
+    let bump = Bump::new();
+    let mut v = bumpalo::collections::Vec::with_capacity_in(16, &bump);
+    v.extend_from_slice(&[7_u64; 16]);
+    let p = v.as_ptr() as *const u8;
+    drop(v);
+    assert!(is_poisoned(p));
+}
@@ -0,0 +1,133 @@
+use bumpalo::{AllocErr, AllocErrKind, BackingAllocator, Bump, BumpBuilder, Global};
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+/// A backing allocator that forwards to the global allocator and keeps count
+/// of the chunks that are currently live.
+#[derive(Debug, Default)]
+struct Counting {
+    live: Cell<usize>,
+    total: Cell<usize>,
+    zeroed: Cell<usize>,
+}
+
+unsafe impl BackingAllocator for Counting {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.live.set(self.live.get() + 1);
+        self.total.set(self.total.get() + 1);
+        Global.allocate(layout)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.zeroed.set(self.zeroed.get() + 1);
+        self.allocate(layout).map(|p| {
+            unsafe { p.as_ptr().write_bytes(0, layout.size()) };
+            p
+        })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn chunks_come_from_backing_allocator() {
+    let counting = Counting::default();
+    {
+        let mut bump = Bump::new_in(&counting);
+        assert_eq!(counting.total.get(), 0);
+
+        for i in 0..10_000_u32 {
+            bump.alloc(i);
+        }
+        assert!(counting.live.get() > 1);
+
+        bump.reset();
+        assert_eq!(counting.live.get(), 1);
+
+        bump.alloc_slice_fill_copy(bump.chunk_capacity() + 1, 1_u8);
+        assert_eq!(counting.live.get(), 2);
+    }
+    assert_eq!(counting.live.get(), 0);
+}
+
+#[test]
+fn zeroed_chunks_come_from_backing_allocator() {
+    let counting = Counting::default();
+    let bump = Bump::new_in(&counting);
+    let layout = Layout::from_size_align(100, 8).unwrap();
+    let p = bump.alloc_layout_zeroed(layout);
+    assert_eq!(counting.zeroed.get(), 1);
+    let bytes = unsafe { std::slice::from_raw_parts(p.as_ptr(), 100) };
+    assert!(bytes.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn with_capacity_in() {
+    let counting = Counting::default();
+    let bump = Bump::with_capacity_in(1024, &counting);
+    assert_eq!(counting.live.get(), 1);
+    assert!(bump.chunk_capacity() >= 1024);
+    assert_eq!(bump.backing_allocator().live.get(), 1);
+}
+
+#[test]
+fn builder_build_in() {
+    let counting = Counting::default();
+    let bump = BumpBuilder::new()
+        .initial_chunk_size(100)
+        .allocation_limit(Some(4096))
+        .build_in(&counting);
+    assert_eq!(counting.live.get(), 1);
+    assert!(bump.try_alloc([0_u8; 8192]).is_err());
+    assert_eq!(counting.live.get(), 1);
+}
+
+#[test]
+fn nested_bump() {
+    let outer = Bump::new();
+    let inner = Bump::with_capacity_in(256, &outer);
+    let outer_bytes = outer.allocated_bytes();
+    assert!(outer_bytes > 256);
+
+    let xs = inner.alloc_slice_fill_iter(0..1000_u32);
+    assert_eq!(xs.iter().sum::<u32>(), 999 * 1000 / 2);
+    assert!(outer.allocated_bytes() > outer_bytes);
+}
+
+#[test]
+fn owned_backing_allocator() {
+    let bump = Bump::new_in(Bump::new());
+    let x = bump.alloc(7);
+    assert_eq!(*x, 7);
+    assert!(bump.backing_allocator().allocated_bytes() > 0);
+}
+
+#[test]
+fn failing_backing_allocator() {
+    #[derive(Debug)]
+    struct Failing;
+
+    unsafe impl BackingAllocator for Failing {
+        fn allocate(&self, _layout: Layout) -> Option<NonNull<u8>> {
+            None
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+            unreachable!()
+        }
+    }
+
+    let bump = Bump::new_in(Failing);
+    assert_eq!(
+        bump.try_alloc(1),
+        Err(AllocErr::from(AllocErrKind::OutOfMemory))
+    );
+    assert_eq!(
+        Bump::try_with_capacity_in(100, Failing).err(),
+        Some(AllocErr::from(AllocErrKind::OutOfMemory))
+    );
+}
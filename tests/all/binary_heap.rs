@@ -0,0 +1,90 @@
+#![cfg(feature = "collections")]
+
+use crate::quickcheck;
+use bumpalo::{
+    collections::{BinaryHeap, CollectIn, Vec},
+    Bump,
+};
+use std::cmp::Reverse;
+
+#[test]
+fn push_pop_peek() {
+    let b = Bump::new();
+    let mut heap = BinaryHeap::new_in(&b);
+    assert_eq!(heap.peek(), None);
+    assert_eq!(heap.pop(), None);
+
+    for x in [5, 1, 8, 3, 9, 2] {
+        heap.push(x);
+    }
+    assert_eq!(heap.len(), 6);
+    assert_eq!(heap.peek(), Some(&9));
+    assert_eq!(heap.pop(), Some(9));
+    assert_eq!(heap.pop(), Some(8));
+    assert_eq!(heap.pop(), Some(5));
+    assert_eq!(heap.len(), 3);
+}
+
+#[test]
+fn min_heap_with_reverse() {
+    let b = Bump::new();
+    let mut heap: BinaryHeap<_> = [3, 1, 2].into_iter().map(Reverse).collect_in(&b);
+    assert_eq!(heap.pop(), Some(Reverse(1)));
+    assert_eq!(heap.pop(), Some(Reverse(2)));
+    assert_eq!(heap.pop(), Some(Reverse(3)));
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn peek_mut_restores_the_heap() {
+    let b = Bump::new();
+    let mut heap = BinaryHeap::from(bumpalo::vec![in &b; 1, 5, 2, 4]);
+
+    *heap.peek_mut().unwrap() = 0;
+    assert_eq!(heap.peek(), Some(&4));
+
+    // Reading through `PeekMut` doesn't reorder anything.
+    assert_eq!(*heap.peek_mut().unwrap(), 4);
+
+    let top = heap.peek_mut().unwrap();
+    assert_eq!(bumpalo::collections::binary_heap::PeekMut::pop(top), 4);
+    assert_eq!(heap.into_sorted_vec(), [0, 1, 2]);
+}
+
+#[test]
+fn stays_in_the_arena() {
+    let b = Bump::new();
+    let mut heap = BinaryHeap::with_capacity_in(100, &b);
+    let used = b.allocated_bytes();
+    heap.extend(0..100);
+    let sorted = heap.into_sorted_vec();
+    assert_eq!(b.allocated_bytes(), used);
+    assert!(sorted.iter().copied().eq(0..100));
+}
+
+quickcheck! {
+    fn pops_in_sorted_order(xs: std::vec::Vec<i32>) -> bool {
+        let b = Bump::new();
+        let mut heap = BinaryHeap::new_in(&b);
+        heap.extend(xs.iter().copied());
+
+        let mut popped = std::vec::Vec::new();
+        while let Some(x) = heap.pop() {
+            popped.push(x);
+        }
+
+        let mut expected = xs;
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        popped == expected
+    }
+
+    fn into_sorted_vec_matches_std(xs: std::vec::Vec<i32>) -> bool {
+        let b = Bump::new();
+        let heap = BinaryHeap::from(Vec::from_iter_in(xs.iter().copied(), &b));
+
+        let mut expected = xs;
+        expected.sort_unstable();
+        let sorted = heap.into_sorted_vec();
+        sorted[..] == expected[..]
+    }
+}
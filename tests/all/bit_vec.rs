@@ -0,0 +1,122 @@
+#![cfg(feature = "collections")]
+
+use crate::quickcheck;
+use bumpalo::{
+    collections::{BitVec, CollectIn},
+    Bump,
+};
+
+#[test]
+fn push_pop_get_set() {
+    let b = Bump::new();
+    let mut bits = BitVec::new_in(&b);
+    assert_eq!(bits.pop(), None);
+    for i in 0..200 {
+        bits.push(i % 3 == 0);
+    }
+    assert_eq!(bits.len(), 200);
+    assert_eq!(bits.get(3), Some(true));
+    assert_eq!(bits.get(4), Some(false));
+    assert_eq!(bits.get(200), None);
+
+    bits.set(4, true);
+    assert!(bits[4]);
+    bits.set(3, false);
+    assert!(!bits[3]);
+
+    assert_eq!(bits.pop(), Some(false));
+    assert_eq!(bits.pop(), Some(true));
+    assert_eq!(bits.len(), 198);
+    assert_eq!(bits.words().len(), 4);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn set_out_of_bounds_panics() {
+    let b = Bump::new();
+    let mut bits = BitVec::from_elem_in(64, false, &b);
+    bits.set(64, true);
+}
+
+#[test]
+fn bulk_operations_keep_trailing_bits_clear() {
+    let b = Bump::new();
+    let mut bits = BitVec::from_elem_in(70, false, &b);
+    bits.negate();
+    assert!(bits.all());
+    assert_eq!(bits.count_ones(), 70);
+    let last = *bits.words().last().unwrap();
+    assert_eq!(last, (1 << (70 % usize::BITS)) - 1);
+
+    bits.clear();
+    assert!(bits.none());
+    assert_eq!(bits.len(), 70);
+
+    bits.set_all();
+    assert_eq!(bits, BitVec::from_elem_in(70, true, &b));
+
+    bits.truncate(65);
+    assert_eq!(bits.words().last(), Some(&((1 << (65 % usize::BITS)) - 1)));
+    bits.grow(10, false);
+    assert_eq!(bits.count_ones(), 65);
+    bits.grow(3, true);
+    assert_eq!(bits.ones().last(), Some(77));
+    assert_eq!(bits.count_ones(), 68);
+}
+
+#[test]
+fn set_operations_report_changes() {
+    let b = Bump::new();
+    let x: BitVec = [true, false, true, false].into_iter().collect_in(&b);
+    let y: BitVec = [true, true, false, false].into_iter().collect_in(&b);
+
+    let mut u = x.clone();
+    assert!(u.union(&y));
+    assert!(u.ones().eq([0, 1, 2]));
+    assert!(!u.union(&y));
+
+    let mut i = x.clone();
+    assert!(i.intersect(&y));
+    assert!(i.ones().eq([0]));
+    assert!(!i.intersect(&y));
+
+    let mut d = x.clone();
+    assert!(d.difference(&y));
+    assert!(d.ones().eq([2]));
+    assert!(!d.difference(&y));
+}
+
+#[test]
+#[should_panic(expected = "bit vectors must have the same length")]
+fn set_operations_require_equal_lengths() {
+    let b = Bump::new();
+    let mut x = BitVec::from_elem_in(3, false, &b);
+    x.union(&BitVec::from_elem_in(4, false, &b));
+}
+
+#[test]
+fn debug_format() {
+    let b = Bump::new();
+    let bits: BitVec = [true, false, true].into_iter().collect_in(&b);
+    assert_eq!(format!("{:?}", bits), "BitVec(101)");
+}
+
+quickcheck! {
+    fn bit_vec_matches_vec_of_bools(xs: Vec<bool>, ys: Vec<bool>) -> bool {
+        let b = Bump::new();
+        let n = xs.len().min(ys.len());
+        let (xs, ys) = (&xs[..n], &ys[..n]);
+
+        let mut bits: BitVec = xs.iter().copied().collect_in(&b);
+        let other: BitVec = ys.iter().copied().collect_in(&b);
+        if !bits.iter().eq(xs.iter().copied())
+            || bits.count_ones() != xs.iter().filter(|x| **x).count()
+        {
+            return false;
+        }
+
+        bits.union(&other);
+        let expected: Vec<usize> = (0..n).filter(|&i| xs[i] || ys[i]).collect();
+        bits.ones().eq(expected) && bits.iter().rev().count() == n
+    }
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "boxed")]
 
-use bumpalo::boxed::Box;
+use bumpalo::boxed::{Box, BoxIn, CloneIn};
 use bumpalo::Bump;
 
 #[test]
@@ -12,3 +12,306 @@ fn into_raw_aliasing() {
     let mut_ref = unsafe { &mut *raw };
     dbg!(mut_ref);
 }
+
+#[test]
+fn unsize_box_to_trait_object() {
+    use std::cell::Cell;
+
+    trait Shape {
+        fn area(&self) -> u32;
+    }
+
+    struct Square<'a>(u32, &'a Cell<usize>);
+
+    impl Shape for Square<'_> {
+        fn area(&self) -> u32 {
+            self.0 * self.0
+        }
+    }
+
+    impl Drop for Square<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+
+    {
+        let shapes: std::vec::Vec<Box<dyn Shape>> = (1..=3)
+            .map(|i| -> Box<dyn Shape> { bumpalo::unsize_box!(in &bump; Square(i, &drops)) })
+            .collect();
+        let areas: std::vec::Vec<u32> = shapes.iter().map(|s| s.area()).collect();
+        assert_eq!(areas, [1, 4, 9]);
+        assert_eq!(drops.get(), 0);
+    }
+
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn unsize_box_to_slice() {
+    let bump = Bump::new();
+    let array = Box::new_in([String::from("a"), String::from("b")], &bump);
+    let slice: Box<[String]> = bumpalo::unsize_box!(array);
+    assert_eq!(&*slice, ["a", "b"]);
+}
+
+#[test]
+fn downcast_any_send_sync() {
+    use std::any::Any;
+
+    let bump = Bump::new();
+    let value: Box<dyn Any + Send + Sync> = bumpalo::unsize_box!(in &bump; String::from("hi"));
+    let value = value.downcast::<i32>().unwrap_err();
+    let string = value.downcast::<String>().unwrap();
+    assert_eq!(*string, "hi");
+
+    let value: Box<dyn Any + Send> = bumpalo::unsize_box!(in &bump; 1_u8);
+    assert_eq!(*value.downcast::<u8>().unwrap(), 1);
+    let value: Box<dyn Any> = bumpalo::unsize_box!(in &bump; 2_u8);
+    assert_eq!(*value.downcast::<u8>().unwrap(), 2);
+}
+
+#[test]
+fn closure_default_and_fallible_constructors() {
+    let bump = Bump::new();
+
+    let drops = std::cell::Cell::new(0);
+    struct Counted<'a>(&'a std::cell::Cell<u32>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+    let counted = Box::new_with_in(|| Counted(&drops), &bump);
+    drop(counted);
+    assert_eq!(drops.get(), 1);
+
+    let default: Box<(u32, String)> = Box::default_in(&bump);
+    assert_eq!(*default, (0, String::new()));
+
+    let ok = Box::try_new_in(5_u64, &bump).unwrap();
+    assert_eq!(*ok, 5);
+
+    bump.set_allocation_limit(Some(bump.allocated_bytes()));
+    assert!(Box::try_new_in([0_u8; 1 << 16], &bump).is_err());
+}
+
+#[test]
+fn clone_in_other_arena() {
+    let a = Bump::new();
+    let b = Bump::new();
+
+    let x = Box::new_in(String::from("hello"), &a);
+    let y = x.clone_in(&b);
+    assert_eq!(x, y);
+    assert!(b.allocated_bytes() > 0);
+
+    let slice: Box<[String]> = bumpalo::unsize_box!(in &a; [String::from("a"), String::from("b")]);
+    assert_eq!(&*slice.clone_in(&b), ["a", "b"]);
+
+    let s: Box<str> = "str".clone_in(&a);
+    assert_eq!(&*s.clone_in(&b), "str");
+}
+
+#[test]
+fn box_in_clones_into_its_arena() {
+    fn clone_all<T: Clone>(xs: &[T]) -> std::vec::Vec<T> {
+        xs.to_vec()
+    }
+
+    let bump = Bump::new();
+    let boxes = [
+        BoxIn::new_in(vec![1], &bump),
+        BoxIn::new_in(vec![2, 3], &bump),
+    ];
+    let capacity = bump.chunk_capacity();
+    let mut clones = clone_all(&boxes);
+    assert!(bump.chunk_capacity() < capacity);
+
+    clones[0].push(10);
+    assert_eq!(*boxes[0], [1]);
+    assert_eq!(*clones[0], [1, 10]);
+    assert_eq!(boxes[1], clones[1]);
+    assert!(std::ptr::eq(BoxIn::bump(&clones[1]), &bump));
+
+    let s = BoxIn::from_box("hi".clone_in(&bump), &bump);
+    let t = s.clone();
+    assert_eq!(Box::from(t), (*s).clone_in(&bump));
+}
+
+#[test]
+fn from_slice_copy_in_and_from_str_in() {
+    let bump = Bump::new();
+    let slice = Box::from_slice_copy_in(&[1_u16, 2, 3], &bump);
+    assert_eq!(*slice, [1, 2, 3]);
+    let empty: Box<[u16]> = Box::from_slice_copy_in(&[], &bump);
+    assert!(empty.is_empty());
+    let s = Box::from_str_in("hello", &bump);
+    assert_eq!(&*s, "hello");
+}
+
+#[test]
+#[cfg(feature = "collections")]
+fn boxed_slice_round_trips_through_vec() {
+    use bumpalo::collections::{String, Vec};
+    use std::rc::Rc;
+
+    let bump = Bump::new();
+    let rc = Rc::new(());
+    let mut v = Vec::new_in(&bump);
+    v.push(rc.clone());
+    v.push(rc.clone());
+
+    let boxed: Box<[Rc<()>]> = v.into();
+    let mut v = boxed.into_vec(&bump);
+    assert_eq!(v.len(), 2);
+    assert_eq!(v.capacity(), 2);
+    v.push(rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(v);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let s = String::from_str_in("hello", &bump);
+    let boxed: Box<str> = s.into();
+    let mut s = boxed.into_string(&bump);
+    s.push('!');
+    assert_eq!(s, "hello!");
+    assert_eq!(&*s.into_boxed_str(), "hello!");
+
+    let zsts = Box::from_slice_copy_in(&[(); 5], &bump).into_vec(&bump);
+    assert_eq!(zsts.len(), 5);
+}
+
+fn poll_once<F: std::future::Future + ?Sized>(
+    fut: std::pin::Pin<&mut F>,
+) -> std::task::Poll<F::Output> {
+    use std::task::{Context, RawWaker, RawWakerVTable, Waker};
+
+    fn raw() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(raw()) };
+    fut.poll(&mut Context::from_waker(&waker))
+}
+
+#[test]
+fn pinned_futures_coerce_to_dyn_future() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Poll;
+
+    let b = Bump::new();
+    let mut futures: [Pin<Box<dyn Future<Output = u32>>>; 2] = [
+        Box::new_in(async { 1 }, &b).into(),
+        Box::new_in(async { 2 }, &b).into(),
+    ];
+    let outputs: Vec<_> = futures.iter_mut().map(|f| poll_once(f.as_mut())).collect();
+    assert_eq!(outputs, [Poll::Ready(1), Poll::Ready(2)]);
+
+    let mut sendable: Pin<Box<dyn Future<Output = &str> + Send>> =
+        Box::new_in(async { "hi" }, &b).into();
+    assert_eq!(poll_once(sendable.as_mut()), Poll::Ready("hi"));
+}
+
+#[test]
+fn pinned_box_in_remembers_its_arena() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Poll;
+
+    let b = Bump::new();
+    let five = Pin::into_inner(BoxIn::pin_in(5, &b));
+    assert!(std::ptr::eq(BoxIn::bump(&five), &b));
+
+    let mut fut = BoxIn::pin_in(async { 7 }, &b);
+    assert_eq!(poll_once(fut.as_mut()), Poll::Ready(7));
+
+    let mut erased: Pin<BoxIn<dyn Future<Output = u8> + Send>> =
+        BoxIn::new_in(async { 8 }, &b).into();
+    assert_eq!(poll_once(erased.as_mut()), Poll::Ready(8));
+}
+
+#[test]
+fn pinned_dyn_future_runs_drop() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+
+    let b = Bump::new();
+    let token = Rc::new(());
+    let held = token.clone();
+    let fut: Pin<Box<dyn Future<Output = ()>>> = Box::new_in(
+        async move {
+            let _held = held;
+        },
+        &b,
+    )
+    .into();
+    assert_eq!(Rc::strong_count(&token), 2);
+    drop(fut);
+    assert_eq!(Rc::strong_count(&token), 1);
+}
+
+#[test]
+fn leaked_pinned_box_in_is_dropped_by_the_arena() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+
+    let token = Arc::new(());
+    let mut b = Bump::new();
+
+    std::mem::forget(BoxIn::pin_in(token.clone(), &b));
+    let held = token.clone();
+    let fut: Pin<BoxIn<dyn Future<Output = ()> + Send>> = BoxIn::new_in(
+        async move {
+            let _held = held;
+        },
+        &b,
+    )
+    .into();
+    std::mem::forget(fut);
+    assert_eq!(Arc::strong_count(&token), 3);
+
+    // Resetting the arena drops the leaked values before reusing their memory.
+    b.reset();
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    let pinned = BoxIn::pin_in(token.clone(), &b);
+    std::mem::forget(pinned.clone());
+    std::mem::forget(pinned);
+    assert_eq!(Arc::strong_count(&token), 3);
+    drop(b);
+    assert_eq!(Arc::strong_count(&token), 1);
+}
+
+#[test]
+fn pinned_box_in_is_dropped_only_once() {
+    use std::pin::Pin;
+    use std::sync::Arc;
+
+    let token = Arc::new(());
+    let mut b = Bump::new();
+
+    drop(BoxIn::pin_in(token.clone(), &b));
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    // Unpinning hands the value back to the box's owner.
+    let unpinned = BoxIn::into_box(Pin::into_inner(BoxIn::pin_in(token.clone(), &b)));
+    let value = Box::into_inner(unpinned);
+    assert_eq!(Arc::strong_count(&token), 2);
+
+    b.reset();
+    assert_eq!(Arc::strong_count(&token), 2);
+    drop(value);
+    assert_eq!(Arc::strong_count(&token), 1);
+}
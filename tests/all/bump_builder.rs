@@ -0,0 +1,281 @@
+use bumpalo::{Bump, BumpBuilder, ChunkGrowthPolicy};
+use std::alloc::Layout;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocate bytes one at a time until the arena has allocated `n` new chunks,
+/// and return each new chunk's size.
+fn chunk_sizes(bump: &Bump, n: usize) -> Vec<usize> {
+    let mut sizes = vec![];
+    let mut allocated = bump.allocated_bytes();
+    while sizes.len() < n {
+        bump.alloc(0_u8);
+        let now = bump.allocated_bytes();
+        if now != allocated {
+            sizes.push(now - allocated);
+            allocated = now;
+        }
+    }
+    sizes
+}
+
+#[test]
+fn default_builder_does_not_allocate() {
+    let bump = BumpBuilder::new().build();
+    assert_eq!(bump.allocated_bytes(), 0);
+    assert_eq!(bump.allocation_limit(), None);
+}
+
+#[test]
+fn small_initial_chunk_is_not_default_sized() {
+    let small = Bump::with_capacity(16);
+    let default = chunk_sizes(&Bump::new(), 1)[0];
+    assert!(small.chunk_capacity() >= 16);
+    assert!(small.allocated_bytes() < default);
+
+    let small = BumpBuilder::new().initial_chunk_size(16).build();
+    assert!(small.chunk_capacity() >= 16);
+    assert!(small.allocated_bytes() < default);
+}
+
+#[test]
+fn default_growth_doubles() {
+    let bump = Bump::new();
+    let sizes = chunk_sizes(&bump, 4);
+    for w in sizes.windows(2) {
+        assert!(w[1] >= 2 * w[0], "{:?}", sizes);
+    }
+}
+
+#[test]
+fn linear_growth() {
+    let bump = BumpBuilder::new()
+        .initial_chunk_size(1000)
+        .growth_factor(1)
+        .build();
+    let initial = bump.allocated_bytes();
+    let sizes = chunk_sizes(&bump, 4);
+    assert!(sizes.iter().all(|&s| s == initial), "{:?}", sizes);
+}
+
+#[test]
+fn faster_growth() {
+    let bump = Bump::builder().growth_factor(4).build();
+    let sizes = chunk_sizes(&bump, 4);
+    for w in sizes.windows(2) {
+        assert!(w[1] >= 4 * w[0], "{:?}", sizes);
+    }
+}
+
+#[test]
+fn max_chunk_size_caps_growth() {
+    let bump = BumpBuilder::new().max_chunk_size(Some(8192)).build();
+    let sizes = chunk_sizes(&bump, 8);
+    let last = *sizes.last().unwrap();
+    assert!(last < 2 * 8192, "{:?}", sizes);
+    assert_eq!(sizes[sizes.len() - 2], last, "{:?}", sizes);
+
+    // Larger allocations still get a large enough chunk.
+    let big = bump.alloc_slice_fill_copy(100_000, 1_u8);
+    assert_eq!(big.len(), 100_000);
+}
+
+fn arithmetic(prev: usize, _layout: Layout) -> usize {
+    prev + 4096
+}
+
+#[test]
+fn growth_policy_sizes_chunks() {
+    let bump = BumpBuilder::new().growth_policy(&arithmetic).build();
+    let sizes = chunk_sizes(&bump, 6);
+    for w in sizes[1..].windows(2) {
+        let step = w[1] - w[0];
+        assert!((4096..4096 + 512).contains(&step), "{:?}", sizes);
+    }
+}
+
+#[test]
+fn growth_policy_still_fits_large_allocations() {
+    let bump = BumpBuilder::new()
+        .growth_policy(&|_: usize, _: Layout| 1)
+        .build();
+    let big = bump.alloc_slice_fill_copy(100_000, 1_u8);
+    assert_eq!(big.len(), 100_000);
+}
+
+#[test]
+fn set_growth_policy() {
+    let bump = Bump::new();
+    assert!(bump.growth_policy().is_none());
+    chunk_sizes(&bump, 2);
+
+    bump.set_growth_policy(Some(&arithmetic));
+    assert!(bump.growth_policy().is_some());
+    let sizes = chunk_sizes(&bump, 4);
+    for w in sizes.windows(2) {
+        assert!(w[1] - w[0] < 2 * 4096, "{:?}", sizes);
+    }
+
+    // Back to doubling.
+    bump.set_growth_policy(None);
+    let sizes = chunk_sizes(&bump, 3);
+    for w in sizes.windows(2) {
+        assert!(w[1] >= 2 * w[0], "{:?}", sizes);
+    }
+}
+
+#[test]
+fn growth_policy_observes_chunks() {
+    struct Observer {
+        chunks: AtomicUsize,
+        bytes: AtomicUsize,
+    }
+
+    impl ChunkGrowthPolicy for Observer {
+        fn chunk_allocated(&self, size: usize) {
+            self.chunks.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(size, Ordering::Relaxed);
+        }
+    }
+
+    static OBSERVER: Observer = Observer {
+        chunks: AtomicUsize::new(0),
+        bytes: AtomicUsize::new(0),
+    };
+
+    let bump = BumpBuilder::new()
+        .initial_chunk_size(1000)
+        .growth_policy(&OBSERVER)
+        .build();
+    let sizes = chunk_sizes(&bump, 3);
+    assert_eq!(OBSERVER.chunks.load(Ordering::Relaxed), 4);
+    assert_eq!(
+        OBSERVER.bytes.load(Ordering::Relaxed),
+        bump.allocated_bytes()
+    );
+
+    // Observing doesn't change the default growth.
+    for w in sizes.windows(2) {
+        assert!(w[1] >= 2 * w[0], "{:?}", sizes);
+    }
+}
+
+#[test]
+fn min_align() {
+    for align in [16, 64, 4096] {
+        let bump = BumpBuilder::new()
+            .initial_chunk_size(100)
+            .min_align(align)
+            .build();
+        // Front allocations start at the beginning of the chunk.
+        let first = bump.alloc_front(0_u8) as *mut u8 as usize;
+        assert_eq!(first % align, 0);
+
+        let sizes = chunk_sizes(&bump, 1);
+        assert_eq!(sizes.len(), 1);
+        let first = bump.alloc_front(0_u8) as *mut u8 as usize;
+        assert_eq!(first % align, 0);
+    }
+}
+
+#[test]
+fn allocation_limit() {
+    let bump = BumpBuilder::new().allocation_limit(Some(1024)).build();
+    assert_eq!(bump.allocation_limit(), Some(1024));
+    assert!(bump.try_alloc([0_u8; 2048]).is_err());
+
+    let result = BumpBuilder::new()
+        .initial_chunk_size(4096)
+        .allocation_limit(Some(1024))
+        .try_build();
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "growth factor must be at least 1")]
+fn zero_growth_factor() {
+    BumpBuilder::new().growth_factor(0);
+}
+
+#[test]
+#[should_panic(expected = "alignment must be a power of two")]
+fn non_power_of_two_min_align() {
+    BumpBuilder::new().min_align(24);
+}
+
+#[test]
+fn cache_aligned_allocations_do_not_share_lines() {
+    for line in [32, 64, 128, 256] {
+        let bump = BumpBuilder::new().cache_line_size(line).build();
+        assert_eq!(bump.cache_line_size(), line);
+
+        let mut addrs = vec![];
+        for i in 0..10_u64 {
+            let x = bump.alloc_cache_aligned(i);
+            assert_eq!(*x, i);
+            addrs.push(x as *mut u64 as usize);
+            bump.alloc(0_u8);
+            let s = bump.alloc_slice_cache_aligned(&[i as u8; 3]);
+            assert_eq!(s, [i as u8; 3]);
+            addrs.push(s.as_ptr() as usize);
+        }
+        for a in &addrs {
+            assert_eq!(a % line, 0);
+        }
+        addrs.sort_unstable();
+        addrs.dedup();
+        assert_eq!(addrs.len(), 20);
+    }
+}
+
+#[test]
+fn cache_aligned_empty_slice() {
+    let bump = Bump::new();
+    let s: &mut [u32] = bump.alloc_slice_cache_aligned(&[]);
+    assert!(s.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "alignment must be a power of two")]
+fn non_power_of_two_cache_line_size() {
+    BumpBuilder::new().cache_line_size(96);
+}
+
+#[test]
+fn huge_grown_chunk_sizes_fall_back_to_smaller_chunks() {
+    static HUGE: fn(usize, Layout) -> usize = |_, _| usize::MAX;
+
+    // Neither a policy asking for an absurd size, nor a growth factor that
+    // overflows, keeps the arena from allocating a chunk that fits.
+    for builder in [
+        BumpBuilder::new().growth_policy(&HUGE),
+        BumpBuilder::new().growth_factor(usize::MAX),
+    ] {
+        let bump = builder.allocation_limit(Some(1 << 20)).build();
+        for _ in 0..3 {
+            assert!(bump.try_alloc([0_u8; 1000]).is_ok());
+            assert!(bump.try_alloc_layout(Layout::new::<[u8; 4096]>()).is_ok());
+        }
+        assert!(bump.allocated_bytes() <= 1 << 20);
+    }
+}
+
+#[test]
+fn absurd_layouts_error_instead_of_panicking() {
+    let bump = BumpBuilder::new().min_align(16).build();
+    bump.alloc(0_u8);
+
+    for align in [1, 16, 4096, 1 << 30] {
+        let size = isize::MAX as usize - (align - 1);
+        let layout = Layout::from_size_align(size, align).unwrap();
+        assert!(bump.try_alloc_layout(layout).is_err());
+        assert!(bump.try_alloc_layout_zeroed(layout).is_err());
+    }
+    assert!(bump.try_reserve_bytes(isize::MAX as usize).is_err());
+    assert!(bump
+        .try_alloc_slice_fill_copy(usize::MAX / 2, 0_u8)
+        .is_err());
+    assert!(BumpBuilder::new()
+        .initial_chunk_size(isize::MAX as usize)
+        .try_build()
+        .is_err());
+}
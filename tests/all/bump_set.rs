@@ -0,0 +1,123 @@
+use bumpalo::set::BumpSet;
+use bumpalo::{BackingAllocator, Global};
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+/// A backing allocator that forwards to the global allocator and keeps count
+/// of the chunks that are currently live.
+#[derive(Debug, Default)]
+struct Counting {
+    live: Cell<usize>,
+    total: Cell<usize>,
+}
+
+unsafe impl BackingAllocator for Counting {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.live.set(self.live.get() + 1);
+        self.total.set(self.total.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn dropped_members_chunks_are_reused() {
+    let set = BumpSet::new_in(Counting::default());
+
+    let first = set.new_bump();
+    first.alloc_slice_fill_copy(1000, 1_u8);
+    drop(first);
+    assert_eq!(set.backing().total.get(), 1);
+    assert_eq!(set.backing().live.get(), 1);
+    assert!(set.retained_bytes() > 0);
+
+    for _ in 0..10 {
+        let bump = set.new_bump();
+        let xs = bump.alloc_slice_fill_copy(1000, 2_u8);
+        assert!(xs.iter().all(|&x| x == 2));
+    }
+    assert_eq!(set.backing().total.get(), 1);
+
+    drop(set);
+}
+
+#[test]
+fn reset_members_return_all_but_one_chunk() {
+    let set = BumpSet::new_in(Counting::default());
+    let mut bump = set.new_bump();
+    for i in 0..10_000_u32 {
+        bump.alloc(i);
+    }
+    let chunks = set.backing().live.get();
+    assert!(chunks > 1);
+    assert_eq!(set.retained_bytes(), 0);
+
+    bump.reset();
+    assert!(set.retained_bytes() > 0);
+
+    // A sibling can reuse the freed chunks.
+    let sibling = set.new_bump();
+    for i in 0..1000_u32 {
+        sibling.alloc(i);
+    }
+    assert_eq!(set.backing().live.get(), chunks);
+}
+
+#[test]
+fn retention_is_limited() {
+    let set = BumpSet::with_max_retained_bytes_in(0, Counting::default());
+    let bump = set.new_bump();
+    bump.alloc(1_u64);
+    drop(bump);
+    assert_eq!(set.retained_bytes(), 0);
+    assert_eq!(set.backing().live.get(), 0);
+
+    set.set_max_retained_bytes(usize::MAX);
+    for _ in 0..3 {
+        set.new_bump().alloc_slice_fill_copy(10_000, 0_u8);
+    }
+    let retained = set.retained_bytes();
+    assert!(retained > 0);
+
+    set.set_max_retained_bytes(retained - 1);
+    assert!(set.retained_bytes() < retained);
+
+    set.release();
+    assert_eq!(set.retained_bytes(), 0);
+    assert_eq!(set.backing().live.get(), 0);
+}
+
+#[test]
+fn recycled_zeroed_chunks_are_zeroed() {
+    let set = BumpSet::new();
+    let bump = set.new_bump();
+    let layout = Layout::from_size_align(4000, 1).unwrap();
+    unsafe {
+        let p = bump.alloc_layout(layout);
+        p.as_ptr().write_bytes(0xff, layout.size());
+    }
+    drop(bump);
+
+    let bump = set.new_bump();
+    let p = bump.alloc_layout_zeroed(layout);
+    let bytes = unsafe { std::slice::from_raw_parts(p.as_ptr(), layout.size()) };
+    assert!(bytes.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn set_drop_releases_chunks() {
+    let counting = Counting::default();
+    {
+        let set = BumpSet::new_in(&counting);
+        let a = set.new_bump();
+        let b = set.new_bump();
+        a.alloc(1);
+        b.alloc(2);
+    }
+    assert_eq!(counting.live.get(), 0);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "bytemuck")]
+
+use crate::quickcheck;
+use bumpalo::Bump;
+use bytemuck::PodCastError;
+
+#[test]
+fn alloc_zeroed_pod_zeroes_reused_memory() {
+    let mut bump = Bump::new();
+    bump.alloc([0xff_u8; 64]);
+    bump.reset();
+
+    let x = bump.alloc_zeroed_pod::<[u64; 8]>();
+    assert_eq!(x, &[0; 8]);
+    assert_eq!(x.as_ptr() as usize % 8, 0);
+
+    let xs = bump.alloc_slice_zeroed_pod::<u32>(16);
+    assert_eq!(xs, [0; 16]);
+}
+
+#[test]
+fn try_alloc_zeroed_pod_respects_the_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    assert!(bump.try_alloc_zeroed_pod::<[u8; 16]>().is_err());
+}
+
+#[test]
+fn alloc_slice_cast_aligns_misaligned_input() {
+    let bump = Bump::new();
+    let bytes: Vec<u8> = (0..33).collect();
+    // Start at an odd offset, so the input can't be cast in place.
+    let src = &bytes[1..];
+
+    let words: &mut [u64] = bump.alloc_slice_cast(src);
+    assert_eq!(words.len(), 4);
+    assert_eq!(words.as_ptr() as usize % 8, 0);
+    assert_eq!(bytemuck::cast_slice::<u64, u8>(words), src);
+}
+
+#[test]
+fn alloc_slice_try_cast_errors() {
+    let bump = Bump::new();
+    assert_eq!(
+        bump.alloc_slice_try_cast::<u32, u8>(&[1, 2, 3]),
+        Err(PodCastError::OutputSliceWouldHaveSlop)
+    );
+    assert_eq!(
+        bump.alloc_slice_try_cast::<(), u8>(&[1]),
+        Err(PodCastError::SizeMismatch)
+    );
+    assert_eq!(bump.alloc_slice_try_cast::<(), u8>(&[]), Ok(&mut [][..]));
+    assert_eq!(bump.alloc_slice_try_cast::<u32, u8>(&[]), Ok(&mut [][..]));
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), 0);
+}
+
+#[test]
+#[should_panic(expected = "alloc_slice_cast")]
+fn alloc_slice_cast_panics_on_slop() {
+    let bump = Bump::new();
+    let _: &mut [u16] = bump.alloc_slice_cast(&[1_u8, 2, 3]);
+}
+
+quickcheck! {
+    fn alloc_slice_cast_round_trips(xs: Vec<u32>) -> bool {
+        let bump = Bump::new();
+        let bytes: &mut [u8] = bump.alloc_slice_cast(&xs);
+        let back: &mut [u32] = bump.alloc_slice_cast(bytes);
+        back[..] == xs[..]
+    }
+}
@@ -1,7 +1,344 @@
 use bumpalo::Bump;
+use std::alloc::Layout;
 
 #[test]
 fn try_with_capacity_too_large() {
     // Shouldn't panic even though the capacity is too large for a `Layout`.
     let _ = Bump::try_with_capacity(isize::MAX as usize + 1);
 }
+
+#[test]
+fn with_capacity_is_usable_capacity() {
+    for capacity in (1..10_000).step_by(7).chain([4096, 8192, 100_000]) {
+        let bump = Bump::with_capacity(capacity);
+        assert!(
+            bump.chunk_capacity() >= capacity,
+            "chunk_capacity() = {} < {}",
+            bump.chunk_capacity(),
+            capacity
+        );
+
+        // Filling the arena up to the requested capacity stays within the
+        // initial chunk.
+        bump.alloc_layout(Layout::from_size_align(capacity, 1).unwrap());
+        assert_eq!(bump.allocated_bytes(), bump.chunk_capacity() + capacity);
+    }
+}
+
+#[test]
+fn reserve_bytes_without_new_chunk() {
+    let bump = Bump::with_capacity(1024);
+    let allocated = bump.allocated_bytes();
+    bump.reserve_bytes(512);
+    assert_eq!(bump.allocated_bytes(), allocated);
+}
+
+#[test]
+fn reserve_bytes_allocates_new_chunk_up_front() {
+    for additional in [1, 100, 4096, 100_000] {
+        let bump = Bump::new();
+        bump.alloc_layout(Layout::from_size_align(bump.chunk_capacity(), 1).unwrap());
+
+        bump.reserve_bytes(additional);
+        assert!(bump.chunk_capacity() >= additional);
+
+        let allocated = bump.allocated_bytes();
+        for _ in 0..additional {
+            bump.alloc(0_u8);
+        }
+        assert_eq!(bump.allocated_bytes(), allocated);
+    }
+}
+
+#[test]
+fn try_reserve_bytes_errors() {
+    let bump = Bump::new();
+    assert!(bump.try_reserve_bytes(isize::MAX as usize + 1).is_err());
+
+    bump.set_allocation_limit(Some(4096));
+    assert!(bump.try_reserve_bytes(8192).is_err());
+    assert!(bump.try_reserve_bytes(1024).is_ok());
+    assert!(bump.chunk_capacity() >= 1024);
+}
+
+#[test]
+fn alloc_unchecked_within_reservation() {
+    let bump = Bump::new();
+    bump.reserve_bytes(1000 * std::mem::size_of::<u32>());
+    let allocated = bump.allocated_bytes();
+
+    let mut refs = Vec::new();
+    for i in 0..1000_u32 {
+        refs.push(unsafe { bump.alloc_unchecked(i) });
+    }
+    for (i, r) in refs.iter().enumerate() {
+        assert_eq!(**r, i as u32);
+    }
+    assert_eq!(bump.allocated_bytes(), allocated);
+
+    // Mixed alignments, with room to spare for padding.
+    bump.reserve_bytes(64);
+    let a = unsafe { bump.alloc_layout_unchecked(Layout::new::<u8>()) };
+    let b = unsafe { bump.alloc_layout_unchecked(Layout::new::<u64>()) };
+    assert_eq!(b.as_ptr() as usize % 8, 0);
+    assert!((b.as_ptr() as usize) < (a.as_ptr() as usize));
+
+    // Zero-sized types don't touch the arena.
+    let before = bump.chunk_capacity();
+    unsafe { bump.alloc_unchecked(()) };
+    assert_eq!(bump.chunk_capacity(), before);
+}
+
+#[test]
+fn current_chunk_usage_adds_up() {
+    let bump = Bump::with_capacity(1024);
+    let size = bump.remaining_capacity();
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), 0);
+
+    bump.alloc(1_u8);
+    bump.alloc(2_u64);
+    bump.alloc_front(3_u16);
+    assert_eq!(
+        bump.allocated_bytes_in_current_chunk() + bump.remaining_capacity(),
+        size
+    );
+    assert!(bump.allocated_bytes_in_current_chunk() >= 11);
+
+    let usage = bump.iter_chunk_usage().next().unwrap();
+    assert_eq!(usage.size(), size);
+    assert_eq!(usage.used(), bump.allocated_bytes_in_current_chunk());
+    assert_eq!(usage.remaining(), bump.remaining_capacity());
+}
+
+#[test]
+fn chunk_usage_across_chunks() {
+    let mut bump = Bump::new();
+    assert_eq!(bump.iter_chunk_usage().count(), 0);
+
+    let usage = bump.iter_chunk_usage();
+    for i in 0..10_000_u32 {
+        bump.alloc(i);
+    }
+    // Chunks allocated after the iterator was created are not visited.
+    assert_eq!(usage.count(), 0);
+
+    let chunks: Vec<_> = bump.iter_chunk_usage().collect();
+    assert!(chunks.len() > 1);
+    assert_eq!(bump.chunk_count(), chunks.len());
+    assert!(chunks[0].is_current());
+    assert!(chunks[1..].iter().all(|c| !c.is_current()));
+    assert_eq!(
+        chunks.iter().map(|c| c.size()).sum::<usize>(),
+        bump.allocated_bytes()
+    );
+    // `u32`s never need any padding.
+    assert_eq!(chunks.iter().map(|c| c.used()).sum::<usize>(), 10_000 * 4);
+
+    bump.reset();
+    let chunks: Vec<_> = bump.iter_chunk_usage().collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(bump.chunk_count(), 1);
+    assert!(chunks[0].is_current());
+    assert_eq!(chunks[0].used(), 0);
+    assert_eq!(chunks[0].size(), bump.allocated_bytes());
+    assert_eq!(chunks[0].remaining(), bump.remaining_capacity());
+}
+
+#[test]
+fn chunk_usage_is_current_at_time_of_visit() {
+    let bump = Bump::new();
+    bump.alloc(1_u64);
+
+    let mut usage = bump.iter_chunk_usage();
+    bump.alloc_slice_fill_copy(bump.remaining_capacity() + 1, 0_u8);
+    assert_eq!(bump.chunk_count(), 2);
+    // The chunk that was current when the iterator was created is not
+    // anymore.
+    assert!(!usage.next().unwrap().is_current());
+    assert!(bump.iter_chunk_usage().next().unwrap().is_current());
+}
+
+#[test]
+fn alloc_layout_in_current_chunk() {
+    // A fresh arena has no chunk to allocate in.
+    let bump = Bump::new();
+    assert!(bump
+        .alloc_layout_in_current_chunk(Layout::new::<u32>())
+        .is_none());
+    assert_eq!(bump.allocated_bytes(), 0);
+
+    let bump = Bump::with_capacity(128);
+    let capacity = bump.chunk_capacity();
+    let p = bump
+        .alloc_layout_in_current_chunk(Layout::new::<u64>())
+        .unwrap();
+    assert_eq!(p.as_ptr() as usize % 8, 0);
+    assert!(bump.chunk_capacity() <= capacity - 8);
+
+    // Exhaust the chunk without ever allocating another one.
+    let allocated = bump.allocated_bytes();
+    while bump
+        .alloc_layout_in_current_chunk(Layout::new::<u64>())
+        .is_some()
+    {}
+    assert!(bump.chunk_capacity() < 8);
+    assert_eq!(bump.allocated_bytes(), allocated);
+}
+
+#[test]
+fn reset_and_shrink_to_keeps_small_chunk() {
+    let mut bump = Bump::with_capacity(1024);
+    let capacity = bump.chunk_capacity();
+    bump.alloc([0_u8; 100]);
+    bump.reset_and_shrink_to(4096);
+    assert_eq!(bump.chunk_capacity(), capacity);
+}
+
+#[test]
+fn reset_and_shrink_to_coalesces_big_chunks() {
+    let mut bump = Bump::new();
+    for _ in 0..100 {
+        bump.alloc_slice_fill_copy(10_000, 0_u8);
+    }
+    let limit = 64 * 1024;
+    assert!(bump.allocated_bytes() > limit);
+
+    bump.reset_and_shrink_to(limit);
+    let capacity = bump.chunk_capacity();
+    assert!(capacity <= limit);
+    assert!(capacity >= limit / 2);
+    assert_eq!(bump.allocated_bytes(), capacity);
+    assert_eq!(unsafe { bump.iter_allocated_chunks_raw().count() }, 1);
+
+    // Shrinking to the same size again keeps the coalesced chunk.
+    bump.alloc_slice_fill_copy(1000, 0_u8);
+    bump.reset_and_shrink_to(limit);
+    assert_eq!(bump.chunk_capacity(), capacity);
+}
+
+#[test]
+fn reset_and_shrink_to_zero() {
+    let mut bump = Bump::with_capacity(1024);
+    bump.alloc(1_u64);
+    bump.reset_and_shrink_to(0);
+    assert_eq!(bump.allocated_bytes(), 0);
+    assert_eq!(bump.chunk_capacity(), 0);
+
+    // The arena is still usable.
+    assert_eq!(*bump.alloc(2_u64), 2);
+}
+
+#[test]
+fn reset_and_shrink_to_respects_allocation_limit() {
+    let mut bump = Bump::new();
+    bump.alloc_slice_fill_copy(100_000, 0_u8);
+    bump.set_allocation_limit(Some(1000));
+    bump.reset_and_shrink_to(64 * 1024);
+    assert_eq!(bump.allocated_bytes(), 0);
+}
+
+#[test]
+fn deferred_reset_keeps_spare_chunks() {
+    let mut bump = Bump::builder().defer_chunk_frees(true).build();
+    assert_eq!(bump.spare_capacity(), 0);
+    for _ in 0..100 {
+        bump.alloc_slice_fill_copy(1000, 0_u8);
+    }
+    assert!(bump.chunk_count() > 2);
+    let total = bump.allocated_bytes();
+
+    bump.reset();
+    assert_eq!(bump.chunk_count(), 1);
+    let capacity = bump.chunk_capacity();
+    assert_eq!(bump.allocated_bytes(), capacity);
+    assert_eq!(bump.spare_capacity(), total - capacity);
+
+    // Running out of room in the kept chunk reuses the spares instead of
+    // allocating more memory.
+    for _ in 0..100 {
+        bump.alloc_slice_fill_copy(1000, 1_u8);
+    }
+    assert!(bump.chunk_count() > 1);
+    assert_eq!(bump.allocated_bytes() + bump.spare_capacity(), total);
+    for chunk in bump.iter_allocated_chunks() {
+        assert!(chunk.iter().all(|b| unsafe { b.assume_init() } == 1));
+    }
+}
+
+#[test]
+fn deferred_reset_accumulates_spares() {
+    let mut bump = Bump::builder().defer_chunk_frees(true).build();
+    bump.alloc_slice_fill_copy(100_000, 0_u8);
+    bump.alloc_slice_fill_copy(100_000, 0_u8);
+    bump.reset();
+    let spare = bump.spare_capacity();
+    assert!(spare > 0);
+
+    // A reset with a single chunk sets nothing aside.
+    bump.reset();
+    assert_eq!(bump.spare_capacity(), spare);
+
+    // An allocation that doesn't fit in the newest spare frees it.
+    bump.alloc_slice_fill_copy(1_000_000, 0_u8);
+    assert_eq!(bump.spare_capacity(), 0);
+
+    bump.alloc_slice_fill_copy(1_000_000, 0_u8);
+    bump.reset();
+    assert!(bump.spare_capacity() > 0);
+    bump.release_spare_chunks();
+    assert_eq!(bump.spare_capacity(), 0);
+    assert_eq!(bump.chunk_count(), 1);
+}
+
+#[test]
+fn deferred_reset_spares_respect_allocation_limit() {
+    let mut bump = Bump::builder().defer_chunk_frees(true).build();
+    for _ in 0..10 {
+        bump.alloc_slice_fill_copy(1000, 0_u8);
+    }
+    bump.reset();
+    let spare = bump.spare_capacity();
+    assert!(spare > 0);
+
+    bump.set_allocation_limit(Some(bump.allocated_bytes()));
+    let capacity = bump.chunk_capacity();
+    bump.alloc_slice_fill_copy(capacity, 0_u8);
+    assert!(bump.try_alloc(0_u8).is_err());
+    assert_eq!(bump.spare_capacity(), spare);
+}
+
+#[test]
+fn deferred_reset_zeroed_allocations_do_not_reuse_spares() {
+    let mut bump = Bump::builder().defer_chunk_frees(true).build();
+    for _ in 0..10 {
+        bump.alloc_slice_fill_copy(1000, 0xff_u8);
+    }
+    bump.reset();
+    let spare = bump.spare_capacity();
+    let capacity = bump.chunk_capacity();
+    bump.alloc_slice_fill_copy(capacity, 0xff_u8);
+
+    let zeroed = unsafe { bump.alloc_slice_zeroed::<u8>(100) };
+    assert!(zeroed.iter().all(|&b| b == 0));
+    assert_eq!(bump.spare_capacity(), spare);
+}
+
+#[test]
+fn with_usable_capacity_reports_usable_capacity() {
+    assert_eq!(Bump::new().usable_capacity(), 0);
+
+    for capacity in [1, 100, 1000, 4096, 100_000] {
+        let bump = Bump::with_usable_capacity(capacity);
+        let usable = bump.usable_capacity();
+        assert!(usable >= capacity);
+        assert_eq!(bump.chunk_capacity(), usable);
+
+        // All of it can be allocated without another chunk.
+        let allocated = bump.allocated_bytes();
+        bump.alloc_layout(Layout::from_size_align(usable, 1).unwrap());
+        assert_eq!(bump.allocated_bytes(), allocated);
+        assert_eq!(bump.chunk_capacity(), 0);
+        assert_eq!(bump.usable_capacity(), usable);
+    }
+    assert!(Bump::try_with_usable_capacity(isize::MAX as usize + 1).is_err());
+}
@@ -0,0 +1,131 @@
+use bumpalo::Bump;
+
+#[test]
+fn empty_arena() {
+    let mut source = Bump::new();
+    let (mut bump, map) = unsafe { Bump::clone_from_bump(&mut source) };
+    assert!(map.regions().is_empty());
+    assert_eq!(map.relocate(&0_u8 as *const u8), None);
+    assert_eq!(bump.allocated_bytes(), 0);
+    assert_eq!(bump.iter_allocated_chunks().count(), 0);
+}
+
+#[test]
+fn compacts_into_a_single_chunk() {
+    let mut source = Bump::with_capacity(64);
+    let mut ptrs = Vec::new();
+    for i in 0..1000_u32 {
+        ptrs.push((i, source.alloc(i) as *const u32));
+    }
+    assert!(source.iter_allocated_chunks().count() > 1);
+    let used: usize = source.iter_allocated_chunks().map(|c| c.len()).sum();
+
+    let (mut bump, map) = unsafe { Bump::clone_from_bump(&mut source) };
+    source.reset();
+
+    assert_eq!(bump.iter_allocated_chunks().count(), 1);
+    let copied: usize = bump.iter_allocated_chunks().map(|c| c.len()).sum();
+    assert!(copied >= used);
+    assert!(copied < used + 16 * map.regions().len());
+
+    // Regions are laid out in order, oldest chunk first.
+    let mut end = 0;
+    for region in map.regions() {
+        assert!(region.offset() >= end);
+        end = region.offset() + region.len();
+    }
+    assert!(map.offset_of(ptrs[0].1).unwrap() < map.regions()[0].len());
+
+    for (i, p) in ptrs {
+        let p = map.relocate(p).unwrap();
+        assert_eq!(p as usize % 4, 0);
+        assert_eq!(unsafe { *p }, i);
+    }
+}
+
+#[test]
+fn keeps_alignment() {
+    let mut source = Bump::with_capacity(64);
+    let mut ptrs = Vec::new();
+    for i in 0..200_u8 {
+        ptrs.push((i, source.alloc(u128::from(i)) as *const u128));
+    }
+    // Leave the most recent chunk's bytes starting at an odd address, without
+    // introducing any padding.
+    source.alloc(0_u8);
+    assert!(newest_chunk_starts_at_odd_address(&mut source));
+
+    let (_bump, map) = unsafe { Bump::clone_from_bump(&mut source) };
+    for (i, p) in ptrs {
+        let p = map.relocate(p).unwrap();
+        assert_eq!(p as usize % core::mem::align_of::<u128>(), 0);
+        assert_eq!(unsafe { *p }, u128::from(i));
+    }
+}
+
+fn newest_chunk_starts_at_odd_address(bump: &mut Bump) -> bool {
+    let chunk = bump.iter_allocated_chunks().next().unwrap();
+    chunk.as_ptr() as usize % 2 == 1
+}
+
+#[test]
+fn relocates_pointers_between_copies() {
+    #[derive(Clone, Copy)]
+    struct Link {
+        value: u64,
+        next: *const Link,
+    }
+
+    let mut source = Bump::with_capacity(64);
+    let mut head: *const Link = core::ptr::null();
+    let mut links = Vec::new();
+    for value in 0..100 {
+        head = source.alloc(Link { value, next: head });
+        links.push(head);
+    }
+
+    let (_bump, map) = unsafe { Bump::clone_from_bump(&mut source) };
+    for &link in &links {
+        let copy = map.relocate(link).unwrap();
+        unsafe {
+            if !(*copy).next.is_null() {
+                (*copy).next = map.relocate((*copy).next).unwrap();
+            }
+        }
+    }
+    source.reset();
+
+    let mut link = map.relocate(head).unwrap() as *const Link;
+    let mut expected = 100;
+    while !link.is_null() {
+        expected -= 1;
+        unsafe {
+            assert_eq!((*link).value, expected);
+            link = (*link).next;
+        }
+    }
+    assert_eq!(expected, 0);
+}
+
+#[test]
+fn into_backing_allocator() {
+    let mut source = Bump::new();
+    let x = source.alloc(7_u64) as *const u64;
+    let backing = Bump::new();
+    let (bump, map) = unsafe { Bump::clone_from_bump_in(&mut source, &backing) };
+    assert_eq!(unsafe { *map.relocate(x).unwrap() }, 7);
+    drop(bump);
+}
+
+#[test]
+fn copies_front_allocations() {
+    let mut source = Bump::with_capacity(256);
+    let back = source.alloc(1_u64) as *const u64;
+    let front = source.alloc_front(2_u64) as *const u64;
+    let (_bump, map) = unsafe { Bump::clone_from_bump(&mut source) };
+    assert_eq!(map.regions().len(), 2);
+    assert_eq!(unsafe { *map.relocate(back).unwrap() }, 1);
+    let front = map.relocate(front).unwrap();
+    assert_eq!(front as usize % 8, 0);
+    assert_eq!(unsafe { *front }, 2);
+}
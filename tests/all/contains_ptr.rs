@@ -0,0 +1,62 @@
+use bumpalo::Bump;
+
+fn addr<T>(x: &T) -> *const u8 {
+    x as *const T as *const u8
+}
+
+#[test]
+fn contains_allocations_in_every_chunk() {
+    let bump = Bump::new();
+    let allocs: Vec<*const u8> = (0..1000_u64).map(|i| addr(bump.alloc(i))).collect();
+    assert!(bump.iter_chunk_usage().count() > 1);
+    assert!(allocs.iter().all(|&p| bump.contains_ptr(p)));
+
+    // Every byte of an allocation is in the arena.
+    let s = bump.alloc_str("hello");
+    let range = s.as_bytes().as_ptr_range();
+    assert!(bump.contains_ptr(range.start));
+    assert!(bump.contains_ptr(unsafe { range.end.sub(1) }));
+}
+
+#[test]
+fn does_not_contain_other_memory() {
+    let bump = Bump::new();
+    assert!(!bump.contains_ptr(std::ptr::null()));
+
+    let other = Bump::new();
+    let x = other.alloc(1_u8);
+    assert!(!bump.contains_ptr(x));
+
+    bump.alloc(2_u8);
+    let on_stack = 3_u8;
+    assert!(!bump.contains_ptr(&on_stack));
+    assert!(!bump.contains_ptr(addr(bump.alloc(()))));
+}
+
+#[test]
+fn free_space_is_not_contained() {
+    let bump = Bump::with_capacity(1024);
+    let x = addr(bump.alloc(1_u64));
+    // The byte just below the allocation is still free.
+    assert!(!bump.contains_ptr(x.wrapping_sub(1)));
+}
+
+#[test]
+fn contains_front_allocations() {
+    let mut bump = Bump::new();
+    let x = addr(bump.alloc_front(1_u32));
+    assert!(bump.contains_ptr(x));
+
+    bump.reset_front();
+    assert!(!bump.contains_ptr(x));
+}
+
+#[test]
+fn reset_frees_everything() {
+    let mut bump = Bump::new();
+    let x = addr(bump.alloc(1_u32));
+    assert!(bump.contains_ptr(x));
+
+    bump.reset();
+    assert!(!bump.contains_ptr(x));
+}
@@ -0,0 +1,63 @@
+use bumpalo::Bump;
+
+#[test]
+fn empty_arena() {
+    let mut bump = Bump::new();
+    let mut out = vec![1, 2, 3];
+    let map = unsafe { bump.copy_allocated_bytes_to(&mut out) };
+    assert_eq!(out, [1, 2, 3]);
+    assert!(map.regions().is_empty());
+    assert_eq!(map.offset_of(&0_u8 as *const u8), None);
+}
+
+#[test]
+fn copies_all_chunks_oldest_first() {
+    let mut bump = Bump::with_capacity(64);
+    let mut ptrs = Vec::new();
+    for i in 0..1000_u32 {
+        let x = bump.alloc_slice_copy(&i.to_le_bytes());
+        ptrs.push((i, x.as_ptr()));
+    }
+    let num_chunks = unsafe { bump.iter_allocated_chunks_raw().count() };
+    assert!(num_chunks > 1);
+
+    let mut out = vec![0xff; 7];
+    let map = unsafe { bump.copy_allocated_bytes_to(&mut out) };
+    assert_eq!(map.regions().len(), num_chunks);
+    let used: usize = unsafe { bump.iter_allocated_chunks_raw().map(|(_, len)| len).sum() };
+    assert_eq!(out.len(), 7 + used);
+
+    // Regions are laid out back to back after the existing contents.
+    let mut offset = 7;
+    for region in map.regions() {
+        assert_eq!(region.offset(), offset);
+        offset += region.len();
+    }
+    assert_eq!(offset, out.len());
+
+    // The oldest allocation is in the first region.
+    let first = map.offset_of(ptrs[0].1).unwrap();
+    assert!(first < 7 + map.regions()[0].len());
+
+    for (i, p) in ptrs {
+        let offset = map.offset_of(p).unwrap();
+        assert_eq!(out[offset..offset + 4], i.to_le_bytes());
+    }
+}
+
+#[test]
+fn copies_front_allocations() {
+    let mut bump = Bump::with_capacity(256);
+    let back = bump.alloc_slice_copy(b"back") as *const [u8];
+    let front = bump.alloc_front(*b"front") as *const [u8; 5];
+
+    let mut out = Vec::new();
+    let map = unsafe { bump.copy_allocated_bytes_to(&mut out) };
+    assert_eq!(map.regions().len(), 2);
+
+    // The chunk's front allocations come first.
+    assert_eq!(map.offset_of(front), Some(0));
+    let back = map.offset_of(back).unwrap();
+    assert_eq!(&out[..5], b"front");
+    assert_eq!(&out[back..back + 4], b"back");
+}
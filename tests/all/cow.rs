@@ -0,0 +1,95 @@
+#![cfg(feature = "collections")]
+
+use bumpalo::collections::{Cow, String, ToOwnedIn, Vec};
+use bumpalo::{vec, Bump};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[test]
+fn borrowed_until_written() {
+    let b = Bump::new();
+    let data = [1, 2, 3];
+    let mut cow = Cow::Borrowed(&data[..]);
+    assert!(cow.is_borrowed());
+    assert_eq!(cow.len(), 3);
+    assert_eq!(b.allocated_bytes(), 0);
+
+    cow.to_mut(&b).push(4);
+    assert!(cow.is_owned());
+    assert_eq!(cow, [1, 2, 3, 4]);
+    assert_eq!(data, [1, 2, 3]);
+}
+
+#[test]
+fn owned_is_not_cloned_again() {
+    let b = Bump::new();
+    let mut cow: Cow<[u32]> = Cow::Owned(vec![in &b; 1, 2]);
+    let ptr = cow.as_ptr();
+    cow.to_mut(&b)[0] = 5;
+    assert_eq!(cow.as_ptr(), ptr);
+
+    let v = cow.into_owned(&b);
+    assert_eq!(v.as_ptr(), ptr);
+    assert_eq!(v, [5, 2]);
+}
+
+#[test]
+fn strings() {
+    let b = Bump::new();
+    let mut cow: Cow<str> = "hello".into();
+    assert_eq!(cow, "hello");
+    assert_eq!(format!("{}", cow), "hello");
+    assert_eq!(format!("{:?}", cow), "\"hello\"");
+
+    cow.to_mut(&b).push_str(", world");
+    assert_eq!(cow, "hello, world");
+
+    let owned: Cow<str> = String::from_str_in("hello, world", &b).into();
+    assert_eq!(cow, owned);
+    assert_eq!(Cow::Borrowed("hello, world"), owned);
+}
+
+#[test]
+fn into_owned_clones_borrowed_into_arena() {
+    let b = Bump::new();
+    let cow: Cow<[std::string::String]> = Cow::Borrowed(&[]);
+    assert!(cow.into_owned(&b).is_empty());
+
+    let items = ["a".to_string(), "b".to_string()];
+    let cow = Cow::from(&items[..]);
+    let owned: Vec<std::string::String> = cow.into_owned(&b);
+    assert_eq!(owned, items);
+    assert!(b.allocated_bytes() > 0);
+}
+
+#[test]
+fn hash_and_ord_match_borrowed() {
+    let b = Bump::new();
+    let hash = |cow: &Cow<str>| {
+        let mut hasher = DefaultHasher::new();
+        cow.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(
+        hash(&Cow::Borrowed("a")),
+        hash(&Cow::Owned(String::from_str_in("a", &b)))
+    );
+
+    let mut cows = [
+        Cow::Borrowed("c"),
+        Cow::Owned("a".to_owned_in(&b)),
+        Cow::Borrowed("b"),
+    ];
+    cows.sort();
+    assert_eq!(cows, ["a", "b", "c"].map(Cow::Borrowed));
+}
+
+#[test]
+fn clone() {
+    let b = Bump::new();
+    let cow: Cow<str> = Cow::Owned(String::from_str_in("x", &b));
+    let mut copy = cow.clone();
+    copy.to_mut(&b).push('y');
+    assert_eq!(cow, "x");
+    assert_eq!(copy, "xy");
+}
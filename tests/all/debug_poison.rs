@@ -0,0 +1,79 @@
+// These tests read memory after it has been freed, which AddressSanitizer
+// rightly reports when the `asan` feature is enabled too.
+#![cfg(all(feature = "debug_poison", not(feature = "asan")))]
+
+use bumpalo::{Bump, POISON_FREED, POISON_FRESH};
+use std::alloc::Layout;
+use std::slice;
+
+/// Get a pointer to `addr` in the arena's current chunk that is derived from
+/// the chunk itself.
+///
+/// Pointers derived from references to allocations are invalidated when the
+/// arena poisons the memory, so reading the poison through them is undefined
+/// behavior.
+fn chunk_ptr(bump: &Bump, addr: usize) -> *const u8 {
+    let (ptr, _) = unsafe { bump.iter_allocated_chunks_raw() }.next().unwrap();
+    ptr.wrapping_offset(addr as isize - ptr as isize)
+}
+
+#[test]
+fn reset_poisons_allocations() {
+    let mut bump = Bump::with_capacity(1024);
+    let addr = bump.alloc_slice_fill_copy(64, 0_u8).as_ptr() as usize;
+    bump.reset();
+    let bytes = unsafe { slice::from_raw_parts(chunk_ptr(&bump, addr), 64) };
+    assert!(bytes.iter().all(|&b| b == POISON_FREED));
+}
+
+#[test]
+fn reset_front_poisons_front_allocations() {
+    let mut bump = Bump::with_capacity(1024);
+    let front = bump.alloc_front([0_u8; 32]).as_ptr() as usize;
+    let back = bump.alloc([1_u8; 32]).as_ptr() as usize;
+    bump.reset_front();
+    let front = unsafe { slice::from_raw_parts(chunk_ptr(&bump, front), 32) };
+    assert!(front.iter().all(|&b| b == POISON_FREED));
+    let back = unsafe { slice::from_raw_parts(chunk_ptr(&bump, back), 32) };
+    assert!(back.iter().all(|&b| b == 1));
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn dealloc_poisons_memory() {
+    let bump = Bump::new();
+    let mut v = bumpalo::collections::Vec::with_capacity_in(16, &bump);
+    v.extend_from_slice(&[7_u8; 16]);
+    let p = v.as_ptr();
+    drop(v);
+    let bytes = unsafe { slice::from_raw_parts(p, 16) };
+    assert!(bytes.iter().all(|&b| b == POISON_FREED));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn fresh_allocations_are_poisoned() {
+    let bump = Bump::new();
+    let layout = Layout::from_size_align(48, 8).unwrap();
+    let p = bump.alloc_layout(layout);
+    let bytes = unsafe { slice::from_raw_parts(p.as_ptr(), 48) };
+    assert!(bytes.iter().all(|&b| b == POISON_FRESH));
+
+    let z = bump.alloc_layout_zeroed(layout);
+    let bytes = unsafe { slice::from_raw_parts(z.as_ptr(), 48) };
+    assert!(bytes.iter().all(|&b| b == 0));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn fresh_front_allocations_are_poisoned() {
+    let mut bump = Bump::with_capacity(1024);
+    bump.alloc_front([1_u8; 48]);
+    bump.reset_front();
+
+    // Reuses the memory of the `alloc_front` above.
+    let layout = Layout::from_size_align(48, 8).unwrap();
+    let p = bump.alloc_front_layout(layout);
+    let bytes = unsafe { slice::from_raw_parts(p.as_ptr(), 48) };
+    assert!(bytes.iter().all(|&b| b == POISON_FRESH));
+}
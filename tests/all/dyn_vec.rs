@@ -0,0 +1,120 @@
+#![cfg(feature = "collections")]
+
+use bumpalo::{collections::DynVec, Bump};
+use std::cell::Cell;
+use std::fmt::Debug;
+
+trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, by: f64);
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn scale(&mut self, by: f64) {
+        self.0 *= by;
+    }
+}
+
+struct Rect(f64, f64);
+
+impl Shape for Rect {
+    fn area(&self) -> f64 {
+        self.0 * self.1
+    }
+    fn scale(&mut self, by: f64) {
+        self.0 *= by;
+        self.1 *= by;
+    }
+}
+
+#[test]
+fn push_and_access() {
+    let b = Bump::new();
+    let mut shapes: DynVec<dyn Shape> = DynVec::with_capacity_in(2, &b);
+    assert!(shapes.is_empty());
+    shapes.push(Square(2.0), |s| s);
+    shapes.push(Rect(2.0, 3.0), |s| s);
+    shapes.push(Square(1.0), |s| s);
+    assert_eq!(shapes.len(), 3);
+
+    assert_eq!(shapes[0].area(), 4.0);
+    assert_eq!(shapes.get(1).map(|s| s.area()), Some(6.0));
+    assert!(shapes.get(3).is_none());
+
+    shapes[2].scale(3.0);
+    shapes.get_mut(0).unwrap().scale(0.5);
+    for s in &mut shapes {
+        s.scale(2.0);
+    }
+    let areas: Vec<f64> = shapes.iter().map(|s| s.area()).collect();
+    assert_eq!(areas, [4.0, 24.0, 36.0]);
+    assert_eq!(shapes.iter().rev().len(), 3);
+}
+
+#[test]
+fn debug() {
+    let b = Bump::new();
+    let mut v: DynVec<dyn Debug> = DynVec::new_in(&b);
+    v.push(1_u8, |x| x);
+    v.push(Some("x"), |x| x);
+    assert_eq!(format!("{:?}", v), r#"[1, Some("x")]"#);
+}
+
+#[test]
+fn slices() {
+    let b = Bump::new();
+    let mut v: DynVec<[u32]> = DynVec::new_in(&b);
+    v.push([1, 2, 3], |x| x);
+    v.push([], |x| x);
+    v.push([4], |x| x);
+    let lens: Vec<usize> = v.iter().map(|s| s.len()).collect();
+    assert_eq!(lens, [3, 0, 1]);
+    assert_eq!(&v[0][1..], &[2, 3]);
+}
+
+struct CountDrops<'a>(&'a Cell<usize>);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+trait Anything {}
+impl<T> Anything for T {}
+
+#[test]
+fn drops_elements() {
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    {
+        let mut v: DynVec<dyn Anything> = DynVec::new_in(&b);
+        for _ in 0..5 {
+            v.push(CountDrops(&drops), |x| x);
+        }
+        v.push(7_u64, |x| x);
+
+        assert!(v.pop_and_drop());
+        assert_eq!(drops.get(), 0);
+        assert!(v.pop_and_drop());
+        assert_eq!(drops.get(), 1);
+
+        v.truncate(2);
+        assert_eq!(drops.get(), 3);
+        v.truncate(10);
+        assert_eq!(v.len(), 2);
+    }
+    assert_eq!(drops.get(), 5);
+
+    let mut v: DynVec<dyn Anything> = DynVec::new_in(&b);
+    v.push(CountDrops(&drops), |x| x);
+    v.clear();
+    assert!(v.is_empty());
+    assert!(!v.pop_and_drop());
+    assert_eq!(drops.get(), 6);
+}
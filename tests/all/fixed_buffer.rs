@@ -0,0 +1,92 @@
+use bumpalo::Bump;
+use std::alloc::Layout;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+#[test]
+fn allocations_come_from_the_buffer() {
+    let mut buf = [MaybeUninit::uninit(); 1024];
+    let (start, len) = (buf.as_ptr() as usize, buf.len());
+    let bump = Bump::from_buffer(&mut buf);
+    assert!(bump.chunk_capacity() > 0);
+    assert!(bump.chunk_capacity() <= len);
+
+    let x = bump.alloc(0xdead_beef_u64) as *const u64 as usize;
+    let s = bump.alloc_str("hello").as_ptr() as usize;
+    assert!((start..start + len).contains(&x));
+    assert!((start..start + len).contains(&s));
+}
+
+#[test]
+fn uses_most_of_the_buffer() {
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let bump = Bump::from_buffer(&mut buf);
+    let capacity = bump.chunk_capacity();
+    assert!(capacity >= 4096 - 128, "capacity = {}", capacity);
+    bump.try_alloc_layout(Layout::from_size_align(capacity, 1).unwrap())
+        .unwrap();
+}
+
+#[test]
+fn never_grows() {
+    let mut buf = [MaybeUninit::uninit(); 256];
+    let bump = Bump::from_buffer(&mut buf);
+    let capacity = bump.chunk_capacity();
+
+    let layout = Layout::from_size_align(capacity + 1, 1).unwrap();
+    assert!(bump.try_alloc_layout(layout).is_err());
+
+    let mut n = 0;
+    while bump.try_alloc(0_u8).is_ok() {
+        n += 1;
+    }
+    assert_eq!(n, capacity);
+    assert!(bump.try_alloc_layout(Layout::new::<u8>()).is_err());
+}
+
+#[test]
+fn reset_reuses_the_buffer() {
+    let mut buf = [MaybeUninit::uninit(); 512];
+    let mut bump = Bump::from_buffer(&mut buf);
+    let capacity = bump.chunk_capacity();
+    let layout = Layout::from_size_align(capacity, 1).unwrap();
+
+    let a = bump.try_alloc_layout(layout).unwrap();
+    bump.reset();
+    let b = bump.try_alloc_layout(layout).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn too_small_buffer_fails_every_allocation() {
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let bump = Bump::from_buffer(&mut buf);
+    assert_eq!(bump.chunk_capacity(), 0);
+    assert!(bump.try_alloc(1_u8).is_err());
+    assert!(bump.try_alloc_layout(Layout::new::<u64>()).is_err());
+}
+
+#[test]
+fn from_raw_parts() {
+    let mut buf = vec![MaybeUninit::<u8>::uninit(); 300];
+    // Take the buffer's address range up front: the arena owns the buffer
+    // while it's alive, so it must not be borrowed again until then.
+    let len = buf.len();
+    let ptr = NonNull::new(buf.as_mut_ptr()).unwrap().cast::<u8>();
+    let range = ptr.as_ptr() as usize..ptr.as_ptr() as usize + len;
+    {
+        let bump = unsafe { Bump::from_raw_parts(ptr, len) };
+        let v = bump.alloc_slice_copy(&[1_u32, 2, 3]);
+        assert_eq!(v, [1, 2, 3]);
+        assert!(range.contains(&(v.as_ptr() as usize)));
+    }
+}
+
+#[test]
+fn unaligned_buffer() {
+    let mut buf = [MaybeUninit::uninit(); 257];
+    let bump = Bump::from_buffer(&mut buf[1..]);
+    let x = bump.alloc(7_u128);
+    assert_eq!(*x, 7);
+    assert_eq!(x as *const u128 as usize % std::mem::align_of::<u128>(), 0);
+}
@@ -0,0 +1,72 @@
+use bumpalo::frozen::FrozenBump;
+use bumpalo::relative::{BumpOffset, RelativeBump};
+
+// Snapshots must not contain uninitialized bytes, so rows store the raw
+// offset of the next row, or 0 for none, rather than an
+// `Option<BumpOffset<Row>>`, whose payload is uninitialized when it is `None`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Row {
+    id: u32,
+    next: u32,
+}
+
+// Copy `snapshot` to the end of a buffer that is aligned for `u64`.
+fn load(snapshot: &[u8], storage: &mut Vec<u64>) -> usize {
+    storage.clear();
+    storage.resize((snapshot.len() + 7) / 8, 0);
+    let buf: &mut [u8] =
+        unsafe { std::slice::from_raw_parts_mut(storage.as_mut_ptr().cast(), storage.len() * 8) };
+    let start = buf.len() - snapshot.len();
+    buf[start..].copy_from_slice(snapshot);
+    start
+}
+
+#[test]
+fn resolves_linked_rows_from_snapshot() {
+    let arena = RelativeBump::with_capacity(256);
+    let mut head = 0;
+    for id in 0..5 {
+        head = arena.alloc_rel(Row { id, next: head }).to_raw();
+    }
+    let snapshot = unsafe { arena.used_bytes() }.to_vec();
+    drop(arena);
+
+    let mut storage = Vec::new();
+    let start = load(&snapshot, &mut storage);
+    let bytes: &[u8] =
+        unsafe { std::slice::from_raw_parts(storage.as_ptr().cast(), storage.len() * 8) };
+    let frozen = FrozenBump::new(&bytes[start..]);
+    assert_eq!(frozen.len(), snapshot.len());
+    assert_eq!(frozen.as_bytes(), &snapshot[..]);
+
+    let mut ids = Vec::new();
+    while head != 0 {
+        let row = unsafe { frozen.get(BumpOffset::<Row>::from_raw(head)) };
+        ids.push(row.id);
+        head = row.next;
+    }
+    assert_eq!(ids, [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn try_get_rejects_out_of_bounds_and_misaligned() {
+    let storage = [0_u64; 2];
+    let bytes: &[u8] = unsafe { std::slice::from_raw_parts(storage.as_ptr().cast(), 16) };
+    let frozen = FrozenBump::new(bytes);
+
+    assert!(unsafe { frozen.try_get(BumpOffset::<u64>::from_raw(16)) }.is_some());
+    assert!(unsafe { frozen.try_get(BumpOffset::<u64>::from_raw(24)) }.is_none());
+    assert!(unsafe { frozen.try_get(BumpOffset::<u64>::from_raw(4)) }.is_none());
+    assert!(unsafe { frozen.try_get(BumpOffset::<u64>::from_raw(12)) }.is_none());
+    assert!(unsafe { frozen.try_get(BumpOffset::<u32>::from_raw(12)) }.is_some());
+}
+
+#[test]
+#[should_panic(expected = "offset out of bounds")]
+fn get_panics_out_of_bounds() {
+    let frozen = FrozenBump::new(&[0; 4]);
+    unsafe {
+        frozen.get(BumpOffset::<u8>::from_raw(5));
+    }
+}
@@ -0,0 +1,71 @@
+use bumpalo::global::GlobalBump;
+use std::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn alloc_realloc_dealloc() {
+    let alloc = GlobalBump::new();
+    assert_eq!(alloc.allocated_bytes(), 0);
+
+    unsafe {
+        let layout = Layout::new::<[u32; 4]>();
+        let p = alloc.alloc(layout);
+        assert!(!p.is_null());
+        assert_eq!(p as usize % layout.align(), 0);
+        p.cast::<[u32; 4]>().write([1, 2, 3, 4]);
+
+        // Growing the last allocation keeps its contents.
+        let p = alloc.realloc(p, layout, 32);
+        assert!(!p.is_null());
+        assert_eq!(p.cast::<[u32; 4]>().read(), [1, 2, 3, 4]);
+
+        let p = alloc.realloc(p, Layout::from_size_align(32, 4).unwrap(), 8);
+        assert_eq!(p.cast::<[u32; 2]>().read(), [1, 2]);
+
+        let z = alloc.alloc_zeroed(Layout::new::<[u64; 8]>());
+        assert_eq!(z.cast::<[u64; 8]>().read(), [0; 8]);
+        alloc.dealloc(z, Layout::new::<[u64; 8]>());
+        alloc.dealloc(p, Layout::from_size_align(8, 4).unwrap());
+    }
+    assert!(alloc.allocated_bytes() > 0);
+}
+
+#[test]
+fn allocation_limit_returns_null() {
+    let alloc = GlobalBump::new();
+    alloc.set_allocation_limit(Some(0));
+    unsafe {
+        assert!(alloc.alloc(Layout::new::<u64>()).is_null());
+    }
+}
+
+#[test]
+fn reset_unchecked_reuses_memory() {
+    let alloc = GlobalBump::new();
+    let layout = Layout::new::<u64>();
+    unsafe {
+        let p = alloc.alloc(layout);
+        alloc.alloc(layout);
+        let allocated = alloc.allocated_bytes();
+        alloc.reset_unchecked();
+        assert_eq!(alloc.alloc(layout), p);
+        assert_eq!(alloc.allocated_bytes(), allocated);
+    }
+}
+
+#[test]
+fn shared_between_threads() {
+    static ALLOC: GlobalBump = GlobalBump::new();
+    let layout = Layout::new::<usize>();
+
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            s.spawn(move || unsafe {
+                for _ in 0..100 {
+                    let p = ALLOC.alloc(layout).cast::<usize>();
+                    p.write(i);
+                    assert_eq!(p.read(), i);
+                }
+            });
+        }
+    });
+}
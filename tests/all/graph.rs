@@ -0,0 +1,136 @@
+#![cfg(feature = "collections")]
+
+use bumpalo::graph::{Graph, NodeId};
+use bumpalo::Bump;
+
+/// Build a graph with `n` nodes numbered `0..n` and the given edges.
+fn graph<'bump>(bump: &'bump Bump, n: usize, edges: &[(usize, usize)]) -> Graph<'bump, usize> {
+    let mut g = Graph::new_in(bump);
+    let ids: Vec<NodeId> = (0..n).map(|i| g.add_node(i)).collect();
+    for &(from, to) in edges {
+        g.add_edge(ids[from], ids[to], ());
+    }
+    g
+}
+
+#[test]
+fn nodes_and_edges() {
+    let bump = Bump::new();
+    let mut g = Graph::new_in(&bump);
+    assert!(g.is_empty());
+
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    g.add_edge(a, b, 1);
+    g.add_edge(a, a, 2);
+    g.add_edge(a, b, 3);
+
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(a.index(), 0);
+    assert_eq!(b.index(), 1);
+    assert!(g.successors(a).eq([b, a, b]));
+    assert_eq!(g.successors(b).len(), 0);
+
+    let weights: Vec<i32> = g
+        .node(a)
+        .unwrap()
+        .edges()
+        .iter()
+        .map(|e| *e.weight())
+        .collect();
+    assert_eq!(weights, [1, 2, 3]);
+    for e in g.node_mut(a).unwrap().edges_mut() {
+        *e.weight_mut() *= 10;
+    }
+    assert_eq!(*g.node(a).unwrap().edges()[2].weight(), 30);
+
+    g[b] = "B";
+    assert_eq!(*g.node(b).unwrap().data(), "B");
+    assert!(g.node_ids().eq([a, b]));
+    assert!(g
+        .nodes()
+        .map(|(id, n)| (id, *n.data()))
+        .eq([(a, "a"), (b, "B")]));
+}
+
+#[test]
+fn edges_live_in_the_graphs_arena() {
+    let bump = Bump::new();
+    let g = graph(&bump, 100, &[(0, 1)]);
+    assert!(std::ptr::eq(g.bump(), &bump));
+    let edges = g.node(g.node_ids().next().unwrap()).unwrap().edges();
+    let edges_ptr = edges.as_ptr() as usize;
+    let in_arena = unsafe { bump.iter_allocated_chunks_raw() }
+        .any(|(ptr, len)| (ptr as usize..ptr as usize + len).contains(&edges_ptr));
+    assert!(in_arena);
+}
+
+#[test]
+#[should_panic(expected = "invalid Graph node")]
+fn add_edge_to_missing_node() {
+    let bump = Bump::new();
+    let mut g = graph(&bump, 1, &[]);
+    let other = graph(&bump, 3, &[]).node_ids().next_back().unwrap();
+    let first = g.node_ids().next().unwrap();
+    g.add_edge(first, other, ());
+}
+
+#[test]
+fn dfs_preorder() {
+    let bump = Bump::new();
+    let scratch = Bump::new();
+    // 0 -> 1 -> 3, 0 -> 2 -> 3, 3 -> 0, and 4 is unreachable.
+    let g = graph(&bump, 5, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)]);
+    let start = g.node_ids().next().unwrap();
+    let order: Vec<usize> = g.dfs(start, &scratch).map(|id| g[id]).collect();
+    assert_eq!(order, [0, 1, 3, 2]);
+
+    let mut dfs = g.dfs(start, &scratch);
+    dfs.next();
+    assert!(dfs.visited(start));
+    assert!(!dfs.visited(g.node_ids().nth(4).unwrap()));
+}
+
+#[test]
+fn bfs_order() {
+    let bump = Bump::new();
+    let scratch = Bump::new();
+    let g = graph(
+        &bump,
+        7,
+        &[
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (1, 4),
+            (2, 5),
+            (5, 0),
+            (3, 6),
+            (4, 6),
+        ],
+    );
+    let start = g.node_ids().next().unwrap();
+    let order: Vec<usize> = g.bfs(start, &scratch).map(|id| g[id]).collect();
+    assert_eq!(order, [0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn walks_allocate_in_scratch_arena_only() {
+    let bump = Bump::new();
+    let mut scratch = Bump::new();
+    let edges: Vec<(usize, usize)> = (0..999).map(|i| (i, i + 1)).collect();
+    let g = graph(&bump, 1000, &edges);
+    let start = g.node_ids().next().unwrap();
+
+    let graph_bytes = bump.allocated_bytes();
+    assert_eq!(g.dfs(start, &scratch).count(), 1000);
+    assert_eq!(g.bfs(start, &scratch).count(), 1000);
+    assert_eq!(bump.allocated_bytes(), graph_bytes);
+    assert!(scratch.allocated_bytes() > 0);
+    scratch.reset();
+
+    // Walks from the last node only see the last node.
+    let last = g.node_ids().next_back().unwrap();
+    assert!(g.dfs(last, &scratch).eq([last]));
+}
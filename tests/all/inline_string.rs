@@ -0,0 +1,66 @@
+#![cfg(feature = "collections")]
+
+use bumpalo::{collections::InlineString, Bump};
+use std::fmt::Write;
+
+#[test]
+fn stays_inline_until_full() {
+    let b = Bump::new();
+    let mut s: InlineString<8> = InlineString::new_in(&b);
+    s.push_str("abcd");
+    s.push('e');
+    s.push('é');
+    assert_eq!(s.len(), 7);
+    assert!(!s.spilled());
+    assert_eq!(b.allocated_bytes(), 0);
+
+    s.push('ß');
+    assert!(s.spilled());
+    assert_eq!(s, "abcdeéß");
+}
+
+#[test]
+fn pop_and_truncate() {
+    let b = Bump::new();
+    let mut s: InlineString<16> = InlineString::from_str_in("héllo", &b);
+    assert_eq!(s.pop(), Some('o'));
+    s.truncate(3);
+    assert_eq!(s, "hé");
+    assert_eq!(s.pop(), Some('é'));
+    assert_eq!(s.pop(), Some('h'));
+    assert_eq!(s.pop(), None);
+}
+
+#[test]
+#[should_panic]
+fn truncate_not_on_char_boundary() {
+    let b = Bump::new();
+    let mut s: InlineString<16> = InlineString::from_str_in("é", &b);
+    s.truncate(1);
+}
+
+#[test]
+fn write_and_into_string() {
+    let b = Bump::new();
+    let mut s: InlineString<4> = InlineString::new_in(&b);
+    write!(s, "{}-{}", 12, 34).unwrap();
+    assert!(s.spilled());
+    assert_eq!(s.to_string(), "12-34");
+
+    let s = s.into_string();
+    assert_eq!(s, "12-34");
+
+    let s: InlineString<4> = InlineString::from_str_in("ab", &b);
+    assert_eq!(s.into_string(), "ab");
+}
+
+#[test]
+fn comparisons() {
+    let b = Bump::new();
+    let s: InlineString<4> = InlineString::from_str_in("ab", &b);
+    let t: InlineString<4> = InlineString::from_str_in("ab", &b);
+    assert_eq!(s, t);
+    assert_eq!(s, "ab");
+    assert_eq!("ab", s);
+    assert_eq!(format!("{:?}", s), "\"ab\"");
+}
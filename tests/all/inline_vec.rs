@@ -0,0 +1,239 @@
+#![cfg(feature = "collections")]
+
+use crate::quickcheck;
+use bumpalo::{collections::InlineVec, Bump};
+use std::cell::Cell;
+
+#[test]
+fn stays_inline_until_full() {
+    let b = Bump::new();
+    let mut v: InlineVec<u64, 3> = InlineVec::new_in(&b);
+    assert_eq!(v.capacity(), 3);
+
+    v.extend([1, 2, 3]);
+    assert!(!v.spilled());
+    assert_eq!(b.allocated_bytes(), 0);
+
+    v.push(4);
+    assert!(v.spilled());
+    assert!(b.allocated_bytes() > 0);
+    assert!(v.capacity() >= 4);
+    assert_eq!(v, [1, 2, 3, 4]);
+
+    // Clearing a spilled vector doesn't move it back inline.
+    v.clear();
+    assert!(v.is_empty());
+    assert!(v.spilled());
+}
+
+#[test]
+fn with_capacity_in_spills_eagerly() {
+    let b = Bump::new();
+    let v: InlineVec<u8, 4> = InlineVec::with_capacity_in(4, &b);
+    assert!(!v.spilled());
+    let v: InlineVec<u8, 4> = InlineVec::with_capacity_in(5, &b);
+    assert!(v.spilled());
+}
+
+#[test]
+fn insert_and_remove() {
+    let b = Bump::new();
+    let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    v.insert(0, 2);
+    v.insert(0, 1);
+    v.insert(2, 4);
+    v.insert(2, 3);
+    assert_eq!(v, [1, 2, 3, 4]);
+    assert!(!v.spilled());
+
+    v.insert(2, 10);
+    assert!(v.spilled());
+    assert_eq!(v, [1, 2, 10, 3, 4]);
+
+    assert_eq!(v.remove(2), 10);
+    assert_eq!(v.remove(0), 1);
+    assert_eq!(v, [2, 3, 4]);
+
+    let mut w: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    w.extend([5, 6, 7]);
+    assert_eq!(w.remove(1), 6);
+    assert_eq!(w.pop(), Some(7));
+    assert_eq!(w.pop(), Some(5));
+    assert_eq!(w.pop(), None);
+}
+
+#[test]
+fn into_vec() {
+    let b = Bump::new();
+    let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    v.extend([1, 2]);
+    assert_eq!(v.into_vec(), [1, 2]);
+
+    let mut v: InlineVec<i32, 1> = InlineVec::new_in(&b);
+    v.extend([1, 2]);
+    assert_eq!(v.into_vec(), [1, 2]);
+}
+
+#[test]
+fn drops_each_element_once() {
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+
+    {
+        let mut v: InlineVec<Counted, 2> = InlineVec::new_in(&b);
+        v.push(Counted(&drops));
+        v.push(Counted(&drops));
+        v.truncate(1);
+        assert_eq!(drops.get(), 1);
+    }
+    assert_eq!(drops.get(), 2);
+
+    drops.set(0);
+    {
+        let mut v: InlineVec<Counted, 2> = InlineVec::new_in(&b);
+        for _ in 0..5 {
+            v.push(Counted(&drops));
+        }
+        assert!(v.spilled());
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 5);
+
+    drops.set(0);
+    {
+        let mut v: InlineVec<Counted, 4> = InlineVec::new_in(&b);
+        v.push(Counted(&drops));
+        v.push(Counted(&drops));
+        let v = v.into_vec();
+        assert_eq!(drops.get(), 0);
+        drop(v);
+    }
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn zero_sized_elements() {
+    let b = Bump::new();
+    let mut v: InlineVec<(), 2> = InlineVec::new_in(&b);
+    for _ in 0..10 {
+        v.push(());
+    }
+    assert_eq!(v.len(), 10);
+    assert_eq!(v.pop(), Some(()));
+}
+
+quickcheck! {
+    fn inline_vec_matches_std_vec(ops: Vec<(u8, i32)>) -> bool {
+        let b = Bump::new();
+        let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+        let mut expected = std::vec::Vec::new();
+
+        for (op, x) in ops {
+            match op % 5 {
+                0 | 1 => {
+                    v.push(x);
+                    expected.push(x);
+                }
+                2 => {
+                    if v.pop() != expected.pop() {
+                        return false;
+                    }
+                }
+                3 => {
+                    let i = x.unsigned_abs() as usize % (expected.len() + 1);
+                    v.insert(i, x);
+                    expected.insert(i, x);
+                }
+                _ => {
+                    if !expected.is_empty() {
+                        let i = x.unsigned_abs() as usize % expected.len();
+                        if v.remove(i) != expected.remove(i) {
+                            return false;
+                        }
+                    }
+                }
+            }
+            if v[..] != expected[..] {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[test]
+fn into_iter_inline_and_spilled() {
+    let b = Bump::new();
+
+    let mut v: InlineVec<String, 4> = InlineVec::new_in(&b);
+    v.extend(["a", "b", "c"].map(String::from));
+    let mut iter = v.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back().as_deref(), Some("c"));
+    assert_eq!(iter.as_slice(), ["a", "b"]);
+    assert_eq!(iter.collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(b.allocated_bytes(), 0);
+
+    let mut v: InlineVec<String, 1> = InlineVec::new_in(&b);
+    v.extend(["x", "y", "z"].map(String::from));
+    assert!(v.spilled());
+    assert_eq!(v.into_iter().rev().collect::<Vec<_>>(), ["z", "y", "x"]);
+}
+
+#[test]
+fn into_iter_drops_remaining_elements() {
+    let rc = std::rc::Rc::new(());
+    let b = Bump::new();
+
+    let mut v: InlineVec<_, 4> = InlineVec::new_in(&b);
+    for _ in 0..3 {
+        v.push(rc.clone());
+    }
+    let mut iter = v.into_iter();
+    drop(iter.next());
+    assert_eq!(std::rc::Rc::strong_count(&rc), 3);
+    drop(iter);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn swap_remove_retain_extend_from_slice() {
+    let b = Bump::new();
+
+    let mut v: InlineVec<i32, 4> = InlineVec::new_in(&b);
+    v.extend_from_slice(&[1, 2, 3, 4]);
+    assert!(!v.spilled());
+    assert_eq!(v.swap_remove(0), 1);
+    assert_eq!(v, [4, 2, 3]);
+
+    v.extend_from_slice(&[5, 6, 7]);
+    assert!(v.spilled());
+    v.retain(|&x| x % 2 == 1);
+    assert_eq!(v, [3, 5, 7]);
+
+    let mut inline: InlineVec<i32, 8> = InlineVec::new_in(&b);
+    inline.extend(0..8);
+    inline.retain(|&x| x >= 6);
+    assert_eq!(inline, [6, 7]);
+}
+
+#[test]
+fn collect_in_and_into_bump_slice() {
+    use bumpalo::collections::CollectIn;
+
+    let b = Bump::new();
+    let v: InlineVec<u8, 4> = (1..=3).collect_in(&b);
+    assert!(!v.spilled());
+
+    let w: InlineVec<u8, 4> = (1..=3).collect_in(&b);
+    assert!(v <= w && v == w);
+    assert_eq!(v.into_bump_slice(), [1, 2, 3]);
+}
@@ -0,0 +1,135 @@
+use bumpalo::{BackingAllocator, Bump, Global, ResetPolicy};
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::thread;
+
+/// A backing allocator that keeps count of the live chunks, and reads the
+/// `watched` bytes, given by address and length, when their chunk is
+/// deallocated.
+#[derive(Default)]
+struct Watching {
+    live: Cell<usize>,
+    watched: Cell<Option<(usize, usize)>>,
+    seen: Cell<Option<Vec<u8>>>,
+}
+
+unsafe impl BackingAllocator for Watching {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.live.set(self.live.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        if let Some((address, len)) = self.watched.get() {
+            // Read through the chunk's pointer, which the arena derived every
+            // allocation from.
+            if let Some(offset) = address.checked_sub(ptr.as_ptr() as usize) {
+                if offset < layout.size() {
+                    let p = ptr.as_ptr().add(offset);
+                    self.seen
+                        .set(Some(std::slice::from_raw_parts(p, len).to_vec()));
+                }
+            }
+        }
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn into_bytes_chunks_empty_arena() {
+    let bump = Bump::new();
+    assert!(bump.into_bytes_chunks().is_empty());
+}
+
+#[test]
+fn into_bytes_chunks_matches_iter_allocated_chunks() {
+    let mut bump = Bump::new();
+    for i in 0..10_000_u64 {
+        bump.alloc(i);
+    }
+
+    let expected: Vec<Vec<u8>> = bump
+        .iter_allocated_chunks()
+        .map(|ch| ch.iter().map(|b| unsafe { b.assume_init() }).collect())
+        .collect();
+    assert!(expected.len() > 1);
+
+    let chunks = bump.into_bytes_chunks();
+    assert_eq!(chunks.len(), expected.len());
+    for (chunk, expected) in chunks.iter().zip(&expected) {
+        assert_eq!(chunk.len(), expected.len());
+        assert_eq!(unsafe { chunk.as_bytes() }, &expected[..]);
+    }
+}
+
+#[test]
+fn into_bytes_chunks_can_be_sent_to_other_threads() {
+    let bump = Bump::new();
+    bump.alloc_str("hello");
+    let chunks = bump.into_bytes_chunks();
+
+    let bytes = thread::spawn(move || {
+        chunks
+            .iter()
+            .flat_map(|ch| unsafe { ch.as_bytes() }.to_vec())
+            .collect::<Vec<u8>>()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(bytes, b"hello");
+}
+
+#[test]
+fn into_bytes_chunks_returns_chunks_to_the_backing_allocator() {
+    let watching = Watching::default();
+    let bump = Bump::new_in(&watching);
+    for i in 0..10_000_u64 {
+        bump.alloc(i);
+    }
+    let chunks = bump.into_bytes_chunks();
+    assert!(chunks.len() > 1);
+    assert_eq!(watching.live.get(), chunks.len());
+    drop(chunks);
+    assert_eq!(watching.live.get(), 0);
+}
+
+#[test]
+fn into_bytes_chunks_honors_zero_reset_policy() {
+    for policy in [ResetPolicy::Leave, ResetPolicy::Zero] {
+        let watching = Watching::default();
+        let bump = Bump::new_in(&watching);
+        bump.set_reset_policy(policy);
+        let front = bump.alloc_front(*b"front");
+        watching.watched.set(Some((front.as_ptr() as usize, 5)));
+        bump.alloc(*b"back");
+
+        let chunks = bump.into_bytes_chunks();
+        drop(chunks);
+        let seen = watching.seen.take().unwrap();
+        if policy == ResetPolicy::Zero {
+            assert_eq!(seen, [0; 5]);
+        } else {
+            assert_eq!(seen, *b"front");
+        }
+    }
+}
+
+#[test]
+fn into_bytes_chunks_includes_front_allocations() {
+    let bump = Bump::new();
+    bump.alloc_front(*b"front");
+    bump.alloc_str("back");
+    bump.alloc_front(*b"!");
+
+    let chunks = bump.into_bytes_chunks();
+    assert_eq!(chunks.len(), 1);
+    let front: Vec<u8> = chunks[0]
+        .front()
+        .iter()
+        .map(|b| unsafe { b.assume_init() })
+        .collect();
+    assert_eq!(front, b"front!");
+    assert_eq!(unsafe { chunks[0].as_bytes() }, b"back");
+}
@@ -0,0 +1,61 @@
+use bumpalo::Bump;
+
+#[test]
+fn empty_arena() {
+    let mut bump = Bump::new();
+    assert_eq!(unsafe { bump.iter_allocated_items::<u32>() }.count(), 0);
+}
+
+#[test]
+fn items_across_chunks_most_recent_first() {
+    let mut bump = Bump::with_capacity(64);
+    for i in 0..1000_u32 {
+        bump.alloc(i);
+    }
+    assert!(unsafe { bump.iter_allocated_chunks_raw().count() } > 1);
+
+    let items: Vec<u32> = unsafe { bump.iter_allocated_items::<u32>() }
+        .copied()
+        .collect();
+    let expected: Vec<u32> = (0..1000).rev().collect();
+    assert_eq!(items, expected);
+}
+
+#[test]
+fn slices_are_flattened() {
+    let mut bump = Bump::new();
+    bump.alloc_slice_copy(&[1_u16, 2, 3]);
+    bump.alloc(4_u16);
+    let items: Vec<u16> = unsafe { bump.iter_allocated_items::<u16>() }
+        .copied()
+        .collect();
+    assert_eq!(items, [4, 1, 2, 3]);
+}
+
+#[test]
+fn front_allocations_are_skipped() {
+    let mut bump = Bump::new();
+    bump.alloc(1_u64);
+    bump.alloc_front(0xff_u8);
+    bump.alloc(2_u64);
+    let items: Vec<u64> = unsafe { bump.iter_allocated_items::<u64>() }
+        .copied()
+        .collect();
+    assert_eq!(items, [2, 1]);
+}
+
+#[test]
+#[should_panic(expected = "chunk is not a whole number of aligned values")]
+fn mismatched_size_panics() {
+    let mut bump = Bump::new();
+    bump.alloc(1_u32);
+    bump.alloc(2_u8);
+    let _ = unsafe { bump.iter_allocated_items::<u32>() };
+}
+
+#[test]
+#[should_panic(expected = "zero-sized types are not supported")]
+fn zero_sized_type_panics() {
+    let mut bump = Bump::new();
+    let _ = unsafe { bump.iter_allocated_items::<()>() };
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "examples-lib")]
+
+use bumpalo::examples::json::{self, Value, MAX_DEPTH};
+use bumpalo::Bump;
+
+const DOCUMENT: &str = r#"
+{
+    "name": "bumpalo",
+    "version": [3, 16, 0],
+    "tags": ["memory-management", "no-std"],
+    "escapes": "quote \" backslash \\ slash \/ tab \t newline \n unicode \u00e9 \ud83d\ude00",
+    "nested": {"z": null, "a": {"y": true, "x": false}, "m": -12.5e-1},
+    "dup": 1,
+    "dup": 2,
+    "empty": [{}, []]
+}
+"#;
+
+fn std_json(s: &str) -> serde_json::Value {
+    serde_json::from_str(s).unwrap()
+}
+
+#[test]
+fn pipeline_matches_serde_json() {
+    let bump = Bump::new();
+    let output = json::pipeline(&bump, DOCUMENT.as_bytes()).unwrap();
+
+    // `serde_json`'s default object representation is sorted and keeps the
+    // last value of duplicate keys, just like `sort_keys`.
+    assert_eq!(std_json(&output), std_json(DOCUMENT));
+    assert_eq!(
+        serde_json::to_string(&std_json(DOCUMENT)).unwrap(),
+        output.as_str()
+    );
+}
+
+#[test]
+fn pipeline_is_idempotent() {
+    let bump = Bump::new();
+    let once = json::pipeline(&bump, DOCUMENT.as_bytes()).unwrap();
+    let twice = json::pipeline(&bump, once.as_bytes()).unwrap();
+    assert_eq!(once, twice);
+}
+
+#[test]
+fn everything_is_allocated_in_the_arena() {
+    let mut bump = Bump::new();
+    for _ in 0..3 {
+        let value = json::parse(&bump, DOCUMENT.as_bytes()).unwrap();
+        let Value::Object(members) = value else {
+            panic!("expected an object");
+        };
+        assert_eq!(members.len(), 8);
+        let (key, _) = members[0];
+        let in_arena = unsafe {
+            bump.iter_allocated_chunks_raw().any(|(p, len)| {
+                let start = p as usize;
+                (start..start + len).contains(&(key.as_ptr() as usize))
+            })
+        };
+        assert!(in_arena);
+        bump.reset();
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let bump = Bump::new();
+    for (input, offset) in [
+        ("", 0),
+        ("[1, 2", 5),
+        ("[1,]", 3),
+        ("{\"a\" 1}", 5),
+        ("{1: 2}", 1),
+        ("tru", 0),
+        ("01", 1),
+        ("1.", 2),
+        ("\"abc", 4),
+        ("\"\\x\"", 2),
+        ("\"\\u12g4\"", 3),
+        ("\"\\ud800\"", 7),
+        ("[] []", 3),
+    ] {
+        let err = json::parse(&bump, input.as_bytes()).unwrap_err();
+        assert_eq!(err.offset(), offset, "input {:?}: {}", input, err);
+    }
+
+    let err = json::parse(&bump, b"\"\xff\"").unwrap_err();
+    assert_eq!(err.offset(), 1);
+}
+
+#[test]
+fn nesting_limit() {
+    let bump = Bump::new();
+
+    let ok = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+    assert!(json::parse(&bump, ok.as_bytes()).is_ok());
+
+    let too_deep = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+    assert!(json::parse(&bump, too_deep.as_bytes()).is_err());
+}
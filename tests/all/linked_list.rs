@@ -0,0 +1,211 @@
+#![cfg(feature = "collections")]
+
+use crate::quickcheck;
+use bumpalo::{
+    collections::{CollectIn, LinkedList},
+    Bump,
+};
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+#[test]
+fn push_and_pop_at_both_ends() {
+    let b = Bump::new();
+    let mut list = LinkedList::new_in(&b);
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&1));
+    assert_eq!(list.back(), Some(&3));
+    assert_eq!(list, [1, 2, 3]);
+
+    *list.front_mut().unwrap() = 10;
+    *list.back_mut().unwrap() = 30;
+    assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [30, 2, 10]);
+
+    assert_eq!(list.pop_front(), Some(10));
+    assert_eq!(list.pop_back(), Some(30));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn popping_the_last_node_reuses_its_memory() {
+    let b = Bump::new();
+    let mut list = LinkedList::new_in(&b);
+    list.push_back(1_u64);
+    let capacity = b.chunk_capacity();
+    for _ in 0..100 {
+        list.push_back(2);
+        list.pop_back();
+    }
+    assert_eq!(b.chunk_capacity(), capacity);
+}
+
+#[test]
+fn append_and_split_off() {
+    let b = Bump::new();
+    let mut list: LinkedList<i32> = (0..3).collect_in(&b);
+    let mut other: LinkedList<i32> = (3..6).collect_in(&b);
+    list.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(list, [0, 1, 2, 3, 4, 5]);
+
+    for at in 0..=6 {
+        let mut l = list.clone();
+        let tail = l.split_off(at);
+        assert_eq!(l.len(), at);
+        assert_eq!(tail.len(), 6 - at);
+        l.extend(&tail);
+        assert_eq!(l, list);
+    }
+}
+
+#[test]
+fn cursor_walks_through_the_ghost() {
+    let b = Bump::new();
+    let list = LinkedList::from_iter_in([1, 2], &b);
+    let mut cursor = list.cursor_front();
+    assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+    cursor.move_next();
+    assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&2)));
+    cursor.move_next();
+    assert_eq!((cursor.index(), cursor.current()), (None, None));
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&2));
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&1));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    cursor.move_prev();
+    assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&2)));
+}
+
+#[test]
+fn cursor_insert_remove_and_splice() {
+    let b = Bump::new();
+    let mut list = LinkedList::from_iter_in([1, 5], &b);
+
+    let mut cursor = list.cursor_front_mut();
+    cursor.insert_after(4);
+    cursor.splice_after(LinkedList::from_iter_in([2, 3], &b));
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_prev();
+    cursor.insert_after(0);
+    cursor.insert_before(6);
+    assert_eq!(cursor.index(), None);
+    assert_eq!(list, [0, 1, 2, 3, 4, 5, 6]);
+
+    let mut cursor = list.cursor_back_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some(5));
+    assert_eq!(cursor.current(), Some(&mut 6));
+    assert_eq!(cursor.index(), Some(5));
+    cursor.splice_before(LinkedList::from_iter_in([7, 8], &b));
+    assert_eq!(cursor.index(), Some(7));
+    assert_eq!(list, [0, 1, 2, 3, 4, 7, 8, 6]);
+}
+
+#[test]
+fn cursor_split() {
+    let b = Bump::new();
+    let mut list = LinkedList::from_iter_in(0..6, &b);
+
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    cursor.move_next();
+    let after = cursor.split_after();
+    assert_eq!(after, [3, 4, 5]);
+    assert_eq!(cursor.index(), Some(2));
+    let before = cursor.split_before();
+    assert_eq!(before, [0, 1]);
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(list, [2]);
+
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    assert_eq!(cursor.split_before(), [2]);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn drops_every_element() {
+    struct Count<'a>(&'a Cell<usize>);
+    impl Drop for Count<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let mut list = LinkedList::new_in(&b);
+    for _ in 0..10 {
+        list.push_back(Count(&drops));
+    }
+    drop(list.pop_front());
+    assert_eq!(drops.get(), 1);
+
+    let mut iter = list.into_iter();
+    drop(iter.next_back());
+    assert_eq!(drops.get(), 2);
+    drop(iter);
+    assert_eq!(drops.get(), 10);
+}
+
+quickcheck! {
+    fn linked_list_matches_vec_deque(ops: Vec<(u8, i32)>) -> bool {
+        let b = Bump::new();
+        let mut list = LinkedList::new_in(&b);
+        let mut expected = VecDeque::new();
+
+        for (op, x) in ops {
+            match op % 6 {
+                0 => {
+                    list.push_back(x);
+                    expected.push_back(x);
+                }
+                1 => {
+                    list.push_front(x);
+                    expected.push_front(x);
+                }
+                2 => {
+                    if list.pop_back() != expected.pop_back() {
+                        return false;
+                    }
+                }
+                3 => {
+                    if list.pop_front() != expected.pop_front() {
+                        return false;
+                    }
+                }
+                4 => {
+                    let at = x.unsigned_abs() as usize % (expected.len() + 1);
+                    let mut cursor = list.cursor_front_mut();
+                    for _ in 0..at {
+                        cursor.move_next();
+                    }
+                    cursor.insert_before(x);
+                    expected.insert(at, x);
+                }
+                _ => {
+                    if expected.is_empty() {
+                        continue;
+                    }
+                    let at = x.unsigned_abs() as usize % expected.len();
+                    let mut cursor = list.cursor_back_mut();
+                    for _ in 0..expected.len() - 1 - at {
+                        cursor.move_prev();
+                    }
+                    if cursor.remove_current() != expected.remove(at) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        list.len() == expected.len() && list.iter().eq(expected.iter())
+    }
+}
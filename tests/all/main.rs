@@ -1,21 +1,78 @@
 #![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+// Lints that newer toolchains report in long-standing tests.
+#![allow(
+    clippy::approx_constant,
+    clippy::large_enum_variant,
+    clippy::legacy_numeric_constants,
+    clippy::match_like_matches_macro
+)]
 
 mod alloc_fill;
+mod alloc_from_reader;
+mod alloc_front;
+mod alloc_header_with_slice;
+mod alloc_init;
 mod alloc_try_with;
 mod alloc_with;
+mod alloc_zeroed;
 mod allocation_limit;
 mod allocator_api;
+mod allocator_size_classes;
+mod arbitrary;
+mod asan;
+mod backing_allocator;
+mod binary_heap;
+mod bit_vec;
 mod boxed;
+mod bump_builder;
+mod bump_set;
+mod bytemuck;
 mod capacity;
+mod clone_from_bump;
 mod collect_in;
+mod contains_ptr;
+mod copy_allocated_bytes;
+mod cow;
+mod debug_poison;
+mod dyn_vec;
+mod fixed_buffer;
+mod frozen;
+mod graph;
+mod inline_string;
+mod inline_vec;
+mod into_bytes_chunks;
+mod iter_allocated_items;
+mod json_example;
+mod linked_list;
+mod offset_tracking;
+mod par_collect_in;
+mod pool;
 mod quickcheck;
 mod quickchecks;
+mod relative;
+mod reset_policy;
+mod scratch;
+mod shared;
+mod slot_map;
+mod split;
 mod string;
+mod sync;
 mod tests;
+mod thread_local;
+mod trace;
 mod try_alloc_try_with;
 mod try_alloc_with;
+mod typed;
+mod uninit;
 mod vec;
+mod with_align;
+mod writer;
+mod zst;
 
+#[cfg(feature = "std")]
+mod global;
+#[cfg(all(feature = "mmap", unix))]
+mod mmap;
 #[cfg(feature = "serde")]
 mod serde;
 
@@ -0,0 +1,79 @@
+use bumpalo::mmap::MmapAllocator;
+use bumpalo::{BackingAllocator, Bump};
+use std::alloc::Layout;
+use std::slice;
+
+#[test]
+fn chunks_end_at_guard_page() {
+    let page = MmapAllocator::page_size();
+    for (size, align) in [(1, 1), (100, 8), (4096, 16), (10_000, 64), (page, page)] {
+        let layout = Layout::from_size_align(size, align).unwrap();
+        let p = MmapAllocator.allocate(layout).unwrap();
+        let start = p.as_ptr() as usize;
+        let end = start + size;
+        assert_eq!(start % align, 0);
+
+        let guard = (end + page - 1) & !(page - 1);
+        assert!(guard - end < align, "{} {} {:#x}", size, align, start);
+
+        unsafe {
+            p.as_ptr().write_bytes(0xff, size);
+            MmapAllocator.deallocate(p, layout);
+        }
+    }
+}
+
+#[test]
+fn too_large_alignment() {
+    let page = MmapAllocator::page_size();
+    let layout = Layout::from_size_align(1, page * 2).unwrap();
+    assert!(MmapAllocator.allocate(layout).is_none());
+}
+
+#[test]
+fn allocate_zeroed() {
+    let layout = Layout::from_size_align(10_000, 16).unwrap();
+    let p = MmapAllocator.allocate_zeroed(layout).unwrap();
+    unsafe {
+        assert!(slice::from_raw_parts(p.as_ptr(), 10_000)
+            .iter()
+            .all(|&b| b == 0));
+        MmapAllocator.deallocate(p, layout);
+    }
+}
+
+#[test]
+fn discard_releases_whole_pages() {
+    let page = MmapAllocator::page_size();
+    let layout = Layout::from_size_align(4 * page, page).unwrap();
+    let p = MmapAllocator.allocate(layout).unwrap();
+    unsafe {
+        let bytes = slice::from_raw_parts_mut(p.as_ptr(), 4 * page);
+        bytes.fill(1);
+
+        // Only the two pages fully within the region are discarded.
+        MmapAllocator.discard(p.add(page / 2), 3 * page);
+        let bytes = slice::from_raw_parts(p.as_ptr(), 4 * page);
+        assert!(bytes[..page].iter().all(|&b| b == 1));
+        assert!(bytes[page..3 * page].iter().all(|&b| b == 0));
+        assert!(bytes[3 * page..].iter().all(|&b| b == 1));
+
+        MmapAllocator.deallocate(p, layout);
+    }
+}
+
+#[test]
+fn bump_with_mmap_chunks() {
+    let mut bump = Bump::with_capacity_in(1 << 16, MmapAllocator);
+    let capacity = bump.chunk_capacity();
+    for round in 0..3_u64 {
+        let xs = bump.alloc_slice_fill_with(10_000, |i| i as u64 + round);
+        assert_eq!(xs[9_999], 9_999 + round);
+        bump.reset();
+        assert!(bump.chunk_capacity() >= capacity);
+
+        let zeroed = bump.alloc_slice_fill_copy(1000, 0_u8);
+        assert!(zeroed.iter().all(|&b| b == 0));
+        bump.reset();
+    }
+}
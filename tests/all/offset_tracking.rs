@@ -0,0 +1,69 @@
+use bumpalo::{AllocErr, AllocErrKind, Bump};
+use std::alloc::Layout;
+
+#[test]
+fn offsets_resolve_across_chunks() {
+    let bump = Bump::new();
+    let mut allocs = vec![];
+    for i in 0..10_000_u32 {
+        let (p, offset) = bump.alloc_layout_with_offset_tracking(Layout::new::<u32>());
+        unsafe { p.cast::<u32>().as_ptr().write(i) };
+        allocs.push((p, offset));
+    }
+    assert!(bump.iter_chunk_usage().count() > 1);
+
+    for (i, &(p, offset)) in allocs.iter().enumerate() {
+        let q = bump.resolve_offset(offset).unwrap();
+        assert_eq!(p, q);
+        assert_eq!(unsafe { *q.cast::<u32>().as_ptr() }, i as u32);
+    }
+}
+
+#[test]
+fn offsets_are_distinct() {
+    let bump = Bump::new();
+    let mut offsets = vec![];
+    for size in [0, 1, 3, 8, 100, 5000] {
+        let layout = Layout::from_size_align(size, 8).unwrap();
+        offsets.push(bump.alloc_layout_with_offset_tracking(layout).1);
+    }
+    let mut sorted = offsets.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted.len(), offsets.len());
+}
+
+#[test]
+fn offsets_past_the_end_do_not_resolve() {
+    let bump = Bump::new();
+    assert_eq!(bump.resolve_offset(0), None);
+
+    bump.alloc_layout_with_offset_tracking(Layout::new::<u8>());
+    assert_eq!(bump.resolve_offset(u32::MAX), None);
+    let end = u32::try_from(bump.allocated_bytes()).unwrap();
+    assert_eq!(bump.resolve_offset(end), None);
+    assert!(bump.resolve_offset(end - 1).is_some());
+}
+
+#[test]
+fn offsets_include_front_allocations() {
+    let bump = Bump::new();
+    let (p, offset) = bump.alloc_layout_with_offset_tracking(Layout::new::<u16>());
+    let front = bump.alloc_front(7_u16);
+    let (q, front_offset) = bump.alloc_layout_with_offset_tracking(Layout::new::<u16>());
+
+    assert_eq!(bump.resolve_offset(offset), Some(p));
+    assert_eq!(bump.resolve_offset(front_offset), Some(q));
+    assert!(front_offset < offset);
+    assert_eq!(*front, 7);
+}
+
+#[test]
+fn offset_tracking_respects_the_allocation_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    assert_eq!(
+        bump.try_alloc_layout_with_offset_tracking(Layout::new::<u64>()),
+        Err(AllocErr::from(AllocErrKind::LimitExceeded))
+    );
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "rayon")]
+
+use bumpalo::collections::{LinkedList, ParallelCollectIn, String, Vec};
+use bumpalo::Bump;
+use rayon::prelude::*;
+
+#[test]
+fn preserves_order() {
+    let bump = Bump::new();
+    let v: Vec<usize> = (0..100_000)
+        .into_par_iter()
+        .map(|x| x * 2)
+        .collect_in(&bump);
+    assert_eq!(v.len(), 100_000);
+    assert!(v.iter().enumerate().all(|(i, &x)| x == i * 2));
+}
+
+#[test]
+fn allocates_the_vec_once() {
+    let bump = Bump::new();
+    let v: Vec<u32> = (0..10_000_u32).into_par_iter().collect_in(&bump);
+    assert_eq!(v.capacity(), v.len());
+}
+
+#[test]
+fn unindexed_iterators() {
+    let bump = Bump::new();
+    let v: Vec<u32> = (0..1000_u32)
+        .into_par_iter()
+        .filter(|x| x % 3 == 0)
+        .collect_in(&bump);
+    let expected: std::vec::Vec<u32> = (0..1000).filter(|x| x % 3 == 0).collect();
+    assert_eq!(v, &expected[..]);
+}
+
+#[test]
+fn other_collections() {
+    let bump = Bump::new();
+
+    let s: String = "hello, world"
+        .par_chars()
+        .map(|c| c.to_ascii_uppercase())
+        .collect_in(&bump);
+    assert_eq!(s, "HELLO, WORLD");
+
+    let list: LinkedList<i32> = vec![1, 2, 3].into_par_iter().collect_in(&bump);
+    assert_eq!(list.into_iter().collect::<std::vec::Vec<_>>(), [1, 2, 3]);
+
+    let ok: Result<Vec<i32>, &str> = vec![Ok(1), Ok(2)].into_par_iter().collect_in(&bump);
+    assert_eq!(ok.unwrap(), [1, 2]);
+}
+
+#[test]
+fn empty() {
+    let bump = Bump::new();
+    let v: Vec<u8> = rayon::iter::empty().collect_in(&bump);
+    assert!(v.is_empty());
+}
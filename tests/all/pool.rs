@@ -0,0 +1,93 @@
+use bumpalo::pool::{ObjectPool, Pooled};
+use bumpalo::Bump;
+use std::cell::Cell;
+
+#[test]
+fn reuses_freed_slots() {
+    let bump = Bump::new();
+    let pool = ObjectPool::new_in(&bump);
+    assert!(pool.is_empty());
+
+    let a = pool.alloc(1_u64);
+    let b = pool.alloc(2_u64);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.capacity(), 2);
+    let a_addr = &*a as *const u64;
+    drop(a);
+    assert_eq!(pool.len(), 1);
+
+    let c = pool.alloc(3_u64);
+    assert_eq!(&*c as *const u64, a_addr);
+    assert_eq!((*b, *c), (2, 3));
+    assert_eq!(pool.capacity(), 2);
+
+    let used = bump.allocated_bytes();
+    for i in 0..10_000 {
+        let mut x = pool.alloc(i);
+        *x += 1;
+        assert_eq!(*x, i + 1);
+    }
+    assert_eq!(bump.allocated_bytes(), used);
+    assert_eq!(pool.capacity(), 3);
+}
+
+#[test]
+fn with_capacity_and_reserve() {
+    let bump = Bump::new();
+    let pool = ObjectPool::with_capacity_in(4, &bump);
+    assert_eq!(pool.capacity(), 4);
+    let held: Vec<_> = (0..4).map(|i| pool.alloc(i)).collect();
+    assert_eq!(pool.capacity(), 4);
+
+    pool.reserve(2);
+    assert_eq!(pool.capacity(), 6);
+    pool.reserve(1);
+    assert_eq!(pool.capacity(), 6);
+    drop(held);
+    assert!(pool.is_empty());
+}
+
+struct CountDrops<'a>(&'a Cell<usize>);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn drops_values() {
+    let drops = Cell::new(0);
+    let bump = Bump::new();
+    let pool = ObjectPool::new_in(&bump);
+    let a = pool.alloc(CountDrops(&drops));
+    let b = pool.alloc(CountDrops(&drops));
+    drop(a);
+    assert_eq!(drops.get(), 1);
+
+    let b = Pooled::into_inner(b);
+    assert_eq!(drops.get(), 1);
+    assert!(pool.is_empty());
+    drop(b);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn try_alloc_respects_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    let pool = ObjectPool::new_in(&bump);
+    assert!(pool.try_alloc([0_u8; 64]).is_err());
+    assert_eq!(pool.capacity(), 0);
+}
+
+#[test]
+fn zero_sized() {
+    let bump = Bump::new();
+    let pool = ObjectPool::new_in(&bump);
+    let a = pool.alloc(());
+    let b = pool.alloc(());
+    assert_eq!(pool.len(), 2);
+    drop(b);
+    assert_eq!(format!("{:?}", a), "()");
+}
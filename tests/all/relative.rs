@@ -0,0 +1,129 @@
+use bumpalo::relative::{BumpOffset, RelativeBump};
+use std::mem;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Node {
+    value: u64,
+    left: Option<BumpOffset<Node>>,
+    right: Option<BumpOffset<Node>>,
+}
+
+fn insert(
+    arena: &mut RelativeBump,
+    root: Option<BumpOffset<Node>>,
+    value: u64,
+) -> BumpOffset<Node> {
+    let Some(root) = root else {
+        return arena.alloc_rel(Node {
+            value,
+            left: None,
+            right: None,
+        });
+    };
+    let node = unsafe { *arena.get(root) };
+    if value < node.value {
+        let left = insert(arena, node.left, value);
+        unsafe { arena.get_mut(root).left = Some(left) };
+    } else {
+        let right = insert(arena, node.right, value);
+        unsafe { arena.get_mut(root).right = Some(right) };
+    }
+    root
+}
+
+fn in_order(
+    resolve: &dyn Fn(BumpOffset<Node>) -> Node,
+    root: Option<BumpOffset<Node>>,
+    out: &mut Vec<u64>,
+) {
+    if let Some(root) = root {
+        let node = resolve(root);
+        in_order(resolve, node.left, out);
+        out.push(node.value);
+        in_order(resolve, node.right, out);
+    }
+}
+
+#[test]
+fn offsets_are_four_bytes() {
+    assert_eq!(mem::size_of::<BumpOffset<Node>>(), 4);
+    assert_eq!(mem::size_of::<Option<BumpOffset<Node>>>(), 8);
+}
+
+#[test]
+fn tree_survives_relocation() {
+    let mut arena = RelativeBump::with_capacity(4096);
+    let mut root = None;
+    for value in [50, 20, 80, 10, 30, 70, 90, 60] {
+        root = Some(insert(&mut arena, root, value));
+    }
+
+    let mut expected = Vec::new();
+    in_order(&|n| unsafe { *arena.get(n) }, root, &mut expected);
+    assert_eq!(expected, [10, 20, 30, 50, 60, 70, 80, 90]);
+
+    // `Node` has no padding, so all used bytes are initialized. Copy them to
+    // the end of a buffer that is aligned for `Node`.
+    let used = unsafe { arena.used_bytes() }.to_vec();
+    let mut storage = vec![0_u64; 4096 / 8];
+    let copy: &mut [u8] =
+        unsafe { std::slice::from_raw_parts_mut(storage.as_mut_ptr().cast(), 4096) };
+    let start = copy.len() - used.len();
+    copy[start..].copy_from_slice(&used);
+    drop(arena);
+
+    let mut relocated = Vec::new();
+    in_order(&|n| unsafe { *n.get_in(copy) }, root, &mut relocated);
+    assert_eq!(relocated, expected);
+}
+
+#[test]
+fn never_allocates_a_second_chunk() {
+    let arena = RelativeBump::with_capacity(64);
+    let capacity = arena.capacity();
+    assert!(capacity >= 64);
+
+    let mut n = 0;
+    while arena.try_alloc_rel(n as u8).is_ok() {
+        n += 1;
+    }
+    assert_eq!(n, capacity);
+    assert_eq!(arena.used_len(), capacity);
+    assert!(arena.try_alloc_rel(0_u64).is_err());
+}
+
+#[test]
+fn reset_reuses_the_chunk() {
+    let mut arena = RelativeBump::with_capacity(64);
+    let a = arena.alloc_rel(1_u32);
+    arena.reset();
+    assert_eq!(arena.used_len(), 0);
+    let b = arena.alloc_rel(2_u32);
+    assert_eq!(a, b);
+    assert_eq!(unsafe { *arena.get(b) }, 2);
+}
+
+#[test]
+fn zero_sized_values() {
+    let arena = RelativeBump::with_capacity(16);
+    let unit = arena.alloc_rel(());
+    unsafe { arena.get(unit) };
+}
+
+#[test]
+#[should_panic(expected = "offset out of bounds")]
+fn out_of_bounds_offset() {
+    let arena = RelativeBump::with_capacity(16);
+    arena.alloc_rel(1_u8);
+    unsafe {
+        arena.get(BumpOffset::<u64>::from_raw(1000));
+    }
+}
+
+#[test]
+fn too_large_capacity() {
+    if mem::size_of::<usize>() > 4 {
+        assert!(RelativeBump::try_with_capacity(u32::MAX as usize + 1).is_err());
+    }
+}
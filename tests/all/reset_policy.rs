@@ -0,0 +1,82 @@
+// `debug_poison` overwrites the bytes that these tests look at.
+#![cfg(not(feature = "debug_poison"))]
+
+use bumpalo::{Bump, Detached, ResetPolicy};
+use std::alloc::Layout;
+use std::slice;
+
+fn alloc_bytes(bump: &Bump, len: usize) -> &[u8] {
+    let p = bump.alloc_layout(Layout::from_size_align(len, 1).unwrap());
+    // The arena's memory was written before, so it is initialized.
+    unsafe { slice::from_raw_parts(p.as_ptr(), len) }
+}
+
+#[test]
+fn default_is_leave() {
+    let bump = Bump::new();
+    assert_eq!(bump.reset_policy(), ResetPolicy::Leave);
+    let bump = Bump::builder().reset_policy(ResetPolicy::Zero).build();
+    assert_eq!(bump.reset_policy(), ResetPolicy::Zero);
+}
+
+#[test]
+fn reset_zeroes_used_bytes() {
+    let mut bump = Bump::with_capacity(4096);
+    bump.set_reset_policy(ResetPolicy::Zero);
+    bump.alloc_slice_fill_copy(1000, 0xaa_u8);
+    bump.alloc_front(0xbbbb_u16);
+    bump.alloc_slice_fill_copy(999, 0xcc_u8);
+    let capacity = bump.chunk_capacity() + 2 + 1999;
+
+    bump.reset();
+    assert_eq!(bump.chunk_capacity(), capacity);
+    // Bytes that were never allocated are left alone, so only check the
+    // front and back allocations.
+    let bytes = alloc_bytes(&bump, capacity);
+    assert_eq!(bytes[..2], [0, 0]);
+    assert!(bytes[capacity - 1999..].iter().all(|&b| b == 0));
+}
+
+#[test]
+fn reset_zeroes_all_chunks() {
+    let mut bump = Bump::builder()
+        .reset_policy(ResetPolicy::Zero)
+        .defer_chunk_frees(true)
+        .build();
+    for _ in 0..100 {
+        bump.alloc_slice_fill_copy(1000, 0xaa_u8);
+    }
+    bump.reset();
+    assert!(bump.spare_capacity() > 0);
+
+    // Fill the kept chunk, so that the rest comes from the spares.
+    let capacity = bump.chunk_capacity();
+    bump.alloc_slice_fill_copy(capacity, 1_u8);
+    for _ in 0..50 {
+        assert!(alloc_bytes(&bump, 1000).iter().all(|&b| b == 0));
+    }
+}
+
+#[test]
+fn detach_keeps_result_and_zeroes_the_rest() {
+    let mut bump = Bump::with_capacity(4096);
+    bump.set_reset_policy(ResetPolicy::Zero);
+    let result = bump.detach_last_allocation(|bump| {
+        bump.alloc_slice_fill_copy(1000, 0xaa_u8);
+        bump.alloc([0xcc_u8; 16])
+    });
+    assert_eq!(*result, [0xcc; 16]);
+    let result = Detached::leak(result).as_ptr();
+
+    let bytes = alloc_bytes(&bump, 1000);
+    assert!(bytes.iter().all(|&b| b == 0));
+    assert_eq!(unsafe { *(result as *const [u8; 16]) }, [0xcc; 16]);
+}
+
+#[test]
+fn leave_keeps_bytes() {
+    let mut bump = Bump::with_capacity(4096);
+    bump.alloc_slice_fill_copy(16, 0xaa_u8);
+    bump.reset();
+    assert_eq!(alloc_bytes(&bump, 16), [0xaa; 16]);
+}
@@ -0,0 +1,64 @@
+use bumpalo::scratch::ScratchPair;
+use bumpalo::Bump;
+
+#[test]
+fn swap_and_reset_alternates_arenas() {
+    let mut pair = ScratchPair::new();
+    let first = pair.current() as *const Bump;
+    let second = pair.previous() as *const Bump;
+    assert_ne!(first, second);
+
+    pair.current().alloc([1_u64; 8]);
+    pair.swap_and_reset();
+    assert_eq!(pair.current() as *const Bump, second);
+    assert_eq!(pair.previous() as *const Bump, first);
+    assert_eq!(pair.previous().allocated_bytes_in_current_chunk(), 64);
+
+    pair.swap_and_reset();
+    assert_eq!(pair.current() as *const Bump, first);
+    assert_eq!(pair.current().allocated_bytes_in_current_chunk(), 0);
+}
+
+#[test]
+fn both_mut_and_into_bumps_follow_the_current_arena() {
+    let mut pair = ScratchPair::from_bumps(Bump::new(), Bump::new());
+    pair.current().alloc_str("current");
+    pair.previous().alloc_str("prev");
+    pair.swap_and_reset();
+    pair.current().alloc_str("new");
+
+    let (current, previous) = pair.both_mut();
+    assert_eq!(current.allocated_bytes_in_current_chunk(), 3);
+    assert_eq!(previous.allocated_bytes_in_current_chunk(), 7);
+
+    let (current, previous) = pair.into_bumps();
+    assert_eq!(current.allocated_bytes_in_current_chunk(), 3);
+    assert_eq!(previous.allocated_bytes_in_current_chunk(), 7);
+}
+
+#[test]
+fn iterate_reuses_memory() {
+    let mut pair = ScratchPair::with_capacity(1 << 16);
+    let allocated = pair.allocated_bytes();
+
+    let init = [0_u32; 1000];
+    let last = pair.iterate(&init[..], 1000, |prev, bump| {
+        bump.alloc_slice_fill_with(prev.len(), |i| prev[i] + i as u32)
+    });
+    assert!(last.iter().enumerate().all(|(i, x)| *x == 1000 * i as u32));
+
+    // Every iteration fit into the arenas' initial chunks.
+    assert_eq!(pair.allocated_bytes(), allocated);
+}
+
+#[test]
+fn iterate_zero_times_returns_init() {
+    let mut pair = ScratchPair::new();
+    let last = pair.iterate("init", 0, |_, bump| bump.alloc_str("next"));
+    assert_eq!(last, "init");
+
+    let last = pair.iterate("init", 2, |prev, bump| {
+        bump.alloc_str(&format!("{}!", prev))
+    });
+    assert_eq!(last, "init!!");
+}
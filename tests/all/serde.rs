@@ -1,7 +1,10 @@
 #![cfg(feature = "collections")]
 #![cfg(feature = "serde")]
 
+use bumpalo::collections::{String as BumpString, Vec as BumpVec};
+use bumpalo::serde::{DeserializeIn, DeserializeInSeed};
 use bumpalo::{boxed::Box, vec, Bump};
+use serde::de::DeserializeSeed;
 use serde::{Deserialize, Serialize};
 
 macro_rules! compare_std_vec {
@@ -117,3 +120,104 @@ fn test_box_serializes_complex() {
         serde_json::from_str(&serde_json::to_string(&vec).unwrap()).unwrap();
     assert_eq!(de, std_vec);
 }
+
+#[test]
+fn test_string_serializes() {
+    let bump = Bump::new();
+    let s = BumpString::from_str_in("hello", &bump);
+    let std_s = "hello".to_string();
+    assert_eq_json!(s, std_s);
+}
+
+#[test]
+fn test_vec_deserialize_in() {
+    let bump = Bump::new();
+    let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+    let vec = BumpVec::<u32>::deserialize_in(&mut de, &bump).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+#[test]
+fn test_string_deserialize_in() {
+    let bump = Bump::new();
+    let mut de = serde_json::Deserializer::from_str(r#""hello \"world\"""#);
+    let s = BumpString::deserialize_in(&mut de, &bump).unwrap();
+    assert_eq!(s, "hello \"world\"");
+
+    let mut de = serde_json::Deserializer::from_str("42");
+    assert!(BumpString::deserialize_in(&mut de, &bump).is_err());
+}
+
+#[test]
+fn test_box_deserialize_in() {
+    let bump = Bump::new();
+    let mut de =
+        serde_json::Deserializer::from_str(r#"{"i":8,"s":"a","o":null,"e":{"t":"First"}}"#);
+    let b = Box::<Mixed>::deserialize_in(&mut de, &bump).unwrap();
+    assert_eq!(
+        *b,
+        Mixed {
+            i: 8,
+            s: "a".into(),
+            o: None,
+            e: Test::First,
+        }
+    );
+}
+
+#[test]
+fn test_nested_deserialize_in() {
+    let bump = Bump::new();
+    let mut de = serde_json::Deserializer::from_str(r#"[["a", "b"], [], ["c"]]"#);
+    let vec = BumpVec::<BumpVec<Box<BumpString>>>::deserialize_in(&mut de, &bump).unwrap();
+    assert_eq!(vec.len(), 3);
+    assert_eq!(
+        vec[0]
+            .iter()
+            .map(|s| s.as_str())
+            .collect::<std::vec::Vec<_>>(),
+        ["a", "b"]
+    );
+    assert!(vec[1].is_empty());
+    assert_eq!(*vec[2][0], "c");
+}
+
+#[test]
+fn test_deserialize_in_seed() {
+    let bump = Bump::new();
+    let mut de = serde_json::Deserializer::from_str("[[1.5], [2.25, 3.0]]");
+    let vec: BumpVec<BumpVec<f64>> = DeserializeInSeed::new(&bump).deserialize(&mut de).unwrap();
+    assert_eq!(vec, [vec![in &bump; 1.5], vec![in &bump; 2.25, 3.0]]);
+}
+
+#[test]
+fn test_stats_serialize() {
+    let bump = Bump::builder().allocation_limit(Some(1 << 20)).build();
+    assert_eq!(
+        serde_json::to_value(bump.stats()).unwrap(),
+        serde_json::json!({
+            "allocated_bytes": 0,
+            "allocated_bytes_including_metadata": 0,
+            "used_bytes": 0,
+            "chunk_count": 0,
+            "spare_capacity": 0,
+            "allocation_limit": 1 << 20,
+            "chunks": [],
+        })
+    );
+
+    bump.alloc_slice_fill_default::<u64>(10);
+    let stats = bump.stats();
+    let json = serde_json::to_value(&stats).unwrap();
+    assert_eq!(json["allocated_bytes"], stats.allocated_bytes());
+    assert_eq!(json["used_bytes"], 80);
+    assert_eq!(json["chunk_count"], 1);
+    assert_eq!(
+        json["chunks"],
+        serde_json::json!([{
+            "size": stats.chunks()[0].size(),
+            "used": 80,
+            "is_current": true,
+        }])
+    );
+}
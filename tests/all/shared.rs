@@ -0,0 +1,68 @@
+#![cfg(all(feature = "allocator-api2", not(feature = "allocator_api")))]
+
+use allocator_api2::alloc::{Allocator, Layout};
+use bumpalo::shared::SharedBump;
+use bumpalo::Bump;
+
+#[test]
+fn clones_share_the_arena() {
+    let a = SharedBump::new();
+    let b = a.clone();
+    assert_eq!(SharedBump::handle_count(&a), 2);
+
+    let layout = Layout::new::<[u64; 4]>();
+    let p = a.allocate(layout).unwrap().cast::<u8>();
+    assert_eq!(a.allocated_bytes_in_current_chunk(), 32);
+
+    // `b` can grow and free what `a` allocated.
+    let p = unsafe {
+        b.grow(p, layout, Layout::new::<[u64; 8]>())
+            .unwrap()
+            .cast::<u8>()
+    };
+    unsafe { b.deallocate(p, Layout::new::<[u64; 8]>()) };
+    assert_eq!(a.allocated_bytes_in_current_chunk(), 0);
+
+    drop(b);
+    let bump: Bump = SharedBump::try_unwrap(a).unwrap();
+    assert!(bump.allocated_bytes() > 0);
+}
+
+#[test]
+fn try_unwrap_with_other_handles() {
+    let a = SharedBump::from(Bump::with_capacity(100));
+    let b = a.clone();
+    let a = SharedBump::try_unwrap(a).unwrap_err();
+    drop(b);
+    assert!(SharedBump::try_unwrap(a).is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sync_bump_allocates_on_other_threads() {
+    use bumpalo::shared::SyncBump;
+
+    let bump = SyncBump::new();
+    let layout = Layout::new::<u64>();
+    let handles: Vec<_> = (0..4_u64)
+        .map(|i| {
+            let bump = bump.clone();
+            std::thread::spawn(move || {
+                let p = bump.allocate(layout).unwrap().cast::<u64>();
+                unsafe { p.as_ptr().write(i) };
+                p.as_ptr() as usize
+            })
+        })
+        .collect();
+    let mut values: Vec<u64> = handles
+        .into_iter()
+        .map(|h| unsafe { *(h.join().unwrap() as *const u64) })
+        .collect();
+    values.sort_unstable();
+    assert_eq!(values, [0, 1, 2, 3]);
+
+    assert_eq!(SyncBump::handle_count(&bump), 1);
+    let used = bump.with_bump(|bump| bump.allocated_bytes_in_current_chunk());
+    assert_eq!(used, 32);
+    assert!(SyncBump::try_unwrap(bump).is_ok());
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "collections")]
+
+use crate::quickcheck;
+use bumpalo::collections::slot_map::Key;
+use bumpalo::{collections::SlotMap, Bump};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[test]
+fn insert_get_remove() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    assert!(map.is_empty());
+
+    let a = map.insert("a");
+    let c = map.insert("c");
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(a), Some(&"a"));
+    assert_eq!(map[c], "c");
+
+    map[a] = "A";
+    assert_eq!(map.remove(a), Some("A"));
+    assert_eq!(map.remove(a), None);
+    assert!(!map.contains_key(a));
+    assert_eq!(map.get_mut(a), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn reused_slots_invalidate_stale_keys() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    let old = map.insert(1);
+    map.remove(old);
+    let new = map.insert(2);
+
+    assert_eq!(new.index(), old.index());
+    assert_eq!(new.generation(), old.generation() + 1);
+    assert_ne!(new, old);
+    assert_eq!(map.get(old), None);
+    assert_eq!(map[new], 2);
+}
+
+#[test]
+#[should_panic(expected = "invalid SlotMap key")]
+fn index_with_stale_key_panics() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    let k = map.insert(1);
+    map.remove(k);
+    let _ = map[k];
+}
+
+#[test]
+fn insert_with_key_sees_its_key() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    map.insert((None, 0));
+    let k = map.insert_with_key(|k| (Some(k), 1));
+    assert_eq!(map[k], (Some(k), 1));
+}
+
+#[test]
+fn storage_does_not_grow_with_churn() {
+    let b = Bump::new();
+    let mut map = SlotMap::with_capacity_in(4, &b);
+    let capacity = map.capacity();
+    for i in 0..1000 {
+        let k = map.insert(i);
+        assert_eq!(map.remove(k), Some(i));
+    }
+    assert_eq!(map.capacity(), capacity);
+}
+
+#[test]
+fn reserve_counts_vacant_slots() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    let keys: Vec<_> = (0..8).map(|i| map.insert(i)).collect();
+    for k in &keys {
+        map.remove(*k);
+    }
+    map.reserve(8);
+    assert!(map.capacity() >= 8);
+}
+
+#[test]
+fn retain_and_clear() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    let keys: Vec<_> = (0..10).map(|i| map.insert(i)).collect();
+
+    map.retain(|_, v| *v % 3 == 0);
+    assert!(map.values().copied().eq([0, 3, 6, 9]));
+    assert_eq!(map.get(keys[1]), None);
+    assert_eq!(map[keys[3]], 3);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert!(keys.iter().all(|k| !map.contains_key(*k)));
+}
+
+#[test]
+fn iterators() {
+    let b = Bump::new();
+    let mut map = SlotMap::new_in(&b);
+    let x = map.insert(1);
+    let y = map.insert(2);
+    let z = map.insert(3);
+    map.remove(y);
+
+    let mut iter = map.iter();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some((x, &1)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some((z, &3)));
+    assert_eq!(iter.next(), None);
+
+    assert!(map.keys().eq([x, z]));
+    for v in map.values_mut() {
+        *v *= 10;
+    }
+    for (_, v) in &mut map {
+        *v += 1;
+    }
+    assert!(map.values().copied().eq([11, 31]));
+    assert_eq!(format!("{:?}", map.values()), "[11, 31]");
+}
+
+#[test]
+fn values_are_dropped() {
+    let rc = Rc::new(());
+    {
+        let b = Bump::new();
+        let mut map = SlotMap::new_in(&b);
+        let k = map.insert(rc.clone());
+        map.insert(rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 3);
+        map.remove(k);
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+quickcheck! {
+    fn slot_map_matches_hash_map(ops: Vec<(bool, usize)>) -> () {
+        let b = Bump::new();
+        let mut map = SlotMap::new_in(&b);
+        let mut model: HashMap<Key, usize> = HashMap::new();
+        let mut keys: Vec<Key> = Vec::new();
+
+        for (insert, n) in ops {
+            if insert || keys.is_empty() {
+                let k = map.insert(n);
+                assert!(model.insert(k, n).is_none());
+                keys.push(k);
+            } else {
+                // Removing may pick a key that was already removed.
+                let k = keys[n % keys.len()];
+                assert_eq!(map.remove(k), model.remove(&k));
+            }
+            assert_eq!(map.len(), model.len());
+        }
+
+        for (k, v) in &map {
+            assert_eq!(model.get(&k), Some(v));
+        }
+        for k in keys {
+            assert_eq!(map.get(k), model.get(&k));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bumpalo::{AllocErr, AllocErrKind, Bump};
+use std::alloc::Layout;
+
+#[test]
+fn parts_are_disjoint_and_cover_the_free_space() {
+    let bump = Bump::with_capacity(4096);
+    let free = bump.chunk_capacity();
+    let subs: Vec<_> = bump.split_remaining(3).collect();
+    assert_eq!(subs.len(), 3);
+    assert_eq!(subs.iter().map(|s| s.capacity()).sum::<usize>(), free);
+    assert_eq!(bump.chunk_capacity(), 0);
+
+    let ptrs: Vec<*const u8> = subs
+        .iter()
+        .map(|s| s.alloc_layout(Layout::new::<u8>()).as_ptr() as *const u8)
+        .collect();
+    assert!(ptrs.iter().all(|&p| bump.contains_ptr(p)));
+    let mut sorted = ptrs.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), 3);
+}
+
+#[test]
+fn sub_bumps_run_out_instead_of_growing() {
+    let bump = Bump::with_capacity(1024);
+    let sub = bump.split_remaining(1).next().unwrap();
+    let capacity = sub.capacity();
+    assert_eq!(
+        sub.try_alloc_layout(Layout::from_size_align(capacity + 1, 1).unwrap()),
+        Err(AllocErr::from(AllocErrKind::OutOfMemory))
+    );
+
+    let all = sub.alloc_layout(Layout::from_size_align(capacity, 1).unwrap());
+    assert_eq!(sub.remaining(), 0);
+    assert!(bump.contains_ptr(all.as_ptr()));
+    assert!(sub.try_alloc(1_u8).is_err());
+    // Zero-sized values don't need room.
+    sub.alloc(());
+}
+
+#[test]
+fn alignment_is_respected() {
+    let bump = Bump::with_capacity(1024);
+    for sub in bump.split_remaining(3) {
+        sub.alloc(1_u8);
+        let x: *const u64 = sub.alloc(2_u64);
+        assert_eq!(x as usize % std::mem::align_of::<u64>(), 0);
+        let s = sub.alloc_str("hi");
+        assert_eq!(s, "hi");
+    }
+}
+
+#[test]
+fn the_arena_keeps_allocating_in_a_new_chunk() {
+    let bump = Bump::with_capacity(1024);
+    let sub = bump.split_remaining(2).next().unwrap();
+    let x = bump.alloc(7_u32);
+    let y = sub.alloc(8_u32);
+    assert_eq!((*x, *y), (7, 8));
+    assert_eq!(bump.iter_chunk_usage().count(), 2);
+}
+
+#[test]
+fn split_into_nothing() {
+    let bump = Bump::with_capacity(1024);
+    let free = bump.chunk_capacity();
+    assert_eq!(bump.split_remaining(0).count(), 0);
+    assert_eq!(bump.chunk_capacity(), free);
+
+    let empty = Bump::new();
+    let subs: Vec<_> = empty.split_remaining(2).collect();
+    assert!(subs.iter().all(|s| s.capacity() == 0));
+    assert!(subs[0].try_alloc(1_u8).is_err());
+}
+
+#[test]
+fn sub_bumps_allocate_on_other_threads() {
+    let bump = Bump::with_capacity(16 * 1024);
+    let results: Vec<&[usize]> = std::thread::scope(|s| {
+        let handles: Vec<_> = bump
+            .split_remaining(4)
+            .enumerate()
+            .map(|(i, sub)| s.spawn(move || &*sub.alloc_slice_copy(&[i; 64])))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    for (i, slice) in results.iter().enumerate() {
+        assert_eq!(slice.len(), 64);
+        assert!(slice.iter().all(|&x| x == i));
+    }
+}
+
+#[test]
+fn merge_gives_unused_space_back() {
+    let bump = Bump::with_capacity(4096);
+    let free = bump.chunk_capacity();
+    let subs: Vec<_> = bump.split_remaining(3).collect();
+    let sizes: Vec<usize> = subs.iter().map(|s| s.capacity()).collect();
+
+    // Merging in order coalesces unused sub-allocators with the arena's free
+    // space, until one of them has allocated something.
+    let mut subs = subs.into_iter();
+    assert!(bump.merge(subs.next().unwrap()));
+    assert_eq!(bump.chunk_capacity(), sizes[0]);
+    let second = subs.next().unwrap();
+    let x: *const u64 = second.alloc(7_u64);
+    assert!(bump.merge(second));
+    assert!(bump.chunk_capacity() >= sizes[0] + sizes[1] - 2 * 8);
+    assert!(bump.chunk_capacity() < free);
+    assert!(!bump.merge(subs.next().unwrap()));
+
+    // The arena allocates in the merged space, without clobbering what the
+    // sub-allocator allocated.
+    let chunks = bump.iter_chunk_usage().count();
+    let y: *const u64 = bump.alloc(8_u64);
+    assert_eq!(bump.iter_chunk_usage().count(), chunks);
+    assert!((y as usize) < x as usize);
+    unsafe {
+        assert_eq!((*x, *y), (7, 8));
+    }
+}
+
+#[test]
+fn merge_rejects_foreign_and_stale_sub_bumps() {
+    let bump = Bump::with_capacity(1024);
+    let other = Bump::with_capacity(1024);
+    let free = other.chunk_capacity();
+    let sub = bump.split_remaining(1).next().unwrap();
+    assert!(!other.merge(sub));
+    assert_eq!(other.chunk_capacity(), free);
+
+    // Once the arena moved on to a new chunk, the space can't be given back.
+    let sub = other.split_remaining(1).next().unwrap();
+    other.alloc(1_u8);
+    let capacity = other.chunk_capacity();
+    assert!(!other.merge(sub));
+    assert_eq!(other.chunk_capacity(), capacity);
+
+    // Nothing to give back from the empty arena.
+    let empty = Bump::new();
+    assert!(!empty.merge(empty.split_remaining(1).next().unwrap()));
+}
@@ -34,3 +34,205 @@ fn push_str() {
     assert_eq!(s.len(), 4009);
     assert_eq!(&s[s.len() - 5..], "xxghi");
 }
+
+#[test]
+fn try_methods_report_allocation_failure() {
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+
+    assert!(String::try_with_capacity_in(1, &b).is_err());
+
+    let mut s = String::new_in(&b);
+    assert!(s.try_push('a').is_err());
+    assert!(s.try_push_str("abc").is_err());
+    assert!(s.try_insert(0, 'a').is_err());
+    assert!(s.try_insert_str(0, "abc").is_err());
+    assert!(s.is_empty());
+}
+
+#[test]
+fn try_methods_succeed_with_capacity() {
+    let b = Bump::new();
+    let mut s = String::try_with_capacity_in(8, &b).unwrap();
+    s.try_push_str("bc").unwrap();
+    s.try_push('d').unwrap();
+    s.try_insert(0, 'a').unwrap();
+    assert_eq!(s, "abcd");
+}
+
+#[test]
+fn extend_from_within() {
+    let b = Bump::new();
+    let mut s = String::from_str_in("αβγ", &b);
+    s.extend_from_within(2..4);
+    assert_eq!(s, "αβγβ");
+    s.extend_from_within(..=1);
+    assert_eq!(s, "αβγβα");
+    s.extend_from_within(..0);
+    assert_eq!(s, "αβγβα");
+}
+
+#[test]
+#[should_panic]
+fn extend_from_within_not_char_boundary() {
+    let b = Bump::new();
+    let mut s = String::from_str_in("αβγ", &b);
+    s.extend_from_within(1..);
+}
+
+#[test]
+#[should_panic]
+fn extend_from_within_out_of_bounds() {
+    let b = Bump::new();
+    let mut s = String::from_str_in("abc", &b);
+    s.extend_from_within(1..4);
+}
+
+#[test]
+fn insert_str_in_the_middle() {
+    let b = Bump::new();
+    let mut s = String::new_in(&b);
+    for i in 0..100 {
+        let mid = s.len() / 2;
+        s.insert_str(mid - mid % 2, if i % 2 == 0 { "ab" } else { "cd" });
+    }
+    assert_eq!(s.len(), 200);
+    // Capacity grows geometrically, not by exactly the inserted length.
+    assert!(s.capacity() < 400);
+
+    s.try_insert_str(0, "xyz").unwrap();
+    assert!(s.starts_with("xyz"));
+}
+
+#[test]
+fn replace_range_and_split_off() {
+    let b = Bump::new();
+    let mut s = String::from_str_in("hello, world", &b);
+    s.replace_range(7.., "bumpalo");
+    assert_eq!(s, "hello, bumpalo");
+    s.replace_range(..5, "goodbye");
+    assert_eq!(s, "goodbye, bumpalo");
+
+    let tail = s.split_off(7);
+    assert_eq!(s, "goodbye");
+    assert_eq!(tail, ", bumpalo");
+}
+
+#[test]
+fn lossy_conversions() {
+    let b = Bump::new();
+    assert_eq!(
+        String::from_utf8_lossy_in(b"Hello \xF0\x90\x80World", &b),
+        "Hello \u{FFFD}World"
+    );
+    assert_eq!(
+        String::from_utf16_lossy_in(&[0x0068, 0xD800, 0x0069], &b),
+        "h\u{FFFD}i"
+    );
+}
+
+#[test]
+fn from_utf8_lossy_in_valid_input() {
+    let b = Bump::new();
+    let s = String::from_utf8_lossy_in("héllo wörld".as_bytes(), &b);
+    assert_eq!(s, "héllo wörld");
+    assert_eq!(s.capacity(), s.len());
+
+    assert_eq!(String::from_utf8_lossy_in(b"", &b), "");
+}
+
+#[test]
+fn from_utf16_in_round_trip() {
+    let b = Bump::new();
+    let text = "mixed ASCII, ünïcödé, and 𝄞 astral";
+    let utf16: Vec<u16> = text.encode_utf16().collect();
+    assert_eq!(String::from_utf16_in(&utf16, &b).unwrap(), text);
+
+    let mut broken = utf16.clone();
+    broken.push(0xD800);
+    assert!(String::from_utf16_in(&broken, &b).is_err());
+}
+
+#[test]
+fn leak() {
+    let b = Bump::new();
+    let mut s = String::with_capacity_in(64, &b);
+    s.push_str("héllo");
+    let used = b.allocated_bytes_in_current_chunk();
+    let leaked = s.leak();
+    leaked.make_ascii_uppercase();
+    assert_eq!(leaked, "HéLLO");
+    assert_eq!(b.allocated_bytes_in_current_chunk(), used - (64 - 6));
+}
+
+#[test]
+fn from_utf8_unchecked_in_wraps_without_copying() {
+    let b = Bump::new();
+    let bytes = b.alloc_slice_copy("grüße".as_bytes());
+    let ptr = bytes.as_ptr();
+
+    let mut s = unsafe { String::from_utf8_unchecked_in(bytes, &b) };
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(s.capacity(), s.len());
+
+    s.push_str(", welt");
+    assert_eq!(s, "grüße, welt");
+}
+
+#[test]
+fn into_raw_parts_round_trip() {
+    let b = Bump::new();
+    let mut s = String::with_capacity_in(16, &b);
+    s.push_str("abc");
+
+    let (ptr, len, capacity) = s.into_raw_parts();
+    assert_eq!((len, capacity), (3, 16));
+
+    let mut s = unsafe { String::from_raw_parts_in(ptr, len, capacity, &b) };
+    s.push('d');
+    assert_eq!(s.as_ptr() as *mut u8, ptr);
+    assert_eq!(s, "abcd");
+}
+
+#[test]
+fn utf8_bytes_round_trip_without_copying() {
+    use bumpalo::collections::Vec;
+
+    let b = Bump::new();
+    let mut bytes = Vec::with_capacity_in(32, &b);
+    bytes.extend_from_slice(b"GET /index.html");
+    let ptr = bytes.as_ptr();
+
+    let mut s = String::try_from(bytes).unwrap();
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(s.capacity(), 32);
+    unsafe { s.as_mut_vec().truncate(3) };
+    assert_eq!(s, "GET");
+
+    let bytes: Vec<u8> = s.into();
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(bytes, b"GET");
+
+    // Invalid UTF-8 hands the same buffer back.
+    let mut bytes = bytes;
+    bytes.push(0xFF);
+    let err = String::try_from(bytes).unwrap_err();
+    assert_eq!(err.utf8_error().valid_up_to(), 3);
+    let bytes = err.into_bytes();
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(bytes, b"GET\xFF");
+}
+
+#[cfg(any(feature = "std", feature = "core_error"))]
+#[test]
+fn conversion_errors_implement_error() {
+    use std::error::Error;
+
+    let b = Bump::new();
+    let err = String::from_utf8(bumpalo::vec![in &b; 0, 159]).unwrap_err();
+    assert_eq!(err.source().unwrap().to_string(), err.to_string());
+
+    let err = String::from_utf16_in(&[0xD800], &b).unwrap_err();
+    let err: &dyn Error = &err;
+    assert!(err.source().is_none());
+}
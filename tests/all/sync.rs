@@ -0,0 +1,90 @@
+use bumpalo::sync::ArcRef;
+use bumpalo::Bump;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug)]
+struct CountDrops<'a>(&'a AtomicUsize);
+
+impl Drop for CountDrops<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_and_sync_when_t_is() {
+    assert_send_sync::<ArcRef<'static, u32>>();
+    assert_send_sync::<ArcRef<'static, std::sync::Mutex<u32>>>();
+}
+
+#[test]
+fn shared_across_scoped_threads() {
+    let bump = Bump::new();
+    let data = ArcRef::new_in((0..100).collect::<Vec<u32>>(), &bump);
+
+    let sums: Vec<u32> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let data = data.clone();
+                s.spawn(move || data.iter().sum())
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    assert_eq!(sums, [4950; 4]);
+    assert_eq!(ArcRef::strong_count(&data), 1);
+}
+
+#[test]
+fn last_handle_drops_the_value() {
+    let drops = AtomicUsize::new(0);
+    let bump = Bump::new();
+
+    let a = ArcRef::new_in(CountDrops(&drops), &bump);
+    let b = a.clone();
+    let c = b.clone();
+    assert_eq!(ArcRef::strong_count(&a), 3);
+    assert!(ArcRef::ptr_eq(&a, &c));
+
+    std::thread::scope(|s| {
+        s.spawn(move || drop(b));
+        s.spawn(move || drop(c));
+    });
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    drop(a);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn get_mut_and_try_unwrap() {
+    let drops = AtomicUsize::new(0);
+    let bump = Bump::new();
+
+    let mut a = ArcRef::new_in((5, CountDrops(&drops)), &bump);
+    ArcRef::get_mut(&mut a).unwrap().0 += 1;
+
+    let b = a.clone();
+    assert!(ArcRef::get_mut(&mut a).is_none());
+    let a = ArcRef::try_unwrap(a).unwrap_err();
+    drop(b);
+
+    let (n, counter) = ArcRef::try_unwrap(a).ok().unwrap();
+    assert_eq!(n, 6);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(counter);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn traits_forward_to_the_value() {
+    let bump = Bump::new();
+    let a = ArcRef::new_in(String::from("hi"), &bump);
+    let b = ArcRef::new_in(String::from("hi"), &bump);
+    assert_eq!(a, b);
+    assert!(!ArcRef::ptr_eq(&a, &b));
+    assert_eq!(format!("{} {:?}", a, b), "hi \"hi\"");
+}
@@ -1,4 +1,4 @@
-use bumpalo::Bump;
+use bumpalo::{Bump, Detached};
 use std::alloc::Layout;
 use std::mem;
 use std::usize;
@@ -189,6 +189,91 @@ fn test_reset() {
     assert_eq!(b.iter_allocated_chunks().count(), 1);
 }
 
+#[test]
+fn detach_last_allocation_keeps_result() {
+    let mut b = Bump::new();
+    b.alloc(1_u8);
+
+    let result = b.detach_last_allocation(|b| {
+        for i in 0u64..10_000 {
+            b.alloc(i);
+        }
+        b.alloc([7_u64; 4])
+    });
+    assert_eq!(*result, [7; 4]);
+    let addr = &*result as *const [u64; 4] as usize;
+
+    let bump = Detached::bump(&result);
+    let chunks: Vec<_> = unsafe { bump.iter_allocated_chunks_raw() }.collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].1, mem::size_of::<[u64; 4]>());
+    assert_eq!(chunks[0].0 as usize, addr);
+
+    // New allocations go below the result.
+    let x = bump.alloc(3_u64);
+    assert_eq!(x as *mut u64 as usize, addr - 8);
+    assert_eq!(*result, [7; 4]);
+
+    // Dropping the guard resets the arena.
+    drop(result);
+    assert_eq!(b.iter_allocated_chunks().count(), 1);
+    assert!(b.iter_allocated_chunks().next().unwrap().is_empty());
+}
+
+#[test]
+fn detach_last_allocation_not_last() {
+    let mut b = Bump::new();
+    let mut result = b.detach_last_allocation(|b| {
+        let result = b.alloc(String::from("hello"));
+        b.alloc_slice_fill_copy(100, 0_u8);
+        result
+    });
+    assert_eq!(*result, "hello");
+    result.push_str(", world");
+    assert_eq!(*result, "hello, world");
+    drop(result);
+
+    assert_eq!(*b.detach_last_allocation(|b| b.alloc(())), ());
+}
+
+#[test]
+fn detach_last_allocation_guard_drops_or_releases_result() {
+    use std::rc::Rc;
+
+    let token = Rc::new(());
+    let mut b = Bump::new();
+    let result = b.detach_last_allocation(|b| b.alloc(token.clone()));
+    assert_eq!(Rc::strong_count(&token), 2);
+    drop(result);
+    assert_eq!(Rc::strong_count(&token), 1);
+
+    let result = b.detach_last_allocation(|b| b.alloc(token.clone()));
+    let moved = Detached::into_inner(result);
+    assert_eq!(Rc::strong_count(&token), 2);
+    assert!(b.iter_allocated_chunks().next().unwrap().is_empty());
+    drop(moved);
+
+    let leaked: *const Rc<()> =
+        Detached::leak(b.detach_last_allocation(|b| b.alloc(token.clone())));
+    assert!(b.contains_ptr(leaked.cast()));
+    b.reset();
+    assert_eq!(Rc::strong_count(&token), 2);
+}
+
+#[test]
+fn detach_last_allocation_with_min_align() {
+    let mut b = Bump::with_min_align(16);
+    let result = b.detach_last_allocation(|b| {
+        b.alloc(1_u64);
+        b.alloc(2_u8)
+    });
+    assert_eq!(*result, 2);
+    let addr = &*result as *const u8 as usize;
+    assert_eq!(addr % 16, 0);
+    let x = Detached::bump(&result).alloc(3_u8);
+    assert_eq!(x as *mut u8 as usize, addr - 16);
+}
+
 #[test]
 fn test_alignment() {
     for &alignment in &[2, 4, 8, 16, 32, 64] {
@@ -219,4 +304,81 @@ fn miri_stacked_borrows_issue_247() {
     drop(unsafe { Box::from_raw_in(a, &bump) });
 
     let _b = Box::new_in(2u16, &bump);
-}
\ No newline at end of file
+}
+#[test]
+fn alloc_slice_copy_large() {
+    let b = Bump::with_capacity(1 << 16);
+    b.alloc(0_u8);
+    let before = b.allocated_bytes();
+
+    let src: Vec<u32> = (0..4096).collect();
+    let dst = b.alloc_slice_copy(&src);
+    assert_eq!(src, dst);
+    assert_eq!(dst.as_ptr() as usize % 64, 0);
+    assert_eq!(b.allocated_bytes(), before);
+}
+
+#[test]
+fn alloc_slice_copy_large_exact_capacity() {
+    // Aligning a large copy must not need a chunk that a naturally aligned
+    // copy wouldn't need.
+    let b = Bump::with_capacity(4096 + 1);
+    b.alloc(0_u8);
+    let src = vec![7_u8; 4096];
+    let before = b.allocated_bytes();
+    let dst = b.alloc_slice_copy(&src);
+    assert_eq!(dst, &src[..]);
+    assert_eq!(b.allocated_bytes(), before);
+}
+
+#[test]
+fn empty_is_const() {
+    static ARENA: std::sync::Mutex<Bump> = std::sync::Mutex::new(Bump::empty());
+
+    thread_local! {
+        static SCRATCH: Bump = const { Bump::empty() };
+    }
+
+    let mut bump = ARENA.lock().unwrap();
+    assert_eq!(bump.allocated_bytes(), 0);
+    assert_eq!(*bump.alloc(7_u32), 7);
+    assert!(bump.allocated_bytes() > 0);
+    bump.reset();
+    assert_eq!(*bump.alloc_str("hi"), *"hi");
+
+    SCRATCH.with(|bump| {
+        let v = bump.alloc_slice_copy(&[1, 2, 3]);
+        assert_eq!(v, [1, 2, 3]);
+    });
+}
+
+#[test]
+fn empty_without_allocating_is_dropped() {
+    let mut bump = Bump::empty();
+    assert_eq!(bump.chunk_capacity(), 0);
+    assert_eq!(bump.iter_allocated_chunks().count(), 0);
+    bump.reset();
+    drop(bump);
+}
+
+#[test]
+fn alloc_str_helpers() {
+    let b = Bump::new();
+    assert_eq!(b.alloc_str_from_utf8("héllo".as_bytes()).unwrap(), "héllo");
+    let used = b.allocated_bytes() - b.chunk_capacity();
+    assert!(b.alloc_str_from_utf8(&[b'a', 0xc3]).is_err());
+    assert_eq!(b.allocated_bytes() - b.chunk_capacity(), used);
+
+    for c in ['a', 'é', '€', '🦀'] {
+        let s = b.alloc_char(c);
+        assert_eq!(s.len(), c.len_utf8());
+        assert_eq!(s.chars().collect::<Vec<_>>(), [c]);
+    }
+
+    for src in ["", "abc", "ÀÉÎõü", "İstanbul", "ǅ", "ﬀ", "Straße", "ΟΔΟΣ"] {
+        let lower: String = src.chars().flat_map(char::to_lowercase).collect();
+        let upper: String = src.chars().flat_map(char::to_uppercase).collect();
+        assert_eq!(*b.alloc_str_lowercase(src), lower);
+        assert_eq!(*b.alloc_str_uppercase(src), upper);
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "std")]
+
+use bumpalo::thread_local::with_bump;
+use std::panic;
+
+#[test]
+fn nested_calls_share_the_arena() {
+    with_bump(|outer| {
+        let a = outer.alloc(1_u64);
+        let used = outer.allocated_bytes_in_current_chunk();
+        with_bump(|inner| {
+            assert!(std::ptr::eq(outer, inner));
+            assert_eq!(inner.allocated_bytes_in_current_chunk(), used);
+            inner.alloc(2_u64);
+        });
+        // Returning from the nested call doesn't reset the arena.
+        assert_eq!(*a, 1);
+        assert!(outer.allocated_bytes_in_current_chunk() > used);
+    });
+}
+
+#[test]
+fn outermost_call_resets() {
+    let allocated = with_bump(|bump| {
+        bump.alloc_slice_fill_copy(100, 0_u8);
+        bump.allocated_bytes()
+    });
+    with_bump(|bump| {
+        assert_eq!(bump.allocated_bytes_in_current_chunk(), 0);
+        // The memory is kept for reuse.
+        assert_eq!(bump.allocated_bytes(), allocated);
+    });
+}
+
+#[test]
+fn resets_after_panic() {
+    let result = panic::catch_unwind(|| {
+        with_bump(|bump| {
+            bump.alloc(1_u32);
+            with_bump(|_| panic!("oops"));
+        })
+    });
+    assert!(result.is_err());
+    with_bump(|bump| assert_eq!(bump.allocated_bytes_in_current_chunk(), 0));
+}
+
+#[test]
+fn arenas_are_per_thread() {
+    let here = with_bump(|bump| bump as *const _ as usize);
+    let there = std::thread::spawn(|| with_bump(|bump| bump as *const _ as usize))
+        .join()
+        .unwrap();
+    assert_ne!(here, there);
+}
+
+#[test]
+fn returns_owned_values() {
+    let s = with_bump(|bump| {
+        let parts = bump.alloc_slice_copy(&["a", "b", "c"]);
+        parts.join("-")
+    });
+    assert_eq!(s, "a-b-c");
+}
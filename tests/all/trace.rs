@@ -0,0 +1,104 @@
+#![cfg(feature = "trace")]
+
+use bumpalo::{AllocationEvent, Bump};
+use std::alloc::Layout;
+use std::cell::RefCell;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<AllocationEvent>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(_bump: &Bump, event: AllocationEvent) {
+    EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+fn take_events() -> Vec<AllocationEvent> {
+    EVENTS.with(|events| events.take())
+}
+
+#[test]
+fn reports_each_allocation() {
+    let bump = Bump::new();
+    bump.set_trace_hook(Some(record));
+    assert!(bump.trace_hook().is_some());
+
+    let x: *const u32 = bump.alloc(1_u32);
+    let s = bump.alloc_str("hello");
+    let z = bump.alloc_layout_zeroed(Layout::new::<[u64; 4]>());
+    let f: *const u16 = bump.alloc_front(2_u16);
+    bump.alloc(());
+
+    let events = take_events();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].ptr().as_ptr() as *const u32, x);
+    assert_eq!(events[0].layout(), Layout::new::<u32>());
+    assert_eq!(events[1].ptr().as_ptr(), s.as_mut_ptr());
+    assert_eq!(events[1].layout().size(), 5);
+    assert_eq!(events[2].ptr(), z);
+    assert_eq!(events[3].ptr().as_ptr() as *const u16, f);
+    assert!(events[3].is_front());
+    assert!(events[..3].iter().all(|e| !e.is_front()));
+    assert!(events.iter().all(|e| e.chunk_id() == 0));
+}
+
+#[test]
+fn chunk_ids_grow_with_new_chunks() {
+    let bump = Bump::new();
+    bump.set_trace_hook(Some(record));
+    for _ in 0..100 {
+        bump.alloc([0_u8; 1000]);
+    }
+
+    let events = take_events();
+    assert_eq!(events.len(), 100);
+    assert!(events
+        .windows(2)
+        .all(|w| w[0].chunk_id() <= w[1].chunk_id()));
+    let mut ids: Vec<_> = events.iter().map(|e| e.chunk_id()).collect();
+    ids.dedup();
+    assert_eq!(ids.len(), bump.iter_chunk_usage().count());
+    for event in &events {
+        let offset = u32::try_from(event.chunk_id()).unwrap();
+        let start = bump.resolve_offset(offset).unwrap();
+        assert!(start <= event.ptr());
+    }
+}
+
+#[test]
+fn hook_allocations_are_not_reported() {
+    fn allocating_hook(bump: &Bump, event: AllocationEvent) {
+        bump.alloc(0_u64);
+        record(bump, event);
+    }
+
+    let bump = Bump::new();
+    bump.set_trace_hook(Some(allocating_hook));
+    bump.alloc(1_u8);
+    bump.alloc(2_u8);
+    assert_eq!(take_events().len(), 2);
+
+    bump.set_trace_hook(None);
+    bump.alloc(3_u8);
+    assert!(take_events().is_empty());
+}
+
+#[test]
+fn reports_with_align_allocations() {
+    let mut bump = Bump::new();
+    bump.set_trace_hook(Some(record));
+    let (a, b) = bump.with_align::<16, _>(|aligned| {
+        let a: *const u8 = aligned.alloc(1_u8);
+        let b = aligned.alloc_layout(Layout::from_size_align(100, 64).unwrap());
+        (a, b)
+    });
+
+    let events = take_events();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].ptr().as_ptr() as *const u8, a);
+    assert_eq!(events[0].layout(), Layout::new::<u8>());
+    assert_eq!(events[1].ptr(), b);
+    assert_eq!(
+        events[1].layout(),
+        Layout::from_size_align(100, 64).unwrap()
+    );
+}
@@ -0,0 +1,114 @@
+use bumpalo::typed::TypedBump;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Logged<'a>(u32, &'a RefCell<Vec<u32>>);
+
+impl Drop for Logged<'_> {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0);
+    }
+}
+
+#[test]
+fn drops_every_value_most_recent_first() {
+    let log = RefCell::new(vec![]);
+    let arena = TypedBump::with_capacity(4);
+    for i in 0..1000 {
+        arena.alloc(Logged(i, &log));
+    }
+    assert_eq!(arena.len(), 1000);
+    drop(arena);
+    let expected: Vec<u32> = (0..1000).rev().collect();
+    assert_eq!(*log.borrow(), expected);
+}
+
+#[test]
+fn reset_drops_values_and_reuses_memory() {
+    let shared = Rc::new(());
+    let mut arena = TypedBump::new();
+    for _ in 0..100 {
+        arena.alloc(shared.clone());
+    }
+    assert_eq!(Rc::strong_count(&shared), 101);
+
+    arena.reset();
+    assert!(arena.is_empty());
+    assert_eq!(Rc::strong_count(&shared), 1);
+
+    arena.alloc(shared.clone());
+    drop(arena);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn zero_sized_values_are_dropped() {
+    thread_local!(static DROPS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) });
+    struct Zst;
+    impl Drop for Zst {
+        fn drop(&mut self) {
+            DROPS.with(|d| d.set(d.get() + 1));
+        }
+    }
+
+    let arena = TypedBump::new();
+    for _ in 0..10 {
+        arena.alloc(Zst);
+    }
+    drop(arena);
+    assert_eq!(DROPS.with(|d| d.get()), 10);
+}
+
+#[test]
+fn over_aligned_values_are_dropped() {
+    #[repr(align(64))]
+    struct Big {
+        _rc: Rc<()>,
+    }
+
+    let shared = Rc::new(());
+    let arena = TypedBump::new();
+    for _ in 0..100 {
+        let big = arena.alloc(Big {
+            _rc: shared.clone(),
+        });
+        assert_eq!(big as *mut Big as usize % 64, 0);
+    }
+    assert_eq!(Rc::strong_count(&shared), 101);
+    drop(arena);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn try_alloc_respects_allocation_limit() {
+    let arena = TypedBump::new();
+    arena.set_allocation_limit(Some(0));
+    assert!(arena.try_alloc(String::from("x")).is_err());
+    assert!(arena.is_empty());
+}
+
+#[test]
+fn panicking_destructor_does_not_double_drop() {
+    struct PanicOnDrop<'a>(bool, &'a std::cell::Cell<usize>);
+    impl Drop for PanicOnDrop<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+            if self.0 {
+                panic!("boom");
+            }
+        }
+    }
+
+    let drops = std::cell::Cell::new(0);
+    let mut arena = TypedBump::new();
+    arena.alloc(PanicOnDrop(false, &drops));
+    arena.alloc(PanicOnDrop(true, &drops));
+    arena.alloc(PanicOnDrop(false, &drops));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| arena.reset()));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 3);
+    assert!(arena.is_empty());
+    drop(arena);
+    assert_eq!(drops.get(), 3);
+}
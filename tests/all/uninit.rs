@@ -0,0 +1,122 @@
+use bumpalo::{uninit, Bump};
+use std::mem::{self, MaybeUninit};
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+#[test]
+fn alloc_uninit_is_aligned_and_writable() {
+    #[repr(align(64))]
+    struct Aligned(u8);
+
+    let bump = Bump::new();
+    bump.alloc(1_u8);
+    let x = bump.alloc_uninit::<Aligned>();
+    assert_eq!(x.as_ptr() as usize % 64, 0);
+    let x = x.write(Aligned(7));
+    assert_eq!(x.0, 7);
+}
+
+#[test]
+fn alloc_uninit_zst_does_not_allocate() {
+    let bump = Bump::new();
+    let x = bump.alloc_uninit::<()>();
+    x.write(());
+    let xs = bump.alloc_uninit_slice::<()>(100);
+    assert_eq!(xs.len(), 100);
+    assert_eq!(bump.allocated_bytes(), 0);
+}
+
+#[test]
+fn alloc_uninit_slice_reserves_len_elements() {
+    let bump = Bump::new();
+    let xs = bump.alloc_uninit_slice::<u64>(10);
+    assert_eq!(xs.len(), 10);
+    assert_eq!(xs.as_ptr() as usize % mem::align_of::<u64>(), 0);
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), 80);
+}
+
+#[test]
+fn try_alloc_uninit_slice_overflow() {
+    let bump = Bump::new();
+    assert!(bump.try_alloc_uninit_slice::<u64>(usize::MAX).is_err());
+}
+
+#[test]
+fn write_slice_copy_and_clone() {
+    let bump = Bump::new();
+    let xs = uninit::write_slice_copy(bump.alloc_uninit_slice(3), &[1, 2, 3]);
+    assert_eq!(xs, [1, 2, 3]);
+
+    let rc = Rc::new(());
+    let src = [rc.clone(), rc.clone()];
+    let ys = uninit::write_slice_clone(bump.alloc_uninit_slice(2), &src);
+    assert_eq!(ys.len(), 2);
+    assert_eq!(Rc::strong_count(&rc), 5);
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn write_slice_copy_length_mismatch() {
+    let bump = Bump::new();
+    uninit::write_slice_copy(bump.alloc_uninit_slice(2), &[1, 2, 3]);
+}
+
+#[test]
+fn write_slice_with_drops_initialized_on_panic() {
+    let bump = Bump::new();
+    let rc = Rc::new(());
+    let xs = bump.alloc_uninit_slice::<Rc<()>>(5);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        uninit::write_slice_with(xs, |i| {
+            if i == 3 {
+                panic!("oops");
+            }
+            rc.clone()
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn assume_init_slice_mut_after_writing_each_element() {
+    let bump = Bump::new();
+    let xs: &mut [MaybeUninit<u16>] = bump.alloc_uninit_slice(4);
+    for (i, x) in xs.iter_mut().enumerate() {
+        x.write(i as u16);
+    }
+    let xs = unsafe { uninit::assume_init_slice_mut(xs) };
+    assert_eq!(xs, [0, 1, 2, 3]);
+}
+
+#[test]
+fn alloc_slice_copy_uninit_tail_is_one_allocation() {
+    let bump = Bump::new();
+    let (head, tail) = bump.alloc_slice_copy_uninit_tail(&[1_u64, 2, 3], 4);
+    assert_eq!(head, [1, 2, 3]);
+    assert_eq!(tail.len(), 4);
+    assert_eq!(bump.allocated_bytes_in_current_chunk(), 7 * 8);
+
+    let head_end = head.as_ptr_range().end as usize;
+    let tail = uninit::write_slice_with(tail, |i| i as u64 + 4);
+    assert_eq!(tail, [4, 5, 6, 7]);
+    assert_eq!(tail.as_ptr() as usize, head_end);
+}
+
+#[test]
+fn alloc_slice_copy_uninit_tail_edge_cases() {
+    let bump = Bump::new();
+    let (head, tail) = bump.alloc_slice_copy_uninit_tail::<u32>(&[], 0);
+    assert!(head.is_empty() && tail.is_empty());
+
+    let (head, tail) = bump.alloc_slice_copy_uninit_tail(&[(); 3], 5);
+    assert_eq!((head.len(), tail.len()), (3, 5));
+
+    assert!(bump
+        .try_alloc_slice_copy_uninit_tail(&[1_u16], usize::MAX)
+        .is_err());
+    assert!(bump
+        .try_alloc_slice_copy_uninit_tail(&[1_u64], usize::MAX / 8)
+        .is_err());
+}
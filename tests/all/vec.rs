@@ -1,7 +1,8 @@
 #![cfg(feature = "collections")]
 
 use crate::quickcheck;
-use bumpalo::{collections::Vec, vec, Bump};
+use bumpalo::collections::{CollectionAllocErr, Vec};
+use bumpalo::{vec, AllocErr, AllocErrKind, Bump};
 use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 
@@ -141,6 +142,128 @@ fn test_extend_from_slices_copy() {
     assert_eq!(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], vec.as_slice());
 }
 
+#[test]
+fn test_remove_many() {
+    let bump = Bump::new();
+    let mut vec: Vec<_> = Vec::from_iter_in(0..10, &bump);
+
+    vec.remove_many(&[]);
+    assert_eq!(vec.len(), 10);
+
+    vec.remove_many(&[0, 3, 4, 9]);
+    assert_eq!(vec, [1, 2, 5, 6, 7, 8]);
+
+    vec.swap_remove_many(&[1, 4]);
+    assert_eq!(vec, [1, 8, 5, 6]);
+
+    vec.swap_remove_many(&[0, 1, 2, 3]);
+    assert!(vec.is_empty());
+}
+
+#[test]
+fn test_remove_many_drops_removed() {
+    let bump = Bump::new();
+    let drops = Cell::new(0);
+
+    struct Dropper<'a>(&'a Cell<usize>);
+
+    impl Drop for Dropper<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let mut vec = Vec::from_iter_in((0..6).map(|_| Dropper(&drops)), &bump);
+    vec.remove_many(&[1, 2]);
+    assert_eq!(drops.get(), 2);
+    vec.swap_remove_many(&[0, 3]);
+    assert_eq!(drops.get(), 4);
+    assert_eq!(vec.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "removal indices are not strictly ascending")]
+fn test_remove_many_unsorted() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 1, 2, 3];
+    vec.remove_many(&[2, 1]);
+}
+
+#[test]
+#[should_panic(expected = "removal index (is 3) should be < len (is 3)")]
+fn test_swap_remove_many_out_of_bounds() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 1, 2, 3];
+    vec.swap_remove_many(&[0, 3]);
+}
+
+#[test]
+fn test_extend_from_within() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; String::from("a"), String::from("b")];
+
+    vec.extend_from_within(..);
+    assert_eq!(vec, ["a", "b", "a", "b"]);
+
+    vec.extend_from_within(1..1);
+    assert_eq!(vec.len(), 4);
+
+    vec.extend_from_within(1..=2);
+    assert_eq!(vec, ["a", "b", "a", "b", "b", "a"]);
+}
+
+#[test]
+#[should_panic(expected = "range end is out of bounds")]
+fn test_extend_from_within_out_of_bounds() {
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; 1, 2, 3];
+    vec.extend_from_within(2..4);
+}
+
+#[test]
+fn test_extend_from_within_copy_lz_style() {
+    let bump = Bump::new();
+    let mut out = Vec::with_capacity_in(1, &bump);
+    out.push(b'x');
+
+    // Repeatedly double the output, forcing reallocations along the way.
+    for _ in 0..10 {
+        out.extend_from_within_copy(..);
+    }
+    assert_eq!(out.len(), 1024);
+    assert!(out.iter().all(|&b| b == b'x'));
+
+    out.truncate(0);
+    out.extend_from_slice_copy(b"abcd");
+    out.extend_from_within_copy(1..3);
+    assert_eq!(out, b"abcdbc");
+}
+
+#[test]
+fn test_extend_from_within_clone_panic_keeps_prefix() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    struct PanicOnClone(u32);
+
+    impl Clone for PanicOnClone {
+        fn clone(&self) -> Self {
+            if self.0 == 2 {
+                panic!("oops");
+            }
+            PanicOnClone(self.0)
+        }
+    }
+
+    let bump = Bump::new();
+    let mut vec = vec![in &bump; PanicOnClone(0), PanicOnClone(1), PanicOnClone(2)];
+    let result = panic::catch_unwind(AssertUnwindSafe(|| vec.extend_from_within(..)));
+    assert!(result.is_err());
+    assert_eq!(
+        vec.iter().map(|x| x.0).collect::<std::vec::Vec<_>>(),
+        [0, 1, 2, 0, 1]
+    );
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn test_vec_write() {
@@ -167,3 +290,539 @@ fn test_vec_write() {
 
     assert_eq!(v, &[1, 2, 3]);
 }
+
+#[test]
+fn test_vec_std_parity() {
+    let b = Bump::new();
+
+    let mut v = vec![in &b; 1, 2, 3, 4, 5, 6];
+    v.retain_mut(|x| {
+        *x *= 10;
+        *x % 20 == 0
+    });
+    assert_eq!(v, &[20, 40, 60]);
+
+    let drained: std::vec::Vec<_> = v.drain(1..).collect();
+    assert_eq!(drained, [40, 60]);
+    assert_eq!(v, &[20]);
+
+    let mut v = vec![in &b; 1, 1, 2, 3, 3, 3, 4];
+    v.dedup_by(|a, b| a == b);
+    assert_eq!(v, &[1, 2, 3, 4]);
+
+    let tail = v.split_off(2);
+    assert_eq!(v, &[1, 2]);
+    assert_eq!(tail, &[3, 4]);
+}
+
+#[test]
+fn test_retain_into() {
+    let b = Bump::new();
+    let spill = Bump::new();
+
+    let mut v = vec![in &b; 5, 1, 8, 2, 9, 3];
+    let removed = v.retain_into(|&x| x < 5, &spill);
+    assert_eq!(v, &[1, 2, 3]);
+    assert_eq!(removed, &[5, 8, 9]);
+    assert!(spill.allocated_bytes() > 0);
+
+    let removed = v.retain_into(|_| true, &spill);
+    assert_eq!(v, &[1, 2, 3]);
+    assert!(removed.is_empty());
+}
+
+#[test]
+fn test_dedup_into() {
+    let b = Bump::new();
+
+    let mut v = vec![in &b; 1, 1, 2, 3, 3, 3, 4];
+    let mut removed = v.dedup_into(&b);
+    assert_eq!(v, &[1, 2, 3, 4]);
+    removed.sort_unstable();
+    assert_eq!(removed, &[1, 3, 3]);
+
+    let mut v = vec![in &b; 10, 20, 21, 30, 20];
+    let removed = v.dedup_by_into(|a, b| *a / 10 == *b / 10, &b);
+    assert_eq!(v, &[10, 20, 30, 20]);
+    assert_eq!(removed, &[21]);
+}
+
+#[test]
+fn test_retain_into_and_dedup_into_do_not_double_drop() {
+    struct Counted<'a>(u32, &'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+
+    {
+        let mut v = Vec::new_in(&b);
+        v.extend([1, 1, 2, 3, 3].iter().map(|&x| Counted(x, &drops)));
+
+        let removed = v.dedup_by_into(|a, b| a.0 == b.0, &b);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(drops.get(), 0);
+
+        let odd = v.retain_into(|x| x.0 % 2 == 0, &b);
+        assert_eq!(odd.len(), 2);
+        assert_eq!(v.len(), 1);
+        assert_eq!(drops.get(), 0);
+    }
+
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn into_iter_by_value() {
+    fn total<I: IntoIterator<Item = String>>(items: I) -> usize {
+        items.into_iter().map(|s| s.len()).sum()
+    }
+
+    let b = Bump::new();
+    let v = vec![in &b; "a".to_string(), "bb".to_string(), "ccc".to_string()];
+    assert_eq!(total(v), 6);
+
+    let v = vec![in &b; 1, 2, 3, 4, 5];
+    let mut iter = v.into_iter();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.as_slice(), &[2, 3, 4]);
+    assert_eq!(iter.as_ref(), &[2, 3, 4]);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.rev().collect::<std::vec::Vec<_>>(), [4, 3, 2]);
+}
+
+#[test]
+fn into_iter_drops_remaining_items() {
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let mut v = Vec::new_in(&b);
+    v.extend((0..5).map(|_| Counted(&drops)));
+
+    let mut iter = v.into_iter();
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(drops.get(), 2);
+    drop(iter);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn into_iter_keeps_dropping_after_panic() {
+    struct PanicOnDrop<'a>(bool, &'a Cell<usize>);
+
+    impl Drop for PanicOnDrop<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+            if self.0 {
+                panic!("PanicOnDrop");
+            }
+        }
+    }
+
+    let drops = Cell::new(0);
+    let b = Bump::new();
+    let mut v = Vec::new_in(&b);
+    v.extend((0..5).map(|i| PanicOnDrop(i == 1, &drops)));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut iter = v.into_iter();
+        drop(iter.next());
+        drop(iter);
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn into_iter_zero_sized_as_slice() {
+    let b = Bump::new();
+    let v = vec![in &b; [0_u64; 0]; 3];
+    let mut iter = v.into_iter();
+    assert_eq!(iter.next(), Some([]));
+    assert_eq!(iter.as_slice().len(), 2);
+    assert_eq!(iter.as_slice().as_ptr() as usize % 8, 0);
+    assert_eq!(iter.count(), 2);
+}
+
+#[test]
+fn try_methods_report_allocation_failure() {
+    let b = Bump::new();
+    b.set_allocation_limit(Some(0));
+
+    assert!(Vec::<u32>::try_with_capacity_in(1, &b).is_err());
+    assert!(Vec::<u32>::try_with_capacity_in(usize::MAX, &b).is_err());
+
+    assert_eq!(
+        Vec::<u32>::try_with_capacity_in(usize::MAX, &b).err(),
+        Some(CollectionAllocErr::CapacityOverflow)
+    );
+
+    let mut v = Vec::new_in(&b);
+    assert_eq!(
+        v.try_push(1),
+        Err(CollectionAllocErr::AllocErr(AllocErr::from(
+            AllocErrKind::LimitExceeded
+        )))
+    );
+    assert!(v.try_insert(0, 1).is_err());
+    assert!(v.try_extend_from_slice(&[1, 2, 3]).is_err());
+    assert!(v.is_empty());
+
+    // Nothing needs to be allocated when there is spare capacity.
+    assert!(v.try_extend_from_slice(&[]).is_ok());
+}
+
+#[test]
+fn try_methods_succeed_with_capacity() {
+    let b = Bump::new();
+    let mut v = Vec::try_with_capacity_in(2, &b).unwrap();
+    b.set_allocation_limit(Some(b.allocated_bytes()));
+
+    v.try_push(1).unwrap();
+    v.try_insert(0, 0).unwrap();
+    assert_eq!(v, [0, 1]);
+
+    let cap = v.capacity();
+    if v.try_extend_from_slice(&[2; 1024]).is_err() {
+        // Failure leaves the vector untouched.
+        assert_eq!(v, [0, 1]);
+        assert_eq!(v.capacity(), cap);
+    }
+}
+
+quickcheck! {
+    fn retain_into_partitions(xs: std::vec::Vec<i32>) -> bool {
+        let b = Bump::new();
+        let mut v = Vec::from_iter_in(xs.iter().copied(), &b);
+        let removed = v.retain_into(|x| x % 3 == 0, &b);
+
+        let (keep, spill): (std::vec::Vec<i32>, std::vec::Vec<i32>) =
+            xs.iter().partition(|x| *x % 3 == 0);
+        v[..] == keep[..] && removed[..] == spill[..]
+    }
+}
+
+#[test]
+fn from_elem_in_allocates_once() {
+    let b = Bump::new();
+    let v = Vec::from_elem_in(7_u8, 10_000, &b);
+    assert_eq!(v.capacity(), 10_000);
+    assert!(v.iter().all(|&x| x == 7));
+
+    let m = bumpalo::vec![in &b; 0_u64; 1000];
+    assert_eq!(m.capacity(), 1000);
+    assert!(m.iter().all(|&x| x == 0));
+}
+
+#[test]
+fn from_elem_in_clones_n_minus_one_times() {
+    use std::rc::Rc;
+
+    let b = Bump::new();
+    let rc = Rc::new(());
+
+    let v = bumpalo::vec![in &b; rc.clone(); 3];
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(v);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    // With `n == 0`, the element is dropped right away.
+    let v = Vec::from_elem_in(rc.clone(), 0, &b);
+    assert!(v.is_empty());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn push_growth_frees_outgrown_chunks() {
+    let mut b = Bump::new();
+    let mut v = Vec::new_in(&b);
+    for i in 0..1_000_000_u32 {
+        v.push(i);
+    }
+    assert!(v.iter().copied().eq(0..1_000_000));
+
+    // Every chunk the vector outgrew, while it was the only thing in it, has
+    // been given back, so the arena holds a single chunk.
+    let capacity = v.capacity() * std::mem::size_of::<u32>();
+    assert!(b.allocated_bytes() <= 2 * capacity);
+    drop(v);
+    assert_eq!(b.iter_allocated_chunks().count(), 1);
+}
+
+#[test]
+fn push_growth_keeps_chunks_with_other_allocations() {
+    let mut b = Bump::new();
+    let x = b.alloc(42_u64);
+    let mut v = Vec::new_in(&b);
+    for i in 0..100_000_u32 {
+        v.push(i);
+    }
+    assert_eq!(*x, 42);
+    assert!(v.iter().copied().eq(0..100_000));
+
+    // The first chunk is still holding `x`.
+    drop(v);
+    let chunks = b.iter_allocated_chunks().count();
+    assert!(chunks >= 2, "{} chunks", chunks);
+}
+
+#[test]
+fn shrink_to_fit_returns_tail_to_the_arena() {
+    let b = Bump::new();
+    let mut v = Vec::with_capacity_in(100, &b);
+    v.extend(0..90_u64);
+    let before = b.allocated_bytes_in_current_chunk();
+
+    // Less than half of the buffer is released, so the kept elements overlap
+    // their old location.
+    v.truncate(60);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 60);
+    assert!(v.iter().copied().eq(0..60));
+    assert_eq!(b.allocated_bytes_in_current_chunk(), before - 40 * 8);
+
+    // The next allocation reuses the released bytes, right below the vector.
+    let x = b.alloc(7_u64);
+    assert_eq!(
+        x as *mut u64 as usize + 8,
+        v.as_ptr() as usize,
+        "allocated right below the vector"
+    );
+}
+
+#[test]
+fn shrink_to_fit_when_not_last_allocation_keeps_pointer() {
+    let b = Bump::new();
+    let mut v = Vec::with_capacity_in(100, &b);
+    v.extend(0..10_u32);
+    b.alloc(0_u8);
+    let ptr = v.as_ptr();
+    let before = b.allocated_bytes_in_current_chunk();
+
+    v.shrink_to_fit();
+    assert_eq!(v.as_ptr(), ptr);
+    assert!(v.iter().copied().eq(0..10));
+    assert_eq!(b.allocated_bytes_in_current_chunk(), before);
+}
+
+#[test]
+fn shrink_to_keeps_lower_bound() {
+    let b = Bump::new();
+    let mut v: Vec<u8> = Vec::with_capacity_in(64, &b);
+    v.extend_from_slice(b"abc");
+    let before = b.allocated_bytes_in_current_chunk();
+
+    v.shrink_to(100);
+    assert_eq!(v.capacity(), 64);
+    v.shrink_to(16);
+    assert_eq!(v.capacity(), 16);
+    assert_eq!(b.allocated_bytes_in_current_chunk(), before - 48);
+    v.shrink_to(0);
+    assert_eq!(v.capacity(), 3);
+    assert_eq!(v, b"abc");
+}
+
+#[test]
+fn leak_gives_back_excess_capacity() {
+    let b = Bump::new();
+    let mut v = Vec::with_capacity_in(64, &b);
+    v.extend_from_slice(&[1_u64, 2, 3]);
+    let used = b.allocated_bytes_in_current_chunk();
+    let slice = v.leak();
+    assert_eq!(slice, [1, 2, 3]);
+    assert_eq!(b.allocated_bytes_in_current_chunk(), used - 61 * 8);
+
+    // Not the most recent allocation: the slice stays where it is.
+    let mut v = Vec::with_capacity_in(64, &b);
+    v.push(4_u64);
+    let ptr = v.as_ptr();
+    b.alloc(0_u8);
+    let used = b.allocated_bytes_in_current_chunk();
+    let slice = v.leak();
+    assert_eq!(slice.as_ptr(), ptr);
+    assert_eq!(slice, [4]);
+    assert_eq!(b.allocated_bytes_in_current_chunk(), used);
+
+    let empty: &mut [String] = Vec::new_in(&b).leak();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn from_slice_and_array_constructors() {
+    let b = Bump::new();
+
+    let v = Vec::from_slice_copy_in(&[1_u8, 2, 3], &b);
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), 3);
+    assert!(Vec::<u8>::from_slice_copy_in(&[], &b).is_empty());
+
+    let strings = [std::string::String::from("x"), "y".into()];
+    let v = Vec::from_slice_clone_in(&strings, &b);
+    assert_eq!(v, strings);
+
+    let v = Vec::from_array_in(strings.clone(), &b);
+    assert_eq!(v, strings);
+    let empty: Vec<u64> = Vec::from_array_in([], &b);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn try_into_array() {
+    let b = Bump::new();
+    let drops = Cell::new(0);
+
+    struct CountDrops<'a>(&'a Cell<u32>, u32);
+    impl Drop for CountDrops<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let v = vec![in &b; CountDrops(&drops, 1), CountDrops(&drops, 2)];
+    let array: [CountDrops; 2] = v.try_into().ok().unwrap();
+    assert_eq!(drops.get(), 0);
+    assert_eq!([array[0].1, array[1].1], [1, 2]);
+    drop(array);
+    assert_eq!(drops.get(), 2);
+
+    let v = vec![in &b; CountDrops(&drops, 3)];
+    let v = <[CountDrops; 2]>::try_from(v).err().unwrap();
+    assert_eq!(v.len(), 1);
+    assert_eq!(drops.get(), 2);
+
+    let v: Vec<u8> = Vec::new_in(&b);
+    let array: [u8; 0] = v.try_into().unwrap();
+    assert_eq!(array, [0_u8; 0]);
+}
+
+#[test]
+fn extend_from_refs_in_generic_code() {
+    fn fill<C: for<'a> Extend<&'a u32>>(c: &mut C) {
+        c.extend(&[1, 2, 3]);
+    }
+    let b = Bump::new();
+    let mut v = Vec::new_in(&b);
+    fill(&mut v);
+    assert_eq!(v, [1, 2, 3]);
+}
+
+quickcheck! {
+    fn splice_matches_std(xs: std::vec::Vec<u8>, ys: std::vec::Vec<u8>, start: usize, len: usize) -> bool {
+        let start = if xs.is_empty() { 0 } else { start % (xs.len() + 1) };
+        let end = start + len % (xs.len() - start + 1);
+
+        let b = Bump::new();
+        let mut v = Vec::from_iter_in(xs.iter().copied(), &b);
+        // Filtering makes the size hint inexact, to exercise the path that
+        // moves the tail more than once.
+        let removed: std::vec::Vec<u8> =
+            v.splice(start..end, ys.iter().copied().filter(|y| y % 2 == 0)).collect();
+
+        let mut std_v = xs.clone();
+        let std_removed: std::vec::Vec<u8> =
+            std_v.splice(start..end, ys.iter().copied().filter(|y| y % 2 == 0)).collect();
+
+        v[..] == std_v[..] && removed == std_removed
+    }
+}
+
+#[test]
+fn splice_replaces_when_dropped_unconsumed() {
+    let b = Bump::new();
+    let mut v = vec![in &b; 1, 2, 3, 4, 5];
+    drop(v.splice(1..4, [10, 20]));
+    assert_eq!(v, [1, 10, 20, 5]);
+
+    v.splice(4.., 30..33);
+    assert_eq!(v, [1, 10, 20, 5, 30, 31, 32]);
+
+    v.splice(..0, [0]);
+    assert_eq!(v, [0, 1, 10, 20, 5, 30, 31, 32]);
+}
+
+#[test]
+fn splice_drops_removed_elements() {
+    let dropped = Cell::new(0);
+    struct Noisy<'a>(&'a Cell<usize>);
+    impl Drop for Noisy<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let b = Bump::new();
+    let mut v = Vec::new_in(&b);
+    v.extend((0..5).map(|_| Noisy(&dropped)));
+    v.splice(1..3, [Noisy(&dropped)]).next();
+    assert_eq!(dropped.get(), 2);
+    assert_eq!(v.len(), 4);
+    drop(v);
+    assert_eq!(dropped.get(), 6);
+}
+
+#[test]
+fn collection_alloc_err_display() {
+    assert_eq!(
+        CollectionAllocErr::CapacityOverflow.to_string(),
+        "capacity overflow"
+    );
+    assert_eq!(
+        CollectionAllocErr::AllocErr(AllocErr::from(AllocErrKind::LimitExceeded)).to_string(),
+        AllocErr::from(AllocErrKind::LimitExceeded).to_string()
+    );
+}
+
+quickcheck! {
+    fn sort_by_cached_key_matches_std(xs: std::vec::Vec<(u8, u8)>) -> bool {
+        let b = Bump::new();
+        let mut v = Vec::from_iter_in(xs.iter().copied(), &b);
+        v.sort_by_cached_key(|&(k, _)| k / 4);
+
+        let mut expected = xs;
+        expected.sort_by_cached_key(|&(k, _)| k / 4);
+        v[..] == expected[..]
+    }
+}
+
+#[test]
+fn sort_by_cached_key_calls_key_once_per_element() {
+    let b = Bump::new();
+    let mut v = bumpalo::vec![in &b; 5, 3, 9, 1, 7, 3];
+    let calls = Cell::new(0);
+    v.sort_by_cached_key(|x| {
+        calls.set(calls.get() + 1);
+        *x
+    });
+    assert_eq!(v, [1, 3, 3, 5, 7, 9]);
+    assert_eq!(calls.get(), 6);
+}
+
+#[test]
+fn sort_by_cached_key_in_uses_the_scratch_arena() {
+    let b = Bump::new();
+    let scratch = Bump::new();
+    let mut v = Vec::from_iter_in((0..1000_u32).rev(), &b);
+    let used = b.allocated_bytes();
+
+    v.sort_by_cached_key_in(|x| x.to_string(), &scratch);
+
+    assert_eq!(b.allocated_bytes(), used);
+    assert!(scratch.allocated_bytes() > 0);
+    let mut expected: std::vec::Vec<u32> = (0..1000).collect();
+    expected.sort_by_cached_key(|x| x.to_string());
+    assert_eq!(v[..], expected[..]);
+}
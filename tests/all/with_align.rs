@@ -0,0 +1,195 @@
+use bumpalo::Bump;
+use std::alloc::Layout;
+
+#[test]
+fn with_align_allocations_are_aligned() {
+    let mut bump = Bump::new();
+    bump.alloc(1_u8);
+
+    let ptrs = bump.with_align::<8, _>(|aligned| {
+        let mut ptrs = vec![];
+        for i in 0..1000 {
+            // Mix up sizes so that padding is required to keep the bump
+            // pointer aligned.
+            let p = if i % 2 == 0 {
+                aligned.alloc(i as u8) as *mut u8 as usize
+            } else {
+                aligned.alloc(i as u64) as *mut u64 as usize
+            };
+            ptrs.push(p);
+        }
+        ptrs
+    });
+
+    for p in ptrs {
+        assert_eq!(p % 8, 0);
+    }
+}
+
+#[test]
+fn with_align_values_outlive_closure() {
+    let mut bump = Bump::new();
+    let xs = bump
+        .with_align::<4, _>(|aligned| (0..100_u32).map(|i| aligned.alloc(i)).collect::<Vec<_>>());
+    for (i, x) in xs.into_iter().enumerate() {
+        assert_eq!(*x, i as u32);
+    }
+}
+
+#[test]
+fn with_align_larger_alignment_than_align() {
+    #[repr(align(16))]
+    struct Big(u8);
+
+    let mut bump = Bump::new();
+    bump.with_align::<2, _>(|aligned| {
+        for _ in 0..100 {
+            let small = aligned.alloc(1_u16);
+            assert_eq!(small as *mut u16 as usize % 2, 0);
+            let big = aligned.alloc(Big(2));
+            assert_eq!(big as *mut Big as usize % 16, 0);
+            assert_eq!(big.0, 2);
+        }
+    });
+}
+
+#[test]
+fn with_align_zero_sized() {
+    let mut bump = Bump::new();
+    bump.with_align::<16, _>(|aligned| {
+        let p = aligned.alloc_layout(Layout::from_size_align(0, 16).unwrap());
+        assert_eq!(p.as_ptr() as usize % 16, 0);
+        let q = aligned.alloc_slice_copy(&[1_u8, 2, 3]);
+        assert_eq!(q.as_ptr() as usize % 16, 0);
+        assert_eq!(q, &[1, 2, 3]);
+    });
+}
+
+#[test]
+#[should_panic(expected = "must be a power of two")]
+fn with_align_rejects_large_align() {
+    let mut bump = Bump::new();
+    bump.with_align::<32, _>(|_| ());
+}
+
+#[test]
+fn with_min_align_aligns_every_allocation() {
+    let bump = Bump::with_min_align(16);
+    assert_eq!(bump.min_align(), 16);
+
+    let mut ptrs = vec![];
+    for i in 0..10_000_usize {
+        // Spill over into several chunks along the way.
+        let p = match i % 3 {
+            0 => bump.alloc(i as u8) as *mut u8 as usize,
+            1 => bump.alloc_slice_copy(&[i as u16; 3]).as_ptr() as usize,
+            _ => bump
+                .alloc_layout(Layout::from_size_align(17, 4).unwrap())
+                .as_ptr() as usize,
+        };
+        ptrs.push(p);
+    }
+    assert!(bump.iter_chunk_usage().count() > 1);
+    assert!(ptrs.iter().all(|p| p % 16 == 0));
+
+    // Greater alignments are still honored.
+    let p = bump.alloc_layout(Layout::from_size_align(1, 64).unwrap());
+    assert_eq!(p.as_ptr() as usize % 64, 0);
+}
+
+#[test]
+fn with_min_align_pads_sizes() {
+    let bump = Bump::builder()
+        .initial_chunk_size(1024)
+        .min_alloc_align(8)
+        .build();
+    let before = bump.remaining_capacity();
+    bump.alloc(1_u8);
+    bump.alloc(2_u16);
+    assert_eq!(before - bump.remaining_capacity(), 16);
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn with_min_align_realloc_stays_aligned() {
+    let bump = Bump::with_min_align(8);
+    let mut v = bumpalo::collections::Vec::new_in(&bump);
+    for i in 0..1000_u16 {
+        v.push(i);
+        assert_eq!(v.as_ptr() as usize % 8, 0);
+    }
+    v.truncate(3);
+    v.shrink_to_fit();
+    assert_eq!(v.as_ptr() as usize % 8, 0);
+    assert_eq!(v, [0, 1, 2]);
+    assert_eq!(bump.alloc(0_u8) as *mut u8 as usize % 8, 0);
+}
+
+#[test]
+fn with_min_align_zero_sized_in_empty_arena() {
+    let bump = Bump::builder()
+        .initial_chunk_size(0)
+        .min_alloc_align(16)
+        .build();
+    let p = bump.alloc_layout(Layout::from_size_align(0, 16).unwrap());
+    assert_eq!(p.as_ptr() as usize % 16, 0);
+}
+
+#[cfg(feature = "collections")]
+#[test]
+fn with_min_align_dealloc_gives_back_padding() {
+    let bump = Bump::with_min_align(8);
+    bump.alloc(0_u8);
+    let before = bump.remaining_capacity();
+    let v = bumpalo::collections::Vec::<u8>::with_capacity_in(3, &bump);
+    assert_eq!(before - bump.remaining_capacity(), 8);
+    drop(v);
+    assert_eq!(bump.remaining_capacity(), before);
+    assert_eq!(bump.alloc(0_u8) as *mut u8 as usize % 8, 0);
+}
+
+#[test]
+fn with_min_align_after_split_remaining() {
+    let bump = Bump::builder()
+        .initial_chunk_size(1024)
+        .min_alloc_align(8)
+        .build();
+    bump.alloc_front(1_u8);
+    let subs: Vec<_> = bump.split_remaining(1).collect();
+    assert_eq!(subs[0].alloc(2_u8), &2);
+    let p = bump.alloc_layout(Layout::from_size_align(0, 1).unwrap());
+    assert_eq!(p.as_ptr() as usize % 8, 0);
+    assert_eq!(bump.alloc(3_u8) as *mut u8 as usize % 8, 0);
+}
+
+#[test]
+fn with_min_align_and_smaller_align() {
+    let mut bump = Bump::with_min_align(8);
+    let (a, b) = bump.with_align::<1, _>(|aligned| {
+        let a: *mut u8 = aligned.alloc(0_u8);
+        let b: *mut [u8; 3] = aligned.alloc([1_u8; 3]);
+        (a as usize, b as usize)
+    });
+    assert_eq!(a % 8, 0);
+    assert_eq!(b % 8, 0);
+    assert_eq!(a - b, 8);
+
+    // The arena's own fast path relies on the bump pointer staying aligned.
+    let x: *mut u64 = bump.alloc(0_u64);
+    assert_eq!(x as usize % 8, 0);
+    let y: *mut u32 = bump.with_align::<2, _>(|aligned| aligned.alloc(1_u32));
+    assert_eq!(y as usize % 8, 0);
+    assert_eq!(bump.alloc(2_u8) as *mut u8 as usize % 8, 0);
+}
+
+#[test]
+#[should_panic(expected = "minimum allocation alignment must be at most 16")]
+fn with_min_align_too_large() {
+    Bump::with_min_align(32);
+}
+
+#[test]
+#[should_panic(expected = "alignment must be a power of two")]
+fn with_min_align_not_power_of_two() {
+    Bump::with_min_align(3);
+}
@@ -0,0 +1,144 @@
+use bumpalo::Bump;
+use std::fmt::Write;
+
+#[test]
+fn fmt_write_into_str() {
+    let bump = Bump::new();
+    let mut w = bump.writer();
+    assert!(w.is_empty());
+    for i in 0..100 {
+        write!(w, "{},", i).unwrap();
+    }
+    let s = w.into_bump_str().unwrap();
+    let expected: String = (0..100).map(|i| format!("{},", i)).collect();
+    assert_eq!(&*s, expected);
+}
+
+#[test]
+fn empty_writer_does_not_allocate() {
+    let bump = Bump::new();
+    let w = bump.writer();
+    assert_eq!(w.capacity(), 0);
+    assert_eq!(w.into_bump_slice(), &[] as &[u8]);
+    assert_eq!(bump.allocated_bytes(), 0);
+}
+
+#[test]
+fn interleaved_allocations() {
+    let bump = Bump::new();
+    let mut w = bump.writer();
+    w.extend_from_slice(b"abc");
+    let x = bump.alloc(42_u64);
+    w.extend_from_slice(&[b'd'; 100]);
+    let y = bump.alloc(7_u32);
+    let bytes = w.into_bump_slice();
+    assert_eq!(&bytes[..3], b"abc");
+    assert!(bytes[3..].iter().all(|&b| b == b'd'));
+    assert_eq!(bytes.len(), 103);
+    assert_eq!((*x, *y), (42, 7));
+}
+
+#[test]
+fn finalizing_gives_back_unused_capacity() {
+    let bump = Bump::with_capacity(1024);
+    let before = bump.chunk_capacity();
+    let mut w = bump.writer();
+    w.extend_from_slice(&[1; 60]);
+    w.extend_from_slice(&[2; 41]);
+    assert!(w.capacity() > 101);
+    let bytes = w.into_bump_slice();
+    assert_eq!(bytes.len(), 101);
+    assert!(bytes[..60].iter().all(|&b| b == 1));
+    assert!(bytes[60..].iter().all(|&b| b == 2));
+    assert_eq!(bump.chunk_capacity(), before - 101);
+}
+
+#[test]
+fn dropping_gives_back_buffer() {
+    let bump = Bump::with_capacity(1024);
+    let before = bump.chunk_capacity();
+    let mut w = bump.writer();
+    w.extend_from_slice(&[1; 100]);
+    drop(w);
+    assert_eq!(bump.chunk_capacity(), before);
+}
+
+#[test]
+fn invalid_utf8() {
+    let bump = Bump::new();
+    let mut w = bump.writer();
+    w.extend_from_slice(&[0xff, 0xfe]);
+    assert!(w.into_bump_str().is_err());
+}
+
+#[test]
+fn allocation_limit() {
+    let bump = Bump::new();
+    bump.set_allocation_limit(Some(0));
+    let mut w = bump.writer();
+    assert!(w.try_extend_from_slice(b"hello").is_err());
+    assert!(write!(w, "hello").is_err());
+    assert!(w.is_empty());
+}
+
+#[cfg(feature = "std")]
+mod io {
+    use bumpalo::Bump;
+    use std::io::Write;
+
+    #[test]
+    fn io_write() {
+        let bump = Bump::new();
+        let mut w = bump.writer();
+        w.write_all(b"hello, ").unwrap();
+        assert_eq!(w.write(b"world").unwrap(), 5);
+        w.flush().unwrap();
+        assert_eq!(w.into_bump_slice(), b"hello, world");
+    }
+
+    #[test]
+    fn io_write_out_of_memory() {
+        let bump = Bump::new();
+        bump.set_allocation_limit(Some(0));
+        let mut w = bump.writer();
+        let err = w.write_all(b"hello").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::OutOfMemory);
+    }
+}
+
+#[test]
+fn write_macro_in_bump() {
+    let bump = Bump::new();
+    let s: &str = bumpalo::write!(in &bump, "{}-{:>3}-{x}", 1, "ab", x = 'c');
+    assert_eq!(s, "1- ab-c");
+
+    let literal = bumpalo::write!(in &bump, "no arguments");
+    assert_eq!(literal, "no arguments");
+
+    let empty = bumpalo::write!(in &bump, "");
+    assert_eq!(empty, "");
+}
+
+#[test]
+fn write_macro_without_in_is_core_write() {
+    let bump = Bump::new();
+    let mut w = bump.writer();
+    bumpalo::write!(w, "{}{}", 1, 2).unwrap();
+    bumpalo::write!(&mut w, "{}", 3).unwrap();
+    assert_eq!(w.into_bump_str().unwrap(), "123");
+}
+
+#[test]
+#[should_panic(expected = "a formatting trait implementation returned an error")]
+fn write_macro_formatting_error_panics() {
+    struct Fails;
+
+    impl std::fmt::Display for Fails {
+        fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            Err(std::fmt::Error)
+        }
+    }
+
+    let bump = Bump::new();
+    bumpalo::write!(in &bump, "{}", Fails);
+}
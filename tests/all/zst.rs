@@ -0,0 +1,64 @@
+use bumpalo::Bump;
+use std::mem;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Marker;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(align(4096))]
+struct HighlyAlignedMarker;
+
+#[test]
+fn zst_allocations_dont_allocate_chunks() {
+    let bump = Bump::new();
+    for _ in 0..1000 {
+        bump.alloc(Marker);
+        bump.alloc(HighlyAlignedMarker);
+        bump.alloc_slice_fill_default::<HighlyAlignedMarker>(100);
+    }
+    assert_eq!(bump.allocated_bytes(), 0);
+}
+
+#[test]
+fn zst_allocations_dont_touch_bump_pointer() {
+    let bump = Bump::with_capacity(100);
+    bump.alloc(1_u8);
+    let capacity = bump.chunk_capacity();
+
+    bump.alloc(Marker);
+    bump.try_alloc(HighlyAlignedMarker).unwrap();
+    bump.alloc_with(|| ());
+    bump.try_alloc_with(|| [0_u64; 0]).unwrap();
+    bump.alloc_slice_copy(&[Marker; 10]);
+    bump.alloc_slice_clone(&[HighlyAlignedMarker; 10]);
+    bump.alloc_front(Marker);
+
+    assert_eq!(bump.chunk_capacity(), capacity);
+}
+
+#[test]
+fn zst_references_are_aligned() {
+    let bump = Bump::new();
+    let a = bump.alloc(HighlyAlignedMarker);
+    assert_eq!(
+        a as *mut _ as usize % mem::align_of::<HighlyAlignedMarker>(),
+        0
+    );
+    let s = bump.alloc_slice_fill_copy(3, HighlyAlignedMarker);
+    assert_eq!(s.len(), 3);
+    assert_eq!(
+        s.as_ptr() as usize % mem::align_of::<HighlyAlignedMarker>(),
+        0
+    );
+}
+
+#[test]
+fn zst_initializers_run() {
+    use std::cell::Cell;
+
+    let bump = Bump::new();
+    let calls = Cell::new(0);
+    bump.alloc_slice_fill_with(5, |_| calls.set(calls.get() + 1));
+    bump.alloc_with(|| calls.set(calls.get() + 1));
+    assert_eq!(calls.get(), 6);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "log")]
+
+use bumpalo::Bump;
+use std::alloc::Layout;
+use std::sync::{Mutex, Once};
+
+struct CapturingLogger {
+    messages: Mutex<Vec<String>>,
+}
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.messages
+            .lock()
+            .unwrap()
+            .push(format!("{}", record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    messages: Mutex::new(Vec::new()),
+};
+
+fn logged(f: impl FnOnce()) -> Vec<String> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+
+    // Other tests may be logging concurrently, so callers should only look
+    // for messages that mention sizes unique to them.
+    f();
+    LOGGER.messages.lock().unwrap().clone()
+}
+
+#[test]
+fn logs_arena_events() {
+    let mut chunk_size = 0;
+    let messages = logged(|| {
+        let mut bump = Bump::new();
+        bump.alloc_layout(Layout::from_size_align(123_456, 1).unwrap());
+        chunk_size = bump.allocated_bytes_including_metadata();
+        bump.reset();
+
+        let bump = Bump::new();
+        bump.set_allocation_limit(Some(0));
+        assert!(bump
+            .try_alloc_layout(Layout::from_size_align(654_321, 1).unwrap())
+            .is_err());
+    });
+
+    let allocated = format!("bumpalo: allocated a new chunk of {} bytes", chunk_size);
+    assert!(messages.iter().any(|m| m.starts_with(&allocated)));
+
+    let reset = format!(
+        "bumpalo: reset arena, retaining a chunk of {} bytes",
+        chunk_size
+    );
+    assert!(messages.contains(&reset));
+
+    let oom =
+        "bumpalo: out of memory: failed to allocate a chunk for a 654321-byte allocation (align 1)";
+    assert!(messages.iter().any(|m| m == oom));
+}